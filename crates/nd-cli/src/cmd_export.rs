@@ -0,0 +1,53 @@
+//! `nativedoctor export`: request files → other formats (OpenAPI 3.0).
+
+use std::path::PathBuf;
+
+use nd_generate::openapi3::{export_openapi, write_openapi, ExportOptions};
+
+#[derive(Debug, Clone)]
+pub(crate) struct ExportOpenApiOptions {
+    /// Request files and directories (walked recursively); `.` when empty.
+    pub paths: Vec<PathBuf>,
+    pub output: PathBuf,
+    pub title: Option<String>,
+    pub version: Option<String>,
+    pub har: Vec<PathBuf>,
+}
+
+pub fn run_export_openapi(opts: ExportOpenApiOptions) -> Result<(), String> {
+    let mut paths = opts.paths;
+    if paths.is_empty() {
+        paths.push(PathBuf::from("."));
+    }
+    let with_har = !opts.har.is_empty();
+    let report = export_openapi(
+        &paths,
+        &ExportOptions {
+            title: opts.title,
+            version: opts.version,
+            har: opts.har,
+        },
+    )
+    .map_err(|e| e.to_string())?;
+    if report.operations == 0 {
+        return Err("no request files to export".to_string());
+    }
+    write_openapi(&opts.output, &report.api).map_err(|e| e.to_string())?;
+
+    println!(
+        "Exported {} request(s) as {} operation(s) to {}",
+        report.requests,
+        report.operations,
+        opts.output.display()
+    );
+    if with_har {
+        println!(
+            "  {} recorded response(s) matched to an operation",
+            report.recorded
+        );
+    }
+    for (path, reason) in &report.skipped {
+        println!("  skipped {}: {reason}", path.display());
+    }
+    return Ok(());
+}
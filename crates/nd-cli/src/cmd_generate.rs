@@ -1,24 +1,58 @@
-//! `nativedoctor generate`: OpenAPI 3.0.x → request files.
+//! `nativedoctor generate`: OpenAPI 3.0.x / 3.1 or Swagger 2.0 → request files.
 
 use std::path::Path;
 
-use nd_generate::{generate_from_openapi_path, GenerateOptions, OutputFormat};
+use nd_generate::{generate_from_openapi_path, GenerateOptions};
 
 pub fn run_generate(
     input: impl AsRef<Path>,
     output: impl AsRef<Path>,
-    format: OutputFormat,
+    options: GenerateOptions,
 ) -> Result<(), String> {
-    let report =
-        generate_from_openapi_path(input.as_ref(), output.as_ref(), GenerateOptions { format })
-            .map_err(|e| e.to_string())?;
-    let n = report.files_written.len();
-    println!(
-        "Generated {n} request file(s) under {}",
-        output.as_ref().display()
-    );
-    for p in &report.files_written {
-        println!("  {}", p.display());
+    let report = generate_from_openapi_path(input.as_ref(), output.as_ref(), options)
+        .map_err(|e| e.to_string())?;
+    if let Some(sync) = &report.sync {
+        println!(
+            "Synced {} with {}: {} added, {} changed, {} unchanged, {} removed, {} deprecated",
+            output.as_ref().display(),
+            input.as_ref().display(),
+            sync.added.len(),
+            sync.changed.len(),
+            sync.unchanged.len(),
+            sync.removed.len(),
+            sync.deprecated.len()
+        );
+        for (label, paths) in [
+            ("added", &sync.added),
+            ("changed", &sync.changed),
+            ("removed", &sync.removed),
+            ("deprecated", &sync.deprecated),
+        ] {
+            for p in paths {
+                println!("  {label:<10} {}", p.display());
+            }
+        }
+    } else {
+        let n = report.files_written.len();
+        println!(
+            "Generated {n} request file(s) under {}",
+            output.as_ref().display()
+        );
+        for p in &report.files_written {
+            println!("  {}", p.display());
+        }
+    }
+    if let Some(index) = report.index_scripts.first() {
+        println!(
+            "Import {} from a Rhai script to call the requests as modules",
+            index.display()
+        );
+    }
+    if let Some(env) = &report.env_example {
+        println!(
+            "Variables the requests use are listed in {} (copy it to .env and fill it in)",
+            env.display()
+        );
     }
     return Ok(());
 }
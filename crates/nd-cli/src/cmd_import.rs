@@ -0,0 +1,225 @@
+//! `nativedoctor import`: convert other tools' request formats into request files.
+
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use nd_generate::bruno::{import_bruno, BrunoImportOptions};
+use nd_generate::curl::{import_curl_args, import_curl_command, CurlImportOptions};
+use nd_generate::har::{import_har, HarImportOptions};
+use nd_generate::http_file::{import_http, HttpImportOptions};
+use nd_generate::insomnia::{import_insomnia, InsomniaImportOptions};
+use nd_generate::postman::{import_postman, PostmanImportOptions};
+use nd_generate::secrets::{to_dotenv, LiftedSecret};
+use nd_generate::{write_request_file, ImportReport, OutputFormat};
+
+#[derive(Debug, Clone)]
+pub(crate) struct ImportCurlOptions {
+    /// One quoted command string, or the already-split words; empty reads stdin.
+    pub command: Vec<String>,
+    pub output: PathBuf,
+    pub format: Option<OutputFormat>,
+    pub lift_secrets: bool,
+    pub env_out: Option<PathBuf>,
+}
+
+pub fn run_import_curl(opts: ImportCurlOptions) -> Result<(), String> {
+    if opts.output.exists() {
+        return Err(format!(
+            "refusing to overwrite existing file: {}",
+            opts.output.display()
+        ));
+    }
+
+    let import_opts = CurlImportOptions {
+        lift_secrets: opts.lift_secrets,
+    };
+    let imported = match opts.command.as_slice() {
+        [] => {
+            let mut text = String::new();
+            std::io::stdin()
+                .read_to_string(&mut text)
+                .map_err(|e| format!("failed to read curl command from stdin: {e}"))?;
+            import_curl_command(&text, import_opts)
+        }
+        [one] => import_curl_command(one, import_opts),
+        words => import_curl_args(words, import_opts),
+    }
+    .map_err(|e| e.to_string())?;
+
+    let format = opts
+        .format
+        .or_else(|| OutputFormat::from_path(&opts.output))
+        .unwrap_or_default();
+    write_request_file(&opts.output, &imported.file, format).map_err(|e| e.to_string())?;
+    println!("Wrote {}", opts.output.display());
+
+    for note in &imported.notes {
+        println!("  note: {note}");
+    }
+    report_secrets(&imported.secrets, opts.env_out.as_deref())?;
+    return Ok(());
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct ImportPostmanOptions {
+    pub input: PathBuf,
+    pub environment: Option<PathBuf>,
+    pub output: PathBuf,
+    pub format: OutputFormat,
+    pub env_out: Option<PathBuf>,
+}
+
+pub fn run_import_postman(opts: ImportPostmanOptions) -> Result<(), String> {
+    let report = import_postman(
+        &opts.input,
+        opts.environment.as_deref(),
+        &opts.output,
+        PostmanImportOptions {
+            format: opts.format,
+            env_out: opts.env_out,
+        },
+    )
+    .map_err(|e| e.to_string())?;
+    print_import_report(&report, &opts.output);
+    return Ok(());
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct ImportInsomniaOptions {
+    pub input: PathBuf,
+    pub output: PathBuf,
+    pub format: OutputFormat,
+}
+
+pub fn run_import_insomnia(opts: ImportInsomniaOptions) -> Result<(), String> {
+    let report = import_insomnia(
+        &opts.input,
+        &opts.output,
+        InsomniaImportOptions {
+            format: opts.format,
+        },
+    )
+    .map_err(|e| e.to_string())?;
+    print_import_report(&report, &opts.output);
+    return Ok(());
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct ImportBrunoOptions {
+    pub input: PathBuf,
+    pub output: PathBuf,
+    pub format: OutputFormat,
+}
+
+pub fn run_import_bruno(opts: ImportBrunoOptions) -> Result<(), String> {
+    let report = import_bruno(
+        &opts.input,
+        &opts.output,
+        BrunoImportOptions {
+            format: opts.format,
+        },
+    )
+    .map_err(|e| e.to_string())?;
+    print_import_report(&report, &opts.output);
+    return Ok(());
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct ImportHttpOptions {
+    pub input: PathBuf,
+    pub output: PathBuf,
+    pub format: OutputFormat,
+}
+
+pub fn run_import_http(opts: ImportHttpOptions) -> Result<(), String> {
+    let report = import_http(
+        &opts.input,
+        &opts.output,
+        HttpImportOptions {
+            format: opts.format,
+        },
+    )
+    .map_err(|e| e.to_string())?;
+    print_import_report(&report, &opts.output);
+    return Ok(());
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct ImportHarOptions {
+    pub input: PathBuf,
+    pub output: PathBuf,
+    pub format: OutputFormat,
+    pub hosts: Vec<String>,
+    pub methods: Vec<String>,
+    pub keep_duplicates: bool,
+    pub lift_secrets: bool,
+}
+
+pub fn run_import_har(opts: ImportHarOptions) -> Result<(), String> {
+    let report = import_har(
+        &opts.input,
+        &opts.output,
+        HarImportOptions {
+            format: opts.format,
+            hosts: opts.hosts,
+            methods: opts.methods,
+            keep_duplicates: opts.keep_duplicates,
+            lift_secrets: opts.lift_secrets,
+        },
+    )
+    .map_err(|e| e.to_string())?;
+    print_import_report(&report, &opts.output);
+    return Ok(());
+}
+
+/// Lists what a collection import wrote, then its notes.
+fn print_import_report(report: &ImportReport, output: &Path) {
+    println!(
+        "Wrote {} request file(s) under {}",
+        report.files_written.len(),
+        output.display()
+    );
+    for p in &report.files_written {
+        println!("  {}", p.display());
+    }
+    if !report.scripts_written.is_empty() {
+        println!(
+            "Wrote {} script stub(s) with the original JavaScript as comments:",
+            report.scripts_written.len()
+        );
+        for p in &report.scripts_written {
+            println!("  {}", p.display());
+        }
+    }
+    for env in &report.env_files {
+        println!("Variables written to {} (pass it with --env)", env.display());
+    }
+    for note in &report.notes {
+        println!("  note: {note}");
+    }
+}
+
+/// Lists lifted variables and appends their values to `env_out` when given.
+fn report_secrets(secrets: &[LiftedSecret], env_out: Option<&Path>) -> Result<(), String> {
+    if secrets.is_empty() {
+        return Ok(());
+    }
+    println!("Lifted {} secret(s):", secrets.len());
+    for s in secrets {
+        println!("  ${{{}}} ← {}", s.var, s.location);
+    }
+
+    let Some(path) = env_out else {
+        println!("  values were not saved; pass --env-out FILE to write them");
+        return Ok(());
+    };
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| format!("{}: {e}", path.display()))?;
+    file.write_all(to_dotenv(secrets).as_bytes())
+        .map_err(|e| format!("{}: {e}", path.display()))?;
+    println!("  values appended to {}", path.display());
+    return Ok(());
+}
@@ -1,15 +1,22 @@
 //! `nativedoctor run <FILE>` and top-level `FILE` shorthand: request files or Rhai scripts.
 
+use std::io::{IsTerminal, Write};
 use std::path::{Path, PathBuf};
-use std::sync::{Arc, Mutex};
+use std::sync::{mpsc, Arc, Mutex};
+
+use colored::Colorize;
 
 use nd_core::execute::types::PrintOptions;
+use nd_core::har;
+use nd_core::rhai::checkpoint::Checkpoints;
 use nd_core::rhai::logger::log_parsed_level;
 use nd_core::stream::events::Event;
+use nd_core::stream::steps::{steps, Step, StepOutcome};
 use nd_core::stream::{MutexSession, Session};
 use nd_core::{
     env::RuntimeEnv,
     execute::format::format_prepared_request,
+    execute::snippet::{format_snippet, SnippetLanguage},
     model::request::RequestFile,
     rhai::{resolver::RhaiScriptRunOptions, run::run_rhai_script},
 };
@@ -29,6 +36,12 @@ pub struct RunOptions {
     pub persistence_file: Option<PathBuf>,
     pub env_files: Vec<PathBuf>,
     pub stream: bool,
+    /// Print request files as a code snippet instead of sending them.
+    pub as_snippet: Option<SnippetLanguage>,
+    /// Write the run's HTTP exchanges to this HAR file when done.
+    pub har: Option<PathBuf>,
+    /// Gate for Rhai `checkpoint()`, resumed from an Enter prompt; `None` when stdin is not a terminal.
+    pub checkpoints: Option<Checkpoints>,
 }
 
 impl RunOptions {
@@ -38,14 +51,19 @@ impl RunOptions {
                 retain_runtime,
                 stream_content,
                 paths,
+                as_snippet,
+                har,
             }) => RunOptions {
-                stream: stream_content.clone(),
+                stream: *stream_content,
+                as_snippet: as_snippet.map(Into::into),
+                har: har.clone(),
                 verbose: cli.verbose,
                 no_network_io: cli.no_network_io,
                 retain_runtime: *retain_runtime,
                 paths: paths.clone(),
                 persistence_file: cli.persistence_file.clone(),
                 env_files: cli.env.clone(),
+                checkpoints: interactive_checkpoints(),
             },
             None => {
                 let path = cli
@@ -55,12 +73,15 @@ impl RunOptions {
 
                 RunOptions {
                     stream: false,
+                    as_snippet: None,
+                    har: None,
                     verbose: cli.verbose,
                     no_network_io: cli.no_network_io,
                     retain_runtime: true,
                     paths: vec![path],
                     persistence_file: cli.persistence_file.clone(),
                     env_files: cli.env.clone(),
+                    checkpoints: interactive_checkpoints(),
                 }
             }
             _ => unreachable!("Shouldn't get here"),
@@ -68,21 +89,67 @@ impl RunOptions {
     }
 }
 
-fn handle_session_events_for_cli(event: Event) {
+fn interactive_checkpoints() -> Option<Checkpoints> {
+    return std::io::stdin().is_terminal().then(Checkpoints::new);
+}
+
+/// Resumes checkpoints from an Enter prompt on a thread of its own. The event sink runs while the
+/// session is locked, so it only hands the checkpoint id over instead of blocking on stdin.
+fn spawn_checkpoint_prompt(checkpoints: Checkpoints) -> mpsc::Sender<String> {
+    let (tx, rx) = mpsc::channel::<String>();
+    std::thread::spawn(move || {
+        for checkpoint_id in rx {
+            eprint!("press Enter to continue… ");
+            let _ = std::io::stderr().flush();
+            let mut line = String::new();
+            let _ = std::io::stdin().read_line(&mut line);
+            checkpoints.resume(&checkpoint_id);
+        }
+    });
+    return tx;
+}
+
+fn handle_session_events_for_cli(event: Event, prompt: Option<&mpsc::Sender<String>>) {
     match event {
         Event::Log {
             level,
             message,
             script,
             ..
+        } => log_parsed_level(level.as_str(), message, script),
+        Event::CheckpointWaiting {
+            checkpoint_id,
+            script,
+            message,
+            observe,
+            ..
         } => {
-            log_parsed_level(level.as_str(), message, script);
+            eprintln!("{} {message} ({script})", "checkpoint:".yellow().bold());
+            if observe.as_object().is_some_and(|o| !o.is_empty()) {
+                eprintln!(
+                    "{}",
+                    serde_json::to_string_pretty(&observe).unwrap_or_default()
+                );
+            }
+            // The script is blocked on this checkpoint until the prompt resumes it; without a
+            // terminal there is no gate and it carries on by itself.
+            if let Some(prompt) = prompt {
+                let _ = prompt.send(checkpoint_id);
+            }
         }
+        Event::PollAttempt {
+            label,
+            attempt,
+            done: false,
+            ..
+        } => eprintln!("{} {label}: attempt {attempt} not done yet", "poll:".dimmed()),
         _ => {}
-    };
+    }
 }
 
 pub(crate) async fn run_run(opts: RunOptions) -> Result<(), String> {
+    let prompt = opts.checkpoints.clone().map(spawn_checkpoint_prompt);
+
     // create runtime session
     let session = Arc::new(Mutex::new(Session::new(
         || {
@@ -92,11 +159,39 @@ pub(crate) async fn run_run(opts: RunOptions) -> Result<(), String> {
                 .with_persistence(&opts.persistence_file)
                 .map_err(|e| e.to_string())
         },
-        Some(Box::new(|event: Event| {
-            handle_session_events_for_cli(event);
+        Some(Box::new(move |event: Event| {
+            handle_session_events_for_cli(event, prompt.as_ref());
         })),
     )?));
 
+    if opts.har.is_some() {
+        if opts.no_network_io || opts.as_snippet.is_some() {
+            tracing::warn!("nothing is sent with --no-network-io or --as; the HAR will have no entries");
+        }
+        session
+            .lock()
+            .map_err(|e| e.to_string())?
+            .keep_results();
+    }
+
+    let outcome = run_paths(&opts, &session).await;
+
+    // Write the HAR even when a file failed, so the exchanges before the failure are kept.
+    if let Some(path) = &opts.har {
+        let har = har::from_session(&*session.lock().map_err(|e| e.to_string())?);
+        let text = serde_json::to_string_pretty(&har).map_err(|e| e.to_string())?;
+        std::fs::write(path, text).map_err(|e| format!("{}: {e}", path.display()))?;
+        println!(
+            "Wrote {} (HAR, {} entries)",
+            path.display(),
+            har.log.entries.len()
+        );
+    }
+
+    return outcome;
+}
+
+async fn run_paths(opts: &RunOptions, session: &Arc<Mutex<Session>>) -> Result<(), String> {
     for path in opts.paths.iter() {
         let session = session.clone();
 
@@ -124,11 +219,13 @@ pub(crate) async fn run_run(opts: RunOptions) -> Result<(), String> {
             .unwrap_or_default();
 
         match ext.as_str() {
-            "json" | "yaml" | "yml" => run_request(path, &opts, session.clone()).await?,
-            "rhai" => run_script(path, &opts, session.clone()).await?,
+            "json" | "yaml" | "yml" | "http" | "rest" => {
+                run_request(path, opts, session.clone()).await?
+            }
+            "rhai" => run_script(path, opts, session.clone()).await?,
             _ => {
                 return Err(String::from(
-                    "Invalid file type. only json, yaml, yml, rhai files accepted",
+                    "Invalid file type. only json, yaml, yml, http, rest, rhai files accepted",
                 ))
             }
         };
@@ -141,17 +238,38 @@ pub(crate) async fn run_run(opts: RunOptions) -> Result<(), String> {
     return Ok(());
 }
 
-/// Run one request
+/// Run the request in a request file, or every request of a `.http` / `.rest` file in order
 pub async fn run_request(
     path: &Path,
     opts: &RunOptions,
     session: Arc<Mutex<Session>>,
 ) -> Result<(), String> {
-    let document = RequestFile::from_file(path).map_err(|e| e.to_string())?;
+    let documents = RequestFile::from_file_all(path).map_err(|e| e.to_string())?;
+    for document in &documents {
+        run_document(document, opts, session.clone()).await?;
+    }
+    return Ok(());
+}
+
+/// Run one request
+async fn run_document(
+    document: &RequestFile,
+    opts: &RunOptions,
+    session: Arc<Mutex<Session>>,
+) -> Result<(), String> {
+    if let Some(language) = opts.as_snippet {
+        let request = document
+            .request
+            .expand(&session.runtime())
+            .map_err(|e| e.to_string())?;
+        let snippet = format_snippet(&request, language).map_err(|e| e.to_string())?;
+        print!("{snippet}");
+        return Ok(());
+    }
 
     // return run_one_with_env(path, cli, opts, &env).await;
     if opts.verbose && !opts.no_network_io {
-        println!("{}", format!("--- request/{:?} ---", document.name));
+        println!("--- request/{:?} ---", document.name);
     }
 
     if opts.no_network_io || opts.verbose {
@@ -172,7 +290,7 @@ pub async fn run_request(
     }
 
     let output = document
-        .execute(session, opts.stream.clone())
+        .execute(session, opts.stream)
         .await
         .map_err(|e| e.to_string())?;
 
@@ -182,6 +300,16 @@ pub async fn run_request(
         PrintOptions::Normal
     });
 
+    if !output.schema_errors.is_empty() {
+        for error in &output.schema_errors {
+            eprintln!("{} {error}", "contract:".red().bold());
+        }
+        return Err(format!(
+            "response does not match its OpenAPI operation or response_schema ({} problem(s))",
+            output.schema_errors.len()
+        ));
+    }
+
     return Ok(());
 }
 
@@ -201,12 +329,52 @@ pub async fn run_script(
         );
     }
 
-    return run_rhai_script(
+    // A script sends whatever it likes; printing snippets would need every request to be dry-run.
+    if opts.as_snippet.is_some() {
+        return Err(format!(
+            "--as only applies to request files, not the script {}",
+            path.display()
+        ));
+    }
+
+    let first_event = session.lock().map_err(|e| e.to_string())?.events().len();
+
+    let outcome = run_rhai_script(
         path,
-        session,
+        session.clone(),
         RhaiScriptRunOptions {
             no_network_io: opts.no_network_io,
+            checkpoints: opts.checkpoints.clone(),
+            ..Default::default()
         },
     )
     .map_err(|e| e.to_string());
+
+    // Print the script's `step(...)` calls as a checklist, failed or not.
+    let guard = session.lock().map_err(|e| e.to_string())?;
+    print_steps(&steps(&guard.events()[first_event..]));
+
+    return outcome;
+}
+
+fn print_steps(steps: &[Step]) {
+    if steps.is_empty() {
+        return;
+    }
+    println!("{}", "steps:".bold());
+    for step in steps {
+        let took = step
+            .duration
+            .map(|d| format!(" ({} ms)", d.as_millis()))
+            .unwrap_or_default();
+        match &step.outcome {
+            StepOutcome::Marked => println!("  {} {}", "•".dimmed(), step.name),
+            StepOutcome::Passed => println!("  {} {}{took}", "✓".green().bold(), step.name),
+            StepOutcome::Failed(error) => println!(
+                "  {} {}{took}: {error}",
+                "✗".red().bold(),
+                step.name
+            ),
+        }
+    }
 }
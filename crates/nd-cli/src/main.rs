@@ -1,6 +1,8 @@
-//! CLI entry for **nativedoctor**: `run`, `runall`, shorthand file path, `list`, `new`, and shared flags.
+//! CLI entry for **nativedoctor**: `run`, `runall`, shorthand file path, `list`, `new`, `import`, `export`, and shared flags.
 
+mod cmd_export;
 mod cmd_generate;
+mod cmd_import;
 mod cmd_new;
 mod cmd_rhai_definitions;
 mod cmd_run;
@@ -15,7 +17,13 @@ use std::process::ExitCode;
 use clap::{ArgAction, Parser, Subcommand, ValueEnum};
 
 use crate::{
-    cmd_new::NewOption, cmd_rhai_definitions::RhaiDefinitionsOptions, cmd_run::RunOptions,
+    cmd_export::ExportOpenApiOptions,
+    cmd_import::{
+        ImportBrunoOptions, ImportCurlOptions, ImportHarOptions, ImportHttpOptions,
+        ImportInsomniaOptions, ImportPostmanOptions,
+    },
+    cmd_new::NewOption,
+    cmd_rhai_definitions::RhaiDefinitionsOptions, cmd_run::RunOptions,
 };
 
 #[derive(Parser)]
@@ -65,6 +73,28 @@ impl From<GenerateFormat> for nd_generate::OutputFormat {
     }
 }
 
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum SnippetFormat {
+    Curl,
+    Httpie,
+    Python,
+    #[value(alias = "js")]
+    Javascript,
+    Rust,
+}
+
+impl From<SnippetFormat> for nd_core::execute::snippet::SnippetLanguage {
+    fn from(f: SnippetFormat) -> Self {
+        match f {
+            SnippetFormat::Curl => Self::Curl,
+            SnippetFormat::Httpie => Self::Httpie,
+            SnippetFormat::Python => Self::Python,
+            SnippetFormat::Javascript => Self::Javascript,
+            SnippetFormat::Rust => Self::Rust,
+        }
+    }
+}
+
 #[derive(Subcommand)]
 enum Command {
     /// Run one or more request-file or rhai scripts
@@ -77,6 +107,12 @@ enum Command {
         paths: Vec<PathBuf>,
         #[arg(long)]
         stream_content: bool,
+        /// Print each request as a code snippet in this language instead of sending it (implies `--no-network-io` for request files).
+        #[arg(long = "as", value_enum, value_name = "LANG")]
+        as_snippet: Option<SnippetFormat>,
+        /// Write every HTTP exchange of the run (headers, bodies, timings) to this HAR 1.2 file.
+        #[arg(long, value_name = "FILE", value_hint = clap::ValueHint::FilePath)]
+        har: Option<PathBuf>,
     },
     /// Run request files or Rhai scripts in an interactive terminal UI (ratatui).
     Tui {
@@ -98,7 +134,7 @@ enum Command {
         #[arg(value_name = "DIR", value_hint = clap::ValueHint::DirPath)]
         dirs: Vec<PathBuf>,
     },
-    /// Generate nativedoctor request files from an OpenAPI 3.0.x document (JSON or YAML).
+    /// Generate nativedoctor request files from an OpenAPI 3.0.x / 3.1 or Swagger 2.0 document (JSON or YAML).
     Generate {
         /// OpenAPI spec file (.json, .yaml, or .yml).
         #[arg(short = 'i', long = "input", value_name = "FILE")]
@@ -109,6 +145,26 @@ enum Command {
         /// Request file format for generated files.
         #[arg(long, value_enum, default_value_t = GenerateFormat::Yaml)]
         format: GenerateFormat,
+        /// Put each operation in a subdirectory named after its first tag.
+        #[arg(long)]
+        by_tag: bool,
+        /// Update the request files already in the output directory (matched by operationId)
+        /// instead of overwriting them, keeping edited headers, bodies and names.
+        #[arg(long)]
+        sync: bool,
+        /// With --sync, mark files whose operation left the spec `deprecated` instead of deleting them.
+        #[arg(long, requires = "sync")]
+        deprecate_removed: bool,
+    },
+    /// Import request files from other tools (curl, Postman, Insomnia, Bruno, HAR, .http).
+    Import {
+        #[command(subcommand)]
+        source: ImportSource,
+    },
+    /// Export request files to other formats (OpenAPI).
+    Export {
+        #[command(subcommand)]
+        target: ExportTarget,
     },
     /// Write Rhai definition files (`.d.rhai`) for IDE / language-server support (builtins + nativedoctor globals).
     Definitions {
@@ -133,6 +189,129 @@ enum Command {
     },
 }
 
+#[derive(Subcommand)]
+enum ImportSource {
+    /// Convert a curl command line (e.g. devtools "Copy as cURL") into one request file.
+    Curl {
+        /// Output request file (`.yaml`, `.yml`, or `.json`).
+        #[arg(short = 'o', long = "output", value_name = "FILE")]
+        output: PathBuf,
+        /// Request file format; defaults to the output file extension, else YAML.
+        #[arg(long, value_enum)]
+        format: Option<GenerateFormat>,
+        /// Replace tokens, API keys, passwords, and cookies with `${VAR}` placeholders.
+        #[arg(long)]
+        lift_secrets: bool,
+        /// Append lifted secrets as `VAR=value` lines to this dotenv file.
+        #[arg(long, value_name = "FILE", requires = "lift_secrets")]
+        env_out: Option<PathBuf>,
+        /// The curl command as one quoted string, or its words after `--`. Read from stdin when omitted.
+        #[arg(value_name = "COMMAND", trailing_var_arg = true, allow_hyphen_values = true)]
+        command: Vec<String>,
+    },
+    /// Convert a Postman collection (v2.0 / v2.1 export) into a directory of request files.
+    Postman {
+        /// Exported collection (`*.postman_collection.json`).
+        #[arg(short = 'i', long = "input", value_name = "FILE")]
+        input: PathBuf,
+        /// Exported environment whose values override collection variables.
+        #[arg(short = 'e', long = "environment", value_name = "FILE")]
+        environment: Option<PathBuf>,
+        /// Output directory (created if missing); folders become subdirectories.
+        #[arg(short = 'o', long = "output", value_name = "DIR")]
+        output: PathBuf,
+        /// Request file format for generated files.
+        #[arg(long, value_enum, default_value_t = GenerateFormat::Yaml)]
+        format: GenerateFormat,
+        /// Dotenv file for collection / environment variables (default: `DIR/.env`).
+        #[arg(long, value_name = "FILE")]
+        env_out: Option<PathBuf>,
+    },
+    /// Convert an Insomnia export (v4 JSON) into a directory of request files.
+    Insomnia {
+        /// Exported data file (`Insomnia_*.json`).
+        #[arg(short = 'i', long = "input", value_name = "FILE")]
+        input: PathBuf,
+        /// Output directory (created if missing); folders become subdirectories.
+        #[arg(short = 'o', long = "output", value_name = "DIR")]
+        output: PathBuf,
+        /// Request file format for generated files.
+        #[arg(long, value_enum, default_value_t = GenerateFormat::Yaml)]
+        format: GenerateFormat,
+    },
+    /// Convert a browser HAR capture into request files (one per distinct call).
+    Har {
+        /// HAR 1.2 file (devtools "Save all as HAR").
+        #[arg(short = 'i', long = "input", value_name = "FILE")]
+        input: PathBuf,
+        /// Output directory (created if missing); several hosts get one subdirectory each.
+        #[arg(short = 'o', long = "output", value_name = "DIR")]
+        output: PathBuf,
+        /// Request file format for generated files.
+        #[arg(long, value_enum, default_value_t = GenerateFormat::Yaml)]
+        format: GenerateFormat,
+        /// Only keep entries for this host (repeatable; `*.example.com` matches subdomains).
+        #[arg(long = "host", value_name = "HOST")]
+        hosts: Vec<String>,
+        /// Only keep entries with this method (repeatable).
+        #[arg(long = "method", value_name = "METHOD")]
+        methods: Vec<String>,
+        /// Keep repeated calls instead of one file per method, host, and path.
+        #[arg(long)]
+        keep_duplicates: bool,
+        /// Replace tokens, API keys, passwords, and cookies with `${VAR}` placeholders and write the values to `DIR/.env`.
+        #[arg(long)]
+        lift_secrets: bool,
+    },
+    /// Convert a Bruno collection directory (or one `.bru` file) into request files.
+    Bruno {
+        /// Collection directory containing `bruno.json`, or a single `.bru` request.
+        #[arg(short = 'i', long = "input", value_name = "PATH")]
+        input: PathBuf,
+        /// Output directory (created if missing); folders become subdirectories.
+        #[arg(short = 'o', long = "output", value_name = "DIR")]
+        output: PathBuf,
+        /// Request file format for generated files.
+        #[arg(long, value_enum, default_value_t = GenerateFormat::Yaml)]
+        format: GenerateFormat,
+    },
+    /// Convert a `.http` / `.rest` file (VS Code REST Client, JetBrains) into request files.
+    Http {
+        /// The `.http` or `.rest` file.
+        #[arg(short = 'i', long = "input", value_name = "FILE")]
+        input: PathBuf,
+        /// Output directory (created if missing); one file per request.
+        #[arg(short = 'o', long = "output", value_name = "DIR")]
+        output: PathBuf,
+        /// Request file format for generated files.
+        #[arg(long, value_enum, default_value_t = GenerateFormat::Yaml)]
+        format: GenerateFormat,
+    },
+}
+
+#[derive(Subcommand)]
+enum ExportTarget {
+    /// Build an OpenAPI 3.0 document from request files: one operation per method and templated
+    /// path, with schemas inferred from request bodies and recorded responses.
+    Openapi {
+        /// Output spec file (`.yaml` / `.yml`, or `.json` for JSON); overwritten if it exists.
+        #[arg(short = 'o', long = "output", value_name = "FILE")]
+        output: PathBuf,
+        /// `info.title` of the document.
+        #[arg(long, value_name = "TITLE")]
+        title: Option<String>,
+        /// `info.version` of the document.
+        #[arg(long = "api-version", value_name = "VERSION")]
+        api_version: Option<String>,
+        /// HAR capture whose responses document the operations (repeatable).
+        #[arg(long = "har", value_name = "FILE")]
+        har: Vec<PathBuf>,
+        /// Request files and directories (searched recursively). Defaults to `.` when omitted.
+        #[arg(value_name = "PATH")]
+        paths: Vec<PathBuf>,
+    },
+}
+
 #[tokio::main]
 async fn main() -> ExitCode {
     let cli = Cli::parse();
@@ -152,9 +331,119 @@ async fn run(cli: Cli) -> std::result::Result<(), String> {
             input,
             output,
             format,
+            by_tag,
+            sync,
+            deprecate_removed,
         }) => {
-            cmd_generate::run_generate(input, output, (*format).into())?;
+            let options = nd_generate::GenerateOptions {
+                format: (*format).into(),
+                by_tag: *by_tag,
+                sync: *sync,
+                deprecate_removed: *deprecate_removed,
+            };
+            cmd_generate::run_generate(input, output, options)?;
         }
+        Some(Command::Import { source }) => match source {
+            ImportSource::Curl {
+                output,
+                format,
+                lift_secrets,
+                env_out,
+                command,
+            } => {
+                cmd_import::run_import_curl(ImportCurlOptions {
+                    command: command.clone(),
+                    output: output.clone(),
+                    format: format.map(Into::into),
+                    lift_secrets: *lift_secrets,
+                    env_out: env_out.clone(),
+                })?;
+            }
+            ImportSource::Postman {
+                input,
+                environment,
+                output,
+                format,
+                env_out,
+            } => {
+                cmd_import::run_import_postman(ImportPostmanOptions {
+                    input: input.clone(),
+                    environment: environment.clone(),
+                    output: output.clone(),
+                    format: (*format).into(),
+                    env_out: env_out.clone(),
+                })?;
+            }
+            ImportSource::Insomnia {
+                input,
+                output,
+                format,
+            } => {
+                cmd_import::run_import_insomnia(ImportInsomniaOptions {
+                    input: input.clone(),
+                    output: output.clone(),
+                    format: (*format).into(),
+                })?;
+            }
+            ImportSource::Har {
+                input,
+                output,
+                format,
+                hosts,
+                methods,
+                keep_duplicates,
+                lift_secrets,
+            } => {
+                cmd_import::run_import_har(ImportHarOptions {
+                    input: input.clone(),
+                    output: output.clone(),
+                    format: (*format).into(),
+                    hosts: hosts.clone(),
+                    methods: methods.clone(),
+                    keep_duplicates: *keep_duplicates,
+                    lift_secrets: *lift_secrets,
+                })?;
+            }
+            ImportSource::Bruno {
+                input,
+                output,
+                format,
+            } => {
+                cmd_import::run_import_bruno(ImportBrunoOptions {
+                    input: input.clone(),
+                    output: output.clone(),
+                    format: (*format).into(),
+                })?;
+            }
+            ImportSource::Http {
+                input,
+                output,
+                format,
+            } => {
+                cmd_import::run_import_http(ImportHttpOptions {
+                    input: input.clone(),
+                    output: output.clone(),
+                    format: (*format).into(),
+                })?;
+            }
+        },
+        Some(Command::Export { target }) => match target {
+            ExportTarget::Openapi {
+                output,
+                title,
+                api_version,
+                har,
+                paths,
+            } => {
+                cmd_export::run_export_openapi(ExportOpenApiOptions {
+                    paths: paths.clone(),
+                    output: output.clone(),
+                    title: title.clone(),
+                    version: api_version.clone(),
+                    har: har.clone(),
+                })?;
+            }
+        },
         Some(Command::Definitions { out_dir, out_file }) => {
             cmd_rhai_definitions::run_rhai_definitions(RhaiDefinitionsOptions {
                 out_dir: out_dir.clone(),
@@ -29,7 +29,7 @@ fn main() {
         schema_dir.join(filename),
         serde_json::to_string_pretty(&request).expect("request schema JSON"),
     )
-    .expect(format!("write {filename}").as_str());
+    .unwrap_or_else(|e| panic!("write {filename}: {e}"));
 
     // Write request yaml
     let filename = "request.schema.yaml";
@@ -38,5 +38,5 @@ fn main() {
         schema_dir.join(filename),
         serde_yaml::to_string(&request).expect("request schema YAML"),
     )
-    .expect(format!("write {filename}").as_str());
+    .unwrap_or_else(|e| panic!("write {filename}: {e}"));
 }
@@ -13,7 +13,12 @@ fn is_request_file(path: &Path) -> bool {
     return path
         .extension()
         .and_then(|e| e.to_str())
-        .map(|e| matches!(e.to_lowercase().as_str(), "json" | "yaml" | "yml"))
+        .map(|e| {
+            matches!(
+                e.to_lowercase().as_str(),
+                "json" | "yaml" | "yml" | "http" | "rest"
+            )
+        })
         .unwrap_or(false);
 }
 
@@ -25,7 +30,7 @@ fn is_rhai_file(path: &Path) -> bool {
         .unwrap_or(false);
 }
 
-/// List `*.json`, `*.yaml`, and `*.yml` files in `dir` only (not subdirectories).
+/// List `*.json`, `*.yaml`, `*.yml`, `*.http`, and `*.rest` files in `dir` only (not subdirectories).
 ///
 /// Missing `dir` yields an empty list. Paths are deduplicated and sorted.
 pub fn list_request_paths(dir: &Path) -> Result<Vec<PathBuf>> {
@@ -87,7 +92,7 @@ pub fn list_rhai_paths(dir: &Path) -> Result<Vec<PathBuf>> {
     return Ok(sorted);
 }
 
-/// A request file path that failed [`RequestFile::from_file`].
+/// A request file path that failed [`RequestFile::from_file_all`].
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SkippedRequestFile {
     pub path: PathBuf,
@@ -101,7 +106,7 @@ pub fn partition_valid_request_paths(paths: &[PathBuf]) -> (Vec<PathBuf>, Vec<Sk
     let mut valid = Vec::new();
     let mut skipped = Vec::new();
     for p in paths {
-        match RequestFile::from_file(p) {
+        match RequestFile::from_file_all(p) {
             Ok(_) => valid.push(p.clone()),
             Err(e) => skipped.push(SkippedRequestFile {
                 path: p.clone(),
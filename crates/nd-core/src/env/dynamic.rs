@@ -31,6 +31,8 @@ pub(crate) fn invoke(name: &str) -> Result<String> {
         "lorem_ipsum" => Ok(lorem_ipsum_snippet(&mut rng)),
         "random_iso_date_string" => Ok(random_iso_datetime(&mut rng)),
         "now" => Ok(Utc::now().format("%d-%m-%Y").to_string()),
+        "timestamp" => Ok(Utc::now().timestamp().to_string()),
+        "iso_timestamp" => Ok(Utc::now().to_rfc3339()),
         "random_username" => Ok(Username().fake()),
         "random_bool" => Ok(rng.gen_bool(0.5).to_string()),
         "random_date_past" => Ok(random_datetime_offset_days(&mut rng, -3650..0)),
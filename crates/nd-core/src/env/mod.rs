@@ -27,6 +27,12 @@ pub struct RuntimeEnv {
     inner: Arc<Mutex<HashMap<String, String>>>,
 }
 
+impl Default for RuntimeEnv {
+    fn default() -> Self {
+        return Self::new();
+    }
+}
+
 impl RuntimeEnv {
     pub fn new() -> Self {
         return Self {
@@ -35,7 +41,7 @@ impl RuntimeEnv {
         };
     }
 
-    pub fn with_env_files(self, paths: &Vec<PathBuf>) -> Result<Self> {
+    pub fn with_env_files(self, paths: &[PathBuf]) -> Result<Self> {
         for path in paths.iter() {
             self.merge_env_file(path)?;
         }
@@ -101,7 +107,7 @@ impl RuntimeEnv {
     /// Stringifies `value`, updates the runtime map, and merges into the persistence file at `path` (JSON or YAML per extension).
     pub fn persist(&self, key: &str, value: &str) -> Result<()> {
         if let Some(file) = &self.file {
-            persist::persist_key_in_file(self, &file, key, value)
+            persist::persist_key_in_file(self, file, key, value)
         } else {
             return Err(Error::NoRuntimePersistFile {
                 message: format!("Attempting to persist '{}'", key),
@@ -22,13 +22,28 @@ pub enum Error {
         source: serde_json::Error,
     },
 
-    #[error("unsupported request file format (use .json, .yaml, or .yml): {0}")]
+    #[error("unsupported request file format (use .json, .yaml, .yml, .http, or .rest): {0}")]
     UnsupportedFormat(PathBuf),
 
+    #[error("invalid .http file {path} line {line}: {message}")]
+    ParseHttpFile {
+        path: PathBuf,
+        line: usize,
+        message: String,
+    },
+
+    /// [`crate::model::request::RequestFile::from_file`] on a `.http` file with several requests.
+    #[error("{path} defines {count} requests; load it with RequestFile::from_file_all or pick one by name")]
+    MultipleRequests { path: PathBuf, count: usize },
+
     /// A `${VAR}` reference had no value in [`crate::RuntimeEnv`] or the process environment.
     #[error("missing environment variable '{0}' in template")]
     MissingTemplateVar(String),
 
+    /// A `${json:VAR}` placeholder whose value is not valid JSON.
+    #[error("variable '{name}' used as ${{json:{name}}} is not valid JSON: {message}")]
+    InvalidJsonTemplateVar { name: String, message: String },
+
     /// Unknown `${!name}` dynamic template function (see `env::dynamic`).
     #[error("unknown dynamic template '{0}'")]
     UnknownDynamicTemplate(String),
@@ -39,6 +54,16 @@ pub enum Error {
     #[error("HTTP error: {0}")]
     Http(#[from] reqwest::Error),
 
+    #[error("{label}: poll_until not met after {attempts} attempt(s) in {timeout_ms} ms")]
+    PollTimedOut {
+        label: String,
+        attempts: u32,
+        timeout_ms: u64,
+    },
+
+    #[error("invalid poll_until: {0}")]
+    InvalidPoll(String),
+
     #[error("Rhai script error: {0}")]
     Rhai(String),
 
@@ -39,6 +39,7 @@ pub(crate) fn merge_url_query(base: &str, query: &[(String, String)]) -> Result<
     return Ok(url.to_string());
 }
 
+/// Builds a [`HeaderMap`], appending so repeated names are all sent in order.
 pub(crate) fn header_map(pairs: &[(String, String)]) -> Result<HeaderMap> {
     let mut map = HeaderMap::new();
     for (k, v) in pairs {
@@ -46,7 +47,7 @@ pub(crate) fn header_map(pairs: &[(String, String)]) -> Result<HeaderMap> {
             .map_err(|_| Error::InvalidRequest(format!("invalid header name: {k}")))?;
         let value = HeaderValue::from_str(v)
             .map_err(|_| Error::InvalidRequest(format!("invalid header value for {k}")))?;
-        map.insert(name, value);
+        map.append(name, value);
     }
     return Ok(map);
 }
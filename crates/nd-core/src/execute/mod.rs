@@ -2,5 +2,7 @@
 
 pub mod client;
 pub mod format;
+pub mod poll;
 pub mod prepare;
+pub mod snippet;
 pub mod types;
@@ -0,0 +1,83 @@
+//! Polling shared by a request file's `poll_until` and Rhai `poll()`: attempt timing and the JSON
+//! condition that ends a request poll.
+
+use std::time::{Duration, Instant};
+
+use jsonpath_rust::JsonPath;
+use serde_json::Value;
+
+use crate::model::request::PollUntil;
+
+/// Waits between attempts: `interval`, multiplied by `backoff` after each one, until `timeout`.
+#[derive(Debug, Clone)]
+pub struct PollSchedule {
+    next: Duration,
+    backoff: f64,
+    /// `None` when `timeout` reaches past what an [`Instant`] can hold: no deadline in practice.
+    deadline: Option<Instant>,
+}
+
+impl PollSchedule {
+    /// Starts the clock for `timeout` now. A `backoff` below `1` is treated as `1`; callers reject
+    /// one that is not finite (see [`check_backoff`]).
+    pub fn new(interval: Duration, timeout: Duration, backoff: f64) -> Self {
+        return Self {
+            next: interval,
+            backoff: backoff.max(1.0),
+            deadline: Instant::now().checked_add(timeout),
+        };
+    }
+
+    /// How long to wait before the next attempt (never past the deadline), or `None` once the
+    /// deadline has passed.
+    pub fn next_delay(&mut self) -> Option<Duration> {
+        let left = match self.deadline {
+            Some(deadline) => deadline.checked_duration_since(Instant::now())?,
+            None => Duration::MAX,
+        };
+        if left.is_zero() {
+            return None;
+        }
+        let delay = self.next.min(left);
+        // Past the deadline the wait no longer matters; cap it there so a large `backoff` can't
+        // overflow a `Duration`.
+        self.next = Duration::try_from_secs_f64(self.next.as_secs_f64() * self.backoff)
+            .map_or(left, |next| next.min(left));
+        return Some(delay);
+    }
+}
+
+/// Fails unless `backoff` is a finite number (YAML's `.inf` / `.nan` parse as floats).
+pub fn check_backoff(backoff: f64) -> Result<(), String> {
+    if !backoff.is_finite() {
+        return Err(format!("`backoff` must be a finite number, got {backoff}"));
+    }
+    return Ok(());
+}
+
+impl From<&PollUntil> for PollSchedule {
+    fn from(until: &PollUntil) -> Self {
+        return Self::new(
+            Duration::from_millis(until.interval_ms),
+            Duration::from_millis(until.timeout_ms),
+            until.backoff,
+        );
+    }
+}
+
+/// Whether a response `body` meets `until`. Bodies that are not JSON never do; an invalid `path`
+/// is an error so a typo fails fast instead of polling until the timeout.
+pub fn condition_met(until: &PollUntil, body: &[u8]) -> Result<bool, String> {
+    let Ok(json) = serde_json::from_slice::<Value>(body) else {
+        return Ok(false);
+    };
+    let found = json
+        .query(&until.path)
+        .map_err(|e| format!("`{}`: {e}", until.path))?;
+    return Ok(match &until.equals {
+        Some(expected) => found.contains(&expected),
+        None => found
+            .iter()
+            .any(|v| !matches!(v, Value::Null | Value::Bool(false))),
+    });
+}
@@ -18,21 +18,21 @@ use crate::utils::template::{
     expand_json_value_with_overrides, expand_string_with_overrides,
 };
 
-/// Adds default `User-Agent`, `Accept`, and (when applicable) `Content-Type` before user headers.
-pub(crate) fn generate_computed_headers(spec: &HttpRequestSpec) -> HashMap<String, String> {
-    let mut headers = HashMap::new();
-    headers.insert(HTTP_HEADER_USER_AGENT.to_string(), USER_AGENT.to_string());
-    headers.insert(HTTP_HEADER_ACCEPT.to_string(), "*/*".to_string());
-
-    let has_content_type = spec
-        .headers
-        .keys()
-        .any(|k| k.to_ascii_lowercase() == HTTP_HEADER_CONTENT_TYPE);
-
-    if !has_content_type {
-        let content_type = spec.get_contenttype_hint();
-        if let Some(ct) = content_type {
-            headers.insert(HTTP_HEADER_CONTENT_TYPE.to_string(), ct.to_string());
+/// Default `User-Agent`, `Accept`, and (when applicable) `Content-Type`, skipping any name the
+/// request file already sets (case-insensitive). These go before user headers.
+pub(crate) fn generate_computed_headers(spec: &HttpRequestSpec) -> Vec<(String, String)> {
+    let user_sets = |name: &str| spec.headers.keys().any(|k| k.eq_ignore_ascii_case(name));
+    let mut headers = Vec::new();
+
+    if !user_sets(HTTP_HEADER_USER_AGENT) {
+        headers.push((HTTP_HEADER_USER_AGENT.to_string(), USER_AGENT.to_string()));
+    }
+    if !user_sets(HTTP_HEADER_ACCEPT) {
+        headers.push((HTTP_HEADER_ACCEPT.to_string(), "*/*".to_string()));
+    }
+    if !user_sets(HTTP_HEADER_CONTENT_TYPE) {
+        if let Some(ct) = spec.get_contenttype_hint() {
+            headers.push((HTTP_HEADER_CONTENT_TYPE.to_string(), ct.to_string()));
         }
     }
 
@@ -127,15 +127,14 @@ pub(crate) fn expand_http_request_with_overrides(
     }
 
     let mut headers = Vec::new();
-    let mut computed_headers = generate_computed_headers(spec);
 
-    for (k, v) in &spec.headers {
-        computed_headers.insert(k.clone().to_lowercase(), v.clone());
+    for (k, v) in &generate_computed_headers(spec) {
+        headers.push((k.clone(), expand_string_with_overrides(env, overrides, v)?));
     }
 
-    for (k, v) in &computed_headers {
+    for (k, v) in &spec.headers {
         headers.push((
-            expand_string_with_overrides(env, overrides, k)?,
+            expand_string_with_overrides(env, overrides, k)?.to_lowercase(),
             expand_string_with_overrides(env, overrides, v)?,
         ));
     }
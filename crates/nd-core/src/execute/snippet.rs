@@ -0,0 +1,275 @@
+//! Copy-pasteable code for a [`PreparedRequest`]: curl, HTTPie, Python `requests`, JavaScript
+//! `fetch`, and Rust `reqwest`.
+//!
+//! Snippets reproduce the expanded request as nativedoctor would send it (all headers, including
+//! computed defaults; query merged into the URL; timeout, redirect, and TLS settings).
+
+use std::fmt::Write as _;
+
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+
+use super::client::merge_url_query;
+use super::types::PreparedRequest;
+use crate::error::Result;
+
+/// Target language/tool for [`format_snippet`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SnippetLanguage {
+    Curl,
+    Httpie,
+    Python,
+    #[serde(alias = "js", alias = "fetch")]
+    Javascript,
+    Rust,
+}
+
+impl SnippetLanguage {
+    /// Stable lowercase identifier (`curl`, `httpie`, `python`, `javascript`, `rust`).
+    pub fn as_str(self) -> &'static str {
+        return match self {
+            Self::Curl => "curl",
+            Self::Httpie => "httpie",
+            Self::Python => "python",
+            Self::Javascript => "javascript",
+            Self::Rust => "rust",
+        };
+    }
+}
+
+impl std::str::FromStr for SnippetLanguage {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        return match s.to_ascii_lowercase().as_str() {
+            "curl" => Ok(Self::Curl),
+            "httpie" | "http" => Ok(Self::Httpie),
+            "python" | "requests" => Ok(Self::Python),
+            "javascript" | "js" | "fetch" => Ok(Self::Javascript),
+            "rust" | "reqwest" => Ok(Self::Rust),
+            other => Err(format!(
+                "unknown snippet language {other} (expected curl, httpie, python, javascript, or rust)"
+            )),
+        };
+    }
+}
+
+/// Renders `prep` as a snippet in `language`. Fails only if the URL cannot take the query string.
+pub fn format_snippet(prep: &PreparedRequest, language: SnippetLanguage) -> Result<String> {
+    let url = merge_url_query(&prep.url, &prep.query)?;
+    return Ok(match language {
+        SnippetLanguage::Curl => curl(prep, &url),
+        SnippetLanguage::Httpie => httpie(prep, &url),
+        SnippetLanguage::Python => python(prep, &url),
+        SnippetLanguage::Javascript => javascript(prep, &url),
+        SnippetLanguage::Rust => rust(prep, &url),
+    });
+}
+
+/// POSIX single-quoted word (`'` becomes `'\''`).
+fn shell_quote(s: &str) -> String {
+    return format!("'{}'", s.replace('\'', r"'\''"));
+}
+
+/// JSON string literals are valid string literals in Python and JavaScript.
+fn quoted(s: &str) -> String {
+    return serde_json::to_string(s).expect("string serializes to JSON");
+}
+
+fn body_text(prep: &PreparedRequest) -> Option<std::result::Result<&str, &[u8]>> {
+    let body = prep.body.as_deref()?;
+    return Some(std::str::from_utf8(body).map_err(|_| body));
+}
+
+/// Joins shell argument lines with `\` continuations. A comment cannot sit on a continued line,
+/// so notes go on their own lines before the command.
+fn shell_command(notes: &[String], lines: &[String]) -> String {
+    let mut s = String::new();
+    for note in notes {
+        let _ = writeln!(s, "# {note}");
+    }
+    s.push_str(&lines.join(" \\\n  "));
+    s.push('\n');
+    return s;
+}
+
+fn curl(prep: &PreparedRequest, url: &str) -> String {
+    let method = prep.method.as_str();
+    let mut lines = vec![match method {
+        "HEAD" => format!("curl --head {}", shell_quote(url)),
+        "GET" if prep.body.is_none() => format!("curl {}", shell_quote(url)),
+        _ => format!("curl -X {method} {}", shell_quote(url)),
+    }];
+    let mut notes = Vec::new();
+    for (k, v) in &prep.headers {
+        lines.push(format!("-H {}", shell_quote(&format!("{k}: {v}"))));
+    }
+    match body_text(prep) {
+        Some(Ok(text)) => lines.push(format!("--data-raw {}", shell_quote(text))),
+        Some(Err(bytes)) => {
+            notes.push(format!("Save the {} byte body as body.bin.", bytes.len()));
+            lines.push("--data-binary @body.bin".into());
+        }
+        None => {}
+    }
+    if prep.follow_redirects {
+        lines.push("-L".into());
+    }
+    if !prep.verify_tls {
+        lines.push("-k".into());
+    }
+    lines.push(format!("--max-time {}", prep.timeout_secs));
+    return shell_command(&notes, &lines);
+}
+
+fn httpie(prep: &PreparedRequest, url: &str) -> String {
+    let mut lines = vec![format!("http {} {}", prep.method.as_str(), shell_quote(url))];
+    let mut notes = Vec::new();
+    for (k, v) in &prep.headers {
+        lines.push(shell_quote(&format!("{k}:{v}")));
+    }
+    match body_text(prep) {
+        Some(Ok(text)) => lines.push(format!("--raw {}", shell_quote(text))),
+        Some(Err(bytes)) => {
+            notes.push(format!("Save the {} byte body as body.bin.", bytes.len()));
+            lines.push("< body.bin".into());
+        }
+        None => {}
+    }
+    if prep.follow_redirects {
+        lines.push("--follow".into());
+    }
+    if !prep.verify_tls {
+        lines.push("--verify=no".into());
+    }
+    lines.push(format!("--timeout={}", prep.timeout_secs));
+    return shell_command(&notes, &lines);
+}
+
+/// Header pairs with repeated names joined by `, ` (for dict-based clients).
+fn joined_headers(prep: &PreparedRequest) -> Vec<(String, String)> {
+    let mut out: Vec<(String, String)> = Vec::new();
+    for (k, v) in &prep.headers {
+        match out.iter_mut().find(|(name, _)| name.eq_ignore_ascii_case(k)) {
+            Some((_, existing)) => {
+                existing.push_str(", ");
+                existing.push_str(v);
+            }
+            None => out.push((k.clone(), v.clone())),
+        }
+    }
+    return out;
+}
+
+fn python(prep: &PreparedRequest, url: &str) -> String {
+    let mut s = String::from("import requests\n");
+    let data = match body_text(prep) {
+        Some(Ok(text)) => Some(quoted(text)),
+        Some(Err(bytes)) => {
+            s.push_str("import base64\n");
+            let b64 = base64::engine::general_purpose::STANDARD.encode(bytes);
+            Some(format!("base64.b64decode({})", quoted(&b64)))
+        }
+        None => None,
+    };
+    s.push_str("\nresponse = requests.request(\n");
+    let _ = writeln!(s, "    {},", quoted(prep.method.as_str()));
+    let _ = writeln!(s, "    {},", quoted(url));
+    s.push_str("    headers={\n");
+    for (k, v) in joined_headers(prep) {
+        let _ = writeln!(s, "        {}: {},", quoted(&k), quoted(&v));
+    }
+    s.push_str("    },\n");
+    if let Some(data) = data {
+        let _ = writeln!(s, "    data={data},");
+    }
+    let _ = writeln!(s, "    timeout={},", prep.timeout_secs);
+    let _ = writeln!(
+        s,
+        "    allow_redirects={},",
+        if prep.follow_redirects { "True" } else { "False" }
+    );
+    if !prep.verify_tls {
+        s.push_str("    verify=False,\n");
+    }
+    s.push_str(")\nprint(response.status_code)\nprint(response.text)\n");
+    return s;
+}
+
+fn javascript(prep: &PreparedRequest, url: &str) -> String {
+    let mut s = String::new();
+    if !prep.verify_tls {
+        s.push_str("// This request skips TLS verification, which fetch cannot do.\n");
+    }
+    let _ = writeln!(s, "const response = await fetch({}, {{", quoted(url));
+    let _ = writeln!(s, "  method: {},", quoted(prep.method.as_str()));
+    s.push_str("  headers: [\n");
+    for (k, v) in &prep.headers {
+        let _ = writeln!(s, "    [{}, {}],", quoted(k), quoted(v));
+    }
+    s.push_str("  ],\n");
+    match body_text(prep) {
+        Some(Ok(text)) => {
+            let _ = writeln!(s, "  body: {},", quoted(text));
+        }
+        Some(Err(bytes)) => {
+            let b64 = base64::engine::general_purpose::STANDARD.encode(bytes);
+            let _ = writeln!(
+                s,
+                "  body: Uint8Array.from(atob({}), (c) => c.charCodeAt(0)),",
+                quoted(&b64)
+            );
+        }
+        None => {}
+    }
+    let _ = writeln!(
+        s,
+        "  redirect: {},",
+        quoted(if prep.follow_redirects { "follow" } else { "manual" })
+    );
+    let _ = writeln!(s, "  signal: AbortSignal.timeout({}),", prep.timeout_secs * 1000);
+    s.push_str("});\nconsole.log(response.status);\nconsole.log(await response.text());\n");
+    return s;
+}
+
+fn rust(prep: &PreparedRequest, url: &str) -> String {
+    let mut s = String::from("let client = reqwest::Client::builder()\n");
+    let _ = writeln!(
+        s,
+        "    .timeout(std::time::Duration::from_secs({}))",
+        prep.timeout_secs
+    );
+    if !prep.follow_redirects {
+        s.push_str("    .redirect(reqwest::redirect::Policy::none())\n");
+    }
+    if !prep.verify_tls {
+        s.push_str("    .danger_accept_invalid_certs(true)\n");
+    }
+    s.push_str("    .build()?;\n");
+
+    let method = prep.method.as_str();
+    let method_expr = match method {
+        "GET" | "POST" | "PUT" | "DELETE" | "HEAD" | "OPTIONS" | "CONNECT" | "PATCH"
+        | "TRACE" => format!("reqwest::Method::{method}"),
+        other => format!("reqwest::Method::from_bytes(b{other:?})?"),
+    };
+    s.push_str("let response = client\n");
+    let _ = writeln!(s, "    .request({method_expr}, {url:?})");
+    for (k, v) in &prep.headers {
+        let _ = writeln!(s, "    .header({k:?}, {v:?})");
+    }
+    match body_text(prep) {
+        Some(Ok(text)) => {
+            let _ = writeln!(s, "    .body({text:?})");
+        }
+        Some(Err(bytes)) => {
+            let _ = writeln!(s, "    .body(vec!{bytes:?})");
+        }
+        None => {}
+    }
+    s.push_str("    .send()\n    .await?;\n");
+    s.push_str("println!(\"{}\", response.status());\n");
+    s.push_str("println!(\"{}\", response.text().await?);\n");
+    return s;
+}
@@ -6,6 +6,7 @@ use colored::Colorize;
 use reqwest::Method;
 
 use crate::model::request::RequestFile;
+use crate::validate::SchemaError;
 
 pub enum PrintOptions {
     Compact,
@@ -45,6 +46,9 @@ fn method_colored(method: &Method) -> colored::ColoredString {
 /// Outcome of a real HTTP call (or a synthetic row for dry-run — see field docs).
 #[derive(Debug, Clone)]
 pub struct ExecutionResult {
+    /// `request_id` of the [`crate::stream::events::Event::HttpRequestStarted`] /
+    /// `HttpResponseCompleted` pair for this send.
+    pub request_id: String,
     pub method: Method,
     /// From the request file’s optional `name` field.
     pub request_name: Option<String>,
@@ -58,7 +62,20 @@ pub struct ExecutionResult {
     pub streamed: bool,
     /// Time to receive the full response; zero for dry-run.
     pub duration: Duration,
+    /// Part of `duration` spent waiting for the status line and headers.
+    pub wait: Duration,
+    /// Response protocol as reported by the client (`HTTP/1.1`, `HTTP/2.0`, …).
+    pub http_version: String,
+    /// Expanded URL as sent, including the query string.
+    pub request_url: String,
+    /// Headers as sent (computed defaults first, then the request file's).
+    pub request_headers: Vec<(String, String)>,
+    /// Expanded request body as sent.
+    pub request_body: Option<Vec<u8>>,
     pub doc: RequestFile,
+    /// Where the response broke the contract declared for it (the linked OpenAPI operation, then
+    /// `response_schema`); empty when it matched or nothing was declared.
+    pub schema_errors: Vec<SchemaError>,
     /// The script that triggered the call to this request
     pub initiator_script: Option<PathBuf>,
 }
@@ -130,7 +147,9 @@ impl ExecutionResult {
 pub struct PreparedRequest {
     pub method: Method,
     pub url: String,
+    /// Query pairs in declaration order (names may repeat).
     pub query: Vec<(String, String)>,
+    /// Computed defaults first, then request-file headers in declaration order (names may repeat).
     pub headers: Vec<(String, String)>,
     pub body: Option<Vec<u8>>,
     pub timeout_secs: u64,
@@ -0,0 +1,395 @@
+//! HTTP Archive (HAR 1.2) types, and export of a run from its [`Session`] timeline.
+//!
+//! [`from_session`] pairs each [`Event::HttpResponseCompleted`] with the [`ExecutionResult`] the
+//! session kept for it (see [`Session::keep_results`]) and with the [`Event::HttpRequestStarted`]
+//! that opened it, so entries carry wall-clock start times, request and response headers and
+//! bodies, and `wait` / `receive` timings. [`Event::NewStepEncountered`] starts a new page, so
+//! HAR viewers group entries by script step.
+//!
+//! The same types deserialize browser captures leniently (missing fields default), for importers.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use base64::Engine;
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::execute::types::ExecutionResult;
+use crate::stream::events::Event;
+use crate::stream::Session;
+
+/// Top-level HAR document: `{"log": {...}}`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Har {
+    pub log: Log,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Log {
+    #[serde(default)]
+    pub version: String,
+    #[serde(default)]
+    pub creator: Creator,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pages: Vec<Page>,
+    #[serde(default)]
+    pub entries: Vec<Entry>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Creator {
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub version: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Page {
+    #[serde(default)]
+    pub started_date_time: String,
+    #[serde(default)]
+    pub id: String,
+    #[serde(default)]
+    pub title: String,
+    #[serde(default)]
+    pub page_timings: PageTimings,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PageTimings {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_content_load: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on_load: Option<f64>,
+}
+
+/// One request / response exchange.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Entry {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pageref: Option<String>,
+    #[serde(default)]
+    pub started_date_time: String,
+    /// Total time in milliseconds.
+    #[serde(default)]
+    pub time: f64,
+    #[serde(default)]
+    pub request: Request,
+    #[serde(default)]
+    pub response: Response,
+    #[serde(default)]
+    pub cache: serde_json::Value,
+    #[serde(default)]
+    pub timings: Timings,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Request {
+    #[serde(default)]
+    pub method: String,
+    #[serde(default)]
+    pub url: String,
+    #[serde(default)]
+    pub http_version: String,
+    #[serde(default)]
+    pub cookies: Vec<Cookie>,
+    #[serde(default)]
+    pub headers: Vec<NameValue>,
+    #[serde(default)]
+    pub query_string: Vec<NameValue>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub post_data: Option<PostData>,
+    #[serde(default = "unknown_size")]
+    pub headers_size: i64,
+    #[serde(default = "unknown_size")]
+    pub body_size: i64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Response {
+    #[serde(default)]
+    pub status: u16,
+    #[serde(default)]
+    pub status_text: String,
+    #[serde(default)]
+    pub http_version: String,
+    #[serde(default)]
+    pub cookies: Vec<Cookie>,
+    #[serde(default)]
+    pub headers: Vec<NameValue>,
+    #[serde(default)]
+    pub content: Content,
+    #[serde(default, rename = "redirectURL")]
+    pub redirect_url: String,
+    #[serde(default = "unknown_size")]
+    pub headers_size: i64,
+    #[serde(default = "unknown_size")]
+    pub body_size: i64,
+}
+
+/// Header or query parameter.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NameValue {
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Cookie {
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PostData {
+    #[serde(default)]
+    pub mime_type: String,
+    #[serde(default)]
+    pub text: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub params: Vec<PostParam>,
+}
+
+/// Form field of a url-encoded or multipart body; `file_name` is set for uploads.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PostParam {
+    #[serde(default)]
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_name: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Content {
+    #[serde(default)]
+    pub size: i64,
+    #[serde(default)]
+    pub mime_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+    /// `base64` when `text` holds a binary body.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub encoding: Option<String>,
+}
+
+/// Phase durations in milliseconds; `-1` means not measured.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Timings {
+    #[serde(default = "unmeasured")]
+    pub blocked: f64,
+    #[serde(default = "unmeasured")]
+    pub dns: f64,
+    #[serde(default = "unmeasured")]
+    pub connect: f64,
+    #[serde(default)]
+    pub send: f64,
+    #[serde(default)]
+    pub wait: f64,
+    #[serde(default)]
+    pub receive: f64,
+    #[serde(default = "unmeasured")]
+    pub ssl: f64,
+}
+
+fn unknown_size() -> i64 {
+    -1
+}
+
+fn unmeasured() -> f64 {
+    -1.0
+}
+
+fn millis(d: Duration) -> f64 {
+    return d.as_secs_f64() * 1000.0;
+}
+
+fn timestamp(at: DateTime<Utc>) -> String {
+    return at.to_rfc3339_opts(SecondsFormat::Millis, true);
+}
+
+fn name_values(pairs: &[(String, String)]) -> Vec<NameValue> {
+    return pairs
+        .iter()
+        .map(|(name, value)| NameValue {
+            name: name.clone(),
+            value: value.clone(),
+        })
+        .collect();
+}
+
+fn header<'a>(pairs: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    return pairs
+        .iter()
+        .find(|(k, _)| k.eq_ignore_ascii_case(name))
+        .map(|(_, v)| v.as_str());
+}
+
+/// Builds a HAR from everything `session` recorded so far.
+pub fn from_session(session: &Session) -> Har {
+    return from_timeline(session.started_at(), session.events(), session.results());
+}
+
+/// Builds a HAR from a session timeline. `results` are matched to the
+/// [`Event::HttpResponseCompleted`] events by `request_id`, so concurrent sends pair up correctly;
+/// exchanges without a result (dry-run, failed sends, or results not kept) are left out.
+pub fn from_timeline(
+    started_at: DateTime<Utc>,
+    events: &[Event],
+    results: &[ExecutionResult],
+) -> Har {
+    let mut pages: Vec<Page> = Vec::new();
+    let mut entries = Vec::new();
+    let mut started: HashMap<&str, Duration> = HashMap::new();
+    let results: HashMap<&str, &ExecutionResult> = results
+        .iter()
+        .map(|r| (r.request_id.as_str(), r))
+        .collect();
+
+    for event in events {
+        match event {
+            Event::NewStepEncountered { name, elapsed, .. } => {
+                pages.push(Page {
+                    started_date_time: timestamp(started_at + *elapsed),
+                    id: format!("step_{}", pages.len() + 1),
+                    title: name.clone(),
+                    page_timings: PageTimings::default(),
+                });
+            }
+            Event::HttpRequestStarted {
+                request_id,
+                elapsed,
+                ..
+            } => {
+                started.insert(request_id.as_str(), *elapsed);
+            }
+            Event::HttpResponseCompleted {
+                request_id,
+                elapsed,
+                ..
+            } => {
+                let Some(result) = results.get(request_id.as_str()) else {
+                    continue;
+                };
+                let started = started
+                    .remove(request_id.as_str())
+                    .unwrap_or_else(|| elapsed.saturating_sub(result.duration));
+                let mut entry = entry(started_at + started, result);
+                entry.pageref = pages.last().map(|p| p.id.clone());
+                entries.push(entry);
+            }
+            _ => {}
+        }
+    }
+
+    return Har {
+        log: Log {
+            version: "1.2".into(),
+            creator: Creator {
+                name: "nativedoctor".into(),
+                version: env!("CARGO_PKG_VERSION").into(),
+            },
+            pages,
+            entries,
+        },
+    };
+}
+
+fn entry(started: DateTime<Utc>, result: &ExecutionResult) -> Entry {
+    let query_string = reqwest::Url::parse(&result.request_url)
+        .map(|u| {
+            u.query_pairs()
+                .map(|(name, value)| NameValue {
+                    name: name.into_owned(),
+                    value: value.into_owned(),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let post_data = result.request_body.as_ref().map(|body| PostData {
+        mime_type: header(&result.request_headers, "content-type")
+            .unwrap_or_default()
+            .to_string(),
+        text: String::from_utf8_lossy(body).into_owned(),
+        params: Vec::new(),
+    });
+
+    let (text, encoding) = match std::str::from_utf8(&result.body) {
+        Ok(text) => (text.to_string(), None),
+        Err(_) => (
+            base64::engine::general_purpose::STANDARD.encode(&result.body),
+            Some("base64".to_string()),
+        ),
+    };
+    let status_text = reqwest::StatusCode::from_u16(result.status)
+        .ok()
+        .and_then(|s| s.canonical_reason())
+        .unwrap_or_default();
+
+    return Entry {
+        pageref: None,
+        started_date_time: timestamp(started),
+        time: millis(result.duration),
+        request: Request {
+            method: result.method.to_string(),
+            url: result.request_url.clone(),
+            http_version: result.http_version.clone(),
+            cookies: Vec::new(),
+            headers: name_values(&result.request_headers),
+            query_string,
+            post_data,
+            headers_size: -1,
+            body_size: result.request_body.as_ref().map_or(0, |b| b.len() as i64),
+        },
+        response: Response {
+            status: result.status,
+            status_text: status_text.to_string(),
+            http_version: result.http_version.clone(),
+            cookies: Vec::new(),
+            headers: name_values(&result.headers),
+            content: Content {
+                size: result.body.len() as i64,
+                mime_type: header(&result.headers, "content-type")
+                    .unwrap_or_default()
+                    .to_string(),
+                text: Some(text),
+                encoding,
+            },
+            redirect_url: header(&result.headers, "location")
+                .unwrap_or_default()
+                .to_string(),
+            headers_size: -1,
+            body_size: result.body.len() as i64,
+        },
+        cache: serde_json::json!({}),
+        timings: Timings {
+            blocked: -1.0,
+            dns: -1.0,
+            connect: -1.0,
+            send: 0.0,
+            wait: millis(result.wait),
+            receive: millis(result.duration.saturating_sub(result.wait)),
+            ssl: -1.0,
+        },
+        comment: result.request_name.clone(),
+    };
+}
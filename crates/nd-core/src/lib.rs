@@ -1,11 +1,13 @@
 //! Core library for **nativedoctor**: load request definitions (JSON/YAML), expand `${VAR}` and `${!name}` templates,
-//! run HTTP requests, and run Rhai scripts (including `import` of other scripts and request files).
+//! run HTTP requests, run Rhai scripts (including `import` of other scripts and request files), and export runs as HAR.
 
 pub mod discover;
 pub mod env;
 pub mod error;
 pub mod execute;
+pub mod har;
 pub mod model;
 pub mod rhai;
 pub mod stream;
 pub mod utils;
+pub mod validate;
@@ -0,0 +1,523 @@
+//! `.http` / `.rest` files (VS Code REST Client and JetBrains HTTP Client) → [`RequestFile`]s.
+//!
+//! A file holds any number of requests separated by `###` lines. Each request is an optional
+//! head of comments (`#` / `//`), metadata (`# @name login`, `# @no-redirect`, `# @timeout 10`) and
+//! file variables (`@host = api.example.com`), then the request line (`POST {{host}}/users
+//! HTTP/1.1`, or just a URL for `GET`), `?` / `&` query continuation lines, headers, a blank line,
+//! and the body. A body of `< ./payload.json` sends that file (relative to the `.http` file) as
+//! is; `<@ ./payload.json` also resolves the `{{…}}` variables in it.
+//!
+//! `{{name}}` references to file variables are inlined; any other `{{name}}` becomes `${name}`,
+//! resolved from the runtime environment like in request files. Common dynamic variables
+//! (`{{$guid}}`, `{{$randomInt}}`, `{{$timestamp}}`, `{{$processEnv NAME}}`, …) map to their
+//! `${!name}` or `${NAME}` equivalents. Request variables (`{{login.response.body.$.token}}`) are
+//! rejected: chain requests from a Rhai script instead. Response handlers (`> {% ... %}`) and
+//! output redirects (`>> file`) are ignored with a warning.
+//!
+//! The request name comes from `@name`, else the `###` separator text, else `<file stem> #<n>`.
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use base64::Engine;
+use regex::Regex;
+use tracing::{debug, warn};
+
+use crate::error::{Error, Result};
+use crate::model::pairs::NameValueList;
+use crate::model::request::{
+    HttpRequestSpec, RequestBody, RequestBodyKind, RequestBodyStructured, RequestFile,
+};
+use crate::utils::path::is_under_roots;
+use crate::utils::template::{dynamic_equivalent, quote_json_placeholders, var_ident};
+
+const METHODS: &[&str] = &[
+    "GET", "POST", "PUT", "PATCH", "DELETE", "HEAD", "OPTIONS", "TRACE", "CONNECT",
+];
+
+/// `true` when `path` has a `.http` or `.rest` extension (case-insensitive).
+pub fn is_http_file(path: &Path) -> bool {
+    return path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| matches!(e.to_lowercase().as_str(), "http" | "rest"))
+        .unwrap_or(false);
+}
+
+fn template_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    return RE.get_or_init(|| Regex::new(r"\{\{\s*([^{}]*?)\s*\}\}").expect("valid regex"));
+}
+
+fn variable_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    return RE.get_or_init(|| {
+        Regex::new(r"^@([A-Za-z_][A-Za-z0-9_.\-]*)\s*=\s*(.*)$").expect("valid regex")
+    });
+}
+
+/// One `###` block before templates are resolved. `line` fields are 1-based.
+#[derive(Debug, Default)]
+struct RawRequest {
+    title: Option<String>,
+    name: Option<String>,
+    comments: Vec<String>,
+    no_redirect: bool,
+    timeout_secs: Option<u64>,
+    method: String,
+    url: String,
+    line: usize,
+    headers: Vec<(String, String, usize)>,
+    body: Vec<String>,
+    body_line: usize,
+}
+
+enum State {
+    Head,
+    Headers,
+    Body,
+    Handler,
+}
+
+/// Read and parse a `.http` / `.rest` file. Every returned request has a name and `_path` set.
+pub fn load_http_file(path: &Path) -> Result<Vec<RequestFile>> {
+    let text = std::fs::read_to_string(path)?;
+    let files = parse_http_file(&text, path)?;
+    debug!(
+        path = %path.display(),
+        count = files.len(),
+        "loaded http file"
+    );
+    return Ok(files);
+}
+
+/// Parse `.http` text; `path` is used for error messages, `< file` bodies, default names, and
+/// [`RequestFile::_path`].
+pub fn parse_http_file(text: &str, path: &Path) -> Result<Vec<RequestFile>> {
+    let err = |line: usize, message: String| Error::ParseHttpFile {
+        path: path.to_path_buf(),
+        line,
+        message,
+    };
+
+    let mut variables: HashMap<String, String> = HashMap::new();
+    let mut raw: Vec<RawRequest> = Vec::new();
+    let mut current = RawRequest::default();
+    let mut state = State::Head;
+
+    for (index, line) in text.lines().enumerate() {
+        let number = index + 1;
+        let trimmed = line.trim();
+
+        if let Some(title) = trimmed.strip_prefix("###") {
+            if !current.method.is_empty() {
+                raw.push(std::mem::take(&mut current));
+            }
+            current = RawRequest::default();
+            let title = title.trim();
+            current.title = (!title.is_empty()).then(|| title.to_string());
+            state = State::Head;
+            continue;
+        }
+
+        match state {
+            State::Head => {
+                if trimmed.is_empty() {
+                    continue;
+                }
+                if let Some(comment) = comment_text(trimmed) {
+                    apply_comment(&mut current, comment).map_err(|m| err(number, m))?;
+                    continue;
+                }
+                if let Some(caps) = variable_re().captures(trimmed) {
+                    variables.insert(caps[1].to_string(), caps[2].trim().to_string());
+                    continue;
+                }
+                let (method, url) = request_line(trimmed);
+                current.method = method;
+                current.url = url;
+                current.line = number;
+                state = State::Headers;
+            }
+            State::Headers => {
+                if trimmed.is_empty() {
+                    state = State::Body;
+                    current.body_line = number + 1;
+                    continue;
+                }
+                if comment_text(trimmed).is_some() {
+                    continue;
+                }
+                if current.headers.is_empty()
+                    && (trimmed.starts_with('?') || trimmed.starts_with('&'))
+                {
+                    current.url.push_str(trimmed);
+                    continue;
+                }
+                let Some((name, value)) = trimmed.split_once(':') else {
+                    return Err(err(
+                        number,
+                        format!("expected a `Name: value` header or a blank line, got `{trimmed}`"),
+                    ));
+                };
+                current
+                    .headers
+                    .push((name.trim().to_string(), value.trim().to_string(), number));
+            }
+            State::Body => {
+                // `> {% ... %}`, `> handler.js`, `>> out.json`, `<> previous.json`: everything
+                // from here to the next `###` belongs to the response, not the body.
+                if trimmed.starts_with('>') || trimmed.starts_with("<>") {
+                    warn!(
+                        path = %path.display(),
+                        line = number,
+                        "response handlers and output redirects are not supported; ignored"
+                    );
+                    state = State::Handler;
+                    continue;
+                }
+                current.body.push(line.to_string());
+            }
+            State::Handler => {}
+        }
+    }
+    if !current.method.is_empty() {
+        raw.push(current);
+    }
+
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let resolver = Resolver {
+        variables: &variables,
+    };
+    let mut files = Vec::with_capacity(raw.len());
+    for (index, request) in raw.into_iter().enumerate() {
+        let name = request
+            .name
+            .clone()
+            .or_else(|| request.title.clone())
+            .unwrap_or_else(|| format!("{stem} #{}", index + 1));
+        let file =
+            request_file(request, name, path, &resolver).map_err(|(line, m)| err(line, m))?;
+        files.push(file);
+    }
+    return Ok(files);
+}
+
+/// Text of a `#` / `//` comment line, or `None`.
+fn comment_text(line: &str) -> Option<&str> {
+    return line
+        .strip_prefix("//")
+        .or_else(|| line.strip_prefix('#'))
+        .map(str::trim);
+}
+
+/// Applies `@name` / `@no-redirect` / `@timeout` metadata; other comments become the description.
+fn apply_comment(request: &mut RawRequest, comment: &str) -> std::result::Result<(), String> {
+    let Some(meta) = comment.strip_prefix('@') else {
+        if !comment.is_empty() {
+            request.comments.push(comment.to_string());
+        }
+        return Ok(());
+    };
+    let (key, value) = match meta.find(|c: char| c.is_whitespace() || c == '=') {
+        Some(i) => (
+            &meta[..i],
+            meta[i..].trim_start_matches(|c: char| c.is_whitespace() || c == '='),
+        ),
+        None => (meta, ""),
+    };
+    match key {
+        "name" => {
+            if value.trim().is_empty() {
+                return Err("`@name` needs a value".into());
+            }
+            request.name = Some(value.trim().to_string());
+        }
+        "no-redirect" => request.no_redirect = true,
+        "timeout" => {
+            let secs = value
+                .trim()
+                .parse::<u64>()
+                .map_err(|_| format!("`@timeout` expects whole seconds, got `{}`", value.trim()))?;
+            request.timeout_secs = Some(secs);
+        }
+        other => debug!(metadata = other, "ignoring unsupported .http metadata"),
+    }
+    return Ok(());
+}
+
+/// `METHOD URL [HTTP/x]`, or a bare URL (`GET`).
+fn request_line(line: &str) -> (String, String) {
+    let mut rest = line;
+    let mut method = "GET".to_string();
+    if let Some((first, tail)) = line.split_once(char::is_whitespace) {
+        if METHODS.contains(&first.to_ascii_uppercase().as_str()) {
+            method = first.to_ascii_uppercase();
+            rest = tail.trim();
+        }
+    }
+    if let Some((url, version)) = rest.rsplit_once(char::is_whitespace) {
+        if version.to_ascii_uppercase().starts_with("HTTP/") {
+            rest = url.trim_end();
+        }
+    }
+    return (method, rest.to_string());
+}
+
+struct Resolver<'a> {
+    variables: &'a HashMap<String, String>,
+}
+
+impl Resolver<'_> {
+    /// Rewrites `{{...}}` references in `text` (see the module docs).
+    fn resolve(&self, text: &str) -> std::result::Result<String, String> {
+        return self.resolve_with(text, &mut Vec::new());
+    }
+
+    fn resolve_with(
+        &self,
+        text: &str,
+        stack: &mut Vec<String>,
+    ) -> std::result::Result<String, String> {
+        let mut out = String::with_capacity(text.len());
+        let mut last = 0;
+        for caps in template_re().captures_iter(text) {
+            let whole = caps.get(0).expect("match");
+            out.push_str(&text[last..whole.start()]);
+            last = whole.end();
+
+            let name = &caps[1];
+            if let Some(dynamic) = name.strip_prefix('$') {
+                out.push_str(&dynamic_variable(dynamic)?);
+                continue;
+            }
+            if let Some(value) = self.variables.get(name) {
+                if stack.iter().any(|n| n == name) {
+                    return Err(format!(
+                        "file variables refer to each other in a cycle: {} → {name}",
+                        stack.join(" → ")
+                    ));
+                }
+                stack.push(name.to_string());
+                out.push_str(&self.resolve_with(value, stack)?);
+                stack.pop();
+                continue;
+            }
+            if name.contains(".response.") || name.contains(".request.") {
+                return Err(format!(
+                    "request variables like {{{{{name}}}}} are not supported; chain requests from a Rhai script"
+                ));
+            }
+            out.push_str(&format!("${{{}}}", var_ident(name)));
+        }
+        out.push_str(&text[last..]);
+        return Ok(out);
+    }
+}
+
+/// `{{$name args}}` → `${!name}` / `${NAME}`.
+fn dynamic_variable(expr: &str) -> std::result::Result<String, String> {
+    let mut words = expr.split_whitespace();
+    let head = words.next().unwrap_or_default();
+    let head = head.split('(').next().unwrap_or_default();
+    let ours = match head {
+        "processEnv" | "dotenv" => {
+            let Some(var) = words.next() else {
+                return Err(format!("`{{{{${head}}}}}` needs a variable name"));
+            };
+            return Ok(format!("${{{}}}", var_ident(var.trim_start_matches('%'))));
+        }
+        // `{{$timestamp -1 d}}` offsets have no equivalent.
+        "timestamp" if words.next().is_some() => None,
+        _ => dynamic_equivalent(head),
+    };
+    let Some(ours) = ours else {
+        return Err(format!(
+            "dynamic variable {{{{${expr}}}}} has no nativedoctor equivalent"
+        ));
+    };
+    return Ok(format!("${{!{ours}}}"));
+}
+
+fn request_file(
+    raw: RawRequest,
+    name: String,
+    path: &Path,
+    resolver: &Resolver,
+) -> std::result::Result<RequestFile, (usize, String)> {
+    let url = resolver.resolve(&raw.url).map_err(|m| (raw.line, m))?;
+
+    let mut headers = NameValueList::new();
+    let mut content_type = String::new();
+    let mut graphql = false;
+    for (header, value, line) in &raw.headers {
+        let value = resolver.resolve(value).map_err(|m| (*line, m))?;
+        if header.eq_ignore_ascii_case("x-request-type") && value.eq_ignore_ascii_case("graphql") {
+            graphql = true;
+            continue;
+        }
+        if header.eq_ignore_ascii_case("content-type") {
+            content_type = value.to_ascii_lowercase();
+        }
+        headers.append(header.clone(), value);
+    }
+
+    let mut lines = raw.body;
+    while lines.last().is_some_and(|l| l.trim().is_empty()) {
+        lines.pop();
+    }
+    let body = if lines.is_empty() {
+        None
+    } else {
+        let text = match lines.as_slice() {
+            [single] if single.trim_start().starts_with('<') => {
+                read_body_file(single.trim_start(), path).map_err(|m| (raw.body_line, m))?
+            }
+            _ => BodyText::Text(lines.join("\n")),
+        };
+        Some(match text {
+            BodyText::Binary(bytes) => RequestBody::Structured(RequestBodyStructured {
+                body_type: RequestBodyKind::Binary,
+                content: base64::engine::general_purpose::STANDARD
+                    .encode(bytes)
+                    .into(),
+            }),
+            BodyText::Text(text) => {
+                let text = resolver.resolve(&text).map_err(|m| (raw.body_line, m))?;
+                body_from_text(text, &content_type, graphql, true)
+                    .map_err(|m| (raw.body_line, m))?
+            }
+            BodyText::Verbatim(text) => body_from_text(text, &content_type, graphql, false)
+                .map_err(|m| (raw.body_line, m))?,
+        })
+    };
+
+    let description = (!raw.comments.is_empty()).then(|| raw.comments.join("\n"));
+    return Ok(RequestFile {
+        version: nd_constants::REQUEST_FILE_DEFAULT_VERSION.into(),
+        name: Some(name),
+        request: HttpRequestSpec {
+            method: raw.method,
+            url,
+            summary: None,
+            description,
+            tags: Vec::new(),
+            deprecated: false,
+            query: NameValueList::new(),
+            headers,
+            body,
+            timeout_secs: raw.timeout_secs,
+            follow_redirects: !raw.no_redirect,
+            verify_tls: true,
+        },
+        openapi: None,
+        response_schema: None,
+        poll_until: None,
+        _path: Some(path.to_path_buf()),
+    });
+}
+
+enum BodyText {
+    /// Inline or `<@` text: `{{…}}` variables are resolved.
+    Text(String),
+    /// `<` file text, sent as it is.
+    Verbatim(String),
+    Binary(Vec<u8>),
+}
+
+/// `< ./file` (raw) or `<@ ./file` (with variables): the file's contents, relative to `http_path`.
+///
+/// The file must stay (after following symlinks) inside the `.http` file's directory, so a
+/// workspace file cannot pull in arbitrary files from the machine it runs on.
+fn read_body_file(line: &str, http_path: &Path) -> std::result::Result<BodyText, String> {
+    let (with_variables, rest) = match line[1..].strip_prefix('@') {
+        Some(rest) => (true, rest.trim()),
+        None => (false, line[1..].trim()),
+    };
+    if rest.contains("{{") {
+        return Err(format!("body file path `{rest}` cannot use variables"));
+    }
+    if Path::new(rest).is_absolute() {
+        return Err(format!(
+            "body file path `{rest}` must be relative to the .http file"
+        ));
+    }
+    let base = http_path.parent().unwrap_or_else(|| Path::new("."));
+    let file = base.join(rest);
+    let canon = std::fs::canonicalize(&file).map_err(|e| format!("{}: {e}", file.display()))?;
+    let root = std::fs::canonicalize(base).map_err(|e| format!("{}: {e}", base.display()))?;
+    if !is_under_roots(&canon, &[root]) {
+        return Err(format!(
+            "body file `{rest}` is outside the .http file's directory"
+        ));
+    }
+    let bytes = std::fs::read(&canon).map_err(|e| format!("{}: {e}", file.display()))?;
+    return Ok(match String::from_utf8(bytes) {
+        Ok(text) if with_variables => BodyText::Text(text),
+        Ok(text) => BodyText::Verbatim(text),
+        Err(e) => BodyText::Binary(e.into_bytes()),
+    });
+}
+
+/// Request body for `text`. With `templated`, whole-value `${VAR}` placeholders in JSON are quoted
+/// first (see [`quote_json_placeholders`]); otherwise JSON is parsed exactly as written.
+fn body_from_text(
+    text: String,
+    content_type: &str,
+    graphql: bool,
+    templated: bool,
+) -> std::result::Result<RequestBody, String> {
+    let json_text = |text: &str| {
+        if templated {
+            return quote_json_placeholders(text);
+        }
+        return text.to_string();
+    };
+    if graphql {
+        let (query, variables) = match text.split_once("\n\n") {
+            Some((query, variables)) => (query.trim(), Some(variables.trim())),
+            None => (text.trim(), None),
+        };
+        let mut content = serde_json::json!({ "query": query });
+        if let Some(variables) = variables.filter(|v| !v.is_empty()) {
+            let variables =
+                serde_json::from_str::<serde_json::Value>(&json_text(variables))
+                    .map_err(|e| format!("GraphQL variables are not valid JSON: {e}"))?;
+            content["variables"] = variables;
+        }
+        return Ok(RequestBody::Structured(RequestBodyStructured {
+            body_type: RequestBodyKind::Graphql,
+            content,
+        }));
+    }
+
+    let (kind, text) = if content_type.contains("json") {
+        if let Ok(value) = serde_json::from_str(&json_text(&text)) {
+            return Ok(RequestBody::Structured(RequestBodyStructured {
+                body_type: RequestBodyKind::Json,
+                content: value,
+            }));
+        }
+        (RequestBodyKind::Text, text)
+    } else if content_type.starts_with("application/x-www-form-urlencoded") {
+        let joined: String = text.lines().map(str::trim).collect();
+        (RequestBodyKind::XWwwFormUrlencoded, joined)
+    } else if content_type.starts_with("multipart/form-data") {
+        (
+            RequestBodyKind::FormData,
+            text.lines().collect::<Vec<_>>().join("\r\n"),
+        )
+    } else if content_type.contains("xml") {
+        (RequestBodyKind::Xml, text)
+    } else {
+        (RequestBodyKind::Text, text)
+    };
+    return Ok(RequestBody::Structured(RequestBodyStructured {
+        body_type: kind,
+        content: text.into(),
+    }));
+}
@@ -1,3 +1,5 @@
+pub mod http_file;
+pub mod pairs;
 pub mod request;
 
 /// Inserts [`$schema`](https://json-schema.org/draft/2020-12/json-schema-core.html#name-the-schema-keyword)
@@ -0,0 +1,227 @@
+//! Ordered, repeatable `name`/`value` pairs for request `query` and `headers`.
+//!
+//! Request files may write either the **map** form (`{ page: "1" }`, one value per name) or the
+//! **list** form (`[{ name: tag, value: a }, { name: tag, value: b }]`) when a name repeats or
+//! order matters. Both deserialize into [`NameValueList`], which keeps declaration order.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use schemars::gen::SchemaGenerator;
+use schemars::schema::Schema;
+use schemars::JsonSchema;
+use serde::de::{MapAccess, SeqAccess, Visitor};
+use serde::ser::{SerializeMap, SerializeSeq};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// One entry of the list form.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct NameValue {
+    pub name: String,
+    pub value: String,
+}
+
+/// Ordered pairs; names may repeat. Serializes as a map when every name is unique, otherwise as a
+/// list of [`NameValue`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NameValueList(Vec<(String, String)>);
+
+impl NameValueList {
+    pub fn new() -> Self {
+        return Self(Vec::new());
+    }
+
+    pub fn len(&self) -> usize {
+        return self.0.len();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.0.is_empty();
+    }
+
+    /// Adds a pair after existing ones, keeping earlier values for the same name.
+    pub fn append(&mut self, name: impl Into<String>, value: impl Into<String>) {
+        self.0.push((name.into(), value.into()));
+    }
+
+    /// Replaces every value for `name` with a single pair (at the first occurrence, else at the end).
+    pub fn insert(&mut self, name: impl Into<String>, value: impl Into<String>) {
+        let name = name.into();
+        let value = value.into();
+        match self.0.iter().position(|(k, _)| *k == name) {
+            Some(i) => {
+                self.0[i].1 = value;
+                let mut seen = false;
+                self.0.retain(|(k, _)| {
+                    if *k != name {
+                        return true;
+                    }
+                    let keep = !seen;
+                    seen = true;
+                    return keep;
+                });
+            }
+            None => self.0.push((name, value)),
+        }
+    }
+
+    /// First value for `name` (exact match).
+    pub fn get(&self, name: &str) -> Option<&str> {
+        return self
+            .0
+            .iter()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.as_str());
+    }
+
+    /// Every value for `name` in declaration order (exact match).
+    pub fn get_all<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        return self
+            .0
+            .iter()
+            .filter(move |(k, _)| k == name)
+            .map(|(_, v)| v.as_str());
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &str> {
+        return self.0.iter().map(|(k, _)| k.as_str());
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, (String, String)> {
+        return self.0.iter();
+    }
+
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, (String, String)> {
+        return self.0.iter_mut();
+    }
+
+    pub fn as_slice(&self) -> &[(String, String)] {
+        return &self.0;
+    }
+
+    fn has_unique_names(&self) -> bool {
+        let mut seen = HashSet::with_capacity(self.0.len());
+        return self.0.iter().all(|(k, _)| seen.insert(k.as_str()));
+    }
+}
+
+impl<'a> IntoIterator for &'a NameValueList {
+    type Item = &'a (String, String);
+    type IntoIter = std::slice::Iter<'a, (String, String)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        return self.0.iter();
+    }
+}
+
+impl IntoIterator for NameValueList {
+    type Item = (String, String);
+    type IntoIter = std::vec::IntoIter<(String, String)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        return self.0.into_iter();
+    }
+}
+
+impl<K: Into<String>, V: Into<String>> FromIterator<(K, V)> for NameValueList {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        return Self(
+            iter.into_iter()
+                .map(|(k, v)| (k.into(), v.into()))
+                .collect(),
+        );
+    }
+}
+
+impl From<Vec<(String, String)>> for NameValueList {
+    fn from(pairs: Vec<(String, String)>) -> Self {
+        return Self(pairs);
+    }
+}
+
+/// Map order is unspecified; names are sorted so the result is stable.
+impl From<HashMap<String, String>> for NameValueList {
+    fn from(map: HashMap<String, String>) -> Self {
+        let mut pairs: Vec<(String, String)> = map.into_iter().collect();
+        pairs.sort();
+        return Self(pairs);
+    }
+}
+
+impl Serialize for NameValueList {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.has_unique_names() {
+            let mut map = serializer.serialize_map(Some(self.0.len()))?;
+            for (k, v) in &self.0 {
+                map.serialize_entry(k, v)?;
+            }
+            return map.end();
+        }
+        let mut seq = serializer.serialize_seq(Some(self.0.len()))?;
+        for (k, v) in &self.0 {
+            seq.serialize_element(&NameValue {
+                name: k.clone(),
+                value: v.clone(),
+            })?;
+        }
+        return seq.end();
+    }
+}
+
+struct NameValueListVisitor;
+
+impl<'de> Visitor<'de> for NameValueListVisitor {
+    type Value = NameValueList;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return f.write_str("a map of strings or a list of { name, value } objects");
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut access: A) -> Result<Self::Value, A::Error> {
+        let mut pairs = Vec::with_capacity(access.size_hint().unwrap_or(0));
+        while let Some((k, v)) = access.next_entry::<String, String>()? {
+            pairs.push((k, v));
+        }
+        return Ok(NameValueList(pairs));
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut access: A) -> Result<Self::Value, A::Error> {
+        let mut pairs = Vec::with_capacity(access.size_hint().unwrap_or(0));
+        while let Some(entry) = access.next_element::<NameValue>()? {
+            pairs.push((entry.name, entry.value));
+        }
+        return Ok(NameValueList(pairs));
+    }
+
+    fn visit_unit<E: serde::de::Error>(self) -> Result<Self::Value, E> {
+        return Ok(NameValueList::new());
+    }
+}
+
+impl<'de> Deserialize<'de> for NameValueList {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        return deserializer.deserialize_any(NameValueListVisitor);
+    }
+}
+
+// Schema-only mirror of the two shapes accepted by `NameValueList`'s `Deserialize`.
+/// A string map, or an ordered list of `{ name, value }` entries.
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+#[serde(untagged)]
+enum NameValueListSchema {
+    /// One value per name.
+    Map(HashMap<String, String>),
+    /// Ordered entries; names may repeat.
+    List(Vec<NameValue>),
+}
+
+impl JsonSchema for NameValueList {
+    fn schema_name() -> String {
+        return "NameValueList".to_string();
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        return NameValueListSchema::json_schema(gen);
+    }
+}
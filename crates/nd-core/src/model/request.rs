@@ -8,11 +8,15 @@
 
 use crate::env::RuntimeEnv;
 use crate::error::{Error, Result};
-use crate::execute::client::{build_client, send_request};
+use crate::execute::client::{build_client, merge_url_query, send_request};
+use crate::execute::poll::{check_backoff, condition_met, PollSchedule};
 use crate::execute::prepare::expand_http_request_with_overrides;
 use crate::execute::types::{ExecutionResult, PreparedRequest};
+use crate::model::http_file::{is_http_file, load_http_file};
+use crate::model::pairs::NameValueList;
 use crate::stream::events::Event;
 use crate::stream::{MutexSession, Session};
+use crate::validate::{emit_asserts, openapi, schema};
 use nanoid::nanoid;
 use nd_constants::REQUEST_FILE_DEFAULT_VERSION;
 use reqwest::header::{CONTENT_LENGTH, CONTENT_TYPE};
@@ -59,12 +63,76 @@ pub struct RequestFile {
     #[serde(default)]
     pub name: Option<String>,
     pub request: HttpRequestSpec,
+    /// OpenAPI operation this file was generated from (`nativedoctor generate`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub openapi: Option<OpenApiLink>,
+    /// JSON Schema the response body must match, checked after every run (see
+    /// [`crate::validate::schema`]).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub response_schema: Option<ResponseSchema>,
+    /// Re-send the request until its JSON response meets a condition (job / status endpoints that
+    /// answer `202` until the work is done).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub poll_until: Option<PollUntil>,
     /// Set only by [`RequestFile::from_file`]; not part of the on-disk format.
     #[serde(skip)]
     #[schemars(skip)]
     pub _path: Option<PathBuf>,
 }
 
+/// Link from a generated request file back to its OpenAPI operation. With `spec` set, each
+/// response is checked against the operation (see [`crate::validate::openapi`]).
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, JsonSchema)]
+pub struct OpenApiLink {
+    /// Path to the OpenAPI / Swagger document, relative to this request file.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spec: Option<String>,
+    /// The operation's `operationId`, or `method-path` when it has none. `generate --sync`
+    /// matches files to operations by it.
+    pub operation_id: String,
+}
+
+/// `response_schema`: a path to a JSON / YAML schema file (relative to this request file), or
+/// the schema itself.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema)]
+#[serde(untagged)]
+pub enum ResponseSchema {
+    Path(String),
+    Inline(serde_json::Value),
+}
+
+/// `poll_until`: when a response counts as done, and how long to keep asking.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, JsonSchema)]
+pub struct PollUntil {
+    /// JSONPath query (RFC 9535) into the response body, e.g. `$.status` or
+    /// `$.jobs[?@.state == 'done']`.
+    pub path: String,
+    /// Value a match must equal. When omitted, any match other than `null` / `false` will do.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub equals: Option<serde_json::Value>,
+    /// Wait between attempts, in milliseconds.
+    #[serde(default = "default_poll_interval_ms")]
+    pub interval_ms: u64,
+    /// Give up (and fail the run) after this many milliseconds.
+    #[serde(default = "default_poll_timeout_ms")]
+    pub timeout_ms: u64,
+    /// Multiply the wait by this after every attempt (`1` keeps it fixed).
+    #[serde(default = "default_poll_backoff")]
+    pub backoff: f64,
+}
+
+fn default_poll_interval_ms() -> u64 {
+    return 1_000;
+}
+
+fn default_poll_timeout_ms() -> u64 {
+    return 60_000;
+}
+
+fn default_poll_backoff() -> f64 {
+    return 1.0;
+}
+
 impl RequestFile {
     /// Default timeout when `request.timeout_secs` is omitted (seconds).
     pub fn default_timeout_secs() -> u64 {
@@ -78,11 +146,23 @@ impl RequestFile {
         return serde_json::to_value(&schema).expect("RequestFile JsonSchema serializes to JSON");
     }
 
-    /// Read and deserialize a request file. Extension must be `.json`, `.yaml`, or `.yml`.
+    /// Read and deserialize a request file. Extension must be `.json`, `.yaml`, `.yml`, or (for a
+    /// file with exactly one request) `.http` / `.rest`.
     ///
     /// Returns the parsed document and the **parent directory** of `path`, used to resolve
     /// [`RequestFile::post_script`] paths.
     pub fn from_file(path: &Path) -> Result<RequestFile> {
+        if is_http_file(path) {
+            let mut files = load_http_file(path)?;
+            if files.len() != 1 {
+                return Err(Error::MultipleRequests {
+                    path: path.to_path_buf(),
+                    count: files.len(),
+                });
+            }
+            return Ok(files.remove(0));
+        }
+
         let ext = path
             .extension()
             .and_then(|e| e.to_str())
@@ -116,6 +196,15 @@ impl RequestFile {
         return Ok(file);
     }
 
+    /// Every request in `path`: all requests of a `.http` / `.rest` file (see
+    /// [`crate::model::http_file`]), otherwise the single document from [`RequestFile::from_file`].
+    pub fn from_file_all(path: &Path) -> Result<Vec<RequestFile>> {
+        if is_http_file(path) {
+            return load_http_file(path);
+        }
+        return Ok(vec![Self::from_file(path)?]);
+    }
+
     pub async fn execute(
         &self,
         session: Arc<Mutex<Session>>,
@@ -126,11 +215,69 @@ impl RequestFile {
 
     /// Run the HTTP request after expanding templates. `var_overrides` take precedence over
     /// [`RuntimeEnv`] for `${VAR}` placeholders (not `${!name}` dynamics).
+    ///
+    /// With [`RequestFile::poll_until`] the request is re-sent until the condition holds, each
+    /// attempt recorded as an [`Event::PollAttempt`]; the contract checks run on the final response.
     pub async fn execute_with_overrides(
         &self,
         session: Arc<Mutex<Session>>,
         var_overrides: Option<&HashMap<String, String>>,
         stream: bool,
+    ) -> Result<ExecutionResult> {
+        let Some(until) = &self.poll_until else {
+            let result = self.send(&session, var_overrides, stream).await?;
+            return Ok(self.check_and_record(&session, result));
+        };
+
+        check_backoff(until.backoff).map_err(Error::InvalidPoll)?;
+        let label = self.name.clone().unwrap_or_else(|| "<unknown>".to_string());
+        let poll_id = nanoid!();
+        let mut schedule = PollSchedule::from(until);
+        let mut attempt = 0;
+
+        loop {
+            attempt += 1;
+            let result = self.send(&session, var_overrides, stream).await?;
+            let done = condition_met(until, &result.body).map_err(Error::InvalidPoll)?;
+
+            session.emit(|id, e| Event::PollAttempt {
+                session_id: id,
+                poll_id: poll_id.clone(),
+                label: label.clone(),
+                attempt,
+                done,
+                elapsed: e,
+            });
+
+            if done {
+                return Ok(self.check_and_record(&session, result));
+            }
+            session.record_result(&result);
+
+            let Some(delay) = schedule.next_delay() else {
+                session.emit(|id, e| Event::PollTimedOut {
+                    session_id: id,
+                    poll_id,
+                    label: label.clone(),
+                    attempts: attempt,
+                    elapsed: e,
+                });
+                return Err(Error::PollTimedOut {
+                    label,
+                    attempts: attempt,
+                    timeout_ms: until.timeout_ms,
+                });
+            };
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    /// Send the request once. The result has no `schema_errors` yet and is not recorded.
+    async fn send(
+        &self,
+        session: &Arc<Mutex<Session>>,
+        var_overrides: Option<&HashMap<String, String>>,
+        stream: bool,
     ) -> Result<ExecutionResult> {
         let name = if let Some(name) = &self.name {
             name.clone()
@@ -161,11 +308,14 @@ impl RequestFile {
             .request
             .expand_with_overrides(&runtime, var_overrides)?;
         let client = build_client(&self.request)?;
+        let request_url = merge_url_query(&prep.url, &prep.query)?;
 
         let start = Instant::now();
+        let request_id = nanoid!();
 
         session.emit(|id, e| Event::HttpRequestStarted {
             session_id: id,
+            request_id: request_id.clone(),
             request_name: self.name.clone(),
             method: prep.method.to_string(),
             url: prep.url.clone(),
@@ -174,9 +324,11 @@ impl RequestFile {
 
         // call request
         let response = send_request(&client, &prep).await?;
+        let wait = start.elapsed();
 
         let status = response.status().as_u16();
         let final_url = response.url().to_string();
+        let http_version = format!("{:?}", response.version());
         let hdrs = response.headers();
         let content_type = header_content_type(hdrs);
         let content_length = header_content_length(hdrs);
@@ -190,7 +342,8 @@ impl RequestFile {
 
         let body = if stream {
             consume_request_stream(
-                &session,
+                session,
+                request_id.clone(),
                 self.name.clone(),
                 status,
                 final_url.clone(),
@@ -207,6 +360,7 @@ impl RequestFile {
 
         session.emit(|id, e| Event::HttpResponseCompleted {
             session_id: id,
+            request_id: request_id.clone(),
             request_name: self.name.clone(),
             status,
             final_url: final_url.clone(),
@@ -222,6 +376,7 @@ impl RequestFile {
         );
 
         return Ok(ExecutionResult {
+            request_id,
             method: prep.method.clone(),
             request_name: self.name.clone(),
             status,
@@ -230,11 +385,53 @@ impl RequestFile {
             body,
             streamed: stream,
             duration,
+            wait,
+            http_version,
+            request_url,
+            request_headers: prep.headers,
+            request_body: prep.body,
             // TODO: if a script called this
             initiator_script: None,
             doc: self.clone(),
+            schema_errors: Vec::new(),
         });
     }
+
+    /// Run the contract checks (OpenAPI operation, `response_schema`) on `result`, then record it.
+    fn check_and_record(
+        &self,
+        session: &Arc<Mutex<Session>>,
+        mut result: ExecutionResult,
+    ) -> ExecutionResult {
+        let mut schema_errors = match &self.openapi {
+            Some(link) if link.spec.is_some() => {
+                let errors = openapi::check_response(
+                    link,
+                    self._path.as_deref(),
+                    result.status,
+                    &result.headers,
+                    &result.body,
+                );
+                let passed = format!(
+                    "response matches OpenAPI operation `{}`",
+                    link.operation_id
+                );
+                emit_asserts(session, &errors, &passed);
+                errors
+            }
+            _ => Vec::new(),
+        };
+        if let Some(response_schema) = &self.response_schema {
+            let errors = schema::check_body(response_schema, self._path.as_deref(), &result.body);
+            emit_asserts(session, &errors, "response body matches its JSON Schema");
+            schema_errors.extend(errors);
+        }
+
+        result.schema_errors = schema_errors;
+        session.record_result(&result);
+
+        return result;
+    }
 }
 
 fn header_content_type(headers: &reqwest::header::HeaderMap) -> Option<String> {
@@ -251,8 +448,10 @@ fn header_content_length(headers: &reqwest::header::HeaderMap) -> Option<u64> {
         .and_then(|s| s.parse().ok())
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn consume_request_stream(
     session: &Arc<Mutex<Session>>,
+    stream_id: String,
     request_name: Option<String>,
     status: u16,
     final_url: String,
@@ -260,8 +459,6 @@ async fn consume_request_stream(
     content_length: Option<u64>,
     mut response: Response,
 ) -> Result<Vec<u8>> {
-    let stream_id = nanoid!();
-
     session.emit(|id, e| Event::HttpResponseStreamStarted {
         session_id: id,
         id: stream_id.clone(),
@@ -351,10 +548,13 @@ pub struct HttpRequestSpec {
     pub tags: Vec<String>,
     #[serde(default = "default_deprecated", skip_serializing_if = "is_false")]
     pub deprecated: bool,
+    /// Query parameters in declaration order: a string map, or a list of `{ name, value }` when a
+    /// name repeats.
     #[serde(default)]
-    pub query: HashMap<String, String>,
+    pub query: NameValueList,
+    /// Request headers in declaration order (same shapes as `query`); repeated names are all sent.
     #[serde(default)]
-    pub headers: HashMap<String, String>,
+    pub headers: NameValueList,
     #[serde(default)]
     pub body: Option<RequestBody>,
     /// Total request timeout in seconds. If omitted, [`RequestFile::default_timeout_secs`] is used.
@@ -466,13 +666,16 @@ impl Default for RequestFile {
                 description: None,
                 tags: vec![],
                 deprecated: false,
-                query: HashMap::new(),
-                headers: HashMap::new(),
+                query: NameValueList::new(),
+                headers: NameValueList::new(),
                 body: None,
                 timeout_secs: None,
                 follow_redirects: true,
                 verify_tls: true,
             },
+            openapi: None,
+            response_schema: None,
+            poll_until: None,
             _path: None,
         };
     }
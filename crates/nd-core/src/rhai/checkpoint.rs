@@ -0,0 +1,139 @@
+//! `checkpoint(when, message, observe)`: pause a script until the host resumes it.
+//!
+//! The host (CLI prompt, TUI key binding, web `/ws` message) holds a [`Checkpoints`] gate and calls
+//! [`Checkpoints::resume`] with the `checkpoint_id` from [`Event::CheckpointWaiting`]. Without a gate
+//! (`RhaiScriptRunOptions::checkpoints == None`) checkpoints are recorded but never block.
+
+use std::collections::HashSet;
+use std::sync::{Arc, Condvar, Mutex};
+
+use rhai::{Engine, FuncRegistration, Map};
+
+use super::utils::dynamic_to_json;
+use crate::stream::events::Event;
+use crate::stream::{MutexSession, Session};
+
+#[derive(Debug, Default)]
+struct GateState {
+    /// Checkpoints emitted but not yet resumed.
+    waiting: HashSet<String>,
+    /// Set by [`Checkpoints::close`]: nothing blocks any more.
+    closed: bool,
+}
+
+/// Shared gate between a running script and the host that resumes its checkpoints.
+#[derive(Debug, Clone, Default)]
+pub struct Checkpoints {
+    state: Arc<(Mutex<GateState>, Condvar)>,
+}
+
+impl Checkpoints {
+    pub fn new() -> Self {
+        return Self::default();
+    }
+
+    /// Unblocks the checkpoint `id`; `false` when no checkpoint with that id is waiting.
+    pub fn resume(&self, id: &str) -> bool {
+        let (lock, cvar) = &*self.state;
+        let mut state = lock.lock().expect("checkpoint mutex poisoned");
+        let found = state.waiting.remove(id);
+        cvar.notify_all();
+        return found;
+    }
+
+    /// Ids of the checkpoints currently blocking, in no particular order.
+    pub fn waiting(&self) -> Vec<String> {
+        let (lock, _) = &*self.state;
+        let state = lock.lock().expect("checkpoint mutex poisoned");
+        return state.waiting.iter().cloned().collect();
+    }
+
+    /// Resumes everything and lets later checkpoints pass straight through (the host went away).
+    pub fn close(&self) {
+        let (lock, cvar) = &*self.state;
+        let mut state = lock.lock().expect("checkpoint mutex poisoned");
+        state.closed = true;
+        state.waiting.clear();
+        cvar.notify_all();
+    }
+
+    /// Marks `id` as waiting. Called before [`Event::CheckpointWaiting`] is emitted so a host that
+    /// resumes from its event sink cannot race the script.
+    fn arm(&self, id: &str) {
+        let (lock, _) = &*self.state;
+        let mut state = lock.lock().expect("checkpoint mutex poisoned");
+        if !state.closed {
+            state.waiting.insert(id.to_string());
+        }
+    }
+
+    /// Blocks until `id` is resumed or the gate is closed.
+    fn wait(&self, id: &str) {
+        let (lock, cvar) = &*self.state;
+        let state = lock.lock().expect("checkpoint mutex poisoned");
+        let _state = cvar
+            .wait_while(state, |s| !s.closed && s.waiting.contains(id))
+            .expect("checkpoint mutex poisoned");
+    }
+}
+
+/// Registers `checkpoint(when, message, observe)` and `checkpoint(when, message)`.
+pub(crate) fn register_checkpoint(
+    engine: &mut Engine,
+    session: Arc<Mutex<Session>>,
+    script_label: String,
+    gate: Option<Checkpoints>,
+) {
+    let pause = move |when: bool, message: &str, observe: Map| {
+        if !when {
+            return;
+        }
+        let checkpoint_id = nanoid::nanoid!();
+        if let Some(gate) = gate.as_ref() {
+            gate.arm(&checkpoint_id);
+        }
+
+        let observe = dynamic_to_json(&observe.into());
+        let (id, msg, script) = (
+            checkpoint_id.clone(),
+            message.to_string(),
+            script_label.clone(),
+        );
+        session.emit(|session_id, elapsed| Event::CheckpointWaiting {
+            session_id,
+            checkpoint_id: id,
+            script,
+            message: msg,
+            observe,
+            elapsed,
+        });
+
+        if let Some(gate) = gate.as_ref() {
+            gate.wait(&checkpoint_id);
+        }
+
+        session.emit(|session_id, elapsed| Event::CheckpointResumed {
+            session_id,
+            elapsed,
+            checkpoint_id,
+        });
+    };
+    let pause_without_observe = pause.clone();
+
+    FuncRegistration::new("checkpoint")
+        .in_global_namespace()
+        .with_volatility(true)
+        .with_comments([
+            "/// When `when` is true, pause the script until the host resumes it (CLI prompt, TUI key,",
+            "/// web resume message). `observe` is shown to whoever resumes it.",
+        ])
+        .register_into_engine(engine, pause);
+
+    FuncRegistration::new("checkpoint")
+        .in_global_namespace()
+        .with_volatility(true)
+        .with_comments(["/// Same as `checkpoint(when, message, #{})`."])
+        .register_into_engine(engine, move |when: bool, message: &str| {
+            pause_without_observe(when, message, Map::new())
+        });
+}
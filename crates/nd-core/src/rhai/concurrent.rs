@@ -0,0 +1,207 @@
+//! `invoke_all(calls, options)` and `parallel_map(items, f, options)`: send imported requests
+//! concurrently instead of one `invoke()` at a time.
+//!
+//! A request module's `request()` (or `request(name)` for `.http` files) returns an
+//! [`ImportedRequest`]; a call is that value, or `[request, #{ overrides }]`. The sends share the
+//! script's [`Session`]: their events interleave, paired by `request_id`.
+
+use std::sync::{Arc, Mutex};
+
+use rhai::{
+    Array, Dynamic, Engine, EvalAltResult, FnPtr, FuncRegistration, Map, NativeCallContext,
+};
+
+use super::resolver::{execute_request_calls, map_to_overrides, RequestCall, RhaiScriptRunOptions};
+use crate::model::request::RequestFile;
+use crate::stream::Session;
+
+/// Default number of requests in flight at once.
+const DEFAULT_CONCURRENCY: i64 = 10;
+
+/// An imported request file as a value, so it can be handed to `invoke_all`.
+#[derive(Debug, Clone)]
+pub struct ImportedRequest {
+    pub(crate) doc: Arc<RequestFile>,
+}
+
+fn error(ctx: &NativeCallContext, message: String) -> Box<EvalAltResult> {
+    return Box::new(EvalAltResult::ErrorRuntime(
+        message.into(),
+        ctx.call_position(),
+    ));
+}
+
+/// One call: a request, `[request]`, or `[request, #{ overrides }]`.
+fn call_spec(
+    ctx: &NativeCallContext,
+    function: &str,
+    index: usize,
+    value: &Dynamic,
+) -> Result<RequestCall, Box<EvalAltResult>> {
+    let invalid = || {
+        error(
+            ctx,
+            format!(
+                "{function}: call {index} must be a request (`mod::request()`) or `[request, #{{ ... }}]`, got {}",
+                value.type_name()
+            ),
+        )
+    };
+
+    if let Some(request) = value.read_lock::<ImportedRequest>() {
+        return Ok((request.doc.clone(), None));
+    }
+    let Some(parts) = value.read_lock::<Array>() else {
+        return Err(invalid());
+    };
+    let (request, overrides) = match parts.as_slice() {
+        [request] => (request, None),
+        [request, overrides] => (request, Some(overrides)),
+        _ => return Err(invalid()),
+    };
+    let Some(request) = request.read_lock::<ImportedRequest>() else {
+        return Err(invalid());
+    };
+    let overrides = match overrides {
+        None => None,
+        Some(o) if o.is_unit() => None,
+        Some(o) => {
+            let map = o.read_lock::<Map>().ok_or_else(invalid)?;
+            Some(map_to_overrides(&map))
+        }
+    };
+    return Ok((request.doc.clone(), overrides));
+}
+
+/// The `concurrency` option, capped at the number of `calls` (more permits would sit idle, and a
+/// huge value would overflow the semaphore).
+fn concurrency(
+    ctx: &NativeCallContext,
+    function: &str,
+    options: &Map,
+    calls: usize,
+) -> Result<usize, Box<EvalAltResult>> {
+    let limit = match options.get("concurrency") {
+        None => DEFAULT_CONCURRENCY,
+        Some(value) => value.as_int().map_err(|t| {
+            error(
+                ctx,
+                format!("{function}: `concurrency` must be an integer, got {t}"),
+            )
+        })?,
+    };
+    if limit < 1 {
+        return Err(error(
+            ctx,
+            format!("{function}: `concurrency` must be at least 1"),
+        ));
+    }
+    let limit = usize::try_from(limit).unwrap_or(usize::MAX);
+    return Ok(limit.min(calls.max(1)));
+}
+
+fn run_calls(
+    ctx: &NativeCallContext,
+    session: &Arc<Mutex<Session>>,
+    script_options: &RhaiScriptRunOptions,
+    function: &str,
+    calls: &[Dynamic],
+    options: &Map,
+) -> Result<Array, Box<EvalAltResult>> {
+    let limit = concurrency(ctx, function, options, calls.len())?;
+    let specs = calls
+        .iter()
+        .enumerate()
+        .map(|(i, call)| call_spec(ctx, function, i, call))
+        .collect::<Result<Vec<_>, _>>()?;
+    return execute_request_calls(specs, session, script_options, limit, ctx.call_position());
+}
+
+/// Registers `invoke_all` and `parallel_map`, plus the `ImportedRequest` type name.
+pub(crate) fn register_concurrent_fns(
+    engine: &mut Engine,
+    session: Arc<Mutex<Session>>,
+    script_options: RhaiScriptRunOptions,
+) {
+    engine.register_type_with_name::<ImportedRequest>("ImportedRequest");
+
+    {
+        let session = session.clone();
+        let script_options = script_options.clone();
+        FuncRegistration::new("invoke_all")
+            .in_global_namespace()
+            .with_volatility(true)
+            .with_comments([
+                "/// Send imported requests concurrently and return their results in order.",
+                "/// Each call is `mod::request()` or `[mod::request(), #{ overrides }]`; `options.concurrency`",
+                "/// caps how many are in flight (default 10). Fails if any request fails.",
+                "/// Example: `invoke_all(ids.map(|id| [user::request(), #{ id: id }]), #{ concurrency: 20 })`.",
+            ])
+            .register_into_engine(
+                engine,
+                move |ctx: NativeCallContext, calls: Array, options: Map| {
+                    run_calls(&ctx, &session, &script_options, "invoke_all", &calls, &options)
+                },
+            );
+    }
+
+    {
+        let session = session.clone();
+        let script_options = script_options.clone();
+        FuncRegistration::new("invoke_all")
+            .in_global_namespace()
+            .with_volatility(true)
+            .with_comments(["/// Same as `invoke_all(calls, #{ concurrency: 10 })`."])
+            .register_into_engine(engine, move |ctx: NativeCallContext, calls: Array| {
+                run_calls(
+                    &ctx,
+                    &session,
+                    &script_options,
+                    "invoke_all",
+                    &calls,
+                    &Map::new(),
+                )
+            });
+    }
+
+    let parallel_map = move |ctx: NativeCallContext,
+                             items: Array,
+                             f: FnPtr,
+                             options: Map|
+          -> Result<Array, Box<EvalAltResult>> {
+        let calls = items
+            .into_iter()
+            .map(|item| f.call_within_context::<Dynamic>(&ctx, (item,)))
+            .collect::<Result<Array, _>>()?;
+        return run_calls(
+            &ctx,
+            &session,
+            &script_options,
+            "parallel_map",
+            &calls,
+            &options,
+        );
+    };
+    let parallel_map_default = parallel_map.clone();
+
+    FuncRegistration::new("parallel_map")
+        .in_global_namespace()
+        .with_volatility(true)
+        .with_comments([
+            "/// Map each item to a call with `f` (which returns what `invoke_all` takes, e.g.",
+            "/// `|id| [user::request(), #{ id: id }]`), then send them all concurrently; results keep the",
+            "/// order of `items`. `f` itself runs one item at a time.",
+        ])
+        .register_into_engine(engine, parallel_map);
+
+    FuncRegistration::new("parallel_map")
+        .in_global_namespace()
+        .with_volatility(true)
+        .with_comments(["/// Same as `parallel_map(items, f, #{ concurrency: 10 })`."])
+        .register_into_engine(
+            engine,
+            move |ctx: NativeCallContext, items: Array, f: FnPtr| {
+                parallel_map_default(ctx, items, f, Map::new())
+            },
+        );
+}
@@ -0,0 +1,330 @@
+//! Hashing, HMAC, encoding and JWT helpers for scripts (webhook signatures, test tokens).
+//!
+//! Functions taking `data` accept a string (its UTF-8 bytes) or a blob. Decoders return a string
+//! when the bytes are valid UTF-8 and a blob otherwise.
+
+use base64::engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD};
+use base64::Engine as _;
+use hmac::{Hmac, Mac};
+use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use rhai::{Blob, Dynamic, Engine, EvalAltResult, FuncRegistration, Map, NativeCallContext};
+use serde_json::Value;
+use sha1::Sha1;
+use sha2::{Digest, Sha256, Sha512};
+
+use super::utils::{dynamic_to_json, json_to_dynamic};
+
+fn error(ctx: &NativeCallContext, message: String) -> Box<EvalAltResult> {
+    return Box::new(EvalAltResult::ErrorRuntime(
+        message.into(),
+        ctx.call_position(),
+    ));
+}
+
+/// The bytes of a string or blob argument.
+fn bytes(ctx: &NativeCallContext, function: &str, data: &Dynamic) -> Result<Vec<u8>, Box<EvalAltResult>> {
+    if let Some(text) = data.read_lock::<rhai::ImmutableString>() {
+        return Ok(text.as_bytes().to_vec());
+    }
+    if let Some(blob) = data.read_lock::<Blob>() {
+        return Ok(blob.clone());
+    }
+    return Err(error(
+        ctx,
+        format!("{function}: expected a string or blob, got {}", data.type_name()),
+    ));
+}
+
+/// A string when `bytes` is UTF-8, a blob otherwise.
+fn text_or_blob(bytes: Vec<u8>) -> Dynamic {
+    return match String::from_utf8(bytes) {
+        Ok(text) => Dynamic::from(text),
+        Err(e) => Dynamic::from_blob(e.into_bytes()),
+    };
+}
+
+/// `D` of a string or blob, as lowercase hex.
+fn hash<D: Digest>(
+    ctx: &NativeCallContext,
+    function: &str,
+    data: &Dynamic,
+) -> Result<String, Box<EvalAltResult>> {
+    return Ok(hex::encode(D::digest(bytes(ctx, function, data)?)));
+}
+
+fn hmac_sha256(
+    ctx: &NativeCallContext,
+    key: &Dynamic,
+    data: &Dynamic,
+) -> Result<String, Box<EvalAltResult>> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(&bytes(ctx, "hmac_sha256", key)?)
+        .expect("HMAC accepts keys of any length");
+    mac.update(&bytes(ctx, "hmac_sha256", data)?);
+    return Ok(hex::encode(mac.finalize().into_bytes()));
+}
+
+fn base64_decode(
+    ctx: &NativeCallContext,
+    function: &str,
+    text: &str,
+    url_safe: bool,
+) -> Result<Dynamic, Box<EvalAltResult>> {
+    let decoded = if url_safe {
+        URL_SAFE_NO_PAD.decode(text.trim_end_matches('='))
+    } else {
+        STANDARD.decode(text)
+    };
+    return decoded
+        .map(text_or_blob)
+        .map_err(|e| error(ctx, format!("{function}: {e}")));
+}
+
+fn algorithm(
+    ctx: &NativeCallContext,
+    function: &str,
+    name: &str,
+) -> Result<Algorithm, Box<EvalAltResult>> {
+    return name
+        .to_ascii_uppercase()
+        .replace("EDDSA", "EdDSA")
+        .parse()
+        .map_err(|_| error(ctx, format!("{function}: unknown algorithm `{name}`")));
+}
+
+/// `HS*` use `key` as the shared secret; the other families expect a PEM private key.
+fn encoding_key(
+    ctx: &NativeCallContext,
+    alg: Algorithm,
+    key: &str,
+) -> Result<EncodingKey, Box<EvalAltResult>> {
+    let pem = key.as_bytes();
+    let key = match alg {
+        Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512 => Ok(EncodingKey::from_secret(pem)),
+        Algorithm::RS256
+        | Algorithm::RS384
+        | Algorithm::RS512
+        | Algorithm::PS256
+        | Algorithm::PS384
+        | Algorithm::PS512 => EncodingKey::from_rsa_pem(pem),
+        Algorithm::ES256 | Algorithm::ES384 => EncodingKey::from_ec_pem(pem),
+        Algorithm::EdDSA => EncodingKey::from_ed_pem(pem),
+    };
+    return key.map_err(|e| error(ctx, format!("jwt_encode: invalid key: {e}")));
+}
+
+/// `HS*` use `key` as the shared secret; the other families expect a PEM public key.
+fn decoding_key(
+    ctx: &NativeCallContext,
+    alg: Algorithm,
+    key: &str,
+) -> Result<DecodingKey, Box<EvalAltResult>> {
+    let pem = key.as_bytes();
+    let key = match alg {
+        Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512 => Ok(DecodingKey::from_secret(pem)),
+        Algorithm::RS256
+        | Algorithm::RS384
+        | Algorithm::RS512
+        | Algorithm::PS256
+        | Algorithm::PS384
+        | Algorithm::PS512 => DecodingKey::from_rsa_pem(pem),
+        Algorithm::ES256 | Algorithm::ES384 => DecodingKey::from_ec_pem(pem),
+        Algorithm::EdDSA => DecodingKey::from_ed_pem(pem),
+    };
+    return key.map_err(|e| error(ctx, format!("jwt_decode: invalid key: {e}")));
+}
+
+fn jwt_encode(
+    ctx: &NativeCallContext,
+    claims: Map,
+    key: &str,
+    alg: &str,
+) -> Result<String, Box<EvalAltResult>> {
+    let alg = algorithm(ctx, "jwt_encode", alg)?;
+    let claims = dynamic_to_json(&Dynamic::from_map(claims));
+    return jsonwebtoken::encode(&Header::new(alg), &claims, &encoding_key(ctx, alg, key)?)
+        .map_err(|e| error(ctx, format!("jwt_encode: {e}")));
+}
+
+/// `#{ header, claims }` of `token`, without checking the signature.
+fn jwt_decode_unverified(ctx: &NativeCallContext, token: &str) -> Result<Dynamic, Box<EvalAltResult>> {
+    let mut parts = token.split('.');
+    let (Some(header), Some(claims), Some(_), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return Err(error(
+            ctx,
+            "jwt_decode: expected three `.`-separated parts".to_string(),
+        ));
+    };
+    let part = |name: &str, text: &str| -> Result<Value, Box<EvalAltResult>> {
+        let raw = URL_SAFE_NO_PAD
+            .decode(text.trim_end_matches('='))
+            .map_err(|e| error(ctx, format!("jwt_decode: {name}: {e}")))?;
+        return serde_json::from_slice(&raw)
+            .map_err(|e| error(ctx, format!("jwt_decode: {name}: {e}")));
+    };
+
+    let mut map = Map::new();
+    map.insert("header".into(), json_to_dynamic(&part("header", header)?));
+    map.insert("claims".into(), json_to_dynamic(&part("claims", claims)?));
+    return Ok(Dynamic::from_map(map));
+}
+
+/// Like [`jwt_decode_unverified`], failing unless the signature matches `key` with `alg`
+/// (and `exp`, when present, is in the future).
+fn jwt_decode_verified(
+    ctx: &NativeCallContext,
+    token: &str,
+    key: &str,
+    alg: &str,
+) -> Result<Dynamic, Box<EvalAltResult>> {
+    let alg = algorithm(ctx, "jwt_decode", alg)?;
+    let mut validation = Validation::new(alg);
+    validation.required_spec_claims.clear();
+    validation.validate_aud = false;
+
+    let data = jsonwebtoken::decode::<Value>(token, &decoding_key(ctx, alg, key)?, &validation)
+        .map_err(|e| error(ctx, format!("jwt_decode: {e}")))?;
+    let header = serde_json::to_value(&data.header).unwrap_or(Value::Null);
+
+    let mut map = Map::new();
+    map.insert("header".into(), json_to_dynamic(&header));
+    map.insert("claims".into(), json_to_dynamic(&data.claims));
+    return Ok(Dynamic::from_map(map));
+}
+
+/// Registers the hashing, encoding and JWT helpers.
+pub(crate) fn register_crypto_fns(engine: &mut Engine) {
+    FuncRegistration::new("sha1")
+        .in_global_namespace()
+        .with_comments(["/// SHA-1 of a string or blob, as lowercase hex."])
+        .register_into_engine(engine, |ctx: NativeCallContext, data: Dynamic| {
+            hash::<Sha1>(&ctx, "sha1", &data)
+        });
+
+    FuncRegistration::new("sha256")
+        .in_global_namespace()
+        .with_comments(["/// SHA-256 of a string or blob, as lowercase hex."])
+        .register_into_engine(engine, |ctx: NativeCallContext, data: Dynamic| {
+            hash::<Sha256>(&ctx, "sha256", &data)
+        });
+
+    FuncRegistration::new("sha512")
+        .in_global_namespace()
+        .with_comments(["/// SHA-512 of a string or blob, as lowercase hex."])
+        .register_into_engine(engine, |ctx: NativeCallContext, data: Dynamic| {
+            hash::<Sha512>(&ctx, "sha512", &data)
+        });
+
+    FuncRegistration::new("hmac_sha256")
+        .in_global_namespace()
+        .with_comments([
+            "/// HMAC-SHA256 of `data` with `key` (strings or blobs), as lowercase hex.",
+            "/// For a base64 signature use `base64_encode(hex_decode(hmac_sha256(key, data)))`.",
+        ])
+        .register_into_engine(engine, |ctx: NativeCallContext, key: Dynamic, data: Dynamic| {
+            hmac_sha256(&ctx, &key, &data)
+        });
+
+    FuncRegistration::new("hex")
+        .in_global_namespace()
+        .with_comments(["/// Lowercase hex of a string or blob."])
+        .register_into_engine(engine, |ctx: NativeCallContext, data: Dynamic| {
+            bytes(&ctx, "hex", &data).map(hex::encode)
+        });
+
+    FuncRegistration::new("hex_decode")
+        .in_global_namespace()
+        .with_comments(["/// Decode hex into a string (UTF-8) or a blob."])
+        .register_into_engine(engine, |ctx: NativeCallContext, text: &str| {
+            hex::decode(text)
+                .map(text_or_blob)
+                .map_err(|e| error(&ctx, format!("hex_decode: {e}")))
+        });
+
+    FuncRegistration::new("base64_encode")
+        .in_global_namespace()
+        .with_comments(["/// Standard (padded) base64 of a string or blob."])
+        .register_into_engine(engine, |ctx: NativeCallContext, data: Dynamic| {
+            bytes(&ctx, "base64_encode", &data).map(|b| STANDARD.encode(b))
+        });
+
+    FuncRegistration::new("base64_decode")
+        .in_global_namespace()
+        .with_comments(["/// Decode standard base64 into a string (UTF-8) or a blob."])
+        .register_into_engine(engine, |ctx: NativeCallContext, text: &str| {
+            base64_decode(&ctx, "base64_decode", text, false)
+        });
+
+    FuncRegistration::new("base64url_encode")
+        .in_global_namespace()
+        .with_comments(["/// URL-safe base64 without padding (as in JWTs) of a string or blob."])
+        .register_into_engine(engine, |ctx: NativeCallContext, data: Dynamic| {
+            bytes(&ctx, "base64url_encode", &data).map(|b| URL_SAFE_NO_PAD.encode(b))
+        });
+
+    FuncRegistration::new("base64url_decode")
+        .in_global_namespace()
+        .with_comments([
+            "/// Decode URL-safe base64 (padding optional) into a string (UTF-8) or a blob.",
+        ])
+        .register_into_engine(engine, |ctx: NativeCallContext, text: &str| {
+            base64_decode(&ctx, "base64url_decode", text, true)
+        });
+
+    FuncRegistration::new("url_encode")
+        .in_global_namespace()
+        .with_comments(["/// Percent-encode `text` for a URL path segment or query value."])
+        .register_into_engine(engine, |text: &str| urlencoding::encode(text).into_owned());
+
+    FuncRegistration::new("url_decode")
+        .in_global_namespace()
+        .with_comments(["/// Decode percent-encoding (`+` is left as is)."])
+        .register_into_engine(engine, |ctx: NativeCallContext, text: &str| {
+            urlencoding::decode(text)
+                .map(|s| s.into_owned())
+                .map_err(|e| error(&ctx, format!("url_decode: {e}")))
+        });
+
+    FuncRegistration::new("jwt_encode")
+        .in_global_namespace()
+        .with_comments([
+            "/// Sign `claims` as a JWT. `alg` is `HS256`/`HS384`/`HS512` (`key` is the secret),",
+            "/// `RS*`/`PS*`/`ES*`/`EdDSA` (`key` is a PEM private key).",
+        ])
+        .register_into_engine(
+            engine,
+            |ctx: NativeCallContext, claims: Map, key: &str, alg: &str| {
+                jwt_encode(&ctx, claims, key, alg)
+            },
+        );
+
+    FuncRegistration::new("jwt_encode")
+        .in_global_namespace()
+        .with_comments(["/// Same as `jwt_encode(claims, secret, \"HS256\")`."])
+        .register_into_engine(engine, |ctx: NativeCallContext, claims: Map, secret: &str| {
+            jwt_encode(&ctx, claims, secret, "HS256")
+        });
+
+    FuncRegistration::new("jwt_decode")
+        .in_global_namespace()
+        .with_comments([
+            "/// Decode a JWT into `#{ header, claims }` **without** checking its signature.",
+        ])
+        .register_into_engine(engine, |ctx: NativeCallContext, token: &str| {
+            jwt_decode_unverified(&ctx, token)
+        });
+
+    FuncRegistration::new("jwt_decode")
+        .in_global_namespace()
+        .with_comments([
+            "/// Decode a JWT into `#{ header, claims }`, failing unless it is signed with `alg` and `key`",
+            "/// (the secret for `HS*`, a PEM public key otherwise) and any `exp` is in the future.",
+        ])
+        .register_into_engine(
+            engine,
+            |ctx: NativeCallContext, token: &str, key: &str, alg: &str| {
+                jwt_decode_verified(&ctx, token, key, alg)
+            },
+        );
+}
@@ -35,7 +35,7 @@ pub fn write_rhai_definition_files(out_dir: &Path) -> io::Result<()> {
     let engine = create_engine(
         Arc::new(Mutex::new(
             Session::new(|| Ok(RuntimeEnv::new()), None)
-                .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?,
+                .map_err(io::Error::other)?,
         )),
         stub,
         RhaiScriptRunOptions::default(),
@@ -1,12 +1,22 @@
 use std::path::Path;
 use std::sync::{Arc, Mutex};
-
-use rhai::{Dynamic, Engine, EvalAltResult, FuncRegistration, Position};
-
+use std::time::Instant;
+
+use rhai::{
+    Dynamic, Engine, EvalAltResult, FnPtr, FuncRegistration, NativeCallContext, Position,
+};
+
+use super::checkpoint::register_checkpoint;
+use super::concurrent::register_concurrent_fns;
+use super::crypto::register_crypto_fns;
+use super::fs::register_fs_fns;
+use super::http::register_http_module;
+use super::json::register_json_fns;
 use super::logger::{emit_script_log_to_tracing, LogLevel};
+use super::poll::register_poll;
 use super::resolver::{NativeImportResolver, RhaiScriptRunOptions};
 
-use crate::rhai::utils::dynamic_to_json;
+use crate::rhai::utils::{dynamic_to_json, dynamic_to_template_string};
 use crate::stream::events::Event;
 use crate::stream::{MutexSession, Session};
 
@@ -32,10 +42,10 @@ fn register_env_fns(engine: &mut Engine, session: Arc<Mutex<Session>>) {
         .in_global_namespace()
         .with_volatility(true)
         .with_comments([
-            "/// Set a runtime variable (value is stringified). Visible to later templates and `env()`.",
+            "/// Set a runtime variable (value is stringified; maps and arrays as JSON). Visible to later templates and `env()`.",
         ])
         .register_into_engine(engine, move |key: &str, value: Dynamic| {
-            e_set.set(key.to_string(), value.to_string());
+            e_set.set(key.to_string(), dynamic_to_template_string(&value));
 
             let json = dynamic_to_json(&value);
             let key_owned = key.to_string();
@@ -64,6 +74,7 @@ fn register_assert(engine: &mut Engine, session: Arc<Mutex<Session>>) {
                 passed: condition,
                 elapsed,
                 message: msg,
+                pointer: None,
             });
 
             if condition {
@@ -100,6 +111,73 @@ fn register_log(engine: &mut Engine, session: Arc<Mutex<Session>>, script_label:
         });
 }
 
+/// Registers `step(name)` and `step(name, || { ... })` — named steps for checklists and timelines.
+fn register_step(engine: &mut Engine, session: Arc<Mutex<Session>>) {
+    let session_marker = session.clone();
+
+    FuncRegistration::new("step")
+        .in_global_namespace()
+        .with_volatility(true)
+        .with_comments([
+            "/// Mark the start of a named step; later events belong to it until the next step.",
+        ])
+        .register_into_engine(engine, move |name: &str| {
+            let name = name.to_string();
+            session_marker.emit(|id, elapsed| Event::NewStepEncountered {
+                session_id: id,
+                name,
+                elapsed,
+            });
+        });
+
+    FuncRegistration::new("step")
+        .in_global_namespace()
+        .with_volatility(true)
+        .with_comments([
+            "/// Run `body` as a named step, recording its duration and whether it failed.",
+            "/// Returns what `body` returns; errors still propagate after the step is recorded.",
+        ])
+        .register_into_engine(
+            engine,
+            move |ctx: NativeCallContext,
+                  name: &str,
+                  body: FnPtr|
+                  -> Result<Dynamic, Box<EvalAltResult>> {
+                let step_name = name.to_string();
+                session.emit(|id, elapsed| Event::NewStepEncountered {
+                    session_id: id,
+                    name: step_name,
+                    elapsed,
+                });
+
+                let start = Instant::now();
+                let result = body.call_within_context::<Dynamic>(&ctx, ());
+                let duration = start.elapsed();
+
+                let step_name = name.to_string();
+                let error = result.as_ref().err().map(|e| innermost_error(e).to_string());
+                session.emit(|id, elapsed| Event::StepFinished {
+                    session_id: id,
+                    name: step_name,
+                    success: error.is_none(),
+                    error,
+                    duration,
+                    elapsed,
+                });
+
+                return result;
+            },
+        );
+}
+
+/// The error raised inside a closure, without the "in closure call" wrappers around it.
+fn innermost_error(error: &EvalAltResult) -> &EvalAltResult {
+    return match error {
+        EvalAltResult::ErrorInFunctionCall(_, _, inner, _) => innermost_error(inner),
+        _ => error,
+    };
+}
+
 /// Registers `persist(key, value)` when `persist_file` is set — updates env and the configured persistence file (JSON or YAML).
 fn register_persist(engine: &mut Engine, session: Arc<Mutex<Session>>) {
     let e = session.runtime();
@@ -112,7 +190,7 @@ fn register_persist(engine: &mut Engine, session: Arc<Mutex<Session>>) {
             "/// Persist a key–value pair to the configured persistence file (if any).",
         ])
         .register_into_engine(engine, move |key: &str, value: Dynamic| {
-            let s = value.to_string();
+            let s = dynamic_to_template_string(&value);
             let key_owned = key.to_string();
             let json = dynamic_to_json(&value);
 
@@ -133,7 +211,7 @@ fn register_persist(engine: &mut Engine, session: Arc<Mutex<Session>>) {
         });
 }
 
-/// Creates the script engine: builtins, `import` resolution (`.rhai` and request files), optional `persist`.
+/// Creates the script engine: builtins (including `checkpoint` and sandboxed file I/O), the `http` module, `import` resolution (`.rhai` and request files), optional `persist`.
 pub(crate) fn create_engine(
     session: Arc<Mutex<Session>>,
     script_path: &Path,
@@ -144,8 +222,21 @@ pub(crate) fn create_engine(
 
     register_env_fns(&mut engine, session.clone());
     register_assert(&mut engine, session.clone());
-    register_log(&mut engine, session.clone(), script_label);
+    register_log(&mut engine, session.clone(), script_label.clone());
+    register_checkpoint(
+        &mut engine,
+        session.clone(),
+        script_label,
+        script_options.checkpoints.clone(),
+    );
     register_persist(&mut engine, session.clone());
+    register_step(&mut engine, session.clone());
+    register_json_fns(&mut engine);
+    register_crypto_fns(&mut engine);
+    register_fs_fns(&mut engine, script_path, &script_options);
+    register_poll(&mut engine, session.clone());
+    register_concurrent_fns(&mut engine, session.clone(), script_options.clone());
+    register_http_module(&mut engine, session.clone(), script_options.clone());
 
     let resolver = NativeImportResolver::new(script_path, session.clone(), script_options);
     engine.set_module_resolver(resolver);
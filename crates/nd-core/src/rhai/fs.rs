@@ -0,0 +1,277 @@
+//! Sandboxed file I/O for scripts: `read_text`, `read_json`, `read_yaml`, `write_text`, `write_json`,
+//! `list_dir`.
+//!
+//! Relative paths resolve against the calling script's directory, like `import`. Every path must land
+//! (after following symlinks) under the main script's directory or one of
+//! [`RhaiScriptRunOptions::fs_roots`], the same check `nd-web` applies to workspace files.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use rhai::{Array, Dynamic, Engine, EvalAltResult, FuncRegistration, Map, NativeCallContext};
+use serde_json::Value;
+
+use super::resolver::RhaiScriptRunOptions;
+use super::utils::{dynamic_to_json, json_to_dynamic};
+use crate::utils::path::{is_under_roots, normalize_path_lexical, resolve_file_path};
+
+/// Directories a script may read and write under.
+#[derive(Debug, Clone)]
+struct Sandbox {
+    main_script_dir: PathBuf,
+    roots: Vec<PathBuf>,
+}
+
+impl Sandbox {
+    fn new(script_path: &Path, options: &RhaiScriptRunOptions) -> Self {
+        let main_script_dir = script_path
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_else(|| PathBuf::from("."));
+        let mut roots =
+            vec![std::fs::canonicalize(&main_script_dir).unwrap_or(main_script_dir.clone())];
+        roots.extend(
+            options
+                .fs_roots
+                .iter()
+                .map(|r| std::fs::canonicalize(r).unwrap_or(r.clone())),
+        );
+        return Self {
+            main_script_dir,
+            roots,
+        };
+    }
+
+    /// `path` relative to the calling script (or the main script), normalized but not yet checked.
+    fn join(&self, ctx: &NativeCallContext, path: &str) -> PathBuf {
+        let base_dir = ctx
+            .call_source()
+            .map(Path::new)
+            .and_then(|p| p.parent())
+            .unwrap_or(self.main_script_dir.as_path());
+        return resolve_file_path(base_dir, path);
+    }
+
+    /// Canonical path of an existing file or directory inside the sandbox.
+    fn existing(
+        &self,
+        ctx: &NativeCallContext,
+        function: &str,
+        path: &str,
+    ) -> Result<PathBuf, Box<EvalAltResult>> {
+        let joined = self.join(ctx, path);
+        let canon = std::fs::canonicalize(&joined)
+            .map_err(|e| error(ctx, format!("{function}: {path}: {e}")))?;
+        if !is_under_roots(&canon, &self.roots) {
+            return Err(error(
+                ctx,
+                format!("{function}: {path}: path outside the sandbox"),
+            ));
+        }
+        return Ok(canon);
+    }
+
+    /// Path to write `path` to: its nearest existing ancestor must be inside the sandbox, and missing
+    /// directories below it are created. Symlinks count as existing, so a dangling one (whose target
+    /// could be anywhere) is rejected rather than written through.
+    fn writable(
+        &self,
+        ctx: &NativeCallContext,
+        function: &str,
+        path: &str,
+    ) -> Result<PathBuf, Box<EvalAltResult>> {
+        let joined = normalize_path_lexical(&self.join(ctx, path));
+        let mut ancestor = joined.as_path();
+        while std::fs::symlink_metadata(ancestor).is_err() {
+            ancestor = ancestor
+                .parent()
+                .ok_or_else(|| error(ctx, format!("{function}: {path}: no such directory")))?;
+        }
+        let canon = std::fs::canonicalize(ancestor).map_err(|e| {
+            if ancestor.is_symlink() {
+                return error(ctx, format!("{function}: {path}: dangling symlink"));
+            }
+            return error(ctx, format!("{function}: {path}: {e}"));
+        })?;
+        if !is_under_roots(&canon, &self.roots) {
+            return Err(error(
+                ctx,
+                format!("{function}: {path}: path outside the sandbox"),
+            ));
+        }
+        if joined.is_dir() {
+            return Err(error(ctx, format!("{function}: {path}: is a directory")));
+        }
+
+        let target = canon.join(joined.strip_prefix(ancestor).unwrap_or(Path::new("")));
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| error(ctx, format!("{function}: {path}: {e}")))?;
+        }
+        return Ok(target);
+    }
+
+    fn read(
+        &self,
+        ctx: &NativeCallContext,
+        function: &str,
+        path: &str,
+    ) -> Result<String, Box<EvalAltResult>> {
+        let file = self.existing(ctx, function, path)?;
+        return std::fs::read_to_string(&file)
+            .map_err(|e| error(ctx, format!("{function}: {path}: {e}")));
+    }
+
+    fn write(
+        &self,
+        ctx: &NativeCallContext,
+        function: &str,
+        path: &str,
+        contents: &str,
+    ) -> Result<(), Box<EvalAltResult>> {
+        let file = self.writable(ctx, function, path)?;
+        // `file` is canonical when it exists; a new file is created exclusively so a symlink
+        // planted after the check is not followed.
+        let mut options = std::fs::OpenOptions::new();
+        match std::fs::symlink_metadata(&file) {
+            Ok(meta) if meta.file_type().is_symlink() => {
+                return Err(error(
+                    ctx,
+                    format!("{function}: {path}: path outside the sandbox"),
+                ));
+            }
+            Ok(_) => options.write(true).truncate(true),
+            Err(_) => options.write(true).create_new(true),
+        };
+        let mut handle = options
+            .open(&file)
+            .map_err(|e| error(ctx, format!("{function}: {path}: {e}")))?;
+        return handle
+            .write_all(contents.as_bytes())
+            .map_err(|e| error(ctx, format!("{function}: {path}: {e}")));
+    }
+
+    /// Entries of the directory `path`, sorted by name.
+    fn list(&self, ctx: &NativeCallContext, path: &str) -> Result<Array, Box<EvalAltResult>> {
+        let dir = self.existing(ctx, "list_dir", path)?;
+        let entries =
+            std::fs::read_dir(&dir).map_err(|e| error(ctx, format!("list_dir: {path}: {e}")))?;
+
+        let mut names: Vec<(String, bool)> = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| {
+                let is_dir = entry.file_type().map(|t| t.is_dir()).unwrap_or(false);
+                (entry.file_name().to_string_lossy().into_owned(), is_dir)
+            })
+            .collect();
+        names.sort();
+
+        let prefix = path.trim_end_matches('/');
+        return Ok(names
+            .into_iter()
+            .map(|(name, is_dir)| {
+                let mut map = Map::new();
+                map.insert("path".into(), format!("{prefix}/{name}").into());
+                map.insert("name".into(), name.into());
+                map.insert("is_dir".into(), is_dir.into());
+                Dynamic::from_map(map)
+            })
+            .collect());
+    }
+}
+
+fn error(ctx: &NativeCallContext, message: String) -> Box<EvalAltResult> {
+    return Box::new(EvalAltResult::ErrorRuntime(
+        message.into(),
+        ctx.call_position(),
+    ));
+}
+
+/// Registers the file functions, confined to the script's directory and `options.fs_roots`.
+pub(crate) fn register_fs_fns(
+    engine: &mut Engine,
+    script_path: &Path,
+    options: &RhaiScriptRunOptions,
+) {
+    let sandbox = Sandbox::new(script_path, options);
+
+    let fs = sandbox.clone();
+    FuncRegistration::new("read_text")
+        .in_global_namespace()
+        .with_volatility(true)
+        .with_comments([
+            "/// Contents of a UTF-8 file, relative to this script (e.g. a CSV fixture).",
+        ])
+        .register_into_engine(engine, move |ctx: NativeCallContext, path: &str| {
+            fs.read(&ctx, "read_text", path)
+        });
+
+    let fs = sandbox.clone();
+    FuncRegistration::new("read_json")
+        .in_global_namespace()
+        .with_volatility(true)
+        .with_comments(["/// Parse a JSON file, relative to this script. `null` becomes `()`."])
+        .register_into_engine(
+            engine,
+            move |ctx: NativeCallContext, path: &str| -> Result<Dynamic, Box<EvalAltResult>> {
+                let text = fs.read(&ctx, "read_json", path)?;
+                let value: Value = serde_json::from_str(&text)
+                    .map_err(|e| error(&ctx, format!("read_json: {path}: {e}")))?;
+                Ok(json_to_dynamic(&value))
+            },
+        );
+
+    let fs = sandbox.clone();
+    FuncRegistration::new("read_yaml")
+        .in_global_namespace()
+        .with_volatility(true)
+        .with_comments(["/// Parse a YAML file, relative to this script. `null` becomes `()`."])
+        .register_into_engine(
+            engine,
+            move |ctx: NativeCallContext, path: &str| -> Result<Dynamic, Box<EvalAltResult>> {
+                let text = fs.read(&ctx, "read_yaml", path)?;
+                let value: Value = serde_yaml::from_str(&text)
+                    .map_err(|e| error(&ctx, format!("read_yaml: {path}: {e}")))?;
+                Ok(json_to_dynamic(&value))
+            },
+        );
+
+    let fs = sandbox.clone();
+    FuncRegistration::new("write_text")
+        .in_global_namespace()
+        .with_volatility(true)
+        .with_comments([
+            "/// Write `text` to a file relative to this script, replacing it and creating missing directories.",
+        ])
+        .register_into_engine(engine, move |ctx: NativeCallContext, path: &str, text: &str| {
+            fs.write(&ctx, "write_text", path, text)
+        });
+
+    let fs = sandbox.clone();
+    FuncRegistration::new("write_json")
+        .in_global_namespace()
+        .with_volatility(true)
+        .with_comments([
+            "/// Write `value` as indented JSON to a file relative to this script (e.g. a response to diff later).",
+        ])
+        .register_into_engine(
+            engine,
+            move |ctx: NativeCallContext, path: &str, value: Dynamic| -> Result<(), Box<EvalAltResult>> {
+                let text = serde_json::to_string_pretty(&dynamic_to_json(&value))
+                    .map_err(|e| error(&ctx, format!("write_json: {path}: {e}")))?;
+                fs.write(&ctx, "write_json", path, &format!("{text}\n"))
+            },
+        );
+
+    let fs = sandbox;
+    FuncRegistration::new("list_dir")
+        .in_global_namespace()
+        .with_volatility(true)
+        .with_comments([
+            "/// Entries of a directory relative to this script, sorted by name: `#{ name, path, is_dir }`,",
+            "/// where `path` can be passed back to `read_text` and friends.",
+        ])
+        .register_into_engine(engine, move |ctx: NativeCallContext, path: &str| {
+            fs.list(&ctx, path)
+        });
+}
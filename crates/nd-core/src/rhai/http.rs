@@ -0,0 +1,214 @@
+//! The `http` module: one-off requests from a script without a request file.
+//!
+//! `http::get(url)` / `http::get(#{ url, headers, query, body, timeout })` (and `post`, `put`,
+//! `patch`, `delete`, plus `http::request(#{ method, url, ... })`) build an [`HttpRequestSpec`] and
+//! run it exactly like an imported request's `invoke()`: `${VAR}` expansion, session events,
+//! `--no-network-io` dry runs, and the same result map.
+
+use std::sync::{Arc, Mutex};
+
+use rhai::{Dynamic, Engine, EvalAltResult, FuncRegistration, ImmutableString, Module};
+use rhai::{NativeCallContext, Position};
+
+use super::resolver::{execute_request_call, RhaiScriptRunOptions};
+use super::utils::{dynamic_to_json, dynamic_to_template_string};
+use crate::model::pairs::NameValueList;
+use crate::model::request::{HttpRequestSpec, RequestBody, RequestFile};
+use crate::stream::Session;
+
+/// Methods with their own function (`http::get`, …); anything else goes through `http::request`.
+const METHODS: [&str; 5] = ["get", "post", "put", "patch", "delete"];
+
+/// Registers the static `http` module.
+pub(crate) fn register_http_module(
+    engine: &mut Engine,
+    session: Arc<Mutex<Session>>,
+    options: RhaiScriptRunOptions,
+) {
+    let mut module = Module::new();
+
+    for method in METHODS {
+        let upper = method.to_ascii_uppercase();
+        {
+            let session = session.clone();
+            let options = options.clone();
+            let upper = upper.clone();
+            FuncRegistration::new(method)
+                .with_volatility(true)
+                .with_comments([format!(
+                    "/// Send a {upper} request to `url` (`${{VAR}}` templates expand) and return the response map."
+                )])
+                .set_into_module(&mut module, move |ctx: NativeCallContext, url: ImmutableString| {
+                    let spec = spec_from_url(&upper, url.as_str());
+                    send(spec, &session, &options, ctx.call_position())
+                });
+        }
+        {
+            let session = session.clone();
+            let options = options.clone();
+            FuncRegistration::new(method)
+                .with_volatility(true)
+                .with_comments([
+                    format!("/// Send a {upper} request described by `#{{ url, headers, query, body, timeout }}`."),
+                    "/// `body` is sent as text when it is a string, as JSON otherwise.".to_string(),
+                ])
+                .set_into_module(&mut module, move |ctx: NativeCallContext, request: rhai::Map| {
+                    let pos = ctx.call_position();
+                    let spec = spec_from_map(Some(&upper), &request, pos)?;
+                    send(spec, &session, &options, pos)
+                });
+        }
+    }
+
+    FuncRegistration::new("request")
+        .with_volatility(true)
+        .with_comments([
+            "/// Send a request described by `#{ method, url, headers, query, body, timeout }`",
+            "/// (also `follow_redirects` and `verify_tls`) and return the response map.",
+        ])
+        .set_into_module(
+            &mut module,
+            move |ctx: NativeCallContext, request: rhai::Map| {
+                let pos = ctx.call_position();
+                let spec = spec_from_map(None, &request, pos)?;
+                send(spec, &session, &options, pos)
+            },
+        );
+
+    engine.register_static_module("http", module.into());
+}
+
+fn send(
+    spec: HttpRequestSpec,
+    session: &Arc<Mutex<Session>>,
+    options: &RhaiScriptRunOptions,
+    pos: Position,
+) -> Result<Dynamic, Box<EvalAltResult>> {
+    let doc = Arc::new(RequestFile {
+        name: None,
+        request: spec,
+        ..Default::default()
+    });
+    return execute_request_call(&doc, session, options, None, "http", pos, false);
+}
+
+/// `method url` with nothing else set (redirects followed, TLS verified, default timeout).
+fn spec_from_url(method: &str, url: &str) -> HttpRequestSpec {
+    return HttpRequestSpec {
+        method: method.to_string(),
+        url: url.to_string(),
+        summary: None,
+        description: None,
+        tags: vec![],
+        deprecated: false,
+        query: NameValueList::new(),
+        headers: NameValueList::new(),
+        body: None,
+        timeout_secs: None,
+        follow_redirects: true,
+        verify_tls: true,
+    };
+}
+
+fn spec_from_map(
+    method: Option<&str>,
+    request: &rhai::Map,
+    pos: Position,
+) -> Result<HttpRequestSpec, Box<EvalAltResult>> {
+    let error = |message: String| Box::new(EvalAltResult::ErrorRuntime(message.into(), pos));
+    let string = |key: &str| -> Result<Option<String>, Box<EvalAltResult>> {
+        return match request.get(key) {
+            None => Ok(None),
+            Some(v) if v.is_unit() => Ok(None),
+            Some(v) => v
+                .clone()
+                .into_string()
+                .map(Some)
+                .map_err(|t| error(format!("http: `{key}` must be a string, got {t}"))),
+        };
+    };
+    let flag = |key: &str, default: bool| -> Result<bool, Box<EvalAltResult>> {
+        return match request.get(key) {
+            None => Ok(default),
+            Some(v) => v
+                .as_bool()
+                .map_err(|t| error(format!("http: `{key}` must be a bool, got {t}"))),
+        };
+    };
+
+    for key in request.keys() {
+        let known = matches!(
+            key.as_str(),
+            "url" | "headers" | "query" | "body" | "timeout" | "follow_redirects" | "verify_tls"
+        ) || (method.is_none() && key == "method");
+        if !known {
+            return Err(error(format!("http: unknown request field `{key}`")));
+        }
+    }
+
+    let url = string("url")?.ok_or_else(|| error("http: `url` is required".to_string()))?;
+    let method = match method {
+        Some(method) => method.to_string(),
+        None => string("method")?
+            .ok_or_else(|| error("http::request: `method` is required".to_string()))?
+            .to_ascii_uppercase(),
+    };
+    let timeout_secs = match request.get("timeout") {
+        None => None,
+        Some(v) if v.is_unit() => None,
+        Some(v) => match v.as_int() {
+            Ok(secs) if secs >= 0 => Some(secs as u64),
+            _ => {
+                return Err(error(format!(
+                    "http: `timeout` must be a whole number of seconds, got {v}"
+                )))
+            }
+        },
+    };
+    let body = match request.get("body") {
+        None => None,
+        Some(v) if v.is_unit() => None,
+        Some(v) if v.is_string() => Some(RequestBody::Text(
+            v.clone().into_string().unwrap_or_default(),
+        )),
+        Some(v) => Some(RequestBody::Json(dynamic_to_json(v))),
+    };
+
+    let mut spec = spec_from_url(&method, &url);
+    spec.query = pairs(request.get("query"), "query", pos)?;
+    spec.headers = pairs(request.get("headers"), "headers", pos)?;
+    spec.body = body;
+    spec.timeout_secs = timeout_secs;
+    spec.follow_redirects = flag("follow_redirects", true)?;
+    spec.verify_tls = flag("verify_tls", true)?;
+    return Ok(spec);
+}
+
+/// `#{ name: value }` → pairs; an array value repeats the name (`#{ tag: ["a", "b"] }`).
+fn pairs(
+    value: Option<&Dynamic>,
+    key: &str,
+    pos: Position,
+) -> Result<NameValueList, Box<EvalAltResult>> {
+    let mut list = NameValueList::new();
+    let Some(value) = value.filter(|v| !v.is_unit()) else {
+        return Ok(list);
+    };
+    let Some(map) = value.read_lock::<rhai::Map>() else {
+        return Err(Box::new(EvalAltResult::ErrorRuntime(
+            format!("http: `{key}` must be a map, got {}", value.type_name()).into(),
+            pos,
+        )));
+    };
+    for (name, value) in map.iter() {
+        match value.read_lock::<rhai::Array>() {
+            Some(values) => {
+                for value in values.iter() {
+                    list.append(name.to_string(), dynamic_to_template_string(value));
+                }
+            }
+            None => list.append(name.to_string(), dynamic_to_template_string(value)),
+        }
+    }
+    return Ok(list);
+}
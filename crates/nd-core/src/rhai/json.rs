@@ -0,0 +1,158 @@
+//! JSON helpers for scripts: `json_parse`, `json_stringify`, `jsonpath`, `jq`, and `header(res, name)`.
+//!
+//! Values cross the boundary through [`json_to_dynamic`] / [`dynamic_to_json`], so blobs become
+//! base64 strings and `()` is `null`, exactly like request bodies and `set()`.
+
+use jaq_core::load::{Arena, File, Loader};
+use jaq_core::{Compiler, Ctx, RcIter};
+use jaq_json::Val;
+use jsonpath_rust::JsonPath;
+use rhai::{Array, Dynamic, Engine, EvalAltResult, FuncRegistration, Map, NativeCallContext};
+use serde_json::Value;
+
+use super::utils::{dynamic_to_json, json_to_dynamic};
+
+fn error(ctx: &NativeCallContext, message: String) -> Box<EvalAltResult> {
+    return Box::new(EvalAltResult::ErrorRuntime(
+        message.into(),
+        ctx.call_position(),
+    ));
+}
+
+fn json_parse(ctx: &NativeCallContext, text: &str) -> Result<Dynamic, Box<EvalAltResult>> {
+    let value: Value = serde_json::from_str(text)
+        .map_err(|e| error(ctx, format!("json_parse: invalid JSON: {e}")))?;
+    return Ok(json_to_dynamic(&value));
+}
+
+fn json_stringify(value: &Dynamic, pretty: bool) -> String {
+    let json = dynamic_to_json(value);
+    let text = if pretty {
+        serde_json::to_string_pretty(&json)
+    } else {
+        serde_json::to_string(&json)
+    };
+    return text.unwrap_or_default();
+}
+
+/// Every match of the JSONPath `path` (RFC 9535) in `value`.
+fn jsonpath(ctx: &NativeCallContext, value: &Dynamic, path: &str) -> Result<Array, Box<EvalAltResult>> {
+    let json = dynamic_to_json(value);
+    let found = json
+        .query(path)
+        .map_err(|e| error(ctx, format!("jsonpath: `{path}`: {e}")))?;
+    return Ok(found.into_iter().map(json_to_dynamic).collect());
+}
+
+/// Every output of the jq program `filter` run on `value`.
+fn jq(ctx: &NativeCallContext, value: &Dynamic, filter: &str) -> Result<Array, Box<EvalAltResult>> {
+    let program = File {
+        code: filter,
+        path: (),
+    };
+    let loader = Loader::new(jaq_std::defs().chain(jaq_json::defs()));
+    let arena = Arena::default();
+    let modules = loader
+        .load(&arena, program)
+        .map_err(|errors| error(ctx, format!("jq: cannot parse `{filter}`: {errors:?}")))?;
+    let compiled = Compiler::default()
+        .with_funs(jaq_std::funs().chain(jaq_json::funs()))
+        .compile(modules)
+        .map_err(|errors| error(ctx, format!("jq: cannot compile `{filter}`: {errors:?}")))?;
+
+    let inputs = RcIter::new(core::iter::empty());
+    let input = Val::from(dynamic_to_json(value));
+    let mut out = Array::new();
+    for result in compiled.run((Ctx::new([], &inputs), input)) {
+        let val = result.map_err(|e| error(ctx, format!("jq: `{filter}`: {e}")))?;
+        out.push(json_to_dynamic(&Value::from(val)));
+    }
+    return Ok(out);
+}
+
+/// First element of `values`, or `()` when there is none.
+fn first(values: Array) -> Dynamic {
+    return values.into_iter().next().unwrap_or(Dynamic::UNIT);
+}
+
+/// Case-insensitive lookup in a result map's `headers`.
+fn header(response: &Map, name: &str) -> Dynamic {
+    let Some(headers) = response
+        .get("headers")
+        .and_then(|h| h.read_lock::<Map>().map(|m| m.clone()))
+    else {
+        return Dynamic::UNIT;
+    };
+    return headers
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        .map(|(_, value)| value.clone())
+        .unwrap_or(Dynamic::UNIT);
+}
+
+/// Registers the JSON and response helpers.
+pub(crate) fn register_json_fns(engine: &mut Engine) {
+    FuncRegistration::new("json_parse")
+        .in_global_namespace()
+        .with_comments([
+            "/// Parse JSON text (e.g. a string from `env()` or a non-JSON response's `body`).",
+            "/// `null` becomes `()`. Fails on invalid JSON.",
+        ])
+        .register_into_engine(engine, |ctx: NativeCallContext, text: &str| {
+            json_parse(&ctx, text)
+        });
+
+    FuncRegistration::new("json_stringify")
+        .in_global_namespace()
+        .with_comments(["/// Serialize `value` as compact JSON."])
+        .register_into_engine(engine, |value: Dynamic| json_stringify(&value, false));
+
+    FuncRegistration::new("json_stringify")
+        .in_global_namespace()
+        .with_comments(["/// Serialize `value` as JSON, indented when `pretty` is true."])
+        .register_into_engine(engine, |value: Dynamic, pretty: bool| {
+            json_stringify(&value, pretty)
+        });
+
+    FuncRegistration::new("jsonpath")
+        .in_global_namespace()
+        .with_comments([
+            "/// All values in `value` matching a JSONPath query, e.g. `jsonpath(r.json, \"$.items[*].id\")`.",
+            "/// Returns an empty array when nothing matches.",
+        ])
+        .register_into_engine(engine, |ctx: NativeCallContext, value: Dynamic, path: &str| {
+            jsonpath(&ctx, &value, path)
+        });
+
+    FuncRegistration::new("jsonpath_first")
+        .in_global_namespace()
+        .with_comments(["/// First value matching a JSONPath query, or `()` when nothing matches."])
+        .register_into_engine(engine, |ctx: NativeCallContext, value: Dynamic, path: &str| {
+            jsonpath(&ctx, &value, path).map(first)
+        });
+
+    FuncRegistration::new("jq")
+        .in_global_namespace()
+        .with_comments([
+            "/// All outputs of a jq filter run on `value`, e.g. `jq(r.json, \".items[] | select(.active) | .id\")`.",
+        ])
+        .register_into_engine(engine, |ctx: NativeCallContext, value: Dynamic, filter: &str| {
+            jq(&ctx, &value, filter)
+        });
+
+    FuncRegistration::new("jq_first")
+        .in_global_namespace()
+        .with_comments([
+            "/// First output of a jq filter run on `value`, or `()` when there is none.",
+        ])
+        .register_into_engine(engine, |ctx: NativeCallContext, value: Dynamic, filter: &str| {
+            jq(&ctx, &value, filter).map(first)
+        });
+
+    FuncRegistration::new("header")
+        .in_global_namespace()
+        .with_comments([
+            "/// Response header by name, ignoring case (`header(r, \"Content-Type\")`); `()` when absent.",
+        ])
+        .register_into_engine(engine, |response: Map, name: &str| header(&response, name));
+}
@@ -1,6 +1,14 @@
-//! Rhai scripting: locked-down engine, response context, env access, and logging.
+//! Rhai scripting: locked-down engine, response context, env access, logging, checkpoints, JSON,
+//! crypto and JWT helpers, sandboxed file I/O, polling, concurrent `invoke_all`, and the `http` module.
+pub mod checkpoint;
+pub mod concurrent;
+mod crypto;
 pub mod definition_export;
 mod engine;
+mod fs;
+mod http;
+mod json;
+mod poll;
 pub mod logger;
 pub mod resolver;
 pub mod run;
@@ -0,0 +1,144 @@
+//! `sleep(ms)` and `poll(f, options)`: wait on asynchronous APIs (a `202` job and its status URL).
+//!
+//! Each call of `f` is an [`Event::PollAttempt`], with the same timing rules as a request file's
+//! `poll_until` ([`PollSchedule`]).
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use rhai::{Dynamic, Engine, EvalAltResult, FnPtr, FuncRegistration, Map, NativeCallContext};
+
+use crate::execute::poll::{check_backoff, PollSchedule};
+use crate::stream::events::Event;
+use crate::stream::{MutexSession, Session};
+
+fn error(ctx: &NativeCallContext, message: String) -> Box<EvalAltResult> {
+    return Box::new(EvalAltResult::ErrorRuntime(
+        message.into(),
+        ctx.call_position(),
+    ));
+}
+
+/// Numeric option `key` (int or float), or `default` when absent.
+fn number(
+    ctx: &NativeCallContext,
+    options: &Map,
+    key: &str,
+    default: f64,
+) -> Result<f64, Box<EvalAltResult>> {
+    let Some(value) = options.get(key) else {
+        return Ok(default);
+    };
+    if let Ok(i) = value.as_int() {
+        return Ok(i as f64);
+    }
+    if let Ok(f) = value.as_float() {
+        return Ok(f);
+    }
+    return Err(error(
+        ctx,
+        format!("poll: `{key}` must be a number, got {}", value.type_name()),
+    ));
+}
+
+/// `()` and `false` keep polling; anything else is the result.
+fn is_done(value: &Dynamic) -> bool {
+    return !(value.is_unit() || value.as_bool().is_ok_and(|b| !b));
+}
+
+fn poll(
+    ctx: &NativeCallContext,
+    session: &Arc<Mutex<Session>>,
+    f: &FnPtr,
+    options: &Map,
+) -> Result<Dynamic, Box<EvalAltResult>> {
+    let interval = number(ctx, options, "interval_ms", 1_000.0)?.max(0.0);
+    let timeout = number(ctx, options, "timeout_ms", 60_000.0)?.max(0.0);
+    let backoff = number(ctx, options, "backoff", 1.0)?;
+    check_backoff(backoff).map_err(|e| error(ctx, format!("poll: {e}")))?;
+    let label = options
+        .get("label")
+        .map(|l| l.to_string())
+        .unwrap_or_else(|| "poll".to_string());
+
+    let poll_id = nanoid::nanoid!();
+    let mut schedule = PollSchedule::new(
+        Duration::from_millis(interval as u64),
+        Duration::from_millis(timeout as u64),
+        backoff,
+    );
+    let mut attempt = 0;
+
+    loop {
+        attempt += 1;
+        let value: Dynamic = f.call_within_context(ctx, ())?;
+        let done = is_done(&value);
+
+        session.emit(|id, e| Event::PollAttempt {
+            session_id: id,
+            poll_id: poll_id.clone(),
+            label: label.clone(),
+            attempt,
+            done,
+            elapsed: e,
+        });
+
+        if done {
+            return Ok(value);
+        }
+
+        let Some(delay) = schedule.next_delay() else {
+            session.emit(|id, e| Event::PollTimedOut {
+                session_id: id,
+                poll_id,
+                label: label.clone(),
+                attempts: attempt,
+                elapsed: e,
+            });
+            return Err(error(
+                ctx,
+                format!(
+                    "{label}: condition not met after {attempt} attempt(s) in {} ms",
+                    timeout as u64
+                ),
+            ));
+        };
+        std::thread::sleep(delay);
+    }
+}
+
+/// Registers `sleep(ms)`, `poll(f, options)` and `poll(f)`.
+pub(crate) fn register_poll(engine: &mut Engine, session: Arc<Mutex<Session>>) {
+    FuncRegistration::new("sleep")
+        .in_global_namespace()
+        .with_volatility(true)
+        .with_comments(["/// Pause the script for `ms` milliseconds."])
+        .register_into_engine(engine, |ms: i64| {
+            std::thread::sleep(Duration::from_millis(ms.max(0) as u64));
+        });
+
+    let poll_session = session.clone();
+    FuncRegistration::new("poll")
+        .in_global_namespace()
+        .with_volatility(true)
+        .with_comments([
+            "/// Call `f` until it returns something other than `false` or `()`, and return that value.",
+            "/// `options`: `interval_ms` (default 1000), `timeout_ms` (default 60000; then the script fails),",
+            "/// `backoff` (multiply the wait after each attempt, default 1) and `label` for the timeline.",
+            "/// Example: `poll(|| job::invoke().json.state == \"done\", #{ interval_ms: 500 })`.",
+        ])
+        .register_into_engine(
+            engine,
+            move |ctx: NativeCallContext, f: FnPtr, options: Map| {
+                poll(&ctx, &poll_session, &f, &options)
+            },
+        );
+
+    FuncRegistration::new("poll")
+        .in_global_namespace()
+        .with_volatility(true)
+        .with_comments(["/// Same as `poll(f, #{})`: every second for up to a minute."])
+        .register_into_engine(engine, move |ctx: NativeCallContext, f: FnPtr| {
+            poll(&ctx, &session, &f, &Map::new())
+        });
+}
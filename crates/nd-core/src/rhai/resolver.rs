@@ -1,4 +1,5 @@
-//! Custom [`rhai::ModuleResolver`]: `.rhai` modules and request files (`json`/`yaml`/`yml`).
+//! Custom [`rhai::ModuleResolver`]: `.rhai` modules and request files (`json`/`yaml`/`yml`, and
+//! `http`/`rest` files, whose modules also take a request name: `api::invoke("login")`).
 //!
 //! Imported request modules expose **`invoke`** (not `call`): Rhai reserves the identifier `call`
 //! in qualified paths such as `module::call(...)`, so scripts should use `my_req::invoke(#{ key: "v" })`.
@@ -7,11 +8,14 @@ use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 
-use rhai::{EvalAltResult, Module, ModuleResolver, Position, Scope, Shared};
+use rhai::{EvalAltResult, ImmutableString, Module, ModuleResolver, Position, Scope, Shared};
 
-use super::utils::json_to_dynamic;
+use super::checkpoint::Checkpoints;
+use super::concurrent::ImportedRequest;
+use super::utils::{dynamic_to_template_string, json_to_dynamic};
 use crate::error::Error as NdError;
 use crate::execute::types::{ExecutionResult, PrintOptions};
+use crate::model::http_file::is_http_file;
 use crate::model::request::RequestFile;
 use crate::stream::{MutexSession, Session};
 use crate::utils::path::resolve_file_path;
@@ -21,6 +25,11 @@ use crate::utils::path::resolve_file_path;
 pub struct RhaiScriptRunOptions {
     /// When true, `invoke()` does not perform network I/O (expand only; see return map).
     pub no_network_io: bool,
+    /// Host gate for `checkpoint()`; `None` records checkpoints without pausing.
+    pub checkpoints: Option<Checkpoints>,
+    /// Directories besides the main script's that `read_*` / `write_*` / `list_dir` may touch
+    /// (the `nd-web` workspace roots).
+    pub fs_roots: Vec<PathBuf>,
 }
 
 /// Resolves `import "path"` relative to the importing script (or the main script directory).
@@ -121,64 +130,92 @@ impl NativeImportResolver {
         path: &str,
         pos: Position,
     ) -> Result<Shared<Module>, Box<EvalAltResult>> {
-        let path_owned = path.to_string();
+        let docs: Arc<Vec<Arc<RequestFile>>> = Arc::new(
+            RequestFile::from_file_all(&file_path)
+                .map_err(|e| nd_error(path, pos, e))?
+                .into_iter()
+                .map(Arc::new)
+                .collect(),
+        );
+        let by_name = is_http_file(&file_path);
 
-        let doc: Arc<RequestFile> =
-            Arc::new(RequestFile::from_file(&file_path).map_err(|e| nd_error(path, pos, e))?);
+        let mut module = Module::new();
 
-        let options = self.options.clone();
+        // SETUP MODULE METHODS: 'invoke' and 'stream' (which fetches the body bit by bit)
+        for (fn_name, stream) in [("invoke", false), ("stream", true)] {
+            // Call the request (Without kwargs)
+            {
+                let docs = docs.clone();
+                let options = self.options.clone();
+                let path = path.to_string();
+                let session = self.session.clone();
+
+                module.set_native_fn(fn_name, move || {
+                    let doc = only_request(&docs, &path, pos)?;
+                    execute_request_call(doc, &session, &options, None, &path, pos, stream)
+                });
+            }
 
-        let mut module = Module::new();
+            // Call the request with kwargs
+            {
+                let docs = docs.clone();
+                let options = self.options.clone();
+                let path = path.to_string();
+                let session = self.session.clone();
+
+                module.set_native_fn(fn_name, move |kwargs: rhai::Map| {
+                    let doc = only_request(&docs, &path, pos)?;
+                    let overrides = map_to_overrides(&kwargs);
+                    execute_request_call(doc, &session, &options, Some(overrides), &path, pos, stream)
+                });
+            }
 
-        // SETUP MODULE METHOD: 'invoke'
-        // So we can call the request (Without kwards)
-        {
-            let doc = doc.clone();
-            let options = options.clone();
-            let path = path_owned.clone();
-            let session = self.session.clone();
+            if !by_name {
+                continue;
+            }
 
-            module.set_native_fn("invoke", move || {
-                execute_request_call(&doc, &session, &options, None, &path, pos, false)
-            });
-        }
+            // `.http` files: pick the request by name
+            {
+                let docs = docs.clone();
+                let options = self.options.clone();
+                let path = path.to_string();
+                let session = self.session.clone();
+
+                module.set_native_fn(fn_name, move |name: ImmutableString| {
+                    let doc = named_request(&docs, &name, &path, pos)?;
+                    execute_request_call(doc, &session, &options, None, &path, pos, stream)
+                });
+            }
 
-        // Call the request with kwargs
-        {
-            let doc = doc.clone();
-            let options = options.clone();
-            let path = path_owned.clone();
-            let session = self.session.clone();
-
-            module.set_native_fn("invoke", move |kwargs: rhai::Map| {
-                let overrides = map_to_overrides(&kwargs);
-                execute_request_call(&doc, &session, &options, Some(overrides), &path, pos, false)
-            });
+            {
+                let docs = docs.clone();
+                let options = self.options.clone();
+                let path = path.to_string();
+                let session = self.session.clone();
+
+                module.set_native_fn(fn_name, move |name: ImmutableString, kwargs: rhai::Map| {
+                    let doc = named_request(&docs, &name, &path, pos)?;
+                    let overrides = map_to_overrides(&kwargs);
+                    execute_request_call(doc, &session, &options, Some(overrides), &path, pos, stream)
+                });
+            }
         }
 
-        // SETUP MODULE METHOD: 'stream'
-        // So we can call the request (Without kwards)
+        // 'request' hands the request itself to `invoke_all` / `parallel_map`
         {
-            let doc = doc.clone();
-            let options = options.clone();
-            let path = path_owned.clone();
-            let session = self.session.clone();
-
-            module.set_native_fn("stream", move || {
-                execute_request_call(&doc, &session, &options, None, &path, pos, true)
+            let docs = docs.clone();
+            let path = path.to_string();
+            module.set_native_fn("request", move || {
+                let doc = only_request(&docs, &path, pos)?;
+                Ok(ImportedRequest { doc: doc.clone() })
             });
         }
 
-        // Call the request with kwargs
-        {
-            let doc = doc.clone();
-            let options = options.clone();
-            let path = path_owned.clone();
-            let session = self.session.clone();
-
-            module.set_native_fn("stream", move |kwargs: rhai::Map| {
-                let overrides = map_to_overrides(&kwargs);
-                execute_request_call(&doc, &session, &options, Some(overrides), &path, pos, true)
+        if by_name {
+            let path = path.to_string();
+            module.set_native_fn("request", move |name: ImmutableString| {
+                let doc = named_request(&docs, &name, &path, pos)?;
+                Ok(ImportedRequest { doc: doc.clone() })
             });
         }
 
@@ -186,6 +223,56 @@ impl NativeImportResolver {
     }
 }
 
+fn request_names(docs: &[Arc<RequestFile>]) -> String {
+    return docs
+        .iter()
+        .filter_map(|d| d.name.as_deref())
+        .map(|n| format!("\"{n}\""))
+        .collect::<Vec<_>>()
+        .join(", ");
+}
+
+/// The request of a single-request module; `.http` files with several need a name.
+fn only_request<'a>(
+    docs: &'a [Arc<RequestFile>],
+    path: &str,
+    pos: Position,
+) -> Result<&'a Arc<RequestFile>, Box<EvalAltResult>> {
+    if let [doc] = docs {
+        return Ok(doc);
+    }
+    return Err(Box::new(EvalAltResult::ErrorRuntime(
+        format!(
+            "{path} defines {} requests; pass one of their names: {}",
+            docs.len(),
+            request_names(docs)
+        )
+        .into(),
+        pos,
+    )));
+}
+
+fn named_request<'a>(
+    docs: &'a [Arc<RequestFile>],
+    name: &str,
+    path: &str,
+    pos: Position,
+) -> Result<&'a Arc<RequestFile>, Box<EvalAltResult>> {
+    return docs
+        .iter()
+        .find(|d| d.name.as_deref() == Some(name))
+        .ok_or_else(|| {
+            Box::new(EvalAltResult::ErrorRuntime(
+                format!(
+                    "{path} has no request named \"{name}\" (available: {})",
+                    request_names(docs)
+                )
+                .into(),
+                pos,
+            ))
+        });
+}
+
 fn nd_error(path: &str, pos: Position, e: NdError) -> Box<EvalAltResult> {
     return Box::new(EvalAltResult::ErrorInModule(
         path.to_string(),
@@ -194,10 +281,10 @@ fn nd_error(path: &str, pos: Position, e: NdError) -> Box<EvalAltResult> {
     ));
 }
 
-fn map_to_overrides(m: &rhai::Map) -> HashMap<String, String> {
+pub(crate) fn map_to_overrides(m: &rhai::Map) -> HashMap<String, String> {
     return m
         .iter()
-        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .map(|(k, v)| (k.to_string(), dynamic_to_template_string(v)))
         .collect();
 }
 
@@ -230,6 +317,22 @@ fn execution_result_to_dynamic(result: &ExecutionResult) -> rhai::Dynamic {
 
     map.insert("headers".into(), rhai::Dynamic::from_map(hdr));
 
+    let schema_errors: rhai::Array = result
+        .schema_errors
+        .iter()
+        .map(|e| {
+            let mut error = rhai::Map::new();
+            let pointer = match &e.pointer {
+                Some(pointer) => rhai::Dynamic::from(pointer.clone()),
+                None => rhai::Dynamic::UNIT,
+            };
+            error.insert("pointer".into(), pointer);
+            error.insert("message".into(), rhai::Dynamic::from(e.message.clone()));
+            rhai::Dynamic::from_map(error)
+        })
+        .collect();
+    map.insert("schema_errors".into(), rhai::Dynamic::from_array(schema_errors));
+
     if let Ok(text) = std::str::from_utf8(&result.body) {
         map.insert("body".into(), rhai::Dynamic::from(text.to_string()));
         if let Ok(j) = serde_json::from_str::<serde_json::Value>(text) {
@@ -246,7 +349,7 @@ fn execution_result_to_dynamic(result: &ExecutionResult) -> rhai::Dynamic {
 }
 
 #[allow(clippy::too_many_arguments)]
-fn execute_request_call(
+pub(crate) fn execute_request_call(
     doc: &Arc<RequestFile>,
     session: &Arc<Mutex<Session>>,
     options: &RhaiScriptRunOptions,
@@ -296,6 +399,72 @@ fn execute_request_call(
     return Ok(execution_result_to_dynamic(&result));
 }
 
+/// One request for [`execute_request_calls`], with its per-call `${VAR}` overrides.
+pub(crate) type RequestCall = (Arc<RequestFile>, Option<HashMap<String, String>>);
+
+/// Run `calls` concurrently on the tokio runtime, at most `concurrency` at a time, and return their
+/// results in the order given. Every call runs to completion before the first failure is reported.
+pub(crate) fn execute_request_calls(
+    calls: Vec<RequestCall>,
+    session: &Arc<Mutex<Session>>,
+    options: &RhaiScriptRunOptions,
+    concurrency: usize,
+    pos: Position,
+) -> Result<rhai::Array, Box<EvalAltResult>> {
+    // Nothing is sent, so there is nothing to overlap.
+    if options.no_network_io {
+        return calls
+            .into_iter()
+            .map(|(doc, overrides)| {
+                execute_request_call(&doc, session, options, overrides, "", pos, false)
+            })
+            .collect();
+    }
+
+    let outcomes = tokio::task::block_in_place(|| {
+        tokio::runtime::Handle::current().block_on(async {
+            let permits = Arc::new(tokio::sync::Semaphore::new(concurrency));
+            let tasks: Vec<_> = calls
+                .into_iter()
+                .map(|(doc, overrides)| {
+                    let session = session.clone();
+                    let permits = permits.clone();
+                    tokio::spawn(async move {
+                        let _permit = permits
+                            .acquire_owned()
+                            .await
+                            .expect("semaphore is never closed");
+                        doc.execute_with_overrides(session, overrides.as_ref(), false)
+                            .await
+                    })
+                })
+                .collect();
+
+            let mut outcomes = Vec::with_capacity(tasks.len());
+            for task in tasks {
+                outcomes.push(task.await);
+            }
+            outcomes
+        })
+    });
+
+    let mut results = rhai::Array::with_capacity(outcomes.len());
+    for (index, outcome) in outcomes.into_iter().enumerate() {
+        let result = outcome
+            .map_err(|e| e.to_string())
+            .and_then(|r| r.map_err(|e| e.to_string()))
+            .map_err(|e| {
+                Box::new(EvalAltResult::ErrorRuntime(
+                    format!("HTTP request failed (call {index}): {e}").into(),
+                    pos,
+                ))
+            })?;
+        result.print(PrintOptions::Compact);
+        results.push(execution_result_to_dynamic(&result));
+    }
+    return Ok(results);
+}
+
 impl ModuleResolver for NativeImportResolver {
     fn resolve(
         &self,
@@ -316,10 +485,12 @@ impl ModuleResolver for NativeImportResolver {
 
             match ext.as_str() {
                 "rhai" => self.load_rhai_module(engine, resolved, path, pos),
-                "json" | "yaml" | "yml" => self.load_request_module(resolved, path, pos),
+                "json" | "yaml" | "yml" | "http" | "rest" => {
+                    self.load_request_module(resolved, path, pos)
+                }
                 _ => Err(Box::new(EvalAltResult::ErrorRuntime(
                     format!(
-                        "import unsupported for extension '{ext}' (use .rhai, .json, .yaml, .yml): {}",
+                        "import unsupported for extension '{ext}' (use .rhai, .json, .yaml, .yml, .http, .rest): {}",
                         resolved.display()
                     )
                     .into(),
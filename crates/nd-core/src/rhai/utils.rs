@@ -33,6 +33,18 @@ pub fn json_to_dynamic(v: &Value) -> Dynamic {
     }
 }
 
+/// Stringifies a Rhai value for the runtime map / `${VAR}` overrides.
+///
+/// Strings are stored verbatim; maps and arrays become compact JSON so they can be inserted with
+/// `${json:VAR}`. Other scalars use their display form (`42`, `true`, `1.5`).
+pub fn dynamic_to_template_string(v: &Dynamic) -> String {
+    let v = v.flatten_clone();
+    if v.is_map() || v.is_array() {
+        return dynamic_to_json_impl(&v).to_string();
+    }
+    return v.to_string();
+}
+
 /// Maps a Rhai [`Dynamic`] into [`serde_json::Value`].
 ///
 /// Function pointers and other non-JSON values become `null` or a short diagnostic string.
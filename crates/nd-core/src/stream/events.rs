@@ -16,6 +16,9 @@ pub enum Event {
     /// Expanded request is about to be sent (or simulated in dry-run).
     HttpRequestStarted {
         session_id: String,
+        /// Unique per send; pairs this with its [`Event::HttpResponseCompleted`] (and the stream
+        /// events' `id`) when requests run concurrently.
+        request_id: String,
         request_name: Option<String>,
         method: String,
         url: String,
@@ -67,6 +70,8 @@ pub enum Event {
     /// Response received (or dry-run row: `status == 0`, see [`crate::execute::ExecutionResult`]).
     HttpResponseCompleted {
         session_id: String,
+        /// Same as in the matching [`Event::HttpRequestStarted`].
+        request_id: String,
         request_name: Option<String>,
         status: u16,
         final_url: String,
@@ -136,13 +141,48 @@ pub enum Event {
         passed: bool,
         elapsed: Duration,
         message: String,
+        /// JSON pointer into the response body a schema check failed at (`""` is the whole body);
+        /// `None` for script `assert` calls and checks not tied to the body.
+        pointer: Option<String>,
     },
-    /// we'd add a new rhai function step(string) that just shoots this events. basically does nothing else
+    /// Rhai `step(name)` or the start of `step(name, || { ... })`: later events belong to this step.
     NewStepEncountered {
         session_id: String,
         name: String,
         elapsed: Duration,
     },
+    /// End of a block `step(name, || { ... })`; plain `step(name)` markers never finish.
+    StepFinished {
+        session_id: String,
+        name: String,
+        success: bool,
+        /// Why the block failed (`None` on success).
+        error: Option<String>,
+        /// Time spent in the block.
+        duration: Duration,
+        elapsed: Duration,
+    },
+    /// One attempt of a request file's `poll_until` or a Rhai `poll()`; `done` is set on the
+    /// attempt that met the condition.
+    PollAttempt {
+        session_id: String,
+        /// Shared by every attempt of the same poll.
+        poll_id: String,
+        /// Request name, or the label passed to `poll()`.
+        label: String,
+        /// 1-based.
+        attempt: u32,
+        done: bool,
+        elapsed: Duration,
+    },
+    /// A poll gave up: its condition was still unmet when the timeout ran out.
+    PollTimedOut {
+        session_id: String,
+        poll_id: String,
+        label: String,
+        attempts: u32,
+        elapsed: Duration,
+    },
     /// Called when we load a file, request or session
     FileLoaded {
         session_id: String,
@@ -3,15 +3,22 @@ use std::{
     time::{Duration, Instant},
 };
 
+use chrono::{DateTime, Utc};
+
 use crate::env::RuntimeEnv;
+use crate::execute::types::ExecutionResult;
 
 pub mod events;
+pub mod steps;
 
 /// In-process timeline for one run: stamps monotonic [`Duration`]s and optionally forwards each event (SSE, channels).
 pub struct Session {
     id: String,
     start: Instant,
+    started_at: DateTime<Utc>,
     events: Vec<events::Event>,
+    /// Completed HTTP exchanges, kept only after [`Self::keep_results`] (e.g. for HAR export).
+    results: Option<Vec<ExecutionResult>>,
     pub runtime: RuntimeEnv,
     live: Option<Box<dyn FnMut(events::Event) + Send>>,
 }
@@ -28,7 +35,9 @@ impl Session {
         let mut instance = Self {
             id: session_id,
             start,
+            started_at: Utc::now(),
             events: Vec::new(),
+            results: None,
             live: sink,
             runtime: runtime()?,
         };
@@ -56,10 +65,32 @@ impl Session {
         self.start.elapsed()
     }
 
+    /// Wall-clock time of [`events::Event::SessionStarted`]; event `elapsed` values are relative to it.
+    pub fn started_at(&self) -> DateTime<Utc> {
+        self.started_at
+    }
+
     pub fn events(&self) -> &[events::Event] {
         self.events.as_slice()
     }
 
+    /// Start keeping a copy of every [`ExecutionResult`] (headers and bodies included) from now on.
+    pub fn keep_results(&mut self) {
+        self.results.get_or_insert_with(Vec::new);
+    }
+
+    /// Results kept since [`Self::keep_results`], in completion order (empty when not enabled).
+    pub fn results(&self) -> &[ExecutionResult] {
+        self.results.as_deref().unwrap_or_default()
+    }
+
+    /// Stores a copy of `result` when [`Self::keep_results`] is on.
+    pub fn record_result(&mut self, result: &ExecutionResult) {
+        if let Some(results) = self.results.as_mut() {
+            results.push(result.clone());
+        }
+    }
+
     /// Build an event with the current session-relative [`Duration`] (preferred over [`Self::push`]).
     pub fn emit(&mut self, f: impl FnOnce(String, Duration) -> events::Event) {
         let event = f(self.id.clone(), self.start.elapsed());
@@ -73,13 +104,19 @@ impl Session {
 
     /// Appends [`events::Event::SessionEnded`] and returns the full timeline (including the end marker).
     pub fn finish(&mut self) -> Vec<events::Event> {
+        self.end();
+        return std::mem::take(&mut self.events);
+    }
+
+    /// Appends [`events::Event::SessionEnded`] but keeps the timeline, for sessions exported after
+    /// the run (e.g. HAR via [`Self::keep_results`]).
+    pub fn end(&mut self) {
         let elapsed = self.start.elapsed();
         let end = events::Event::SessionEnded {
             session_id: self.id.clone(),
             elapsed,
         };
         self.record(end);
-        return std::mem::take(&mut self.events);
     }
 
     /// Consume the buffer without appending `SessionEnded` (e.g. after fatal error you already recorded).
@@ -112,6 +149,7 @@ pub trait MutexSession {
     fn emit(&self, f: impl FnOnce(String, Duration) -> events::Event);
     fn reload_runtime(&self);
     fn runtime(&self) -> RuntimeEnv;
+    fn record_result(&self, result: &ExecutionResult);
 }
 
 impl MutexSession for Arc<Mutex<Session>> {
@@ -133,6 +171,11 @@ impl MutexSession for Arc<Mutex<Session>> {
         let session = self.lock().expect("session mutex poisoned");
         return session.runtime.clone();
     }
+
+    fn record_result(&self, result: &ExecutionResult) {
+        let mut session = self.lock().expect("session mutex poisoned");
+        session.record_result(result);
+    }
 }
 
 impl MutexSession for &Arc<Mutex<Session>> {
@@ -151,4 +194,8 @@ impl MutexSession for &Arc<Mutex<Session>> {
     fn runtime(&self) -> RuntimeEnv {
         Arc::clone(self).runtime()
     }
+
+    fn record_result(&self, result: &ExecutionResult) {
+        Arc::clone(self).record_result(result);
+    }
 }
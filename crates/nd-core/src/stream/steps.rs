@@ -0,0 +1,63 @@
+//! Named steps of a run, rebuilt from [`Event::NewStepEncountered`] / [`Event::StepFinished`].
+
+use std::time::Duration;
+
+use super::events::Event;
+
+/// How a step ended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StepOutcome {
+    /// A plain `step(name)` marker: no block, so nothing to pass or fail.
+    Marked,
+    Passed,
+    Failed(String),
+}
+
+/// One `step(...)` call, in the order the script reached it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Step {
+    pub name: String,
+    /// Session-relative time the step started.
+    pub started: Duration,
+    /// Time spent in a block step; `None` for markers.
+    pub duration: Option<Duration>,
+    pub outcome: StepOutcome,
+}
+
+/// Steps in `events`, pairing each [`Event::StepFinished`] with the latest open step of that name.
+pub fn steps(events: &[Event]) -> Vec<Step> {
+    let mut steps: Vec<Step> = Vec::new();
+
+    for event in events {
+        match event {
+            Event::NewStepEncountered { name, elapsed, .. } => steps.push(Step {
+                name: name.clone(),
+                started: *elapsed,
+                duration: None,
+                outcome: StepOutcome::Marked,
+            }),
+            Event::StepFinished {
+                name,
+                success,
+                error,
+                duration,
+                ..
+            } => {
+                let open = steps
+                    .iter_mut()
+                    .rev()
+                    .find(|s| &s.name == name && s.duration.is_none());
+                if let Some(step) = open {
+                    step.duration = Some(*duration);
+                    step.outcome = match (success, error) {
+                        (true, _) => StepOutcome::Passed,
+                        (false, error) => StepOutcome::Failed(error.clone().unwrap_or_default()),
+                    };
+                }
+            }
+            _ => {}
+        }
+    }
+
+    return steps;
+}
@@ -33,6 +33,13 @@ pub fn normalize_path_lexical(path: &Path) -> PathBuf {
     }
 }
 
+/// `true` if `canon` is exactly a root or nested under one (component-wise prefix match).
+///
+/// Both sides should be canonical, so symlinks pointing outside the roots are rejected.
+pub fn is_under_roots(canon: &Path, roots: &[PathBuf]) -> bool {
+    return roots.iter().any(|root| canon.starts_with(root));
+}
+
 /// Join `base_dir` with the relative `file` path from the request file, then normalize.
 ///
 /// If the resulting path exists, [`std::fs::canonicalize`] is applied so symlinks resolve; otherwise
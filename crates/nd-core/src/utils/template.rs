@@ -1,4 +1,12 @@
 //! `${VAR}` and `${!name}` substitution: environment lookups and dynamic generators (see [`crate::env::dynamic`]).
+//!
+//! JSON bodies also accept a whole-value `${json:VAR}` form: when a JSON string is **exactly** that
+//! placeholder, the variable is parsed as JSON and the typed value (number, bool, object, array,
+//! null) replaces the string in the tree.
+//!
+//! The importers (`.http` files here, other tools in `nd-generate`) share the helpers that turn
+//! foreign templates into these placeholders: [`var_ident`], [`dynamic_equivalent`] and
+//! [`quote_json_placeholders`].
 
 use std::collections::HashMap;
 use std::sync::OnceLock;
@@ -10,15 +18,117 @@ use crate::env::dynamic;
 use crate::env::RuntimeEnv;
 use crate::error::{Error, Result};
 
-/// Placeholders: `${VAR}` (env) or `${!name}` (dynamic), `IDENT` = `[A-Za-z_][A-Za-z0-9_]*`.
+/// Placeholders: `${VAR}` / `${json:VAR}` (env) or `${!name}` (dynamic), `IDENT` = `[A-Za-z_][A-Za-z0-9_]*`.
 fn placeholder_re() -> &'static Regex {
     static RE: OnceLock<Regex> = OnceLock::new();
     return RE.get_or_init(|| {
-        Regex::new(r"\$\{(?:!([A-Za-z_][A-Za-z0-9_]*)|([A-Za-z_][A-Za-z0-9_]*))\}")
+        Regex::new(r"\$\{(?:!([A-Za-z_][A-Za-z0-9_]*)|(?:json:)?([A-Za-z_][A-Za-z0-9_]*))\}")
             .expect("valid regex")
     });
 }
 
+/// Any `${...}` span, valid or not, capturing the text between the braces.
+pub fn placeholder_span_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    return RE.get_or_init(|| Regex::new(r"\$\{([^}]*)\}").expect("valid regex"));
+}
+
+/// Dynamic variables of other tools (`{{$guid}}` in Postman, Bruno and `.http` files, Insomnia's
+/// `{% faker 'name' %}`) with a `${!name}` equivalent.
+const DYNAMIC_VARIABLES: &[(&str, &str)] = &[
+    ("guid", "uuidv4"),
+    ("uuid", "uuidv4"),
+    ("random.uuid", "uuidv4"),
+    ("randomUUID", "uuidv4"),
+    ("randomInt", "random_int"),
+    ("random.integer", "random_int"),
+    ("timestamp", "timestamp"),
+    ("isoTimestamp", "iso_timestamp"),
+    ("randomFirstName", "random_name"),
+    ("randomEmail", "random_email"),
+    ("randomExampleEmail", "random_email"),
+    ("random.email", "random_email"),
+    ("randomUserName", "random_username"),
+    ("randomPhoneNumber", "random_phone"),
+    ("randomBoolean", "random_bool"),
+    ("random.bool", "random_bool"),
+    ("randomWords", "random_words"),
+    ("randomLoremWords", "random_words"),
+    ("randomLoremParagraph", "random_paragraph"),
+    ("randomHexColor", "color"),
+    ("randomDatePast", "random_date_past"),
+    ("randomDateFuture", "random_date_future"),
+];
+
+/// The `${!name}` generator for another tool's dynamic variable `name` (without the `$`).
+pub fn dynamic_equivalent(name: &str) -> Option<&'static str> {
+    return DYNAMIC_VARIABLES
+        .iter()
+        .find(|(theirs, _)| *theirs == name)
+        .map(|(_, ours)| *ours);
+}
+
+/// Variable name from another tool → `[A-Za-z_][A-Za-z0-9_]*` as `${VAR}` requires
+/// (`base-url` → `base_url`, `api.host` → `api_host`).
+pub fn var_ident(name: &str) -> String {
+    let mut out: String = name
+        .trim()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if out.is_empty() || out.starts_with(|c: char| c.is_ascii_digit()) {
+        out.insert(0, '_');
+    }
+    return out;
+}
+
+/// Quotes placeholders that stand for a whole value in JSON text (`"id": ${id}` →
+/// `"id": "${json:id}"`), so the body parses and the variable keeps its type when expanded.
+/// Dynamic placeholders are quoted as they are; placeholders inside strings are left alone.
+pub fn quote_json_placeholders(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        if !in_string && rest.starts_with("${") {
+            if let Some(end) = rest.find('}') {
+                let inner = &rest[2..end];
+                if inner.starts_with('!') {
+                    out.push_str(&format!("\"{}\"", &rest[..=end]));
+                } else {
+                    let name = inner.strip_prefix("json:").unwrap_or(inner);
+                    out.push_str(&format!("\"${{json:{name}}}\""));
+                }
+                rest = &rest[end + 1..];
+                continue;
+            }
+        }
+        if in_string {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == '"' {
+                in_string = false;
+            }
+        } else if c == '"' {
+            in_string = true;
+        }
+        out.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    return out;
+}
+
+/// A JSON string that is exactly `${json:VAR}` (no surrounding text).
+fn json_value_placeholder_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    return RE.get_or_init(|| {
+        Regex::new(r"^\$\{json:([A-Za-z_][A-Za-z0-9_]*)\}$").expect("valid regex")
+    });
+}
+
 fn resolve_env_var(
     env: &RuntimeEnv,
     overrides: Option<&HashMap<String, String>>,
@@ -33,6 +143,19 @@ fn resolve_env_var(
         .ok_or_else(|| Error::MissingTemplateVar(name.to_string()))
 }
 
+/// Resolve `name` like `${VAR}` and parse the text as JSON for `${json:VAR}` whole-value placeholders.
+fn resolve_json_var(
+    env: &RuntimeEnv,
+    overrides: Option<&HashMap<String, String>>,
+    name: &str,
+) -> Result<Value> {
+    let raw = resolve_env_var(env, overrides, name)?;
+    return serde_json::from_str(&raw).map_err(|e| Error::InvalidJsonTemplateVar {
+        name: name.to_string(),
+        message: e.to_string(),
+    });
+}
+
 /// Replace every `${VAR}` in `input` with values from `env`, and every `${!name}` with a dynamic
 /// value from [`dynamic::invoke`]. Fails if any variable is unset or the function name is unknown.
 pub fn expand_string(env: &RuntimeEnv, input: &str) -> Result<String> {
@@ -41,6 +164,9 @@ pub fn expand_string(env: &RuntimeEnv, input: &str) -> Result<String> {
 
 /// Like [`expand_string`], but for each `${VAR}` uses `overrides` first (when provided), then `env`.
 /// `${!name}` dynamic placeholders are unchanged.
+///
+/// Inside plain strings `${json:VAR}` behaves like `${VAR}` (the raw text is inserted); typed
+/// insertion only happens in [`expand_json_value_with_overrides`].
 pub fn expand_string_with_overrides(
     env: &RuntimeEnv,
     overrides: Option<&HashMap<String, String>>,
@@ -70,6 +196,9 @@ pub fn expand_string_with_overrides(
 }
 
 /// Recursively expand `${VAR}` / `${!name}` in JSON strings and in object keys (same rules as [`expand_string`]).
+///
+/// A string value that is exactly `${json:VAR}` is replaced by the variable parsed as JSON, so
+/// `"${json:USER_ID}"` with `USER_ID=42` becomes the number `42` rather than the string `"42"`.
 pub fn expand_json_value(env: &RuntimeEnv, value: &Value) -> Result<Value> {
     return expand_json_value_with_overrides(env, None, value);
 }
@@ -81,7 +210,13 @@ pub fn expand_json_value_with_overrides(
     value: &Value,
 ) -> Result<Value> {
     match value {
-        Value::String(s) => return Ok(Value::String(expand_string_with_overrides(env, overrides, s)?)),
+        Value::String(s) => {
+            if let Some(cap) = json_value_placeholder_re().captures(s) {
+                let name = cap.get(1).expect("json placeholder name").as_str();
+                return resolve_json_var(env, overrides, name);
+            }
+            return Ok(Value::String(expand_string_with_overrides(env, overrides, s)?));
+        }
         Value::Array(items) => {
             let mut out = Vec::with_capacity(items.len());
             for item in items {
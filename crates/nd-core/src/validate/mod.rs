@@ -0,0 +1,112 @@
+//! Response validation after a request runs: JSON Schema checks of the body a request file declares
+//! ([`schema`]), and contract checks against the OpenAPI operation it links to ([`openapi`]).
+//!
+//! Problems are collected as [`SchemaError`]s on [`crate::execute::types::ExecutionResult`] and
+//! reported as [`Event::AssertCalled`] events, one per problem (or one passing event when there
+//! are none).
+
+pub mod openapi;
+pub mod schema;
+
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use jsonschema::Draft;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::stream::events::Event;
+use crate::stream::{MutexSession, Session};
+
+/// One way a response broke the contract declared for it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchemaError {
+    /// JSON pointer into the response body (`/items/0/id`; `""` is the whole body). `None` when
+    /// the problem is not in the body (status, content type, or a contract that failed to load).
+    pub pointer: Option<String>,
+    pub message: String,
+}
+
+impl SchemaError {
+    pub(crate) fn new(message: impl Into<String>) -> Self {
+        return Self {
+            pointer: None,
+            message: message.into(),
+        };
+    }
+}
+
+impl std::fmt::Display for SchemaError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return match &self.pointer {
+            Some(pointer) if pointer.is_empty() => write!(f, "body: {}", self.message),
+            Some(pointer) => write!(f, "body{pointer}: {}", self.message),
+            None => write!(f, "{}", self.message),
+        };
+    }
+}
+
+/// Validate `instance` against `schema`; an invalid schema is reported as one [`SchemaError`].
+/// Without `draft`, the schema's `$schema` picks one (default: 2020-12).
+pub(crate) fn validate_json(
+    schema: &Value,
+    draft: Option<Draft>,
+    instance: &Value,
+) -> Vec<SchemaError> {
+    let mut options = jsonschema::options();
+    if let Some(draft) = draft {
+        options = options.with_draft(draft);
+    }
+    let validator = match options.build(schema) {
+        Ok(validator) => validator,
+        Err(err) => return vec![SchemaError::new(format!("invalid schema: {err}"))],
+    };
+    return validator
+        .iter_errors(instance)
+        .map(|err| SchemaError {
+            pointer: Some(err.instance_path.to_string()),
+            message: err.to_string(),
+        })
+        .collect();
+}
+
+/// Read a JSON document, or YAML for a `.yaml` / `.yml` extension.
+pub(crate) fn load_document(path: &Path) -> Result<Value, String> {
+    let text = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_ascii_lowercase();
+    return match ext.as_str() {
+        "yaml" | "yml" => serde_yaml::from_str(&text).map_err(|e| e.to_string()),
+        _ => serde_json::from_str(&text).map_err(|e| e.to_string()),
+    };
+}
+
+/// Emit one failing [`Event::AssertCalled`] per error, or a passing one (`passed_message`) when
+/// there are none.
+pub(crate) fn emit_asserts(
+    session: &Arc<Mutex<Session>>,
+    errors: &[SchemaError],
+    passed_message: &str,
+) {
+    if errors.is_empty() {
+        session.emit(|id, elapsed| Event::AssertCalled {
+            session_id: id,
+            passed: true,
+            elapsed,
+            message: passed_message.to_string(),
+            pointer: None,
+        });
+    }
+    for error in errors {
+        session.emit(|id, elapsed| Event::AssertCalled {
+            session_id: id,
+            passed: false,
+            elapsed,
+            message: error.message.clone(),
+            pointer: error.pointer.clone(),
+        });
+    }
+}
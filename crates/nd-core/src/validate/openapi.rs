@@ -0,0 +1,352 @@
+//! Contract checks against the OpenAPI operation a request file links to (`openapi.spec` and
+//! `openapi.operation_id`, written by `nativedoctor generate`).
+//!
+//! The spec path is resolved against the request file's directory and read as Swagger 2.0 or
+//! OpenAPI 3.0 / 3.1 (JSON or YAML). The operation is found by `operationId`, or by the
+//! `method-path` name `generate` gives operations without one. Then:
+//!
+//! - the status must be documented: its exact code, else its range (`2XX`), else `default`;
+//! - the `Content-Type` must be one of the response's media types (`type/*` and `*/*` match
+//!   too; Swagger 2.0 uses `produces`);
+//! - a JSON body must match that media type's schema. 3.0 and 2.0 schemas are checked as JSON
+//!   Schema draft 4 with `nullable` / `x-nullable` allowing `null`; 3.1 schemas as draft 2020-12.
+//!   Local `$ref`s resolve against the spec.
+//!
+//! Parsed specs are cached per canonical path until the file's modification time changes, so
+//! many responses checked against one spec (e.g. `invoke_all` over generated requests) parse it
+//! once.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::SystemTime;
+
+use jsonschema::Draft;
+use serde_json::{json, Map, Value};
+
+use super::{load_document, validate_json, SchemaError};
+use crate::model::request::OpenApiLink;
+use crate::utils::path::resolve_file_path;
+
+const METHODS: [&str; 8] = [
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+/// Longest `$ref` chain followed before giving up (cycles).
+const MAX_REF_DEPTH: usize = 32;
+
+/// Problems with a response to the operation `link` names; empty when it matches the contract.
+/// `request_path` is the request file, which `link.spec` is relative to. Nothing is checked when
+/// `link.spec` is `None`.
+pub fn check_response(
+    link: &OpenApiLink,
+    request_path: Option<&Path>,
+    status: u16,
+    headers: &[(String, String)],
+    body: &[u8],
+) -> Vec<SchemaError> {
+    let Some(spec) = &link.spec else {
+        return Vec::new();
+    };
+    let base = request_path
+        .and_then(Path::parent)
+        .unwrap_or(Path::new("."));
+    let spec_path = resolve_file_path(base, spec);
+    let doc = match cached_document(&spec_path) {
+        Ok(doc) => doc,
+        Err(message) => {
+            return vec![SchemaError::new(format!(
+                "cannot read OpenAPI spec {}: {message}",
+                spec_path.display()
+            ))]
+        }
+    };
+    return match check(&doc, &link.operation_id, status, headers, body) {
+        Ok(errors) => errors,
+        Err(message) => vec![SchemaError::new(message)],
+    };
+}
+
+/// Parsed specs by canonical path, with the modification time they were read at.
+type SpecCache = Mutex<HashMap<PathBuf, (SystemTime, Arc<Value>)>>;
+
+/// The parsed spec at `path`, reloaded when the file's modification time changes.
+fn cached_document(path: &Path) -> Result<Arc<Value>, String> {
+    static CACHE: OnceLock<SpecCache> = OnceLock::new();
+    let modified = std::fs::metadata(path)
+        .and_then(|m| m.modified())
+        .map_err(|e| e.to_string())?;
+    let cache = CACHE.get_or_init(|| Mutex::new(HashMap::new()));
+
+    if let Some((at, doc)) = cache.lock().expect("spec cache poisoned").get(path) {
+        if *at == modified {
+            return Ok(doc.clone());
+        }
+    }
+    // Parse outside the lock; two threads racing on a cold spec both parse it once.
+    let doc = Arc::new(load_document(path)?);
+    cache
+        .lock()
+        .expect("spec cache poisoned")
+        .insert(path.to_path_buf(), (modified, doc.clone()));
+    return Ok(doc);
+}
+
+/// Which JSON Schema flavour the spec's schemas are written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Dialect {
+    Swagger2,
+    OpenApi30,
+    OpenApi31,
+}
+
+fn dialect(doc: &Value) -> Dialect {
+    if doc.get("swagger").is_some() {
+        return Dialect::Swagger2;
+    }
+    let version = match doc.get("openapi") {
+        Some(Value::String(v)) => v.trim().to_string(),
+        Some(other) => other.to_string(),
+        None => String::new(),
+    };
+    if version.starts_with("3.1") {
+        return Dialect::OpenApi31;
+    }
+    return Dialect::OpenApi30;
+}
+
+/// `Err` when the operation or its response cannot be looked up (reported as one error).
+fn check(
+    doc: &Value,
+    operation_id: &str,
+    status: u16,
+    headers: &[(String, String)],
+    body: &[u8],
+) -> Result<Vec<SchemaError>, String> {
+    let dialect = dialect(doc);
+    let operation = find_operation(doc, operation_id)?
+        .ok_or_else(|| format!("operation `{operation_id}` is not in the OpenAPI spec"))?;
+    let responses = match operation.get("responses") {
+        Some(responses) => resolve(doc, responses)?,
+        None => return Ok(Vec::new()),
+    };
+    let code = status.to_string();
+    let range = format!("{}XX", status / 100);
+    let response = responses
+        .get(&code)
+        .or_else(|| responses.get(&range))
+        .or_else(|| responses.get(range.to_ascii_lowercase()))
+        .or_else(|| responses.get("default"));
+    let Some(response) = response else {
+        let documented: Vec<&str> = responses
+            .as_object()
+            .into_iter()
+            .flat_map(|r| r.keys())
+            .map(String::as_str)
+            .filter(|k| !k.starts_with("x-"))
+            .collect();
+        return Ok(vec![SchemaError::new(format!(
+            "status {status} is not documented for `{operation_id}` (documented: {})",
+            documented.join(", ")
+        ))]);
+    };
+    let response = resolve(doc, response)?;
+
+    // Media types the response may have, each with its schema.
+    let media: Vec<(String, Option<&Value>)> = if dialect == Dialect::Swagger2 {
+        let produces = operation
+            .get("produces")
+            .or_else(|| doc.get("produces"))
+            .and_then(Value::as_array);
+        let schema = response.get("schema");
+        match produces {
+            Some(types) => types
+                .iter()
+                .filter_map(Value::as_str)
+                .map(|t| (t.to_string(), schema))
+                .collect(),
+            None if schema.is_some() => vec![("application/json".to_string(), schema)],
+            None => Vec::new(),
+        }
+    } else {
+        response
+            .get("content")
+            .and_then(Value::as_object)
+            .into_iter()
+            .flatten()
+            .map(|(t, m)| (t.clone(), m.get("schema")))
+            .collect()
+    };
+    if media.is_empty() || body.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let expected = || {
+        media
+            .iter()
+            .map(|(t, _)| t.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let Some(content_type) = headers
+        .iter()
+        .find(|(k, _)| k.eq_ignore_ascii_case("content-type"))
+        .map(|(_, v)| essence(v))
+    else {
+        return Ok(vec![SchemaError::new(format!(
+            "response has no Content-Type (expected one of: {})",
+            expected()
+        ))]);
+    };
+    let (major, _) = content_type.split_once('/').unwrap_or((&content_type, ""));
+    let wildcard = format!("{major}/*");
+    let matched = [content_type.as_str(), wildcard.as_str(), "*/*"]
+        .into_iter()
+        .find_map(|want| media.iter().find(|(t, _)| essence(t) == want));
+    let Some((_, schema)) = matched else {
+        return Ok(vec![SchemaError::new(format!(
+            "content type {content_type} is not one of: {}",
+            expected()
+        ))]);
+    };
+
+    let Some(schema) = schema else {
+        return Ok(Vec::new());
+    };
+    if content_type != "application/json" && !content_type.ends_with("+json") {
+        return Ok(Vec::new());
+    }
+    let instance: Value = match serde_json::from_slice(body) {
+        Ok(v) => v,
+        Err(err) => {
+            return Ok(vec![SchemaError {
+                pointer: Some(String::new()),
+                message: format!("body is not valid JSON: {err}"),
+            }])
+        }
+    };
+    let (schema, draft) = json_schema(doc, schema, dialect);
+    return Ok(validate_json(&schema, Some(draft), &instance));
+}
+
+/// Lowercased media type without parameters (`application/json; charset=utf-8` → `application/json`).
+fn essence(content_type: &str) -> String {
+    let essence = content_type.split(';').next().unwrap_or_default();
+    return essence.trim().to_ascii_lowercase();
+}
+
+/// The operation whose `operationId` (or `method-path` fallback name) is `operation_id`.
+fn find_operation<'a>(doc: &'a Value, operation_id: &str) -> Result<Option<&'a Value>, String> {
+    let Some(paths) = doc.get("paths").and_then(Value::as_object) else {
+        return Ok(None);
+    };
+    for (path, item) in paths {
+        let item = resolve(doc, item)?;
+        for method in METHODS {
+            let Some(operation) = item.get(method) else {
+                continue;
+            };
+            let matches = match operation.get("operationId").and_then(Value::as_str) {
+                Some(id) => id == operation_id,
+                None => fallback_operation_name(method, path) == operation_id,
+            };
+            if matches {
+                return Ok(Some(operation));
+            }
+        }
+    }
+    return Ok(None);
+}
+
+/// Same as `generate`'s name for operations without an `operationId` (`get-users-id`).
+fn fallback_operation_name(method: &str, path: &str) -> String {
+    let slug: String = path
+        .trim_start_matches('/')
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    let slug = slug
+        .split('-')
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    return format!("{}-{}", method.to_ascii_lowercase(), slug);
+}
+
+/// Follow local `$ref`s (`#/components/responses/NotFound`) until a non-reference value.
+fn resolve<'a>(doc: &'a Value, mut value: &'a Value) -> Result<&'a Value, String> {
+    for _ in 0..MAX_REF_DEPTH {
+        let Some(reference) = value.get("$ref").and_then(Value::as_str) else {
+            return Ok(value);
+        };
+        let pointer = reference
+            .strip_prefix('#')
+            .ok_or_else(|| format!("cannot resolve $ref {reference} (only local refs)"))?;
+        value = doc
+            .pointer(pointer)
+            .ok_or_else(|| format!("cannot resolve $ref {reference}"))?;
+    }
+    return Err("$ref chain too long (cycle?)".to_string());
+}
+
+/// A standalone JSON Schema for `schema`: the spec's schema containers ride along so local
+/// `$ref`s resolve, with 3.0 / 2.0 nullability rewritten as a `null` type.
+fn json_schema(doc: &Value, schema: &Value, dialect: Dialect) -> (Value, Draft) {
+    let (container, draft) = match dialect {
+        Dialect::Swagger2 => ("definitions", Draft::Draft4),
+        Dialect::OpenApi30 => ("components", Draft::Draft4),
+        Dialect::OpenApi31 => ("components", Draft::Draft202012),
+    };
+    let mut root = Map::new();
+    root.insert("allOf".into(), json!([schema]));
+    if let Some(defs) = doc.get(container) {
+        root.insert(container.into(), defs.clone());
+    }
+    let mut root = Value::Object(root);
+    if dialect != Dialect::OpenApi31 {
+        nullable_to_null_type(&mut root);
+    }
+    return (root, draft);
+}
+
+/// `nullable: true` (3.0) / `x-nullable: true` (2.0) → also accept `null`, everywhere in `value`.
+fn nullable_to_null_type(value: &mut Value) {
+    match value {
+        Value::Array(items) => items.iter_mut().for_each(nullable_to_null_type),
+        Value::Object(fields) => {
+            fields.values_mut().for_each(nullable_to_null_type);
+            let mut nullable = false;
+            for key in ["nullable", "x-nullable"] {
+                if fields.get(key) == Some(&Value::Bool(true)) {
+                    fields.remove(key);
+                    nullable = true;
+                }
+            }
+            if !nullable {
+                return;
+            }
+            if let Some(Value::Array(values)) = fields.get_mut("enum") {
+                if !values.contains(&Value::Null) {
+                    values.push(Value::Null);
+                }
+            }
+            match fields.get("type") {
+                Some(Value::String(t)) if !fields.contains_key("$ref") => {
+                    let t = t.clone();
+                    fields.insert("type".into(), json!([t, "null"]));
+                }
+                _ => {
+                    let inner = Value::Object(std::mem::take(fields));
+                    fields.insert("anyOf".into(), json!([{ "type": "null" }, inner]));
+                }
+            }
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,53 @@
+//! JSON Schema checks of the response body against a request file's `response_schema`, for APIs
+//! without an OpenAPI spec.
+//!
+//! The schema is given inline or as a path to a JSON / YAML file, resolved against the request
+//! file's directory. Its `$schema` picks the draft (default: 2020-12); `$ref`s must stay within
+//! the schema document. The body must be JSON, whatever its `Content-Type`.
+
+use std::path::Path;
+
+use serde_json::Value;
+
+use super::{load_document, validate_json, SchemaError};
+use crate::model::request::ResponseSchema;
+use crate::utils::path::resolve_file_path;
+
+/// Where `body` does not match `schema`; empty when it does. `request_path` is the request file,
+/// which a schema path is relative to.
+pub fn check_body(
+    schema: &ResponseSchema,
+    request_path: Option<&Path>,
+    body: &[u8],
+) -> Vec<SchemaError> {
+    let loaded;
+    let schema = match schema {
+        ResponseSchema::Inline(schema) => schema,
+        ResponseSchema::Path(path) => {
+            let base = request_path
+                .and_then(Path::parent)
+                .unwrap_or(Path::new("."));
+            let path = resolve_file_path(base, path);
+            loaded = match load_document(&path) {
+                Ok(schema) => schema,
+                Err(message) => {
+                    return vec![SchemaError::new(format!(
+                        "cannot read JSON Schema {}: {message}",
+                        path.display()
+                    ))]
+                }
+            };
+            &loaded
+        }
+    };
+    let instance: Value = match serde_json::from_slice(body) {
+        Ok(v) => v,
+        Err(err) => {
+            return vec![SchemaError {
+                pointer: Some(String::new()),
+                message: format!("body is not valid JSON: {err}"),
+            }]
+        }
+    };
+    return validate_json(schema, None, &instance);
+}
@@ -0,0 +1,62 @@
+//! Helpers shared by the integration tests (each test binary uses a different subset).
+#![allow(dead_code)]
+
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use nd_core::env::RuntimeEnv;
+use nd_core::stream::events::Event;
+use nd_core::stream::Session;
+use wiremock::matchers::method;
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// Session over an empty runtime map.
+pub fn new_session() -> Arc<Mutex<Session>> {
+    return session_with(&[]);
+}
+
+/// Session whose runtime map starts with `vars`.
+pub fn session_with(vars: &[(&str, &str)]) -> Arc<Mutex<Session>> {
+    let env = RuntimeEnv::new();
+    for (key, value) in vars {
+        env.set(*key, *value);
+    }
+    return Arc::new(Mutex::new(Session::new(move || Ok(env), None).unwrap()));
+}
+
+/// Mock server answering every `GET` with `status`, `content_type` and `body`.
+pub async fn serve(status: u16, content_type: &str, body: &str) -> MockServer {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(
+            ResponseTemplate::new(status).set_body_raw(body.as_bytes().to_vec(), content_type),
+        )
+        .mount(&server)
+        .await;
+    return server;
+}
+
+/// `(passed, message, pointer)` of every [`Event::AssertCalled`] so far.
+pub fn asserts(session: &Arc<Mutex<Session>>) -> Vec<(bool, String, Option<String>)> {
+    let guard = session.lock().unwrap();
+    return guard
+        .events()
+        .iter()
+        .filter_map(|e| match e {
+            Event::AssertCalled {
+                passed,
+                message,
+                pointer,
+                ..
+            } => Some((*passed, message.clone(), pointer.clone())),
+            _ => None,
+        })
+        .collect();
+}
+
+/// Writes `script` as `dir/main.rhai` and returns its path.
+pub fn write_script(dir: &Path, script: &str) -> PathBuf {
+    let main = dir.join("main.rhai");
+    std::fs::write(&main, script).unwrap();
+    return main;
+}
@@ -85,3 +85,22 @@ fn partition_valid_request_paths_empty_input() {
     assert!(valid.is_empty());
     assert!(skipped.is_empty());
 }
+
+#[test]
+fn http_files_are_listed_and_validated() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("api.http"),
+        "GET https://example.com/a\n\n###\nGET https://example.com/b\n",
+    )
+    .unwrap();
+    std::fs::write(dir.path().join("bad.rest"), "GET https://example.com\nnot a header\n").unwrap();
+
+    let paths = list_request_paths(dir.path()).unwrap();
+    assert_eq!(paths.len(), 2);
+
+    let (valid, skipped) = partition_valid_request_paths(&paths);
+    assert_eq!(valid, vec![dir.path().join("api.http")]);
+    assert_eq!(skipped.len(), 1);
+    assert!(skipped[0].message.contains("line 2"), "{}", skipped[0].message);
+}
@@ -0,0 +1,140 @@
+use std::sync::{Arc, Mutex};
+
+use nd_core::env::RuntimeEnv;
+use nd_core::har;
+use nd_core::model::pairs::NameValueList;
+use nd_core::model::request::{HttpRequestSpec, RequestBody, RequestFile};
+use nd_core::stream::{events, MutexSession, Session};
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn document(name: &str, method: &str, url: String, body: Option<RequestBody>) -> RequestFile {
+    let mut query = NameValueList::new();
+    query.append("page", "2");
+    RequestFile {
+        version: "0.1.1".into(),
+        name: Some(name.into()),
+        request: HttpRequestSpec {
+            method: method.into(),
+            url,
+            summary: None,
+            description: None,
+            tags: vec![],
+            deprecated: false,
+            query,
+            headers: NameValueList::new(),
+            body,
+            timeout_secs: Some(5),
+            follow_redirects: true,
+            verify_tls: true,
+        },
+        openapi: None,
+        response_schema: None,
+        poll_until: None,
+        _path: None,
+    }
+}
+
+#[tokio::test]
+async fn har_from_session_has_entries_with_headers_bodies_timings_and_pages() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/items"))
+        .respond_with(
+            ResponseTemplate::new(201).set_body_raw(r#"{"id":7}"#, "application/json"),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/items"))
+        .respond_with(ResponseTemplate::new(200).set_body_bytes(vec![0xff, 0x00, 0x10]))
+        .mount(&server)
+        .await;
+
+    let session = Arc::new(Mutex::new(
+        Session::new(|| Ok(RuntimeEnv::new()), None).unwrap(),
+    ));
+    session.lock().unwrap().keep_results();
+
+    let create = document(
+        "Create",
+        "POST",
+        format!("{}/items", server.uri()),
+        Some(RequestBody::Text("hello".into())),
+    );
+    create.execute(session.clone(), false).await.unwrap();
+    session.emit(|id, e| events::Event::NewStepEncountered {
+        session_id: id,
+        name: "list".into(),
+        elapsed: e,
+    });
+    let list = document("List", "GET", format!("{}/items", server.uri()), None);
+    list.execute(session.clone(), true).await.unwrap();
+
+    let har = har::from_session(&session.lock().unwrap());
+    assert_eq!(har.log.version, "1.2");
+    assert_eq!(har.log.creator.name, "nativedoctor");
+    assert_eq!(har.log.pages.len(), 1);
+    assert_eq!(har.log.pages[0].title, "list");
+    assert_eq!(har.log.entries.len(), 2);
+
+    let first = &har.log.entries[0];
+    assert_eq!(first.pageref, None);
+    assert_eq!(first.comment.as_deref(), Some("Create"));
+    assert_eq!(first.request.method, "POST");
+    assert_eq!(first.request.url, format!("{}/items?page=2", server.uri()));
+    assert_eq!(
+        first.request.query_string,
+        vec![har::NameValue {
+            name: "page".into(),
+            value: "2".into()
+        }]
+    );
+    assert!(first
+        .request
+        .headers
+        .iter()
+        .any(|h| h.name.eq_ignore_ascii_case("content-type")));
+    let post = first.request.post_data.as_ref().unwrap();
+    assert_eq!(post.text, "hello");
+    assert_eq!(first.response.status, 201);
+    assert_eq!(first.response.status_text, "Created");
+    assert_eq!(first.response.content.mime_type, "application/json");
+    assert_eq!(first.response.content.text.as_deref(), Some(r#"{"id":7}"#));
+    assert!(first.timings.wait >= 0.0 && first.timings.receive >= 0.0);
+    assert!(first.time >= first.timings.wait);
+    assert!(first.started_date_time.ends_with('Z'));
+
+    let second = &har.log.entries[1];
+    assert_eq!(second.pageref.as_deref(), Some("step_1"));
+    assert_eq!(second.response.content.encoding.as_deref(), Some("base64"));
+    assert_eq!(second.response.content.text.as_deref(), Some("/wAQ"));
+    assert!(second.request.post_data.is_none());
+
+    let json = serde_json::to_value(&har).unwrap();
+    assert!(json["log"]["entries"][0]["response"]
+        .get("redirectURL")
+        .is_some());
+    assert!(json["log"]["entries"][0]["request"]
+        .get("postData")
+        .is_some());
+}
+
+#[tokio::test]
+async fn har_is_empty_unless_results_are_kept() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(ResponseTemplate::new(204))
+        .mount(&server)
+        .await;
+
+    let session = Arc::new(Mutex::new(
+        Session::new(|| Ok(RuntimeEnv::new()), None).unwrap(),
+    ));
+    let list = document("List", "GET", format!("{}/items", server.uri()), None);
+    list.execute(session.clone(), false).await.unwrap();
+
+    let guard = session.lock().unwrap();
+    assert!(guard.results().is_empty());
+    assert!(har::from_session(&guard).log.entries.is_empty());
+}
@@ -0,0 +1,205 @@
+//! `.http` / `.rest` parsing ([`nd_core::model::http_file`]) and loading through [`RequestFile`].
+
+use std::path::Path;
+
+use nd_core::error::Error;
+use nd_core::model::http_file::parse_http_file;
+use nd_core::model::request::{RequestBody, RequestBodyKind, RequestFile};
+use serde_json::json;
+
+const API_HTTP: &str = r#"@host = https://api.example.com
+@users = {{host}}/v1/users
+
+### List users
+GET {{users}}
+    ?page=2
+    &limit={{page_size}}
+Accept: application/json
+
+###
+# Creates a user.
+# @name createUser
+# @no-redirect
+POST {{users}} HTTP/1.1
+Content-Type: application/json
+Authorization: Bearer {{api-token}}
+
+{
+  "id": "{{$guid}}",
+  "age": {{age}},
+  "name": "Ada"
+}
+
+> {%
+    client.global.set("id", response.body.id);
+%}
+
+###
+DELETE {{users}}/1
+"#;
+
+fn structured(body: &Option<RequestBody>) -> (RequestBodyKind, serde_json::Value) {
+    let Some(RequestBody::Structured(s)) = body else {
+        panic!("expected a structured body, got {body:?}");
+    };
+    return (s.body_type, s.content.clone());
+}
+
+#[test]
+fn parses_requests_variables_and_names() {
+    let files = parse_http_file(API_HTTP, Path::new("api.http")).unwrap();
+    assert_eq!(files.len(), 3);
+
+    let list = &files[0];
+    assert_eq!(list.name.as_deref(), Some("List users"));
+    assert_eq!(list.request.method, "GET");
+    assert_eq!(
+        list.request.url,
+        "https://api.example.com/v1/users?page=2&limit=${page_size}"
+    );
+    assert_eq!(list.request.headers.get("Accept"), Some("application/json"));
+    assert!(list.request.body.is_none());
+
+    let create = &files[1];
+    assert_eq!(create.name.as_deref(), Some("createUser"));
+    assert_eq!(create.request.method, "POST");
+    assert_eq!(create.request.url, "https://api.example.com/v1/users");
+    assert_eq!(create.request.description.as_deref(), Some("Creates a user."));
+    assert!(!create.request.follow_redirects);
+    assert_eq!(
+        create.request.headers.get("Authorization"),
+        Some("Bearer ${api_token}")
+    );
+    let (kind, content) = structured(&create.request.body);
+    assert_eq!(kind, RequestBodyKind::Json);
+    assert_eq!(
+        content,
+        json!({ "id": "${!uuidv4}", "age": "${json:age}", "name": "Ada" })
+    );
+
+    let delete = &files[2];
+    assert_eq!(delete.name.as_deref(), Some("api #3"));
+    assert_eq!(delete.request.method, "DELETE");
+    assert_eq!(delete._path.as_deref(), Some(Path::new("api.http")));
+}
+
+#[test]
+fn bare_url_is_a_get_and_text_bodies_keep_their_content_type() {
+    let text = "https://example.com/ping\n\n###\nPUT https://example.com/note\nContent-Type: text/plain\n\nline one\n\nline two\n\n";
+    let files = parse_http_file(text, Path::new("misc.rest")).unwrap();
+
+    assert_eq!(files[0].request.method, "GET");
+    assert_eq!(files[0].request.url, "https://example.com/ping");
+
+    let (kind, content) = structured(&files[1].request.body);
+    assert_eq!(kind, RequestBodyKind::Text);
+    assert_eq!(content, json!("line one\n\nline two"));
+}
+
+#[test]
+fn rejects_request_variables_with_line_number() {
+    let text = "GET https://example.com/login\n\n###\nGET https://example.com/me\nAuthorization: Bearer {{login.response.body.$.token}}\n";
+    let err = parse_http_file(text, Path::new("chain.http")).unwrap_err();
+
+    match err {
+        Error::ParseHttpFile { line, message, .. } => {
+            assert_eq!(line, 5);
+            assert!(message.contains("Rhai"), "{message}");
+        }
+        other => panic!("unexpected error: {other}"),
+    }
+}
+
+#[test]
+fn rejects_file_variable_cycles() {
+    let text = "@a = {{b}}\n@b = {{a}}\nGET https://example.com/{{a}}\n";
+    let err = parse_http_file(text, Path::new("cycle.http")).unwrap_err();
+
+    assert!(err.to_string().contains("cycle"), "{err}");
+}
+
+#[test]
+fn body_file_reference_is_read_relative_to_the_http_file() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("payload.json"), r#"{"ok": true}"#).unwrap();
+    let path = dir.path().join("upload.http");
+    std::fs::write(
+        &path,
+        "POST https://example.com/upload\nContent-Type: application/json\n\n< ./payload.json\n",
+    )
+    .unwrap();
+
+    let file = RequestFile::from_file(&path).unwrap();
+
+    let (kind, content) = structured(&file.request.body);
+    assert_eq!(kind, RequestBodyKind::Json);
+    assert_eq!(content, json!({ "ok": true }));
+}
+
+#[test]
+fn body_file_reference_is_sent_verbatim_unless_it_asks_for_variables() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("payload.json"),
+        r#"{"host": "{{host}}", "name": "{{name}}"}"#,
+    )
+    .unwrap();
+    let path = dir.path().join("upload.http");
+    let request = |reference: &str| {
+        let text = format!(
+            "@host = api.example.com\n\nPOST https://example.com/upload\nContent-Type: application/json\n\n{reference} ./payload.json\n"
+        );
+        let files = parse_http_file(&text, &path).unwrap();
+        structured(&files[0].request.body)
+    };
+
+    let (kind, content) = request("<");
+    assert_eq!(kind, RequestBodyKind::Json);
+    assert_eq!(content, json!({ "host": "{{host}}", "name": "{{name}}" }));
+
+    let (kind, content) = request("<@");
+    assert_eq!(kind, RequestBodyKind::Json);
+    assert_eq!(content, json!({ "host": "api.example.com", "name": "${name}" }));
+}
+
+#[test]
+fn body_file_reference_must_stay_inside_the_http_file_directory() {
+    let dir = tempfile::tempdir().unwrap();
+    let outside = dir.path().join("secret.txt");
+    std::fs::write(&outside, "secret").unwrap();
+    let project = dir.path().join("project");
+    std::fs::create_dir(&project).unwrap();
+    let path = project.join("upload.http");
+
+    for reference in [outside.display().to_string(), "../secret.txt".to_string()] {
+        let text = format!("POST https://example.com/upload\n\n< {reference}\n");
+        let err = parse_http_file(&text, &path).unwrap_err();
+        match err {
+            Error::ParseHttpFile { line, message, .. } => {
+                assert_eq!(line, 3);
+                assert!(
+                    message.contains("relative") || message.contains("outside"),
+                    "{message}"
+                );
+            }
+            other => panic!("unexpected error: {other}"),
+        }
+    }
+}
+
+#[test]
+fn from_file_needs_a_single_request_but_from_file_all_loads_every_one() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("api.http");
+    std::fs::write(&path, API_HTTP).unwrap();
+
+    let err = RequestFile::from_file(&path).unwrap_err();
+    assert!(
+        matches!(err, Error::MultipleRequests { count: 3, .. }),
+        "{err}"
+    );
+
+    let all = RequestFile::from_file_all(&path).unwrap();
+    assert_eq!(all.len(), 3);
+    assert_eq!(all[0]._path.as_deref(), Some(path.as_path()));
+}
@@ -0,0 +1,185 @@
+mod common;
+
+use std::path::Path;
+
+use common::{asserts, new_session, serve};
+use nd_core::model::request::RequestFile;
+
+const SPEC: &str = r##"
+openapi: 3.0.3
+info: {title: Users, version: "1"}
+paths:
+  /users/{id}:
+    get:
+      operationId: getUser
+      responses:
+        "200":
+          description: ok
+          content:
+            application/json:
+              schema: {$ref: "#/components/schemas/User"}
+        4XX:
+          description: not found
+          content:
+            application/problem+json:
+              schema: {type: object, required: [title]}
+components:
+  schemas:
+    User:
+      type: object
+      required: [id, name]
+      properties:
+        id: {type: integer}
+        name: {type: string, nullable: true}
+        tags: {type: array, items: {type: string}}
+"##;
+
+const SWAGGER_SPEC: &str = r##"
+swagger: "2.0"
+info: {title: Users, version: "1"}
+produces: [application/json]
+paths:
+  /users:
+    get:
+      responses:
+        200:
+          description: ok
+          schema:
+            type: array
+            items: {$ref: "#/definitions/User"}
+definitions:
+  User:
+    type: object
+    required: [id]
+    properties:
+      id: {type: integer}
+      email: {type: string, x-nullable: true}
+"##;
+
+/// Writes the spec and a request file linked to `operation_id` into `dir`, then loads it.
+fn linked_request(dir: &Path, spec: &str, operation_id: &str, url: &str) -> RequestFile {
+    std::fs::write(dir.join("openapi.yaml"), spec).unwrap();
+    let requests = dir.join("requests");
+    std::fs::create_dir_all(&requests).unwrap();
+    let file = requests.join("request.yaml");
+    let text = format!(
+        "request:\n  method: GET\n  url: \"{url}\"\nopenapi:\n  spec: ../openapi.yaml\n  operation_id: {operation_id}\n"
+    );
+    std::fs::write(&file, text).unwrap();
+    RequestFile::from_file(&file).unwrap()
+}
+
+#[tokio::test]
+async fn matching_response_emits_a_passing_assert() {
+    let server = serve(200, "application/json", r#"{"id": 7, "name": null}"#).await;
+    let tmp = tempfile::tempdir().unwrap();
+    let url = format!("{}/users/7", server.uri());
+    let document = linked_request(tmp.path(), SPEC, "getUser", &url);
+
+    let session = new_session();
+    let result = document.execute(session.clone(), false).await.unwrap();
+
+    assert!(
+        result.schema_errors.is_empty(),
+        "{:?}",
+        result.schema_errors
+    );
+    let asserts = asserts(&session);
+    assert_eq!(asserts.len(), 1);
+    assert!(asserts[0].0);
+    assert!(asserts[0].1.contains("getUser"), "{}", asserts[0].1);
+}
+
+#[tokio::test]
+async fn body_violations_are_reported_with_json_pointers() {
+    let body = r#"{"id": "seven", "tags": ["a", 2]}"#;
+    let server = serve(200, "application/json; charset=utf-8", body).await;
+    let tmp = tempfile::tempdir().unwrap();
+    let url = format!("{}/users/7", server.uri());
+    let document = linked_request(tmp.path(), SPEC, "getUser", &url);
+
+    let session = new_session();
+    let result = document.execute(session.clone(), false).await.unwrap();
+
+    let mut pointers: Vec<_> = result
+        .schema_errors
+        .iter()
+        .map(|e| e.pointer.clone().unwrap())
+        .collect();
+    pointers.sort();
+    assert_eq!(
+        pointers,
+        vec!["", "/id", "/tags/1"],
+        "{:?}",
+        result.schema_errors
+    );
+    let asserts = asserts(&session);
+    assert_eq!(asserts.len(), 3);
+    assert!(asserts
+        .iter()
+        .all(|(passed, _, pointer)| !passed && pointer.is_some()));
+}
+
+#[tokio::test]
+async fn status_and_content_type_are_checked() {
+    let tmp = tempfile::tempdir().unwrap();
+
+    let server = serve(500, "application/json", "{}").await;
+    let url = format!("{}/users/7", server.uri());
+    let document = linked_request(tmp.path(), SPEC, "getUser", &url);
+    let result = document.execute(new_session(), false).await.unwrap();
+    assert_eq!(result.schema_errors.len(), 1);
+    assert_eq!(result.schema_errors[0].pointer, None);
+    assert!(
+        result.schema_errors[0].message.contains("status 500"),
+        "{}",
+        result.schema_errors[0]
+    );
+
+    // 404 falls under `4XX`, which only declares problem+json.
+    let server = serve(404, "text/html", "<h1>gone</h1>").await;
+    let url = format!("{}/users/7", server.uri());
+    let document = linked_request(tmp.path(), SPEC, "getUser", &url);
+    let result = document.execute(new_session(), false).await.unwrap();
+    assert_eq!(result.schema_errors.len(), 1);
+    assert!(
+        result.schema_errors[0].message.contains("text/html"),
+        "{}",
+        result.schema_errors[0]
+    );
+
+    let server = serve(404, "application/problem+json", r#"{"title": "gone"}"#).await;
+    let url = format!("{}/users/7", server.uri());
+    let document = linked_request(tmp.path(), SPEC, "getUser", &url);
+    let result = document.execute(new_session(), false).await.unwrap();
+    assert!(
+        result.schema_errors.is_empty(),
+        "{:?}",
+        result.schema_errors
+    );
+}
+
+#[tokio::test]
+async fn swagger_2_operations_match_by_fallback_name() {
+    let body = r#"[{"id": 1, "email": null}, {"email": "a@example.test"}]"#;
+    let server = serve(200, "application/json", body).await;
+    let tmp = tempfile::tempdir().unwrap();
+    let url = format!("{}/users", server.uri());
+    let document = linked_request(tmp.path(), SWAGGER_SPEC, "get-users", &url);
+
+    let result = document.execute(new_session(), false).await.unwrap();
+    assert_eq!(result.schema_errors.len(), 1, "{:?}", result.schema_errors);
+    assert_eq!(result.schema_errors[0].pointer.as_deref(), Some("/1"));
+}
+
+#[tokio::test]
+async fn unknown_operation_fails_the_check() {
+    let server = serve(200, "application/json", "{}").await;
+    let tmp = tempfile::tempdir().unwrap();
+    let url = format!("{}/users/7", server.uri());
+    let document = linked_request(tmp.path(), SPEC, "removedOperation", &url);
+
+    let result = document.execute(new_session(), false).await.unwrap();
+    assert_eq!(result.schema_errors.len(), 1);
+    assert!(result.schema_errors[0].message.contains("removedOperation"));
+}
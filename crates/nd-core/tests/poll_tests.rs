@@ -0,0 +1,238 @@
+//! Polling: a request file's `poll_until`, and Rhai `poll()` / `sleep()`.
+
+mod common;
+
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use common::{new_session, write_script};
+use nd_core::error::Error;
+use nd_core::execute::poll::PollSchedule;
+use nd_core::model::request::RequestFile;
+use nd_core::rhai::{resolver::RhaiScriptRunOptions, run::run_rhai_script};
+use nd_core::stream::events::Event;
+use nd_core::stream::Session;
+use wiremock::matchers::method;
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// `(attempt, done)` of every poll attempt, and the attempt count of a timeout if there was one.
+fn poll_events(session: &Arc<Mutex<Session>>) -> (Vec<(u32, bool)>, Option<u32>) {
+    let guard = session.lock().unwrap();
+    let mut attempts = Vec::new();
+    let mut timed_out = None;
+    for event in guard.events() {
+        match event {
+            Event::PollAttempt { attempt, done, .. } => attempts.push((*attempt, *done)),
+            Event::PollTimedOut { attempts, .. } => timed_out = Some(*attempts),
+            _ => {}
+        }
+    }
+    (attempts, timed_out)
+}
+
+/// Writes `job.yaml` under `dir` with the given `poll_until` block.
+fn request(dir: &Path, url: &str, poll_until: &str) -> RequestFile {
+    let file = dir.join("job.yaml");
+    let text =
+        format!("name: job\nrequest:\n  method: GET\n  url: \"{url}\"\npoll_until:\n{poll_until}");
+    std::fs::write(&file, text).unwrap();
+    RequestFile::from_file(&file).unwrap()
+}
+
+fn json(status: u16, body: &str) -> ResponseTemplate {
+    ResponseTemplate::new(status).set_body_raw(body.as_bytes().to_vec(), "application/json")
+}
+
+#[tokio::test]
+async fn poll_until_resends_until_the_condition_holds() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(json(202, r#"{"state": "running"}"#))
+        .up_to_n_times(2)
+        .with_priority(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .respond_with(json(200, r#"{"state": "done", "result": 42}"#))
+        .mount(&server)
+        .await;
+
+    let tmp = tempfile::tempdir().unwrap();
+    let doc = request(
+        tmp.path(),
+        &server.uri(),
+        "  path: $.state\n  equals: done\n  interval_ms: 10\n  timeout_ms: 5000\n",
+    );
+    let session = new_session();
+    let result = doc.execute(session.clone(), false).await.unwrap();
+
+    assert_eq!(result.status, 200);
+    assert_eq!(
+        poll_events(&session),
+        (vec![(1, false), (2, false), (3, true)], None)
+    );
+}
+
+#[tokio::test]
+async fn poll_until_fails_after_the_timeout() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(json(202, r#"{"state": "running", "done": false}"#))
+        .mount(&server)
+        .await;
+
+    let tmp = tempfile::tempdir().unwrap();
+    let doc = request(
+        tmp.path(),
+        &server.uri(),
+        "  path: $.done\n  interval_ms: 20\n  timeout_ms: 100\n",
+    );
+    let session = new_session();
+    let err = doc.execute(session.clone(), false).await.unwrap_err();
+
+    assert!(
+        matches!(
+            err,
+            Error::PollTimedOut {
+                timeout_ms: 100,
+                ..
+            }
+        ),
+        "{err}"
+    );
+    let (attempts, timed_out) = poll_events(&session);
+    assert!(attempts.len() >= 2, "{attempts:?}");
+    assert!(attempts.iter().all(|(_, done)| !done));
+    assert_eq!(timed_out, Some(attempts.len() as u32));
+}
+
+#[tokio::test]
+async fn poll_until_rejects_a_non_finite_backoff() {
+    let tmp = tempfile::tempdir().unwrap();
+    let doc = request(tmp.path(), "http://127.0.0.1:1", "  path: $.done\n  backoff: .inf\n");
+    let err = doc.execute(new_session(), false).await.unwrap_err();
+
+    assert!(matches!(err, Error::InvalidPoll(_)), "{err}");
+    assert!(err.to_string().contains("finite"), "{err}");
+}
+
+#[tokio::test]
+async fn poll_until_survives_a_huge_backoff() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(json(202, r#"{"done": false}"#))
+        .mount(&server)
+        .await;
+
+    let tmp = tempfile::tempdir().unwrap();
+    let doc = request(
+        tmp.path(),
+        &server.uri(),
+        "  path: $.done\n  interval_ms: 10\n  timeout_ms: 100\n  backoff: 1e300\n",
+    );
+    let err = doc.execute(new_session(), false).await.unwrap_err();
+
+    assert!(matches!(err, Error::PollTimedOut { .. }), "{err}");
+}
+
+#[test]
+fn schedule_without_a_representable_deadline_keeps_going() {
+    let mut schedule = PollSchedule::new(Duration::from_millis(5), Duration::MAX, 2.0);
+    assert_eq!(schedule.next_delay(), Some(Duration::from_millis(5)));
+    assert_eq!(schedule.next_delay(), Some(Duration::from_millis(10)));
+}
+
+#[tokio::test]
+async fn poll_until_accepts_the_largest_timeout() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .respond_with(json(200, r#"{"done": true}"#))
+        .mount(&server)
+        .await;
+
+    let tmp = tempfile::tempdir().unwrap();
+    let doc = request(
+        tmp.path(),
+        &server.uri(),
+        "  path: $.done\n  timeout_ms: 18446744073709551615\n",
+    );
+    let session = new_session();
+    let result = doc.execute(session.clone(), false).await.unwrap();
+
+    assert_eq!(result.status, 200);
+    assert_eq!(poll_events(&session), (vec![(1, true)], None));
+}
+
+fn run(script: &str, session: Arc<Mutex<Session>>) -> nd_core::error::Result<()> {
+    let tmp = tempfile::tempdir().unwrap();
+    let main = write_script(tmp.path(), script);
+    run_rhai_script(&main, session, RhaiScriptRunOptions::default())
+}
+
+#[test]
+fn rhai_poll_returns_the_first_truthy_value() {
+    let session = new_session();
+    run(
+        r#"let n = 0;
+let value = poll(|| {
+    n += 1;
+    if n == 3 { #{ attempts: n } } else { () }
+}, #{ interval_ms: 5, backoff: 2 });
+assert(value.attempts == 3, "third attempt");
+sleep(1);
+"#,
+        session.clone(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        poll_events(&session),
+        (vec![(1, false), (2, false), (3, true)], None)
+    );
+}
+
+#[test]
+fn rhai_poll_times_out() {
+    let session = new_session();
+    let err = run(
+        r#"poll(|| false, #{ interval_ms: 10, timeout_ms: 50, label: "never" });"#,
+        session.clone(),
+    )
+    .unwrap_err();
+
+    assert!(
+        err.to_string().contains("never: condition not met"),
+        "{err}"
+    );
+    assert!(poll_events(&session).1.is_some());
+}
+
+#[test]
+fn rhai_poll_checks_the_backoff() {
+    let err = run(
+        r#"poll(|| false, #{ interval_ms: 1, timeout_ms: 10, backoff: 1.0 / 0.0 });"#,
+        new_session(),
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("`backoff` must be a finite number"), "{err}");
+
+    let err = run(
+        r#"poll(|| false, #{ interval_ms: 10, timeout_ms: 50, backoff: 1e300 });"#,
+        new_session(),
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("condition not met"), "{err}");
+}
+
+#[test]
+fn rhai_poll_accepts_a_huge_timeout() {
+    run(
+        r#"let n = 0;
+let value = poll(|| { n += 1; n == 2 }, #{ interval_ms: 1, timeout_ms: 1e30 });
+assert(value == true, "second attempt");
+"#,
+        new_session(),
+    )
+    .unwrap();
+}
@@ -1,7 +1,7 @@
-use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 
 use nd_core::env::RuntimeEnv;
+use nd_core::model::pairs::NameValueList;
 use nd_core::model::request::{HttpRequestSpec, RequestBody, RequestFile};
 use nd_core::stream::events;
 use nd_core::stream::Session;
@@ -34,13 +34,16 @@ async fn request_file_execute_sends_request_and_returns_response_details() {
             description: None,
             tags: vec![],
             deprecated: false,
-            query: HashMap::new(),
-            headers: HashMap::new(),
+            query: NameValueList::new(),
+            headers: NameValueList::new(),
             body: Some(RequestBody::Text("hello from nativedoctor".into())),
             timeout_secs: Some(5),
             follow_redirects: true,
             verify_tls: true,
         },
+        openapi: None,
+        response_schema: None,
+        poll_until: None,
         _path: None,
     };
 
@@ -89,13 +92,16 @@ async fn request_file_execute_stream_collects_body_and_emits_stream_events() {
             description: None,
             tags: vec![],
             deprecated: false,
-            query: HashMap::new(),
-            headers: HashMap::new(),
+            query: NameValueList::new(),
+            headers: NameValueList::new(),
             body: None,
             timeout_secs: Some(5),
             follow_redirects: true,
             verify_tls: true,
         },
+        openapi: None,
+        response_schema: None,
+        poll_until: None,
         _path: None,
     };
 
@@ -131,3 +137,49 @@ async fn request_file_execute_stream_collects_body_and_emits_stream_events() {
         .iter()
         .any(|e| matches!(e, events::Event::HttpResponseCompleted { .. })));
 }
+
+#[tokio::test]
+async fn request_file_execute_sends_repeated_headers_and_query_pairs() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/multi"))
+        .respond_with(ResponseTemplate::new(204))
+        .mount(&server)
+        .await;
+
+    let document = RequestFile {
+        request: HttpRequestSpec {
+            method: "GET".into(),
+            url: format!("{}/multi", server.uri()),
+            summary: None,
+            description: None,
+            tags: vec![],
+            deprecated: false,
+            query: NameValueList::from_iter([("id", "1"), ("id", "2")]),
+            headers: NameValueList::from_iter([("X-Tag", "a"), ("X-Tag", "b")]),
+            body: None,
+            timeout_secs: Some(5),
+            follow_redirects: true,
+            verify_tls: true,
+        },
+        ..Default::default()
+    };
+
+    let session = Arc::new(Mutex::new(
+        Session::new(|| Ok(RuntimeEnv::new()), None).unwrap(),
+    ));
+    let result = document.execute(session, false).await.unwrap();
+    assert_eq!(result.status, 204);
+
+    let received = server.received_requests().await.unwrap();
+    let request = &received[0];
+    assert_eq!(request.url.query(), Some("id=1&id=2"));
+    let tags: Vec<_> = request
+        .headers
+        .get_all("x-tag")
+        .iter()
+        .map(|v| v.to_str().unwrap().to_string())
+        .collect();
+    assert_eq!(tags, vec!["a", "b"]);
+}
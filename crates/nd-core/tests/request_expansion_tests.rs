@@ -2,6 +2,7 @@ use std::collections::HashMap;
 
 use nd_core::env::RuntimeEnv;
 use nd_core::execute::format::format_prepared_request;
+use nd_core::model::pairs::NameValueList;
 use nd_core::model::request::{
     HttpRequestSpec, RequestBody, RequestBodyKind, RequestBodyStructured, RequestFile,
 };
@@ -22,8 +23,8 @@ fn request_expand_overrides_take_precedence_over_env() {
         description: None,
         tags: vec![],
         deprecated: false,
-        query: HashMap::new(),
-        headers: HashMap::new(),
+        query: NameValueList::new(),
+        headers: NameValueList::new(),
         body: None,
         timeout_secs: None,
         follow_redirects: true,
@@ -56,10 +57,10 @@ fn request_expand_applies_templates_and_computed_headers() {
         description: None,
         tags: vec![],
         deprecated: false,
-        query: HashMap::from([("name".into(), "${NAME}".into())]),
-        headers: HashMap::from([
-            ("Authorization".into(), "Bearer ${TOKEN}".into()),
-            ("Content-Type".into(), "application/custom+json".into()),
+        query: NameValueList::from_iter([("name", "${NAME}")]),
+        headers: NameValueList::from_iter([
+            ("Authorization", "Bearer ${TOKEN}"),
+            ("Content-Type", "application/custom+json"),
         ]),
         body: Some(RequestBody::Structured(RequestBodyStructured {
             body_type: RequestBodyKind::Json,
@@ -108,8 +109,8 @@ fn request_expand_rejects_invalid_binary_body_base64() {
         description: None,
         tags: vec![],
         deprecated: false,
-        query: HashMap::new(),
-        headers: HashMap::new(),
+        query: NameValueList::new(),
+        headers: NameValueList::new(),
         body: Some(RequestBody::Structured(RequestBodyStructured {
             body_type: RequestBodyKind::Binary,
             content: serde_json::Value::String("%%%not-base64%%%".into()),
@@ -136,8 +137,8 @@ fn format_prepared_request_prints_request_line_headers_and_body() {
         description: None,
         tags: vec![],
         deprecated: false,
-        query: HashMap::from([("q".into(), "1".into())]),
-        headers: HashMap::from([("x-test".into(), "yes".into())]),
+        query: NameValueList::from_iter([("q", "1")]),
+        headers: NameValueList::from_iter([("x-test", "yes")]),
         body: Some(RequestBody::Text("hello world".into())),
         timeout_secs: None,
         follow_redirects: true,
@@ -174,3 +175,172 @@ request:
     assert_eq!(doc.request.url, "https://example.test/health");
     assert_eq!(doc._path.as_deref(), Some(path.as_path()));
 }
+
+#[test]
+fn request_expand_json_placeholder_inserts_typed_values() {
+    let env = RuntimeEnv::new();
+    env.set("USER_ID", "42");
+    env.set("ACTIVE", "true");
+    env.set("PROFILE", r#"{"a":1,"tags":["x"]}"#);
+    env.set("NAME", "Kelvin");
+
+    let spec = HttpRequestSpec {
+        method: "POST".into(),
+        url: "https://example.test/users/${json:USER_ID}".into(),
+        summary: None,
+        description: None,
+        tags: vec![],
+        deprecated: false,
+        query: NameValueList::new(),
+        headers: NameValueList::new(),
+        body: Some(RequestBody::Json(serde_json::json!({
+            "id": "${json:USER_ID}",
+            "active": "${json:ACTIVE}",
+            "profile": "${json:PROFILE}",
+            "label": "user ${json:USER_ID}",
+            "name": "${NAME}"
+        }))),
+        timeout_secs: None,
+        follow_redirects: true,
+        verify_tls: true,
+    };
+
+    let prepared = spec.expand(&env).unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&prepared.body.unwrap()).unwrap();
+
+    assert_eq!(prepared.url, "https://example.test/users/42");
+    assert_eq!(
+        body,
+        serde_json::json!({
+            "id": 42,
+            "active": true,
+            "profile": {"a": 1, "tags": ["x"]},
+            "label": "user 42",
+            "name": "Kelvin"
+        })
+    );
+}
+
+#[test]
+fn request_expand_json_placeholder_rejects_invalid_json() {
+    let env = RuntimeEnv::new();
+    env.set("PAYLOAD", "not json");
+
+    let spec = HttpRequestSpec {
+        method: "POST".into(),
+        url: "https://example.test/items".into(),
+        summary: None,
+        description: None,
+        tags: vec![],
+        deprecated: false,
+        query: NameValueList::new(),
+        headers: NameValueList::new(),
+        body: Some(RequestBody::Json(serde_json::json!({ "payload": "${json:PAYLOAD}" }))),
+        timeout_secs: None,
+        follow_redirects: true,
+        verify_tls: true,
+    };
+
+    let err = match spec.expand(&env) {
+        Ok(_) => panic!("expected invalid JSON variable to fail"),
+        Err(err) => err.to_string(),
+    };
+    assert!(err.contains("PAYLOAD"));
+    assert!(err.contains("not valid JSON"));
+}
+
+#[test]
+fn request_file_list_form_keeps_order_and_repeated_names() {
+    let doc: RequestFile = serde_yaml::from_str(
+        r#"request:
+  method: GET
+  url: https://example.test/search
+  query:
+    - { name: tag, value: b }
+    - { name: page, value: "${PAGE}" }
+    - { name: tag, value: a }
+  headers:
+    - { name: X-Trace, value: one }
+    - { name: x-trace, value: two }
+    - { name: Accept, value: application/json }
+"#,
+    )
+    .unwrap();
+    let env = RuntimeEnv::new();
+    env.set("PAGE", "2");
+
+    let prepared = doc.request.expand(&env).unwrap();
+
+    assert_eq!(
+        prepared.query,
+        vec![
+            ("tag".into(), "b".into()),
+            ("page".into(), "2".into()),
+            ("tag".into(), "a".into()),
+        ]
+    );
+    let user_headers: Vec<_> = prepared
+        .headers
+        .iter()
+        .filter(|(k, _)| k != "user-agent")
+        .cloned()
+        .collect();
+    assert_eq!(
+        user_headers,
+        vec![
+            ("x-trace".into(), "one".into()),
+            ("x-trace".into(), "two".into()),
+            ("accept".into(), "application/json".into()),
+        ]
+    );
+    let formatted = format_prepared_request(&prepared).unwrap();
+    assert!(formatted.starts_with("GET https://example.test/search?tag=b&page=2&tag=a\n"));
+}
+
+#[test]
+fn request_file_map_form_keeps_declaration_order() {
+    let doc: RequestFile = serde_yaml::from_str(
+        r#"request:
+  method: GET
+  url: https://example.test/search
+  query:
+    z: "1"
+    a: "2"
+"#,
+    )
+    .unwrap();
+
+    let prepared = doc.request.expand(&RuntimeEnv::new()).unwrap();
+
+    assert_eq!(
+        prepared.query,
+        vec![("z".into(), "1".into()), ("a".into(), "2".into())]
+    );
+}
+
+#[test]
+fn name_value_list_serializes_as_map_unless_names_repeat() {
+    let unique = NameValueList::from_iter([("a", "1"), ("b", "2")]);
+    assert_eq!(
+        serde_json::to_value(&unique).unwrap(),
+        serde_json::json!({"a": "1", "b": "2"})
+    );
+
+    let mut repeated = unique.clone();
+    repeated.append("a", "3");
+    assert_eq!(
+        serde_json::to_value(&repeated).unwrap(),
+        serde_json::json!([
+            {"name": "a", "value": "1"},
+            {"name": "b", "value": "2"},
+            {"name": "a", "value": "3"}
+        ])
+    );
+    assert_eq!(repeated.get_all("a").collect::<Vec<_>>(), vec!["1", "3"]);
+
+    repeated.insert("a", "4");
+    assert_eq!(
+        repeated.iter().cloned().collect::<Vec<_>>(),
+        vec![("a".into(), "4".into()), ("b".into(), "2".into())]
+    );
+}
@@ -0,0 +1,146 @@
+mod common;
+
+use std::path::Path;
+
+use common::{asserts, new_session, serve};
+use nd_core::model::request::{RequestFile, ResponseSchema};
+use nd_core::rhai::{resolver::RhaiScriptRunOptions, run::run_rhai_script};
+use serde_json::json;
+
+const USER_SCHEMA: &str = r#"
+type: object
+required: [id, name]
+properties:
+  id: {type: integer}
+  name: {type: string}
+  roles:
+    type: array
+    items: {enum: [admin, member]}
+"#;
+
+/// Writes `requests/user.yaml` under `dir`, with `schema` as its `response_schema` value.
+fn request(dir: &Path, url: &str, schema: &str) -> RequestFile {
+    let requests = dir.join("requests");
+    std::fs::create_dir_all(&requests).unwrap();
+    let file = requests.join("user.yaml");
+    let text = format!(
+        "name: user\nrequest:\n  method: GET\n  url: \"{url}\"\nresponse_schema: {schema}\n"
+    );
+    std::fs::write(&file, text).unwrap();
+    RequestFile::from_file(&file).unwrap()
+}
+
+#[test]
+fn response_schema_is_a_path_or_an_inline_schema() {
+    let path: RequestFile = serde_yaml::from_str(
+        "request: {method: GET, url: 'http://x'}\nresponse_schema: schemas/user.json\n",
+    )
+    .unwrap();
+    assert_eq!(
+        path.response_schema,
+        Some(ResponseSchema::Path("schemas/user.json".into()))
+    );
+
+    let inline: RequestFile = serde_yaml::from_str(
+        "request: {method: GET, url: 'http://x'}\nresponse_schema: {type: array}\n",
+    )
+    .unwrap();
+    assert_eq!(
+        inline.response_schema,
+        Some(ResponseSchema::Inline(json!({"type": "array"})))
+    );
+}
+
+#[tokio::test]
+async fn schema_file_is_resolved_relative_to_the_request() {
+    let server = serve(200, "application/json", r#"{"id": 1, "name": "Ada", "roles": ["admin"]}"#).await;
+    let tmp = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(tmp.path().join("schemas")).unwrap();
+    std::fs::write(tmp.path().join("schemas/user.yaml"), USER_SCHEMA).unwrap();
+    let document = request(tmp.path(), &server.uri(), "../schemas/user.yaml");
+
+    let session = new_session();
+    let result = document.execute(session.clone(), false).await.unwrap();
+
+    assert!(
+        result.schema_errors.is_empty(),
+        "{:?}",
+        result.schema_errors
+    );
+    assert_eq!(
+        asserts(&session),
+        vec![(true, "response body matches its JSON Schema".to_string(), None)]
+    );
+}
+
+#[tokio::test]
+async fn violations_carry_instance_paths() {
+    let server = serve(200, "application/json", r#"{"id": "1", "roles": ["admin", "root"]}"#).await;
+    let tmp = tempfile::tempdir().unwrap();
+    let inline =
+        serde_json::to_string(&serde_yaml::from_str::<serde_json::Value>(USER_SCHEMA).unwrap())
+            .unwrap();
+    let document = request(tmp.path(), &server.uri(), &inline);
+
+    let session = new_session();
+    let result = document.execute(session.clone(), false).await.unwrap();
+
+    let mut pointers: Vec<_> = result
+        .schema_errors
+        .iter()
+        .map(|e| e.pointer.clone().unwrap())
+        .collect();
+    pointers.sort();
+    assert_eq!(
+        pointers,
+        vec!["", "/id", "/roles/1"],
+        "{:?}",
+        result.schema_errors
+    );
+    let asserts = asserts(&session);
+    assert_eq!(asserts.len(), 3);
+    assert!(asserts.iter().all(|(passed, _, _)| !passed));
+}
+
+#[tokio::test]
+async fn unreadable_schema_and_non_json_bodies_fail() {
+    let tmp = tempfile::tempdir().unwrap();
+
+    let server = serve(200, "application/json", r#"{"id": 1}"#).await;
+    let document = request(tmp.path(), &server.uri(), "missing.json");
+    let result = document.execute(new_session(), false).await.unwrap();
+    assert_eq!(result.schema_errors.len(), 1);
+    assert_eq!(result.schema_errors[0].pointer, None);
+    assert!(result.schema_errors[0].message.contains("missing.json"));
+
+    let server = serve(200, "application/json", "<html/>").await;
+    let document = request(tmp.path(), &server.uri(), "{type: object}");
+    let result = document.execute(new_session(), false).await.unwrap();
+    assert_eq!(result.schema_errors.len(), 1);
+    assert_eq!(result.schema_errors[0].pointer.as_deref(), Some(""));
+    assert!(result.schema_errors[0].message.contains("not valid JSON"));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn rhai_results_expose_schema_errors() {
+    let server = serve(200, "application/json", r#"{"id": "1", "name": "Ada"}"#).await;
+    let tmp = tempfile::tempdir().unwrap();
+    request(
+        tmp.path(),
+        &server.uri(),
+        "{required: [id], properties: {id: {type: integer}}}",
+    );
+    let main = tmp.path().join("main.rhai");
+    std::fs::write(
+        &main,
+        r#"import "requests/user.yaml" as user;
+let r = user::invoke();
+assert(r.schema_errors.len() == 1, "one error");
+assert(r.schema_errors[0].pointer == "/id", "pointer");
+assert(r.schema_errors[0].message.contains("integer"), "message");
+"#,
+    )
+    .unwrap();
+
+    run_rhai_script(&main, new_session(), RhaiScriptRunOptions::default()).unwrap();
+}
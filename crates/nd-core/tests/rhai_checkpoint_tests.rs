@@ -0,0 +1,110 @@
+//! Integration tests for Rhai `checkpoint()` and the host-side [`Checkpoints`] gate.
+
+mod common;
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use common::{new_session, write_script};
+use nd_core::rhai::checkpoint::Checkpoints;
+use nd_core::rhai::{resolver::RhaiScriptRunOptions, run::run_rhai_script};
+use nd_core::stream::events::Event;
+use nd_core::stream::Session;
+use serde_json::json;
+
+fn checkpoint_events(session: &Arc<Mutex<Session>>) -> Vec<Event> {
+    let guard = session.lock().unwrap();
+    guard
+        .events()
+        .iter()
+        .filter(|e| {
+            matches!(
+                e,
+                Event::CheckpointWaiting { .. } | Event::CheckpointResumed { .. }
+            )
+        })
+        .cloned()
+        .collect()
+}
+
+#[test]
+fn checkpoint_blocks_until_resumed() {
+    let tmp = tempfile::tempdir().unwrap();
+    let main = write_script(
+        tmp.path(),
+        "checkpoint(false, \"skipped\");\ncheckpoint(true, \"inspect\", #{ id: 7 });\nset(\"done\", \"1\");\n",
+    );
+    let session = new_session();
+    let gate = Checkpoints::new();
+
+    let runner = {
+        let session = session.clone();
+        let options = RhaiScriptRunOptions {
+            checkpoints: Some(gate.clone()),
+            ..Default::default()
+        };
+        std::thread::spawn(move || run_rhai_script(&main, session, options))
+    };
+
+    let id = loop {
+        if let Some(id) = gate.waiting().pop() {
+            break id;
+        }
+        std::thread::sleep(Duration::from_millis(5));
+    };
+    std::thread::sleep(Duration::from_millis(50));
+    assert!(
+        !runner.is_finished(),
+        "script should wait on the checkpoint"
+    );
+    assert_eq!(session.lock().unwrap().runtime.get("done"), None);
+
+    assert!(!gate.resume("unknown"));
+    assert!(gate.resume(&id));
+    runner.join().unwrap().unwrap();
+    assert_eq!(
+        session.lock().unwrap().runtime.get("done").as_deref(),
+        Some("1")
+    );
+
+    let events = checkpoint_events(&session);
+    assert_eq!(events.len(), 2, "checkpoint(false, ..) emits nothing");
+    match &events[0] {
+        Event::CheckpointWaiting {
+            checkpoint_id,
+            message,
+            observe,
+            ..
+        } => {
+            assert_eq!(checkpoint_id, &id);
+            assert_eq!(message, "inspect");
+            assert_eq!(observe, &json!({ "id": 7 }));
+        }
+        other => panic!("unexpected {other:?}"),
+    }
+    assert!(matches!(
+        &events[1],
+        Event::CheckpointResumed { checkpoint_id, .. } if checkpoint_id == &id
+    ));
+}
+
+#[test]
+fn checkpoint_without_a_gate_or_after_close_does_not_block() {
+    let tmp = tempfile::tempdir().unwrap();
+    let main = write_script(
+        tmp.path(),
+        "checkpoint(true, \"a\");\ncheckpoint(true, \"b\");\n",
+    );
+
+    let session = new_session();
+    run_rhai_script(&main, session.clone(), RhaiScriptRunOptions::default()).unwrap();
+    assert_eq!(checkpoint_events(&session).len(), 4);
+
+    let gate = Checkpoints::new();
+    gate.close();
+    let options = RhaiScriptRunOptions {
+        checkpoints: Some(gate),
+        ..Default::default()
+    };
+    run_rhai_script(&main, new_session(), options).unwrap();
+}
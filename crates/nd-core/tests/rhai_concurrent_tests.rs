@@ -0,0 +1,159 @@
+//! Integration tests for `invoke_all` / `parallel_map`: concurrent sends of imported requests.
+
+mod common;
+
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use common::{session_with, write_script};
+use nd_core::har;
+use nd_core::rhai::{resolver::RhaiScriptRunOptions, run::run_rhai_script};
+use nd_core::stream::events::Event;
+use nd_core::stream::Session;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// Serves `GET /items/{id}` for ids `1..=count`; lower ids answer more slowly, so responses
+/// complete in the reverse of the order they were sent.
+async fn serve(count: u64) -> MockServer {
+    let server = MockServer::start().await;
+    for id in 1..=count {
+        Mock::given(method("GET"))
+            .and(path(format!("/items/{id}")))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_raw(format!(r#"{{"id": {id}}}"#), "application/json")
+                    .set_delay(Duration::from_millis(50 * (count - id + 1))),
+            )
+            .mount(&server)
+            .await;
+    }
+    server
+}
+
+fn new_session(base: &str) -> Arc<Mutex<Session>> {
+    let session = session_with(&[("BASE", base)]);
+    session.lock().unwrap().keep_results();
+    session
+}
+
+fn run(dir: &Path, script: &str, session: Arc<Mutex<Session>>) -> nd_core::error::Result<()> {
+    std::fs::write(
+        dir.join("item.yaml"),
+        "name: item\nrequest:\n  method: GET\n  url: \"${BASE}/items/${id}\"\n",
+    )
+    .unwrap();
+    let main = write_script(dir, script);
+    run_rhai_script(&main, session, RhaiScriptRunOptions::default())
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn invoke_all_runs_concurrently_and_keeps_order() {
+    let server = serve(5).await;
+    let tmp = tempfile::tempdir().unwrap();
+    let session = new_session(&server.uri());
+
+    let started = Instant::now();
+    run(
+        tmp.path(),
+        r#"import "item.yaml" as item;
+let calls = [];
+for id in 1..=5 { calls.push([item::request(), #{ id: id }]); }
+let results = invoke_all(calls, #{ concurrency: 5 });
+assert(results.map(|r| r.json.id) == [1, 2, 3, 4, 5], "results in call order");
+assert(results.all(|r| r.status == 200), "all ok");
+"#,
+        session.clone(),
+    )
+    .unwrap();
+    // Sequentially this takes 50 + 100 + ... + 250 = 750 ms; concurrently about 250 ms.
+    assert!(
+        started.elapsed() < Duration::from_millis(600),
+        "{:?}",
+        started.elapsed()
+    );
+
+    let guard = session.lock().unwrap();
+    let mut starts = Vec::new();
+    let mut completions = Vec::new();
+    for event in guard.events() {
+        match event {
+            Event::HttpRequestStarted { request_id, .. } => starts.push(request_id.clone()),
+            Event::HttpResponseCompleted { request_id, .. } => completions.push(request_id.clone()),
+            _ => {}
+        }
+    }
+    assert_eq!(starts.len(), 5);
+    assert_ne!(starts, completions, "responses interleave");
+    let mut sorted = completions.clone();
+    sorted.sort();
+    starts.sort();
+    assert_eq!(starts, sorted, "every start has its completion");
+
+    // The HAR pairs each entry with its own response despite the interleaving.
+    let archive = har::from_session(&guard);
+    assert_eq!(archive.log.entries.len(), 5);
+    for entry in &archive.log.entries {
+        let id = entry.request.url.rsplit('/').next().unwrap();
+        assert_eq!(
+            entry.response.content.text.as_deref(),
+            Some(format!(r#"{{"id": {id}}}"#).as_str())
+        );
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn parallel_map_builds_calls_with_a_closure() {
+    let server = serve(3).await;
+    let tmp = tempfile::tempdir().unwrap();
+
+    run(
+        tmp.path(),
+        r#"import "item.yaml" as item;
+let results = parallel_map([3, 1, 2], |id| [item::request(), #{ id: id }], #{ concurrency: 2 });
+assert(results.map(|r| r.json.id) == [3, 1, 2], "order of items");
+assert(invoke_all([]) == [], "nothing to do");
+"#,
+        new_session(&server.uri()),
+    )
+    .unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn invoke_all_rejects_malformed_calls() {
+    let tmp = tempfile::tempdir().unwrap();
+    let session = new_session("http://127.0.0.1:9");
+
+    let err = run(tmp.path(), r#"invoke_all([#{ id: 1 }]);"#, session.clone()).unwrap_err();
+    assert!(
+        err.to_string().contains("call 0 must be a request"),
+        "{err}"
+    );
+
+    let err = run(
+        tmp.path(),
+        r#"import "item.yaml" as item; invoke_all([item::request()], #{ concurrency: 0 });"#,
+        session,
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("at least 1"), "{err}");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn invoke_all_caps_the_concurrency_at_the_number_of_calls() {
+    let server = serve(2).await;
+    let tmp = tempfile::tempdir().unwrap();
+
+    run(
+        tmp.path(),
+        r#"import "item.yaml" as item;
+let results = invoke_all([[item::request(), #{ id: 1 }], [item::request(), #{ id: 2 }]],
+    #{ concurrency: 9223372036854775807 });
+assert(results.map(|r| r.json.id) == [1, 2], "both calls ran");
+assert(invoke_all([], #{ concurrency: 9223372036854775807 }) == [], "no calls");
+"#,
+        new_session(&server.uri()),
+    )
+    .unwrap();
+}
@@ -0,0 +1,187 @@
+//! Integration tests for the sandboxed Rhai file functions (`read_*`, `write_*`, `list_dir`).
+
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use nd_core::env::RuntimeEnv;
+use nd_core::rhai::{resolver::RhaiScriptRunOptions, run::run_rhai_script};
+use nd_core::stream::Session;
+
+fn run_in(dir: &Path, script: &str, options: RhaiScriptRunOptions) -> nd_core::error::Result<()> {
+    let main = dir.join("main.rhai");
+    std::fs::write(&main, script).unwrap();
+    let session = Arc::new(Mutex::new(
+        Session::new(|| Ok(RuntimeEnv::new()), None).unwrap(),
+    ));
+    run_rhai_script(&main, session, options)
+}
+
+#[test]
+fn reads_fixtures_relative_to_the_script() {
+    let tmp = tempfile::tempdir().unwrap();
+    std::fs::create_dir(tmp.path().join("fixtures")).unwrap();
+    std::fs::write(tmp.path().join("fixtures/users.csv"), "id\n1\n2\n").unwrap();
+    std::fs::write(
+        tmp.path().join("fixtures/user.json"),
+        r#"{"id": 7, "tags": ["a"]}"#,
+    )
+    .unwrap();
+    std::fs::write(
+        tmp.path().join("fixtures/config.yaml"),
+        "base: http://x\nretries: 3\n",
+    )
+    .unwrap();
+
+    run_in(
+        tmp.path(),
+        r#"let ids = read_text("fixtures/users.csv").split("\n");
+assert(ids[1] == "1", "csv");
+assert(read_json("fixtures/user.json").tags[0] == "a", "json");
+assert(read_yaml("./fixtures/config.yaml").retries == 3, "yaml");
+
+let entries = list_dir("fixtures");
+assert(entries.len() == 3, "three entries");
+assert(entries[0].name == "config.yaml", "sorted");
+assert(entries[2].path == "fixtures/users.csv", "path");
+assert(read_text(entries[2].path) != "", "path can be read back");
+"#,
+        RhaiScriptRunOptions::default(),
+    )
+    .unwrap();
+}
+
+#[test]
+fn writes_create_missing_directories() {
+    let tmp = tempfile::tempdir().unwrap();
+    run_in(
+        tmp.path(),
+        r#"write_json("out/response.json", #{ status: 200, body: [1, ()] });
+write_text("out/notes.txt", "done");
+assert(read_json("out/response.json").status == 200, "round trip");
+"#,
+        RhaiScriptRunOptions::default(),
+    )
+    .unwrap();
+
+    let written = std::fs::read_to_string(tmp.path().join("out/response.json")).unwrap();
+    assert_eq!(
+        written,
+        "{\n  \"body\": [\n    1,\n    null\n  ],\n  \"status\": 200\n}\n"
+    );
+    assert_eq!(
+        std::fs::read_to_string(tmp.path().join("out/notes.txt")).unwrap(),
+        "done"
+    );
+}
+
+#[test]
+fn paths_outside_the_sandbox_are_rejected() {
+    let outside = tempfile::tempdir().unwrap();
+    std::fs::write(outside.path().join("secret.txt"), "s3cret").unwrap();
+    let tmp = tempfile::tempdir().unwrap();
+    let scripts = tmp.path().join("scripts");
+    std::fs::create_dir(&scripts).unwrap();
+
+    let secret = outside.path().join("secret.txt").display().to_string();
+    let err = run_in(
+        &scripts,
+        &format!("read_text(`{secret}`);"),
+        RhaiScriptRunOptions::default(),
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("outside the sandbox"), "{err}");
+
+    let err = run_in(
+        &scripts,
+        r#"write_text("../escaped.txt", "x");"#,
+        RhaiScriptRunOptions::default(),
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("outside the sandbox"), "{err}");
+    assert!(!tmp.path().join("escaped.txt").exists());
+
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink(outside.path().join("secret.txt"), scripts.join("link.txt"))
+            .unwrap();
+        let err = run_in(
+            &scripts,
+            r#"read_text("link.txt");"#,
+            RhaiScriptRunOptions::default(),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("outside the sandbox"), "{err}");
+    }
+
+    // An extra root (the `nd-web` workspace) widens the sandbox.
+    run_in(
+        &scripts,
+        &format!("assert(read_text(`{secret}`) == \"s3cret\", \"root\");"),
+        RhaiScriptRunOptions {
+            fs_roots: vec![outside.path().to_path_buf()],
+            ..Default::default()
+        },
+    )
+    .unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn writes_do_not_follow_symlinks_out_of_the_sandbox() {
+    let outside = tempfile::tempdir().unwrap();
+    let tmp = tempfile::tempdir().unwrap();
+    let pwned = outside.path().join("pwned.txt");
+    std::os::unix::fs::symlink(&pwned, tmp.path().join("link.txt")).unwrap();
+    std::os::unix::fs::symlink(outside.path().join("missing"), tmp.path().join("dir")).unwrap();
+
+    let err = run_in(
+        tmp.path(),
+        r#"write_text("link.txt", "x");"#,
+        RhaiScriptRunOptions::default(),
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("dangling symlink"), "{err}");
+
+    let err = run_in(
+        tmp.path(),
+        r#"write_text("dir/pwned.txt", "x");"#,
+        RhaiScriptRunOptions::default(),
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("dangling symlink"), "{err}");
+
+    assert!(!pwned.exists());
+    assert!(!outside.path().join("missing").exists());
+
+    // A live symlink to a file outside is still rejected, and the file is left alone.
+    std::fs::write(&pwned, "original").unwrap();
+    let err = run_in(
+        tmp.path(),
+        r#"write_text("link.txt", "x");"#,
+        RhaiScriptRunOptions::default(),
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("outside the sandbox"), "{err}");
+    assert_eq!(std::fs::read_to_string(&pwned).unwrap(), "original");
+}
+
+#[test]
+fn imported_modules_resolve_paths_from_their_own_directory() {
+    let tmp = tempfile::tempdir().unwrap();
+    std::fs::create_dir(tmp.path().join("lib")).unwrap();
+    std::fs::write(tmp.path().join("lib/data.txt"), "from lib").unwrap();
+    std::fs::write(
+        tmp.path().join("lib/helpers.rhai"),
+        r#"fn load() { read_text("data.txt") }"#,
+    )
+    .unwrap();
+
+    run_in(
+        tmp.path(),
+        r#"import "lib/helpers" as helpers;
+assert(helpers::load() == "from lib", "module-relative");
+"#,
+        RhaiScriptRunOptions::default(),
+    )
+    .unwrap();
+}
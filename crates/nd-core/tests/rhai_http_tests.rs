@@ -0,0 +1,116 @@
+//! Integration tests for the Rhai `http` module (one-off requests without a request file).
+
+mod common;
+
+use std::path::Path;
+
+use common::{session_with, write_script};
+use nd_core::rhai::{resolver::RhaiScriptRunOptions, run::run_rhai_script};
+use wiremock::matchers::{body_json, header, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+fn run(dir: &Path, script: &str, options: RhaiScriptRunOptions) -> nd_core::error::Result<()> {
+    let main = write_script(dir, script);
+    run_rhai_script(&main, session_with(&[("TOKEN", "secret")]), options)
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn http_get_sends_query_and_headers() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/users"))
+        .and(query_param("page", "2"))
+        .and(header("authorization", "Bearer secret"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([{"id": 1}])))
+        .mount(&server)
+        .await;
+
+    let tmp = tempfile::tempdir().unwrap();
+    let script = format!(
+        r#"let r = http::get(#{{
+    url: "{}/users",
+    query: #{{ page: 2 }},
+    headers: #{{ authorization: "Bearer ${{TOKEN}}" }},
+}});
+assert(r.status == 200, "status");
+assert(r.method == "GET", "method");
+assert(r.json[0].id == 1, "json");
+"#,
+        server.uri()
+    );
+    run(tmp.path(), &script, RhaiScriptRunOptions::default()).unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn http_post_and_request_send_bodies() {
+    let server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/users"))
+        .and(body_json(serde_json::json!({"name": "Ada", "tags": ["x"]})))
+        .respond_with(ResponseTemplate::new(201))
+        .mount(&server)
+        .await;
+    Mock::given(method("OPTIONS"))
+        .and(path("/users"))
+        .respond_with(ResponseTemplate::new(204))
+        .mount(&server)
+        .await;
+
+    let tmp = tempfile::tempdir().unwrap();
+    let script = format!(
+        r#"let base = "{}";
+let r = http::post(#{{ url: base + "/users", body: #{{ name: "Ada", tags: ["x"] }} }});
+assert(r.status == 201, "post");
+let r = http::request(#{{ method: "options", url: base + "/users" }});
+assert(r.status == 204, "options");
+"#,
+        server.uri()
+    );
+    run(tmp.path(), &script, RhaiScriptRunOptions::default()).unwrap();
+}
+
+#[test]
+fn http_dry_run_expands_without_sending() {
+    let tmp = tempfile::tempdir().unwrap();
+    run(
+        tmp.path(),
+        r#"let r = http::delete("http://127.0.0.1:9/items/${TOKEN}");
+assert(r.dry_run, "dry run");
+assert(r.method == "DELETE", "method");
+assert(r.final_url == "http://127.0.0.1:9/items/secret", "url");
+"#,
+        RhaiScriptRunOptions {
+            no_network_io: true,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+}
+
+#[test]
+fn http_rejects_malformed_requests() {
+    let tmp = tempfile::tempdir().unwrap();
+    let options = RhaiScriptRunOptions {
+        no_network_io: true,
+        ..Default::default()
+    };
+
+    let err = run(
+        tmp.path(),
+        r#"http::get(#{ url: "http://x", header: #{} });"#,
+        options.clone(),
+    )
+    .unwrap_err();
+    assert!(
+        err.to_string().contains("unknown request field `header`"),
+        "{err}"
+    );
+
+    let err = run(
+        tmp.path(),
+        r#"http::request(#{ url: "http://x" });"#,
+        options,
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("`method` is required"), "{err}");
+}
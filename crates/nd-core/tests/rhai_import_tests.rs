@@ -97,6 +97,7 @@ u::check();
         )),
         RhaiScriptRunOptions {
             no_network_io: true,
+            ..Default::default()
         },
     )
     .unwrap();
@@ -147,6 +148,7 @@ assert(r.status == 200, "status");
         Arc::new(Mutex::new(Session::new(|| Ok(env.clone()), None).unwrap())),
         RhaiScriptRunOptions {
             no_network_io: false,
+            ..Default::default()
         },
     )
     .unwrap();
@@ -188,7 +190,71 @@ assert(r.final_url == "https://example.invalid/no-network", "url");
         )),
         RhaiScriptRunOptions {
             no_network_io: true,
+            ..Default::default()
         },
     )
     .unwrap();
 }
+
+#[test]
+fn rhai_imports_http_file_requests_by_name() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("api.http"),
+        "@host = https://example.invalid\n\n### login\nPOST {{host}}/login\n\n### me\nGET {{host}}/users/{{ID}}\n",
+    )
+    .unwrap();
+    std::fs::write(dir.path().join("one.rest"), "GET https://example.invalid/ping\n").unwrap();
+    std::fs::write(
+        dir.path().join("main.rhai"),
+        r#"import "api.http" as api;
+import "one.rest" as one;
+let r = api::invoke("me", #{ ID: "7" });
+assert(r.final_url == "https://example.invalid/users/7", "named request");
+assert(api::invoke("login").method == "POST", "named request without kwargs");
+assert(one::invoke().final_url == "https://example.invalid/ping", "single request");
+"#,
+    )
+    .unwrap();
+
+    run_rhai_script(
+        &dir.path().join("main.rhai"),
+        Arc::new(Mutex::new(
+            Session::new(|| Ok(RuntimeEnv::new()), None).unwrap(),
+        )),
+        RhaiScriptRunOptions {
+            no_network_io: true,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+}
+
+#[test]
+fn rhai_http_import_without_name_fails_when_file_has_several_requests() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("api.http"),
+        "GET https://example.invalid/a\n\n###\nGET https://example.invalid/b\n",
+    )
+    .unwrap();
+    std::fs::write(
+        dir.path().join("main.rhai"),
+        "import \"api.http\" as api;\napi::invoke();\n",
+    )
+    .unwrap();
+
+    let err = run_rhai_script(
+        &dir.path().join("main.rhai"),
+        Arc::new(Mutex::new(
+            Session::new(|| Ok(RuntimeEnv::new()), None).unwrap(),
+        )),
+        RhaiScriptRunOptions {
+            no_network_io: true,
+            ..Default::default()
+        },
+    )
+    .unwrap_err();
+
+    assert!(err.to_string().contains("defines 2 requests"), "{err}");
+}
@@ -0,0 +1,85 @@
+//! Integration tests for the Rhai JSON helpers (`json_parse`, `json_stringify`, `jsonpath`, `jq`, `header`).
+
+use std::sync::{Arc, Mutex};
+
+use nd_core::env::RuntimeEnv;
+use nd_core::rhai::definition_export::rhai_definitions_single_file;
+use nd_core::rhai::{resolver::RhaiScriptRunOptions, run::run_rhai_script};
+use nd_core::stream::Session;
+
+fn run(script: &str) -> nd_core::error::Result<()> {
+    let tmp = tempfile::tempdir().unwrap();
+    let main = tmp.path().join("main.rhai");
+    std::fs::write(&main, script).unwrap();
+    let env = RuntimeEnv::new();
+    env.set("USER", r#"{"id": 7, "tags": ["a", "b"], "manager": null}"#);
+    let session = Arc::new(Mutex::new(Session::new(move || Ok(env), None).unwrap()));
+    run_rhai_script(&main, session, RhaiScriptRunOptions::default())
+}
+
+#[test]
+fn parse_and_stringify_round_trip() {
+    run(r#"let user = json_parse(env("USER"));
+assert(user.id == 7, "id");
+assert(user.tags[1] == "b", "tags");
+assert(user.manager == (), "null is unit");
+assert(json_stringify(#{ a: [1, true, ()] }) == "{\"a\":[1,true,null]}", "compact");
+assert(json_stringify(#{ a: 1 }, true) == "{\n  \"a\": 1\n}", "pretty");
+"#)
+    .unwrap();
+
+    let err = run(r#"json_parse("{nope");"#).unwrap_err();
+    assert!(err.to_string().contains("invalid JSON"), "{err}");
+}
+
+#[test]
+fn jsonpath_and_jq_select_values() {
+    run(r#"let doc = #{ items: [#{ id: 1, active: true }, #{ id: 2, active: false }, #{ id: 3, active: true }] };
+let ids = jsonpath(doc, "$.items[*].id");
+assert(ids == [1, 2, 3], "jsonpath all");
+assert(jsonpath(doc, "$.items[?@.active == false].id") == [2], "jsonpath filter");
+assert(jsonpath_first(doc, "$.missing") == (), "jsonpath none");
+
+assert(jq(doc, ".items[] | select(.active) | .id") == [1, 3], "jq select");
+assert(jq_first(doc, "[.items[].id] | add") == 6, "jq first");
+assert(jq_first(doc, "empty") == (), "jq none");
+"#)
+    .unwrap();
+
+    let err = run(r#"jsonpath(#{}, "$[");"#).unwrap_err();
+    assert!(err.to_string().contains("jsonpath"), "{err}");
+    let err = run(r#"jq(#{}, ".[");"#).unwrap_err();
+    assert!(err.to_string().contains("jq: cannot parse"), "{err}");
+}
+
+#[test]
+fn errors_point_at_the_calling_line() {
+    for (call, function) in [
+        (r#"json_parse("{nope")"#, "json_parse"),
+        (r#"jsonpath(#{}, "$[")"#, "jsonpath"),
+        (r#"jq(#{}, ".[")"#, "jq"),
+    ] {
+        let err = run(&format!("let a = 1;\nlet b = {call};\n")).unwrap_err();
+        assert!(err.to_string().contains(function), "{err}");
+        assert!(err.to_string().contains("line 2"), "{err}");
+    }
+}
+
+#[test]
+fn header_ignores_case() {
+    run(
+        r#"let r = #{ status: 200, headers: #{ "content-type": "application/json" } };
+assert(header(r, "Content-Type") == "application/json", "found");
+assert(header(r, "x-missing") == (), "missing");
+"#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn json_helpers_appear_in_definitions() {
+    let defs = rhai_definitions_single_file();
+    for name in ["json_parse", "json_stringify", "jsonpath", "jq", "header"] {
+        assert!(defs.contains(&format!("fn {name}(")), "missing {name}");
+    }
+}
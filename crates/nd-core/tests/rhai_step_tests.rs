@@ -0,0 +1,66 @@
+//! Integration tests for Rhai `step()` and [`nd_core::stream::steps::steps`].
+
+use std::sync::{Arc, Mutex};
+
+use nd_core::env::RuntimeEnv;
+use nd_core::rhai::{resolver::RhaiScriptRunOptions, run::run_rhai_script};
+use nd_core::stream::steps::{steps, StepOutcome};
+use nd_core::stream::Session;
+
+fn run(script: &str) -> (Arc<Mutex<Session>>, nd_core::error::Result<()>) {
+    let tmp = tempfile::tempdir().unwrap();
+    let main = tmp.path().join("main.rhai");
+    std::fs::write(&main, script).unwrap();
+    let session = Arc::new(Mutex::new(
+        Session::new(|| Ok(RuntimeEnv::new()), None).unwrap(),
+    ));
+    let result = run_rhai_script(&main, session.clone(), RhaiScriptRunOptions::default());
+    (session, result)
+}
+
+#[test]
+fn steps_are_markers_or_timed_blocks() {
+    let (session, result) = run(r#"step("setup");
+let n = step("compute", || { 40 + 2 });
+assert(n == 42, "block returns its value");
+step("outer", || {
+    step("inner", || { () });
+});
+"#);
+    result.unwrap();
+
+    let steps = steps(session.lock().unwrap().events());
+    let summary: Vec<_> = steps
+        .iter()
+        .map(|s| (s.name.as_str(), s.outcome.clone(), s.duration.is_some()))
+        .collect();
+    assert_eq!(
+        summary,
+        vec![
+            ("setup", StepOutcome::Marked, false),
+            ("compute", StepOutcome::Passed, true),
+            ("outer", StepOutcome::Passed, true),
+            ("inner", StepOutcome::Passed, true),
+        ]
+    );
+    assert!(steps[0].started <= steps[1].started);
+}
+
+#[test]
+fn failing_block_is_recorded_and_still_fails_the_script() {
+    let (session, result) = run(r#"step("check", || {
+    assert(false, "nope");
+});
+step("never");
+"#);
+    let err = result.unwrap_err().to_string();
+    assert!(err.contains("nope"), "{err}");
+
+    let steps = steps(session.lock().unwrap().events());
+    assert_eq!(steps.len(), 1);
+    assert_eq!(steps[0].name, "check");
+    match &steps[0].outcome {
+        StepOutcome::Failed(error) => assert!(error.contains("nope"), "{error}"),
+        other => panic!("unexpected {other:?}"),
+    }
+}
@@ -0,0 +1,98 @@
+use nd_core::env::RuntimeEnv;
+use nd_core::execute::snippet::{format_snippet, SnippetLanguage};
+use nd_core::execute::types::PreparedRequest;
+use nd_core::model::request::RequestFile;
+
+fn prepared(yaml: &str) -> PreparedRequest {
+    let doc: RequestFile = serde_yaml::from_str(yaml).unwrap();
+    return doc.request.expand(&RuntimeEnv::new()).unwrap();
+}
+
+const POST_YAML: &str = r#"request:
+  method: POST
+  url: https://api.example.test/items
+  query:
+    - { name: tag, value: a }
+    - { name: tag, value: b }
+  headers:
+    - { name: X-Note, value: "it's" }
+    - { name: X-Note, value: two }
+  body: { "name": "x" }
+  timeout_secs: 5
+  follow_redirects: false
+  verify_tls: false
+"#;
+
+#[test]
+fn curl_snippet_quotes_values_and_maps_flags() {
+    let snippet = format_snippet(&prepared(POST_YAML), SnippetLanguage::Curl).unwrap();
+
+    assert!(snippet.starts_with("curl -X POST 'https://api.example.test/items?tag=a&tag=b' \\\n"));
+    assert!(snippet.contains("-H 'x-note: it'\\''s' \\\n"));
+    assert!(snippet.contains("-H 'x-note: two' \\\n"));
+    assert!(snippet.contains("-H 'content-type: application/json' \\\n"));
+    assert!(snippet.contains(r#"--data-raw '{"name":"x"}'"#));
+    assert!(snippet.contains("-k"));
+    assert!(!snippet.contains(" -L"));
+    assert!(snippet.trim_end().ends_with("--max-time 5"));
+}
+
+#[test]
+fn code_snippets_carry_method_url_headers_and_body() {
+    let prep = prepared(POST_YAML);
+
+    let httpie = format_snippet(&prep, SnippetLanguage::Httpie).unwrap();
+    assert!(httpie.starts_with("http POST 'https://api.example.test/items?tag=a&tag=b'"));
+    assert!(httpie.contains("--verify=no"));
+
+    let python = format_snippet(&prep, SnippetLanguage::Python).unwrap();
+    assert!(python.contains(r#""x-note": "it's, two","#));
+    assert!(python.contains(r#"data="{\"name\":\"x\"}","#));
+    assert!(python.contains("allow_redirects=False,"));
+    assert!(python.contains("verify=False,"));
+
+    let js = format_snippet(&prep, SnippetLanguage::Javascript).unwrap();
+    assert!(js.contains(r#"["x-note", "it's"],"#));
+    assert!(js.contains(r#"["x-note", "two"],"#));
+    assert!(js.contains(r#"redirect: "manual","#));
+    assert!(js.contains("AbortSignal.timeout(5000)"));
+
+    let rust = format_snippet(&prep, SnippetLanguage::Rust).unwrap();
+    assert!(rust.contains(
+        r#".request(reqwest::Method::POST, "https://api.example.test/items?tag=a&tag=b")"#
+    ));
+    assert!(rust.contains(".redirect(reqwest::redirect::Policy::none())"));
+    assert!(rust.contains(r#".body("{\"name\":\"x\"}")"#));
+}
+
+#[test]
+fn binary_body_note_stays_outside_the_shell_command() {
+    let mut prep = prepared(POST_YAML);
+    prep.body = Some(vec![0xff, 0xfe, 0x00, 0x01]);
+
+    for language in [SnippetLanguage::Curl, SnippetLanguage::Httpie] {
+        let snippet = format_snippet(&prep, language).unwrap();
+        let (note, command) = snippet.split_once('\n').unwrap();
+        assert_eq!(note, "# Save the 4 byte body as body.bin.");
+        // Every continued line is a plain argument: a `#` would swallow the continuation.
+        assert!(!command.contains('#'), "{snippet}");
+        assert!(
+            command.lines().rev().skip(1).all(|l| l.ends_with(" \\")),
+            "{snippet}"
+        );
+    }
+
+    let curl = format_snippet(&prep, SnippetLanguage::Curl).unwrap();
+    assert!(curl.contains("--data-binary @body.bin \\\n"));
+    assert!(curl.trim_end().ends_with("--max-time 5"));
+    let httpie = format_snippet(&prep, SnippetLanguage::Httpie).unwrap();
+    assert!(httpie.contains("< body.bin \\\n"));
+    assert!(httpie.trim_end().ends_with("--timeout=5"));
+}
+
+#[test]
+fn snippet_language_parses_aliases() {
+    assert_eq!("js".parse::<SnippetLanguage>(), Ok(SnippetLanguage::Javascript));
+    assert_eq!("HTTPie".parse::<SnippetLanguage>(), Ok(SnippetLanguage::Httpie));
+    assert!("perl".parse::<SnippetLanguage>().is_err());
+}
@@ -70,3 +70,18 @@ fn live_sink_sees_every_event() {
     let _ = s.finish();
     assert_eq!(*n.lock().unwrap(), 4);
 }
+
+#[test]
+fn end_keeps_the_timeline_finish_drains_it() {
+    let mut s = Session::new(|| Ok(RuntimeEnv::new()), None).unwrap();
+    s.end();
+    assert_eq!(s.events().len(), 3);
+    assert!(matches!(
+        s.events().last(),
+        Some(events::Event::SessionEnded { .. })
+    ));
+
+    let out = s.finish();
+    assert_eq!(out.len(), 4);
+    assert!(s.events().is_empty());
+}
@@ -1,5 +1,5 @@
 use base64::Engine;
-use nd_core::rhai::utils::{dynamic_to_json, json_to_dynamic};
+use nd_core::rhai::utils::{dynamic_to_json, dynamic_to_template_string, json_to_dynamic};
 use rhai::Dynamic;
 use serde_json::{json, Value};
 
@@ -40,3 +40,16 @@ fn blob_becomes_base64_string() {
         Value::String(base64::engine::general_purpose::STANDARD.encode([1u8, 2, 3]))
     );
 }
+
+#[test]
+fn template_string_keeps_scalars_and_encodes_collections_as_json() {
+    assert_eq!(dynamic_to_template_string(&Dynamic::from("abc".to_string())), "abc");
+    assert_eq!(dynamic_to_template_string(&Dynamic::from(42_i64)), "42");
+
+    let d = json_to_dynamic(&json!({"id": 7, "tags": ["a"]}));
+    let s = dynamic_to_template_string(&d);
+    assert_eq!(
+        serde_json::from_str::<Value>(&s).unwrap(),
+        json!({"id": 7, "tags": ["a"]})
+    );
+}
@@ -0,0 +1,406 @@
+//! Bruno collection (a directory with `bruno.json`, or a single `.bru` file) → nativedoctor
+//! request files.
+//!
+//! Folders become directories and each request `.bru` becomes a request file named after its
+//! `meta.name`, in `seq` order. `{{var}}` and `{{process.env.VAR}}` placeholders become `${var}` /
+//! `${VAR}` (see [`crate::convert_template`]). Query and path parameters, headers (including
+//! collection and folder headers), json / text / xml / form-urlencoded / multipart / GraphQL
+//! bodies, and bearer, basic, and API key auth (resolving `inherit` through folders to the
+//! collection) are carried over.
+//!
+//! Collection, folder, and request `vars:pre-request` values and path parameter defaults go to
+//! `.env`; each file under `environments/` becomes its own `NAME.env` (secret values are not
+//! stored in Bruno files, so they are left empty). Pre-request, post-response, and test scripts
+//! become stub `.rhai` files, as in [`crate::postman`].
+
+mod parse;
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use nd_core::model::pairs::NameValueList;
+use nd_core::model::request::RequestFile;
+
+use crate::convert::{
+    apply_auth, convert_body, convert_pairs, has_header, note, split_url, AuthSpec, BodySpec,
+    ImportReport, ImportedRequest, Plan, ScriptSource, Variables,
+};
+use crate::error::{Error, Result};
+use crate::output::{sanitize_stem, unique_stem, OutputFormat};
+use parse::{parse_bru, BruFile};
+
+/// Method blocks; the first one present holds `url`, `body`, and `auth`.
+const METHODS: &[&str] = &[
+    "get", "post", "put", "patch", "delete", "options", "head", "trace", "connect",
+];
+
+/// Options for [`import_bruno`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BrunoImportOptions {
+    /// YAML or JSON output for each request file.
+    pub format: OutputFormat,
+}
+
+/// Reads a Bruno collection directory (or one `.bru` request) and writes the converted tree into
+/// `out_dir`. Refuses to overwrite existing files; nothing is written in that case.
+pub fn import_bruno(
+    input: &Path,
+    out_dir: &Path,
+    options: BrunoImportOptions,
+) -> Result<ImportReport> {
+    let mut walk = Walk {
+        plan: Plan::new(out_dir, options.format),
+        vars: Variables::default(),
+    };
+
+    if input.is_file() {
+        let bru = read_bru(input)?;
+        walk.request(input, &bru, out_dir, &[]);
+    } else {
+        let manifest = input.join("bruno.json");
+        if !manifest.is_file() {
+            return Err(Error::Bruno(format!(
+                "{}: no bruno.json found (pass a collection directory or a .bru file)",
+                input.display()
+            )));
+        }
+        let name = std::fs::read_to_string(&manifest)
+            .ok()
+            .and_then(|t| serde_json::from_str::<serde_json::Value>(&t).ok())
+            .and_then(|v| v.get("name")?.as_str().map(str::to_string))
+            .unwrap_or_else(|| dir_name(input));
+        let collection_bru = input.join("collection.bru");
+        let collection = if collection_bru.is_file() {
+            read_bru(&collection_bru)?
+        } else {
+            BruFile::default()
+        };
+        let level = Level {
+            origin: format!("collection {name:?}"),
+            file: collection,
+        };
+        walk.level_vars(&level);
+        walk.dir(input, out_dir, &mut vec![level])?;
+        walk.environments(&input.join("environments"), out_dir)?;
+        if input.join(".env").is_file() {
+            walk.plan.note(
+                "the collection's .env (process.env values) was not copied; pass it with --env"
+                    .into(),
+            );
+        }
+    }
+
+    let mut plan = walk.plan;
+    if !walk.vars.is_empty() {
+        plan.env(
+            out_dir.join(".env"),
+            walk.vars.to_dotenv("Variables imported from Bruno"),
+        );
+    }
+    return plan.write();
+}
+
+fn read_bru(path: &Path) -> Result<BruFile> {
+    let text = std::fs::read_to_string(path).map_err(|source| Error::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    return parse_bru(&text).map_err(|e| Error::Bruno(format!("{}: {e}", path.display())));
+}
+
+fn dir_name(path: &Path) -> String {
+    return path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+}
+
+/// `meta.seq` for ordering (missing sorts last).
+fn seq(bru: &BruFile) -> f64 {
+    return bru
+        .get("meta", "seq")
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(f64::MAX);
+}
+
+/// The collection or a folder: its headers, auth, vars, and scripts apply to everything inside.
+struct Level {
+    origin: String,
+    file: BruFile,
+}
+
+struct Walk {
+    plan: Plan,
+    vars: Variables,
+}
+
+impl Walk {
+    fn level_vars(&mut self, level: &Level) {
+        for (k, v) in level.file.dict("vars:pre-request") {
+            self.vars.set_default(&k, v);
+        }
+    }
+
+    fn dir(&mut self, src: &Path, out: &Path, levels: &mut Vec<Level>) -> Result<()> {
+        let entries = std::fs::read_dir(src).map_err(|source| Error::Io {
+            path: src.to_path_buf(),
+            source,
+        })?;
+        let mut folders: Vec<(f64, String, PathBuf, BruFile)> = Vec::new();
+        let mut requests: Vec<(f64, PathBuf, BruFile)> = Vec::new();
+        for entry in entries {
+            let path = entry
+                .map_err(|source| Error::Io {
+                    path: src.to_path_buf(),
+                    source,
+                })?
+                .path();
+            let name = dir_name(&path);
+            if path.is_dir() {
+                let skip = levels.len() == 1 && name == "environments";
+                if skip || name.starts_with('.') || name == "node_modules" {
+                    continue;
+                }
+                let folder_bru = path.join("folder.bru");
+                let bru = if folder_bru.is_file() {
+                    read_bru(&folder_bru)?
+                } else {
+                    BruFile::default()
+                };
+                let display = bru.get("meta", "name").unwrap_or_else(|| name.clone());
+                folders.push((seq(&bru), display, path, bru));
+            } else if path.extension().is_some_and(|e| e == "bru")
+                && name != "folder.bru"
+                && name != "collection.bru"
+            {
+                let bru = read_bru(&path)?;
+                requests.push((seq(&bru), path, bru));
+            }
+        }
+        folders.sort_by(|a, b| a.0.total_cmp(&b.0).then_with(|| a.1.cmp(&b.1)));
+        requests.sort_by(|a, b| a.0.total_cmp(&b.0).then_with(|| a.1.cmp(&b.1)));
+
+        for (_, name, path, bru) in folders {
+            let stem = self.plan.stem(out, &name);
+            let level = Level {
+                origin: format!("folder {name:?}"),
+                file: bru,
+            };
+            self.level_vars(&level);
+            levels.push(level);
+            self.dir(&path, &out.join(stem), levels)?;
+            levels.pop();
+        }
+        for (_, path, bru) in requests {
+            self.request(&path, &bru, out, levels);
+        }
+        return Ok(());
+    }
+
+    fn request(&mut self, path: &Path, bru: &BruFile, out: &Path, levels: &[Level]) {
+        let name = bru.get("meta", "name").unwrap_or_else(|| {
+            path.file_stem()
+                .map(|s| s.to_string_lossy().into_owned())
+                .unwrap_or_default()
+        });
+        let kind = bru.get("meta", "type").unwrap_or_else(|| "http".into());
+        let method = METHODS.iter().find(|m| bru.block(m).is_some());
+        let (Some(method), "http" | "graphql") = (method, kind.as_str()) else {
+            self.plan
+                .note(format!("{}: {kind} requests are not supported; skipped", path.display()));
+            return;
+        };
+
+        let mut notes = Vec::new();
+        let (url, url_query) = split_url(&bru.get(method, "url").unwrap_or_default(), &mut notes);
+        let query = match bru.block("params:query") {
+            Some(_) => pairs(&bru.dict("params:query"), &mut notes),
+            None => url_query,
+        };
+        for (k, v) in bru.dict("params:path") {
+            if !v.is_empty() {
+                self.vars.set_default(&k, v);
+            }
+        }
+        for (k, v) in bru.dict("vars:pre-request") {
+            self.vars.set_default(&k, v);
+        }
+
+        let mut headers = pairs(&bru.dict("headers"), &mut notes);
+        for level in levels.iter().rev() {
+            for (k, v) in pairs(&level.file.dict("headers"), &mut notes) {
+                if !has_header(&headers, &k) {
+                    headers.append(k, v);
+                }
+            }
+        }
+
+        let mut query = query;
+        let auth = resolve_auth(bru.get(method, "auth"), bru, levels);
+        if let Some(auth) = auth {
+            apply_auth(&auth, &mut headers, &mut query, &mut notes);
+        }
+        let body = body_spec(bru, bru.get(method, "body").as_deref(), &mut notes)
+            .and_then(|b| convert_body(&b, &mut headers, &mut notes));
+
+        let file: RequestFile = ImportedRequest {
+            name: name.clone(),
+            method: method.to_string(),
+            url,
+            query,
+            headers,
+            body,
+            description: bru.text("docs").map(str::to_string),
+        }
+        .into_request_file();
+
+        let mut scripts = Vec::new();
+        let request_level = Level {
+            origin: format!("request {name:?}"),
+            file: bru.clone(),
+        };
+        let all: Vec<&Level> = levels.iter().chain([&request_level]).collect();
+        for level in &all {
+            push_script(&mut scripts, level, "script:pre-request", "pre-request script", true);
+        }
+        for level in &all {
+            push_script(&mut scripts, level, "script:post-response", "post-response script", false);
+            push_script(&mut scripts, level, "tests", "tests", false);
+        }
+
+        let stem = self.plan.stem(out, &name);
+        self.plan.request(out, &stem, file, &scripts, "Bruno", notes);
+    }
+
+    fn environments(&mut self, dir: &Path, out: &Path) -> Result<()> {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return Ok(());
+        };
+        let mut paths: Vec<PathBuf> = entries
+            .filter_map(|e| e.ok().map(|e| e.path()))
+            .filter(|p| p.extension().is_some_and(|e| e == "bru"))
+            .collect();
+        paths.sort();
+
+        let mut used = HashSet::new();
+        for path in paths {
+            let bru = read_bru(&path)?;
+            let name = path
+                .file_stem()
+                .map(|s| s.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let mut vars = Variables::default();
+            for (k, v) in bru.dict("vars") {
+                vars.set(&k, v);
+            }
+            let secrets = bru.list("vars:secret");
+            for k in &secrets {
+                vars.set(k, String::new());
+            }
+            if !secrets.is_empty() {
+                self.plan.note(format!(
+                    "environment {name:?}: secret values ({}) are not stored in Bruno files; fill them in",
+                    secrets.join(", ")
+                ));
+            }
+            let stem = unique_stem(&sanitize_stem(&name), &mut used);
+            let comment = format!("Bruno environment {name:?}");
+            self.plan
+                .env(out.join(format!("{stem}.env")), vars.to_dotenv(&comment));
+        }
+        return Ok(());
+    }
+}
+
+fn pairs(list: &[(String, String)], notes: &mut Vec<String>) -> NameValueList {
+    return convert_pairs(list.iter().map(|(k, v)| (k.as_str(), v.as_str())), notes);
+}
+
+fn push_script(
+    scripts: &mut Vec<ScriptSource>,
+    level: &Level,
+    block: &str,
+    label: &str,
+    before: bool,
+) {
+    if let Some(code) = level.file.text(block) {
+        scripts.push(ScriptSource {
+            heading: format!("{label} from {}", level.origin),
+            before,
+            code: code.to_string(),
+        });
+    }
+}
+
+/// The request's auth mode, or for `inherit` the nearest folder's (then the collection's)
+/// `auth { mode }` together with that file's `auth:*` block.
+fn resolve_auth(mode: Option<String>, bru: &BruFile, levels: &[Level]) -> Option<AuthSpec> {
+    let mode = mode.unwrap_or_else(|| "none".into());
+    if mode != "inherit" {
+        return auth_spec(bru, &mode);
+    }
+    for level in levels.iter().rev() {
+        match level.file.get("auth", "mode").as_deref() {
+            None | Some("inherit") => continue,
+            Some(mode) => return auth_spec(&level.file, mode),
+        }
+    }
+    return None;
+}
+
+fn auth_spec(bru: &BruFile, mode: &str) -> Option<AuthSpec> {
+    let field = |block: &str, key: &str| bru.get(block, key).unwrap_or_default();
+    return match mode {
+        "none" => None,
+        "bearer" => Some(AuthSpec::Bearer {
+            token: field("auth:bearer", "token"),
+            prefix: None,
+        }),
+        "basic" => Some(AuthSpec::Basic {
+            username: field("auth:basic", "username"),
+            password: field("auth:basic", "password"),
+        }),
+        "apikey" => Some(AuthSpec::ApiKey {
+            key: field("auth:apikey", "key"),
+            value: field("auth:apikey", "value"),
+            in_query: field("auth:apikey", "placement") == "queryparams",
+        }),
+        other => Some(AuthSpec::Unsupported(other.to_string())),
+    };
+}
+
+fn body_spec(bru: &BruFile, mode: Option<&str>, notes: &mut Vec<String>) -> Option<BodySpec> {
+    let raw = |block: &str, hint: &str| {
+        Some(BodySpec::Raw {
+            text: bru.text(block).unwrap_or("").to_string(),
+            hint: hint.to_string(),
+        })
+    };
+    return match mode.unwrap_or("none") {
+        "none" => None,
+        "json" => raw("body:json", "json"),
+        "text" => raw("body:text", "text"),
+        "xml" => raw("body:xml", "xml"),
+        "sparql" => raw("body:sparql", "text"),
+        "formUrlEncoded" => Some(BodySpec::Form(bru.dict("body:form-urlencoded"))),
+        "multipartForm" => {
+            let mut fields = Vec::new();
+            for (k, v) in bru.dict("body:multipart-form") {
+                if v.starts_with("@file(") {
+                    note(notes, format!("multipart file field {k} was dropped"));
+                    continue;
+                }
+                fields.push((k, v));
+            }
+            Some(BodySpec::Multipart(fields))
+        }
+        "graphql" => Some(BodySpec::Graphql {
+            query: bru.text("body:graphql").unwrap_or("").to_string(),
+            variables: bru.text("body:graphql:vars").map(str::to_string),
+        }),
+        other => {
+            note(notes, format!("{other} bodies are not supported; body dropped"));
+            None
+        }
+    };
+}
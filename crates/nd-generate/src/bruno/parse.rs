@@ -0,0 +1,146 @@
+//! Parser for Bruno's `.bru` markup: a sequence of top-level `name { … }` or `name [ … ]` blocks.
+//!
+//! Dictionary blocks hold `key: value` lines (a leading `~` disables the entry), list blocks hold
+//! one item per line, and text blocks (`body:*`, `script:*`, `tests`, `docs`) hold free text
+//! indented by two spaces. A block ends at a `}` / `]` in the first column.
+
+/// One `key: value` line of a dictionary block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DictEntry {
+    pub key: String,
+    pub value: String,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BlockBody {
+    Dict(Vec<DictEntry>),
+    List(Vec<String>),
+    Text(String),
+}
+
+/// Parsed `.bru` file, blocks in file order.
+#[derive(Debug, Clone, Default)]
+pub struct BruFile {
+    pub blocks: Vec<(String, BlockBody)>,
+}
+
+impl BruFile {
+    pub fn block(&self, name: &str) -> Option<&BlockBody> {
+        return self.blocks.iter().find(|(n, _)| n == name).map(|(_, b)| b);
+    }
+
+    /// Enabled entries of dictionary block `name` (empty when missing).
+    pub fn dict(&self, name: &str) -> Vec<(String, String)> {
+        return match self.block(name) {
+            Some(BlockBody::Dict(entries)) => entries
+                .iter()
+                .filter(|e| e.enabled)
+                .map(|e| (e.key.clone(), e.value.clone()))
+                .collect(),
+            _ => Vec::new(),
+        };
+    }
+
+    /// First enabled value for `key` in dictionary block `name`.
+    pub fn get(&self, name: &str, key: &str) -> Option<String> {
+        return self
+            .dict(name)
+            .into_iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v);
+    }
+
+    pub fn text(&self, name: &str) -> Option<&str> {
+        return match self.block(name) {
+            Some(BlockBody::Text(text)) => Some(text.as_str()),
+            _ => None,
+        };
+    }
+
+    pub fn list(&self, name: &str) -> Vec<String> {
+        return match self.block(name) {
+            Some(BlockBody::List(items)) => items.clone(),
+            _ => Vec::new(),
+        };
+    }
+}
+
+fn is_text_block(name: &str) -> bool {
+    if name == "body:form-urlencoded" || name == "body:multipart-form" {
+        return false;
+    }
+    return name.starts_with("body:")
+        || name.starts_with("script:")
+        || name == "tests"
+        || name == "docs";
+}
+
+/// Parses `text`; errors name the offending line.
+pub fn parse_bru(text: &str) -> std::result::Result<BruFile, String> {
+    let mut file = BruFile::default();
+    let mut lines = text.lines().enumerate();
+    while let Some((index, line)) = lines.next() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        let (name, close) = match trimmed.strip_suffix('{') {
+            Some(name) => (name.trim(), "}"),
+            None => match trimmed.strip_suffix('[') {
+                Some(name) => (name.trim(), "]"),
+                None => return Err(format!("line {}: expected a block, got {trimmed:?}", index + 1)),
+            },
+        };
+
+        let mut content = Vec::new();
+        let mut closed = false;
+        for (_, line) in lines.by_ref() {
+            if line.trim_end() == close {
+                closed = true;
+                break;
+            }
+            content.push(line);
+        }
+        if !closed {
+            return Err(format!("line {}: block {name} is not closed", index + 1));
+        }
+
+        let body = if close == "]" {
+            BlockBody::List(
+                content
+                    .iter()
+                    .map(|l| l.trim().trim_end_matches(',').to_string())
+                    .filter(|l| !l.is_empty())
+                    .collect(),
+            )
+        } else if is_text_block(name) {
+            let dedented: Vec<&str> = content
+                .iter()
+                .map(|l| l.strip_prefix("  ").unwrap_or(l.trim_start()))
+                .collect();
+            BlockBody::Text(dedented.join("\n").trim_matches('\n').to_string())
+        } else {
+            let mut entries = Vec::new();
+            for l in &content {
+                let l = l.trim();
+                if l.is_empty() {
+                    continue;
+                }
+                let (enabled, l) = match l.strip_prefix('~') {
+                    Some(rest) => (false, rest),
+                    None => (true, l),
+                };
+                let (key, value) = l.split_once(':').unwrap_or((l, ""));
+                entries.push(DictEntry {
+                    key: key.trim().to_string(),
+                    value: value.trim().to_string(),
+                    enabled,
+                });
+            }
+            BlockBody::Dict(entries)
+        };
+        file.blocks.push((name.to_string(), body));
+    }
+    return Ok(file);
+}
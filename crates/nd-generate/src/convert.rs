@@ -0,0 +1,626 @@
+//! Shared pieces of the collection importers ([`crate::postman`], [`crate::insomnia`],
+//! [`crate::bruno`]): template rewriting, auth and body mapping, Rhai script stubs, dotenv output,
+//! and writing the planned files without overwriting anything.
+
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use base64::Engine;
+use nd_core::model::pairs::NameValueList;
+use nd_core::model::request::{
+    HttpRequestSpec, RequestBody, RequestBodyKind, RequestBodyStructured, RequestFile,
+};
+pub use nd_core::utils::template::var_ident;
+use nd_core::utils::template::{dynamic_equivalent, placeholder_span_re, quote_json_placeholders};
+use regex::Regex;
+
+use crate::error::{Error, Result};
+use crate::output::{write_request_file, OutputFormat};
+use crate::secrets::dotenv_quote;
+
+/// Boundary used when form fields (imported collections, curl `-F`) are turned into a literal
+/// multipart body.
+const FORM_BOUNDARY: &str = "nativedoctor-form-boundary";
+
+fn template_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    return RE.get_or_init(|| {
+        Regex::new(r"\{\{\s*([^{}]+?)\s*\}\}|\{%\s*([^%]+?)\s*%\}").expect("valid regex")
+    });
+}
+
+/// Rewrites `{{name}}` to `${name}`, dropping Insomnia's `_.` and Bruno's `process.env.` prefixes,
+/// and maps dynamic variables (`{{$guid}}`, `{% uuid 'v4' %}`, `{% faker 'randomEmail' %}`) to
+/// `${!name}` where an equivalent exists. Anything else is reported in `notes`.
+pub fn convert_template(text: &str, notes: &mut Vec<String>) -> String {
+    return template_re()
+        .replace_all(text, |caps: &regex::Captures| {
+            if let Some(tag) = caps.get(2) {
+                return convert_tag(tag.as_str(), &caps[0], notes);
+            }
+            let name = &caps[1];
+            let Some(dynamic) = name.strip_prefix('$') else {
+                let name = name
+                    .strip_prefix("_.")
+                    .or_else(|| name.strip_prefix("process.env."))
+                    .unwrap_or(name);
+                return format!("${{{}}}", var_ident(name));
+            };
+            if let Some(ours) = dynamic_equivalent(dynamic) {
+                return format!("${{!{ours}}}");
+            }
+            let var = var_ident(dynamic);
+            note(
+                notes,
+                format!("no equivalent for dynamic variable {{{{${dynamic}}}}}; left as ${{{var}}}"),
+            );
+            format!("${{{var}}}")
+        })
+        .into_owned();
+}
+
+/// Insomnia template tags (`{% uuid 'v4' %}`); unknown tags are kept verbatim and noted.
+fn convert_tag(tag: &str, original: &str, notes: &mut Vec<String>) -> String {
+    let mut words = tag
+        .split_whitespace()
+        .map(|w| w.trim_matches(|c| c == '\'' || c == '"' || c == ','));
+    let ours = match (words.next(), words.next()) {
+        (Some("uuid"), _) => Some("uuidv4"),
+        (Some("faker"), Some(name)) => dynamic_equivalent(name),
+        _ => None,
+    };
+    if let Some(ours) = ours {
+        return format!("${{!{ours}}}");
+    }
+    note(notes, format!("template tag {original} has no equivalent; left as is"));
+    return original.to_string();
+}
+
+/// Like [`convert_template`] for JSON text, except that a placeholder outside any string
+/// (`"id": {{id}}`) becomes `"${json:id}"`, so the body still parses and keeps the value's type.
+fn convert_json_template(text: &str, notes: &mut Vec<String>) -> String {
+    return quote_json_placeholders(&convert_template(text, notes));
+}
+
+/// Appends `message` unless it is already there.
+pub(crate) fn note(notes: &mut Vec<String>, message: String) {
+    if !notes.contains(&message) {
+        notes.push(message);
+    }
+}
+
+/// Name / value pairs in order, with templates converted.
+pub(crate) fn convert_pairs<'a>(
+    pairs: impl IntoIterator<Item = (&'a str, &'a str)>,
+    notes: &mut Vec<String>,
+) -> NameValueList {
+    let mut out = NameValueList::new();
+    for (k, v) in pairs {
+        out.append(convert_template(k, notes), convert_template(v, notes));
+    }
+    return out;
+}
+
+/// Splits `raw` into the URL (fragment dropped, `:param` path segments → `${param}`) and its
+/// query. Query values are kept as typed (no percent-decoding).
+pub(crate) fn split_url(raw: &str, notes: &mut Vec<String>) -> (String, NameValueList) {
+    let raw = raw.split('#').next().unwrap_or("");
+    let (base, query_text) = match raw.split_once('?') {
+        Some((base, q)) => (base, Some(q)),
+        None => (raw, None),
+    };
+
+    static PATH_VAR: OnceLock<Regex> = OnceLock::new();
+    let path_var = PATH_VAR
+        .get_or_init(|| Regex::new(r"/:([A-Za-z_][A-Za-z0-9_]*)").expect("valid regex"));
+    let base = path_var.replace_all(base.trim(), |caps: &regex::Captures| {
+        format!("/${{{}}}", &caps[1])
+    });
+    let url = convert_template(&base, notes);
+
+    let pairs = query_text
+        .unwrap_or("")
+        .split('&')
+        .filter(|p| !p.is_empty())
+        .map(|pair| pair.split_once('=').unwrap_or((pair, "")));
+    return (url, convert_pairs(pairs, notes));
+}
+
+pub(crate) fn has_header(headers: &NameValueList, name: &str) -> bool {
+    return headers.keys().any(|k| k.eq_ignore_ascii_case(name));
+}
+
+/// Replaces any existing header with `name` (case-insensitive) by a single `name: value`.
+pub(crate) fn set_header(headers: &mut NameValueList, name: &str, value: &str) {
+    let mut kept: NameValueList = headers
+        .iter()
+        .filter(|(k, _)| !k.eq_ignore_ascii_case(name))
+        .cloned()
+        .collect();
+    kept.append(name, value);
+    *headers = kept;
+}
+
+/// Auth settings read out of a tool's format (values not yet template-converted).
+#[derive(Debug, Clone)]
+pub(crate) enum AuthSpec {
+    /// `Authorization: {prefix} {token}` (prefix defaults to `Bearer`).
+    Bearer {
+        token: String,
+        prefix: Option<String>,
+    },
+    Basic {
+        username: String,
+        password: String,
+    },
+    ApiKey {
+        key: String,
+        value: String,
+        in_query: bool,
+    },
+    /// Recognised but not representable; the tool's name for it.
+    Unsupported(String),
+}
+
+/// Adds the header or query parameter for `auth`; an explicit `Authorization` (or API key)
+/// header on the request wins.
+pub(crate) fn apply_auth(
+    auth: &AuthSpec,
+    headers: &mut NameValueList,
+    query: &mut NameValueList,
+    notes: &mut Vec<String>,
+) {
+    let authorization_set = has_header(headers, "authorization");
+    match auth {
+        AuthSpec::Bearer { token, prefix } if !authorization_set => {
+            let prefix = prefix.as_deref().filter(|p| !p.is_empty()).unwrap_or("Bearer");
+            let token = convert_template(token, notes);
+            headers.append("Authorization", format!("{prefix} {token}"));
+        }
+        AuthSpec::Basic { username, password } if !authorization_set => {
+            let credentials = format!(
+                "{}:{}",
+                convert_template(username, notes),
+                convert_template(password, notes)
+            );
+            if placeholder_span_re().is_match(&credentials) {
+                note(
+                    notes,
+                    "basic auth uses variables; set BASIC_AUTH to base64(username:password)".into(),
+                );
+                headers.append("Authorization", "Basic ${BASIC_AUTH}");
+            } else {
+                let encoded = base64::engine::general_purpose::STANDARD.encode(credentials);
+                headers.append("Authorization", format!("Basic {encoded}"));
+            }
+        }
+        AuthSpec::ApiKey {
+            key,
+            value,
+            in_query,
+        } => {
+            let key = convert_template(key, notes);
+            let value = convert_template(value, notes);
+            if *in_query {
+                query.append(key, value);
+            } else if !has_header(headers, &key) {
+                headers.append(key, value);
+            }
+        }
+        AuthSpec::Bearer { .. } | AuthSpec::Basic { .. } => {}
+        AuthSpec::Unsupported(kind) => note(
+            notes,
+            format!("{kind} auth is not supported; set the Authorization header by hand"),
+        ),
+    }
+}
+
+/// Body read out of a tool's format (text not yet template-converted).
+#[derive(Debug, Clone)]
+pub(crate) enum BodySpec {
+    /// Raw text; `hint` is a language (`json`, `xml`, …) or media type used to pick the kind.
+    Raw { text: String, hint: String },
+    /// `application/x-www-form-urlencoded` fields.
+    Form(Vec<(String, String)>),
+    /// `multipart/form-data` text fields (file fields are dropped by the caller).
+    Multipart(Vec<(String, String)>),
+    /// GraphQL query plus variables as JSON text.
+    Graphql {
+        query: String,
+        variables: Option<String>,
+    },
+}
+
+/// Maps `body`; a multipart body also sets `Content-Type` with the boundary it uses, replacing
+/// any given one.
+pub(crate) fn convert_body(
+    body: &BodySpec,
+    headers: &mut NameValueList,
+    notes: &mut Vec<String>,
+) -> Option<RequestBody> {
+    match body {
+        BodySpec::Raw { text, hint } => {
+            if text.is_empty() {
+                return None;
+            }
+            let hint = hint.to_ascii_lowercase();
+            if hint.contains("json") {
+                let converted = convert_json_template(text, notes);
+                if let Ok(value) = serde_json::from_str::<serde_json::Value>(&converted) {
+                    return Some(structured(RequestBodyKind::Json, value));
+                }
+            }
+            let kind = if hint.contains("xml") {
+                RequestBodyKind::Xml
+            } else if hint.contains("javascript") {
+                RequestBodyKind::Other
+            } else if hint.contains("json") {
+                note(notes, "a JSON body did not parse; kept as text".into());
+                RequestBodyKind::Other
+            } else {
+                RequestBodyKind::Text
+            };
+            let text = convert_template(text, notes);
+            return Some(structured(kind, serde_json::Value::String(text)));
+        }
+        BodySpec::Form(fields) => {
+            if fields.is_empty() {
+                return None;
+            }
+            let fields = convert_pairs(fields.iter().map(|(k, v)| (k.as_str(), v.as_str())), notes);
+            let encoded = fields
+                .iter()
+                .map(|(k, v)| format!("{}={}", form_encode(k), form_encode(v)))
+                .collect::<Vec<_>>()
+                .join("&");
+            return Some(structured(
+                RequestBodyKind::XWwwFormUrlencoded,
+                serde_json::Value::String(encoded),
+            ));
+        }
+        BodySpec::Multipart(fields) => {
+            if fields.is_empty() {
+                return None;
+            }
+            let fields: Vec<(String, String)> = fields
+                .iter()
+                .map(|(k, v)| (convert_template(k, notes), convert_template(v, notes)))
+                .collect();
+            return Some(multipart_body(
+                fields.iter().map(|(k, v)| (k.as_str(), v.as_str())),
+                headers,
+            ));
+        }
+        BodySpec::Graphql { query, variables } => {
+            let mut content = serde_json::json!({ "query": convert_template(query, notes) });
+            let variables = variables
+                .as_deref()
+                .filter(|v| !v.trim().is_empty())
+                .and_then(|v| serde_json::from_str(&convert_json_template(v, notes)).ok());
+            if let Some(variables) = variables {
+                content["variables"] = variables;
+            }
+            return Some(structured(RequestBodyKind::Graphql, content));
+        }
+    }
+}
+
+/// Literal `multipart/form-data` body of text `fields`, taken as they are. Also sets `Content-Type`
+/// with the boundary, replacing any given one: a `multipart/form-data` header set in the source has
+/// no boundary (or another one).
+pub(crate) fn multipart_body<'a>(
+    fields: impl IntoIterator<Item = (&'a str, &'a str)>,
+    headers: &mut NameValueList,
+) -> RequestBody {
+    let mut out = String::new();
+    for (name, value) in fields {
+        out.push_str(&format!(
+            "--{FORM_BOUNDARY}\r\nContent-Disposition: form-data; name=\"{name}\"\r\n\r\n{value}\r\n"
+        ));
+    }
+    out.push_str(&format!("--{FORM_BOUNDARY}--\r\n"));
+    set_header(
+        headers,
+        "Content-Type",
+        &format!("multipart/form-data; boundary={FORM_BOUNDARY}"),
+    );
+    return structured(RequestBodyKind::FormData, serde_json::Value::String(out));
+}
+
+/// Percent-encodes a form field, leaving `${…}` placeholders intact so they still expand.
+fn form_encode(text: &str) -> String {
+    let encode = |s: &str| url::form_urlencoded::byte_serialize(s.as_bytes()).collect::<String>();
+    let mut out = String::new();
+    let mut last = 0;
+    for m in placeholder_span_re().find_iter(text) {
+        out.push_str(&encode(&text[last..m.start()]));
+        out.push_str(m.as_str());
+        last = m.end();
+    }
+    out.push_str(&encode(&text[last..]));
+    return out;
+}
+
+fn structured(body_type: RequestBodyKind, content: serde_json::Value) -> RequestBody {
+    return RequestBody::Structured(RequestBodyStructured { body_type, content });
+}
+
+/// Everything an importer extracts for one request, already converted.
+pub(crate) struct ImportedRequest {
+    pub name: String,
+    pub method: String,
+    pub url: String,
+    pub query: NameValueList,
+    pub headers: NameValueList,
+    pub body: Option<RequestBody>,
+    pub description: Option<String>,
+}
+
+impl ImportedRequest {
+    /// Request file with the defaults these tools use (follow redirects, verify TLS).
+    pub fn into_request_file(self) -> RequestFile {
+        return RequestFile {
+            name: Some(self.name),
+            request: HttpRequestSpec {
+                method: self.method.to_ascii_uppercase(),
+                url: self.url,
+                summary: None,
+                description: self.description.filter(|d| !d.trim().is_empty()),
+                tags: vec![],
+                deprecated: false,
+                query: self.query,
+                headers: self.headers,
+                body: self.body,
+                timeout_secs: None,
+                follow_redirects: true,
+                verify_tls: true,
+            },
+            ..Default::default()
+        };
+    }
+}
+
+/// One script attached to a request or one of its parents.
+#[derive(Debug, Clone)]
+pub(crate) struct ScriptSource {
+    /// e.g. `pre-request script from folder "Users"`.
+    pub heading: String,
+    /// Runs before the request (`true`) or after the response (`false`).
+    pub before: bool,
+    pub code: String,
+}
+
+/// Rhai script that imports `request_file` and invokes it, with the `tool` scripts commented out
+/// above the call (before the request) and below it (after the response). `None` when every
+/// script is blank.
+pub(crate) fn script_stub(
+    tool: &str,
+    name: &str,
+    request_file: &str,
+    scripts: &[ScriptSource],
+) -> Option<String> {
+    let mut before = String::new();
+    let mut after = String::new();
+    for script in scripts {
+        if script.code.trim().is_empty() {
+            continue;
+        }
+        let target = if script.before {
+            &mut before
+        } else {
+            &mut after
+        };
+        let _ = writeln!(target, "// {tool} {}:", script.heading);
+        for line in script.code.trim_end().lines() {
+            match line.trim_end() {
+                "" => target.push_str("//\n"),
+                line => {
+                    let _ = writeln!(target, "// {line}");
+                }
+            }
+        }
+        target.push('\n');
+    }
+    if before.is_empty() && after.is_empty() {
+        return None;
+    }
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "// Generated from the {tool} scripts of {name:?}. The original JavaScript is kept as\n\
+         // comments below; port what you need with `env`, `set`, `assert`, and `log`.\n"
+    );
+    let _ = writeln!(out, "import {request_file:?} as request;\n");
+    out.push_str(&before);
+    out.push_str("let response = request::invoke();\n");
+    if !after.is_empty() {
+        out.push('\n');
+        out.push_str(&after);
+    }
+    return Some(out);
+}
+
+/// Ordered variables for a dotenv file; names go through [`var_ident`] to match placeholders.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Variables(Vec<(String, String)>);
+
+impl Variables {
+    /// Sets `name`, replacing an earlier value.
+    pub fn set(&mut self, name: &str, value: String) {
+        let name = var_ident(name);
+        match self.0.iter_mut().find(|(k, _)| *k == name) {
+            Some((_, existing)) => *existing = value,
+            None => self.0.push((name, value)),
+        }
+    }
+
+    /// Sets `name` only if nothing defined it yet.
+    pub fn set_default(&mut self, name: &str, value: String) {
+        let name = var_ident(name);
+        if !self.0.iter().any(|(k, _)| *k == name) {
+            self.0.push((name, value));
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        let name = var_ident(name);
+        return self.0.iter().find(|(k, _)| *k == name).map(|(_, v)| v.as_str());
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.0.is_empty();
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &(String, String)> {
+        return self.0.iter();
+    }
+
+    /// Dotenv text with a leading `# comment` line.
+    pub fn to_dotenv(&self, comment: &str) -> String {
+        let mut out = format!("# {comment}\n");
+        for (k, v) in &self.0 {
+            let _ = writeln!(out, "{k}={}", dotenv_quote(v));
+        }
+        return out;
+    }
+}
+
+/// Summary of a collection import ([`crate::postman`], [`crate::insomnia`], [`crate::bruno`]).
+#[derive(Debug, Clone, Default)]
+pub struct ImportReport {
+    /// Request files, in collection order.
+    pub files_written: Vec<PathBuf>,
+    /// Stub `.rhai` scripts for requests that had pre-request or test scripts.
+    pub scripts_written: Vec<PathBuf>,
+    /// Dotenv files with the collection's variables and environments.
+    pub env_files: Vec<PathBuf>,
+    /// Dropped or approximated input, prefixed with the affected file.
+    pub notes: Vec<String>,
+}
+
+enum Output {
+    Request(Box<RequestFile>),
+    Script(String),
+    Env(String),
+}
+
+/// Files an import will write, collected first so nothing is written if any target exists.
+pub(crate) struct Plan {
+    pub out_dir: PathBuf,
+    pub format: OutputFormat,
+    outputs: Vec<(PathBuf, Output)>,
+    notes: Vec<String>,
+    /// Stems taken per directory.
+    used: std::collections::HashMap<PathBuf, HashSet<String>>,
+}
+
+impl Plan {
+    pub fn new(out_dir: &Path, format: OutputFormat) -> Self {
+        return Self {
+            out_dir: out_dir.to_path_buf(),
+            format,
+            outputs: Vec::new(),
+            notes: Vec::new(),
+            used: Default::default(),
+        };
+    }
+
+    /// Unique stem for `name` inside `dir` (shared by files and subdirectories).
+    pub fn stem(&mut self, dir: &Path, name: &str) -> String {
+        let used = self.used.entry(dir.to_path_buf()).or_default();
+        return crate::output::unique_stem(&crate::output::sanitize_stem(name), used);
+    }
+
+    /// Adds a request file (and its script stub) as `dir/stem.{yaml,json}`, recording `notes`.
+    pub fn request(
+        &mut self,
+        dir: &Path,
+        stem: &str,
+        file: RequestFile,
+        scripts: &[ScriptSource],
+        tool: &str,
+        notes: Vec<String>,
+    ) {
+        let file_name = format!("{stem}.{}", self.format.extension());
+        let path = dir.join(&file_name);
+        let shown = path.strip_prefix(&self.out_dir).unwrap_or(&path).display().to_string();
+        self.notes.extend(notes.into_iter().map(|n| format!("{shown}: {n}")));
+        let name = file.name.clone().unwrap_or_default();
+        if let Some(stub) = script_stub(tool, &name, &file_name, scripts) {
+            self.outputs
+                .push((dir.join(format!("{stem}.rhai")), Output::Script(stub)));
+        }
+        self.outputs.push((path, Output::Request(Box::new(file))));
+    }
+
+    pub fn env(&mut self, path: PathBuf, text: String) {
+        self.outputs.push((path, Output::Env(text)));
+    }
+
+    pub fn note(&mut self, message: String) {
+        self.notes.push(message);
+    }
+
+    /// Writes everything, or nothing if any target file already exists.
+    pub fn write(self) -> Result<ImportReport> {
+        if let Some((path, _)) = self.outputs.iter().find(|(p, _)| p.exists()) {
+            return Err(Error::Io {
+                path: path.clone(),
+                source: std::io::Error::new(
+                    std::io::ErrorKind::AlreadyExists,
+                    "refusing to overwrite existing file",
+                ),
+            });
+        }
+        let mut report = ImportReport {
+            notes: self.notes,
+            ..Default::default()
+        };
+        for (path, output) in self.outputs {
+            match output {
+                Output::Request(file) => {
+                    write_request_file(&path, &file, self.format)?;
+                    report.files_written.push(path);
+                }
+                Output::Script(text) => {
+                    write_text(&path, &text)?;
+                    report.scripts_written.push(path);
+                }
+                Output::Env(text) => {
+                    write_text(&path, &text)?;
+                    report.env_files.push(path);
+                }
+            }
+        }
+        return Ok(report);
+    }
+}
+
+fn write_text(path: &Path, text: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|source| Error::Io {
+            path: parent.to_path_buf(),
+            source,
+        })?;
+    }
+    std::fs::write(path, text).map_err(|source| Error::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    return Ok(());
+}
+
+/// Reads and parses a JSON export, wrapping parse errors with `invalid`.
+pub(crate) fn read_json<T: serde::de::DeserializeOwned>(
+    path: &Path,
+    invalid: fn(String) -> Error,
+) -> Result<T> {
+    let text = std::fs::read_to_string(path).map_err(|source| Error::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    return serde_json::from_str(&text).map_err(|e| invalid(format!("{}: {e}", path.display())));
+}
@@ -0,0 +1,151 @@
+//! Map a parsed [`CurlCommand`] onto a [`RequestFile`].
+
+use base64::Engine;
+use nd_core::model::pairs::NameValueList;
+use nd_core::model::request::{
+    HttpRequestSpec, RequestBody, RequestBodyKind, RequestBodyStructured, RequestFile,
+};
+
+use super::parse::CurlCommand;
+use crate::convert::multipart_body;
+use crate::error::{Error, Result};
+
+/// Headers dropped on import: computed by the client, or not decodable by it.
+const DROPPED_HEADERS: &[&str] = &["content-length", "accept-encoding"];
+
+/// Builds the request file; `notes` receives human-readable remarks about dropped input.
+pub fn curl_to_request_file(cmd: &CurlCommand, notes: &mut Vec<String>) -> Result<RequestFile> {
+    if !cmd.form.is_empty() && !cmd.data.is_empty() {
+        return Err(Error::Curl(
+            "-F and -d cannot be combined (curl rejects this too)".into(),
+        ));
+    }
+
+    let (url, mut query) = split_url(&cmd.url)?;
+    let mut headers = NameValueList::new();
+    for (name, value) in &cmd.headers {
+        if DROPPED_HEADERS.contains(&name.to_ascii_lowercase().as_str()) {
+            notes.push(format!("dropped header {name}"));
+            continue;
+        }
+        headers.append(name.clone(), value.clone());
+    }
+
+    if let Some(user) = &cmd.user {
+        if !has_header(&headers, "authorization") {
+            let credentials = if user.contains(':') {
+                user.clone()
+            } else {
+                format!("{user}:")
+            };
+            let encoded = base64::engine::general_purpose::STANDARD.encode(credentials);
+            headers.append("Authorization", format!("Basic {encoded}"));
+        }
+    }
+
+    if cmd.json {
+        if !has_header(&headers, "content-type") {
+            headers.append("Content-Type", "application/json");
+        }
+        if !has_header(&headers, "accept") {
+            headers.append("Accept", "application/json");
+        }
+    }
+
+    let joined_data = cmd.data.join("&");
+    let body = if cmd.get {
+        for (k, v) in url::form_urlencoded::parse(joined_data.as_bytes()) {
+            query.append(k, v);
+        }
+        None
+    } else if !cmd.form.is_empty() {
+        let fields = cmd.form.iter().map(|f| (f.name.as_str(), f.value.as_str()));
+        Some(multipart_body(fields, &mut headers))
+    } else if !cmd.data.is_empty() {
+        Some(data_body(&joined_data, header(&headers, "content-type"), cmd.json))
+    } else {
+        None
+    };
+
+    let method = match &cmd.method {
+        Some(m) => m.clone(),
+        None if body.is_some() => "POST".into(),
+        None => "GET".into(),
+    };
+
+    let path = url::Url::parse(&url)
+        .map(|u| u.path().to_string())
+        .unwrap_or_else(|_| "/".into());
+
+    return Ok(RequestFile {
+        name: Some(format!("{method} {path}")),
+        request: HttpRequestSpec {
+            method,
+            url,
+            summary: None,
+            description: None,
+            tags: vec![],
+            deprecated: false,
+            query,
+            headers,
+            body,
+            timeout_secs: cmd.timeout_secs,
+            follow_redirects: cmd.follow_redirects,
+            verify_tls: !cmd.insecure,
+        },
+        ..Default::default()
+    });
+}
+
+/// Splits the query string off `raw` (adding `http://` when no scheme is given, like curl).
+fn split_url(raw: &str) -> Result<(String, NameValueList)> {
+    let with_scheme = if raw.contains("://") {
+        raw.to_string()
+    } else {
+        format!("http://{raw}")
+    };
+    let mut url = url::Url::parse(&with_scheme)
+        .map_err(|e| Error::Curl(format!("invalid URL {raw}: {e}")))?;
+    let query: NameValueList = url.query_pairs().into_owned().collect();
+    url.set_query(None);
+    url.set_fragment(None);
+    return Ok((url.to_string(), query));
+}
+
+fn data_body(data: &str, content_type: Option<&str>, json: bool) -> RequestBody {
+    let ct = content_type.unwrap_or("").to_ascii_lowercase();
+    let text = || serde_json::Value::String(data.to_string());
+    if json || ct.contains("json") {
+        if let Ok(value) = serde_json::from_str::<serde_json::Value>(data) {
+            return structured(RequestBodyKind::Json, value);
+        }
+        return structured(RequestBodyKind::Other, text());
+    }
+    let kind = if ct.is_empty() || ct.contains("x-www-form-urlencoded") {
+        RequestBodyKind::XWwwFormUrlencoded
+    } else if ct.starts_with("multipart/form-data") {
+        RequestBodyKind::FormData
+    } else if ct.contains("xml") {
+        RequestBodyKind::Xml
+    } else if ct.starts_with("text/") {
+        RequestBodyKind::Text
+    } else {
+        RequestBodyKind::Other
+    };
+    return structured(kind, text());
+}
+
+fn structured(body_type: RequestBodyKind, content: serde_json::Value) -> RequestBody {
+    return RequestBody::Structured(RequestBodyStructured { body_type, content });
+}
+
+fn header<'a>(headers: &'a NameValueList, name: &str) -> Option<&'a str> {
+    return headers
+        .iter()
+        .find(|(k, _)| k.eq_ignore_ascii_case(name))
+        .map(|(_, v)| v.as_str());
+}
+
+fn has_header(headers: &NameValueList, name: &str) -> bool {
+    return header(headers, name).is_some();
+}
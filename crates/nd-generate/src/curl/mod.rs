@@ -0,0 +1,60 @@
+//! `curl` command line → nativedoctor request file.
+//!
+//! Understands the flags browser devtools and API docs emit: method, URL, `-H`, `-d` /
+//! `--data-raw` / `--data-binary` / `--data-urlencode` / `--json`, `-F`, `-u`, `-k`,
+//! `--compressed`, `-L`, `-G`, `-I`, `-m`, `-A`, `-e`, `-b`. Output-only flags (`-o`, `-s`, `-v`,
+//! …) are skipped and reported in [`CurlImport::notes`]. Reading bodies or uploads from files
+//! (`@file`) is rejected, since the request file would not be self-contained.
+
+mod build;
+mod parse;
+mod shell;
+
+pub use build::curl_to_request_file;
+pub use parse::{parse_curl_args, CurlCommand, FormField};
+pub use shell::split_command_line;
+
+use nd_core::model::request::RequestFile;
+
+use crate::error::Result;
+use crate::secrets::{lift_secrets, LiftedSecret};
+
+/// Options for [`import_curl_command`] / [`import_curl_args`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CurlImportOptions {
+    /// Replace tokens, API keys, passwords, and cookies with `${VAR}` placeholders.
+    pub lift_secrets: bool,
+}
+
+/// Result of importing one curl command.
+#[derive(Debug, Clone)]
+pub struct CurlImport {
+    pub file: RequestFile,
+    /// Values replaced by `${VAR}` (empty unless [`CurlImportOptions::lift_secrets`] is set).
+    pub secrets: Vec<LiftedSecret>,
+    /// Skipped flags and dropped headers, for display.
+    pub notes: Vec<String>,
+}
+
+/// Imports a pasted command line (shell quoting is handled; a leading `curl` is optional).
+pub fn import_curl_command(command: &str, options: CurlImportOptions) -> Result<CurlImport> {
+    let args = split_command_line(command)?;
+    return import_curl_args(&args, options);
+}
+
+/// Imports already-split arguments (e.g. from the process argv).
+pub fn import_curl_args(args: &[String], options: CurlImportOptions) -> Result<CurlImport> {
+    let cmd = parse_curl_args(args)?;
+    let mut notes: Vec<String> = cmd.ignored.iter().map(|f| format!("ignored {f}")).collect();
+    let mut file = curl_to_request_file(&cmd, &mut notes)?;
+    let secrets = if options.lift_secrets {
+        lift_secrets(&mut file)
+    } else {
+        Vec::new()
+    };
+    return Ok(CurlImport {
+        file,
+        secrets,
+        notes,
+    });
+}
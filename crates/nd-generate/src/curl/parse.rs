@@ -0,0 +1,237 @@
+//! Interpret curl's command-line flags into a [`CurlCommand`].
+
+use std::collections::VecDeque;
+
+use crate::error::{Error, Result};
+
+/// One `-F` / `--form` field.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FormField {
+    pub name: String,
+    pub value: String,
+}
+
+/// The parts of a curl invocation that map onto a request file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CurlCommand {
+    /// `-X` / `--request`, or `HEAD` for `-I`.
+    pub method: Option<String>,
+    pub url: String,
+    /// `-H` headers in order, with the name as written.
+    pub headers: Vec<(String, String)>,
+    /// `-d` / `--data*` / `--json` chunks, joined with `&` when building the body.
+    pub data: Vec<String>,
+    /// Set by `--json`: body is JSON and JSON headers apply.
+    pub json: bool,
+    /// `-G` / `--get`: `data` is appended to the query instead of sent as a body.
+    pub get: bool,
+    pub form: Vec<FormField>,
+    /// `-u user:password`.
+    pub user: Option<String>,
+    /// `-k` / `--insecure`.
+    pub insecure: bool,
+    /// `-L` / `--location`.
+    pub follow_redirects: bool,
+    /// `--compressed` (recognised; the client negotiates encoding itself).
+    pub compressed: bool,
+    /// `-m` / `--max-time`, rounded up to whole seconds.
+    pub timeout_secs: Option<u64>,
+    /// Flags that were recognised or skipped without affecting the request file.
+    pub ignored: Vec<String>,
+}
+
+/// Flags that take a value but do not affect the request file (output, retries, TLS client certs…).
+const IGNORED_WITH_VALUE: &[&str] = &[
+    "-o",
+    "--output",
+    "-w",
+    "--write-out",
+    "-D",
+    "--dump-header",
+    "-c",
+    "--cookie-jar",
+    "--retry",
+    "--retry-delay",
+    "--retry-max-time",
+    "--connect-timeout",
+    "--max-redirs",
+    "-x",
+    "--proxy",
+    "-U",
+    "--proxy-user",
+    "--cacert",
+    "--capath",
+    "-E",
+    "--cert",
+    "--key",
+    "--cert-type",
+    "--key-type",
+    "--resolve",
+    "--connect-to",
+    "--interface",
+    "-r",
+    "--range",
+    "-T",
+    "--upload-file",
+    "--limit-rate",
+    "-K",
+    "--config",
+    "--trace",
+    "--trace-ascii",
+    "--stderr",
+];
+
+/// Short flags that take a value; used to split combined flags like `-XPOST` or `-sSLX POST`.
+const SHORT_WITH_VALUE: &str = "XHdFuAebmowDcxUErTK";
+
+/// Parses curl arguments. A leading `curl` word is skipped.
+pub fn parse_curl_args(args: &[String]) -> Result<CurlCommand> {
+    let mut cmd = CurlCommand::default();
+    let mut url: Option<String> = None;
+    let mut queue: VecDeque<String> = args.iter().cloned().collect();
+    if queue.front().map(String::as_str) == Some("curl") {
+        queue.pop_front();
+    }
+
+    while let Some(arg) = queue.pop_front() {
+        if !arg.starts_with('-') || arg == "-" {
+            if url.is_some() {
+                return Err(Error::Curl(format!(
+                    "more than one URL given ({arg}); import one request at a time"
+                )));
+            }
+            url = Some(arg);
+            continue;
+        }
+
+        let (flag, inline) = split_flag(&arg, &mut queue);
+        let mut value = |name: &str| -> Result<String> {
+            if let Some(v) = inline.clone() {
+                return Ok(v);
+            }
+            return queue
+                .pop_front()
+                .ok_or_else(|| Error::Curl(format!("{name} needs a value")));
+        };
+
+        match flag.as_str() {
+            "-X" | "--request" => cmd.method = Some(value(&flag)?.to_ascii_uppercase()),
+            "--url" => url = Some(value(&flag)?),
+            "-H" | "--header" => {
+                let raw = value(&flag)?;
+                cmd.headers.push(parse_header(&raw)?);
+            }
+            "-A" | "--user-agent" => cmd.headers.push(("User-Agent".into(), value(&flag)?)),
+            "-e" | "--referer" => cmd.headers.push(("Referer".into(), value(&flag)?)),
+            "-b" | "--cookie" => {
+                let raw = value(&flag)?;
+                if !raw.contains('=') {
+                    return Err(Error::Curl(format!(
+                        "{flag} {raw}: reading cookies from a file is not supported"
+                    )));
+                }
+                cmd.headers.push(("Cookie".into(), raw));
+            }
+            "-d" | "--data" | "--data-ascii" | "--data-binary" => {
+                let raw = value(&flag)?;
+                if raw.starts_with('@') {
+                    return Err(Error::Curl(format!(
+                        "{flag} {raw}: reading the body from a file is not supported"
+                    )));
+                }
+                cmd.data.push(raw);
+            }
+            "--data-raw" => cmd.data.push(value(&flag)?),
+            "--data-urlencode" => cmd.data.push(urlencode_data(&value(&flag)?)?),
+            "--json" => {
+                cmd.data.push(value(&flag)?);
+                cmd.json = true;
+            }
+            "-F" | "--form" | "--form-string" => {
+                let raw = value(&flag)?;
+                let Some((name, val)) = raw.split_once('=') else {
+                    return Err(Error::Curl(format!("{flag} {raw}: expected name=value")));
+                };
+                if flag != "--form-string" && (val.starts_with('@') || val.starts_with('<')) {
+                    return Err(Error::Curl(format!(
+                        "{flag} {raw}: file uploads are not supported"
+                    )));
+                }
+                cmd.form.push(FormField {
+                    name: name.to_string(),
+                    value: val.to_string(),
+                });
+            }
+            "-u" | "--user" => cmd.user = Some(value(&flag)?),
+            "-k" | "--insecure" => cmd.insecure = true,
+            "-L" | "--location" => cmd.follow_redirects = true,
+            "--compressed" => cmd.compressed = true,
+            "-G" | "--get" => cmd.get = true,
+            "-I" | "--head" => cmd.method = Some("HEAD".into()),
+            "-m" | "--max-time" => {
+                let raw = value(&flag)?;
+                let secs: f64 = raw
+                    .parse()
+                    .map_err(|_| Error::Curl(format!("{flag} {raw}: expected seconds")))?;
+                cmd.timeout_secs = Some(secs.ceil().max(1.0) as u64);
+            }
+            f if IGNORED_WITH_VALUE.contains(&f) => {
+                let v = value(f)?;
+                cmd.ignored.push(format!("{f} {v}"));
+            }
+            f => cmd.ignored.push(f.to_string()),
+        }
+    }
+
+    cmd.url = url.ok_or_else(|| Error::Curl("no URL found".into()))?;
+    return Ok(cmd);
+}
+
+/// Splits `--flag=value`, `-XPOST` (value attached), and `-sSL` (remaining flags are queued).
+fn split_flag(arg: &str, queue: &mut VecDeque<String>) -> (String, Option<String>) {
+    if arg.starts_with("--") {
+        return match arg.split_once('=') {
+            Some((f, v)) => (f.to_string(), Some(v.to_string())),
+            None => (arg.to_string(), None),
+        };
+    }
+    let mut chars = arg[1..].chars();
+    let Some(c) = chars.next() else {
+        return (arg.to_string(), None);
+    };
+    let rest = chars.as_str();
+    if rest.is_empty() {
+        return (format!("-{c}"), None);
+    }
+    if SHORT_WITH_VALUE.contains(c) {
+        return (format!("-{c}"), Some(rest.to_string()));
+    }
+    queue.push_front(format!("-{rest}"));
+    return (format!("-{c}"), None);
+}
+
+/// `Name: value`, or `Name;` for an empty header.
+fn parse_header(raw: &str) -> Result<(String, String)> {
+    if let Some((name, value)) = raw.split_once(':') {
+        return Ok((name.trim().to_string(), value.trim().to_string()));
+    }
+    if let Some(name) = raw.strip_suffix(';') {
+        return Ok((name.trim().to_string(), String::new()));
+    }
+    return Err(Error::Curl(format!("invalid header: {raw}")));
+}
+
+/// `--data-urlencode` forms: `content`, `=content`, `name=content` (content is percent-encoded).
+fn urlencode_data(raw: &str) -> Result<String> {
+    let encode = |s: &str| url::form_urlencoded::byte_serialize(s.as_bytes()).collect::<String>();
+    if raw.starts_with('@') {
+        return Err(Error::Curl(format!(
+            "--data-urlencode {raw}: reading from a file is not supported"
+        )));
+    }
+    return Ok(match raw.split_once('=') {
+        Some(("", content)) => encode(content),
+        Some((name, content)) => format!("{name}={}", encode(content)),
+        None => encode(raw),
+    });
+}
@@ -0,0 +1,138 @@
+//! Split a pasted shell command line into words (POSIX-style quoting, as produced by browser
+//! devtools "Copy as cURL").
+
+use crate::error::{Error, Result};
+
+/// Splits `input` into words, honouring `'…'`, `"…"`, `$'…'` (ANSI-C escapes), backslash escapes,
+/// and backslash-newline continuations. Windows `^` continuations are treated as whitespace.
+pub fn split_command_line(input: &str) -> Result<Vec<String>> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut in_word = false;
+    let mut chars = input.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => match chars.next() {
+                Some('\n') => {}
+                Some('\r') => {
+                    if chars.peek() == Some(&'\n') {
+                        chars.next();
+                    }
+                }
+                Some(other) => {
+                    current.push(other);
+                    in_word = true;
+                }
+                None => return Err(Error::Curl("trailing backslash".into())),
+            },
+            '^' if matches!(chars.peek(), Some('\n') | Some('\r')) => {}
+            '\'' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(ch) => current.push(ch),
+                        None => return Err(Error::Curl("unterminated single quote".into())),
+                    }
+                }
+            }
+            '"' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(ch @ ('"' | '\\' | '$' | '`')) => current.push(ch),
+                            Some('\n') => {}
+                            Some(ch) => {
+                                current.push('\\');
+                                current.push(ch);
+                            }
+                            None => return Err(Error::Curl("unterminated double quote".into())),
+                        },
+                        Some(ch) => current.push(ch),
+                        None => return Err(Error::Curl("unterminated double quote".into())),
+                    }
+                }
+            }
+            '$' if chars.peek() == Some(&'\'') => {
+                chars.next();
+                in_word = true;
+                read_ansi_c_quoted(&mut chars, &mut current)?;
+            }
+            c if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut current));
+                    in_word = false;
+                }
+            }
+            c => {
+                current.push(c);
+                in_word = true;
+            }
+        }
+    }
+
+    if in_word {
+        words.push(current);
+    }
+    return Ok(words);
+}
+
+/// Reads the body of a `$'…'` word (after the opening quote) into `out`.
+fn read_ansi_c_quoted(
+    chars: &mut std::iter::Peekable<std::str::Chars<'_>>,
+    out: &mut String,
+) -> Result<()> {
+    loop {
+        let Some(c) = chars.next() else {
+            return Err(Error::Curl("unterminated $'…' quote".into()));
+        };
+        if c == '\'' {
+            return Ok(());
+        }
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        let Some(esc) = chars.next() else {
+            return Err(Error::Curl("unterminated $'…' quote".into()));
+        };
+        match esc {
+            'n' => out.push('\n'),
+            't' => out.push('\t'),
+            'r' => out.push('\r'),
+            '0' => out.push('\0'),
+            'a' => out.push('\x07'),
+            'b' => out.push('\x08'),
+            'e' | 'E' => out.push('\x1b'),
+            'f' => out.push('\x0c'),
+            'v' => out.push('\x0b'),
+            'x' => out.push(read_hex_char(chars, 2)?),
+            'u' => out.push(read_hex_char(chars, 4)?),
+            'U' => out.push(read_hex_char(chars, 8)?),
+            other => out.push(other),
+        }
+    }
+}
+
+fn read_hex_char(
+    chars: &mut std::iter::Peekable<std::str::Chars<'_>>,
+    max_digits: usize,
+) -> Result<char> {
+    let mut digits = String::new();
+    while digits.len() < max_digits {
+        match chars.peek() {
+            Some(c) if c.is_ascii_hexdigit() => {
+                digits.push(*c);
+                chars.next();
+            }
+            _ => break,
+        }
+    }
+    let code = u32::from_str_radix(&digits, 16)
+        .map_err(|_| Error::Curl(format!("invalid escape in $'…' quote: \\{digits}")))?;
+    return char::from_u32(code)
+        .ok_or_else(|| Error::Curl(format!("invalid character code in $'…' quote: {code:#x}")));
+}
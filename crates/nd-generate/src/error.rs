@@ -1,16 +1,41 @@
 use std::path::PathBuf;
 
-/// Failure modes for loading OpenAPI, validating version, or writing generated files.
+/// Failure modes for loading OpenAPI, curl, Postman, Insomnia, Bruno, HAR, or .http input, validating version, or writing generated files.
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
-    #[error("unsupported OpenAPI version: {0} (only 3.0.x is supported in this release)")]
+    #[error("unsupported OpenAPI version: {0} (supported: Swagger 2.0, OpenAPI 3.0.x and 3.1.x)")]
     UnsupportedOpenApiVersion(String),
-    #[error("path item $ref not supported: {0}")]
-    PathItemRef(String),
-    #[error("parameter $ref not supported")]
-    ParameterRef,
-    #[error("request body $ref not supported")]
-    RequestBodyRef,
+    #[error("not an OpenAPI document: no `openapi` or `swagger` version field")]
+    MissingOpenApiVersion,
+    #[error("{version} document uses constructs that cannot be converted to OpenAPI 3.0:\n  - {}", .constructs.join("\n  - "))]
+    UnsupportedConstructs {
+        version: &'static str,
+        constructs: Vec<String>,
+    },
+    #[error("invalid {version} document after conversion to OpenAPI 3.0: {source}")]
+    ConvertedSpec {
+        version: &'static str,
+        #[source]
+        source: serde_json::Error,
+    },
+    #[error("cannot resolve $ref {0} (only local #/components/… and #/paths/… references are supported)")]
+    UnresolvedRef(String),
+    #[error("$ref cycle: {0}")]
+    RefCycle(String),
+    #[error("invalid curl command: {0}")]
+    Curl(String),
+    #[error("invalid Postman collection: {0}")]
+    Postman(String),
+    #[error("invalid Insomnia export: {0}")]
+    Insomnia(String),
+    #[error("invalid Bruno collection: {0}")]
+    Bruno(String),
+    #[error("invalid HAR file: {0}")]
+    Har(String),
+    #[error("{0}")]
+    HttpFile(String),
+    #[error("cannot export OpenAPI: {0}")]
+    Export(String),
     #[error("I/O error at {path}: {source}")]
     Io {
         path: PathBuf,
@@ -0,0 +1,232 @@
+//! HAR 1.2 capture (browser devtools "Save all as HAR") → a directory of nativedoctor request
+//! files.
+//!
+//! Each entry becomes a request file named after its method and path, in capture order. Entries
+//! can be filtered by host and method; repeated calls (same method, host, and path) are kept
+//! once unless asked otherwise. Browser-managed headers (`:authority`, `host`, `content-length`,
+//! `sec-*`, …) are dropped, the query string is split into `query`, and captured bodies are kept
+//! (JSON parsed, anything else as text). Captures spanning several hosts get one subdirectory per
+//! host. With `lift_secrets`, credentials go to `.env` as in [`crate::secrets`].
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use nd_core::har::{Entry, Har};
+use nd_core::model::pairs::NameValueList;
+use nd_core::model::request::{RequestBody, RequestBodyKind, RequestBodyStructured, RequestFile};
+
+use crate::convert::{has_header, read_json, ImportReport, ImportedRequest, Plan, Variables};
+use crate::error::{Error, Result};
+use crate::output::OutputFormat;
+use crate::secrets::lift_secrets;
+
+/// Request headers the browser or HTTP client manages itself; not copied into request files.
+const BROWSER_HEADERS: &[&str] = &[
+    "host",
+    "content-length",
+    "connection",
+    "keep-alive",
+    "accept-encoding",
+    "upgrade-insecure-requests",
+    "priority",
+    "te",
+    "transfer-encoding",
+];
+
+/// Options for [`import_har`].
+#[derive(Debug, Clone, Default)]
+pub struct HarImportOptions {
+    /// YAML or JSON output for each request file.
+    pub format: OutputFormat,
+    /// Only entries whose host matches one of these (`api.example.com`, or `*.example.com` for
+    /// subdomains). Empty keeps every host.
+    pub hosts: Vec<String>,
+    /// Only these methods (case-insensitive). Empty keeps every method.
+    pub methods: Vec<String>,
+    /// Keep every entry instead of one per method, host, and path.
+    pub keep_duplicates: bool,
+    /// Replace credentials with `${VAR}` placeholders and write the values to `.env`.
+    pub lift_secrets: bool,
+}
+
+/// Reads a HAR file and writes one request file per selected entry into `out_dir`. Refuses to
+/// overwrite existing files; nothing is written in that case.
+pub fn import_har(har: &Path, out_dir: &Path, options: HarImportOptions) -> Result<ImportReport> {
+    let har: Har = read_json(har, Error::Har)?;
+    if har.log.entries.is_empty() {
+        return Err(Error::Har("the capture has no entries".into()));
+    }
+
+    let mut selected: Vec<(url::Url, &Entry)> = Vec::new();
+    let mut seen = HashSet::new();
+    let (mut filtered, mut duplicates, mut unsupported) = (0, 0, 0);
+    for entry in &har.log.entries {
+        let Ok(url) = url::Url::parse(&entry.request.url) else {
+            unsupported += 1;
+            continue;
+        };
+        if !matches!(url.scheme(), "http" | "https") {
+            unsupported += 1;
+            continue;
+        }
+        let host = url.host_str().unwrap_or_default().to_string();
+        let method = entry.request.method.to_ascii_uppercase();
+        let host_ok = options.hosts.is_empty()
+            || options.hosts.iter().any(|h| host_matches(&host, h));
+        let method_ok = options.methods.is_empty()
+            || options.methods.iter().any(|m| m.eq_ignore_ascii_case(&method));
+        if !host_ok || !method_ok {
+            filtered += 1;
+            continue;
+        }
+        let key = format!("{method} {}://{}{}", url.scheme(), host, url.path());
+        if !options.keep_duplicates && !seen.insert(key) {
+            duplicates += 1;
+            continue;
+        }
+        selected.push((url, entry));
+    }
+
+    let hosts: HashSet<&str> = selected
+        .iter()
+        .map(|(url, _)| url.host_str().unwrap_or_default())
+        .collect();
+    let per_host = hosts.len() > 1;
+
+    let mut plan = Plan::new(out_dir, options.format);
+    let mut secrets = Variables::default();
+    for (url, entry) in &selected {
+        let mut notes = Vec::new();
+        let mut file = request_file(url, entry, &mut notes);
+        if options.lift_secrets {
+            for lifted in lift_secrets(&mut file) {
+                match secrets.get(&lifted.var) {
+                    Some(existing) if existing != lifted.value => notes.push(format!(
+                        "{} had different values across entries; .env keeps the first",
+                        lifted.var
+                    )),
+                    Some(_) => {}
+                    None => secrets.set(&lifted.var, lifted.value),
+                }
+            }
+        }
+        let dir = if per_host {
+            let host = url.host_str().unwrap_or_default();
+            let stem = crate::output::sanitize_stem(host);
+            out_dir.join(stem)
+        } else {
+            out_dir.to_path_buf()
+        };
+        let name = file.name.clone().unwrap_or_default();
+        let stem = plan.stem(&dir, &name);
+        plan.request(&dir, &stem, file, &[], "HAR", notes);
+    }
+
+    if filtered > 0 {
+        plan.note(format!("skipped {filtered} entries filtered out by host or method"));
+    }
+    if duplicates > 0 {
+        plan.note(format!(
+            "skipped {duplicates} repeated entries (same method, host, and path); pass --keep-duplicates to keep them"
+        ));
+    }
+    if unsupported > 0 {
+        plan.note(format!("skipped {unsupported} entries that are not http(s) URLs"));
+    }
+    if !secrets.is_empty() {
+        plan.env(
+            out_dir.join(".env"),
+            secrets.to_dotenv("Credentials lifted from the HAR capture"),
+        );
+    }
+    return plan.write();
+}
+
+/// `pattern` is a host name, or `*.example.com` for example.com and its subdomains.
+fn host_matches(host: &str, pattern: &str) -> bool {
+    let host = host.to_ascii_lowercase();
+    let pattern = pattern.trim().to_ascii_lowercase();
+    return match pattern.strip_prefix("*.") {
+        Some(domain) => host == domain || host.ends_with(&format!(".{domain}")),
+        None => host == pattern,
+    };
+}
+
+fn is_browser_header(name: &str) -> bool {
+    let lower = name.to_ascii_lowercase();
+    return lower.starts_with(':')
+        || lower.starts_with("sec-")
+        || BROWSER_HEADERS.contains(&lower.as_str());
+}
+
+fn request_file(url: &url::Url, entry: &Entry, notes: &mut Vec<String>) -> RequestFile {
+    let request = &entry.request;
+    let method = request.method.to_ascii_uppercase();
+
+    let mut base = url.clone();
+    base.set_query(None);
+    base.set_fragment(None);
+    let mut query = NameValueList::new();
+    for (k, v) in url.query_pairs() {
+        query.append(k.into_owned(), v.into_owned());
+    }
+
+    let mut headers = NameValueList::new();
+    for h in &request.headers {
+        if !is_browser_header(&h.name) {
+            headers.append(h.name.clone(), h.value.clone());
+        }
+    }
+
+    let body = request.post_data.as_ref().and_then(|post| {
+        let mime = post.mime_type.to_ascii_lowercase();
+        if !mime.is_empty() && !has_header(&headers, "content-type") {
+            headers.append("Content-Type", post.mime_type.clone());
+        }
+        if post.text.is_empty() {
+            if !post.params.is_empty() {
+                notes.push("the capture has form fields but no body text; body dropped".into());
+            }
+            return None;
+        }
+        if post.params.iter().any(|p| p.file_name.is_some()) {
+            notes.push("uploaded file contents are not in the capture; check the body".into());
+        }
+        let (kind, content) = if mime.contains("json") {
+            match serde_json::from_str(&post.text) {
+                Ok(value) => (RequestBodyKind::Json, value),
+                Err(_) => (RequestBodyKind::Other, post.text.clone().into()),
+            }
+        } else if mime.starts_with("application/x-www-form-urlencoded") {
+            (RequestBodyKind::XWwwFormUrlencoded, post.text.clone().into())
+        } else if mime.starts_with("multipart/form-data") {
+            (RequestBodyKind::FormData, post.text.clone().into())
+        } else if mime.contains("xml") {
+            (RequestBodyKind::Xml, post.text.clone().into())
+        } else {
+            (RequestBodyKind::Text, post.text.clone().into())
+        };
+        Some(RequestBody::Structured(RequestBodyStructured {
+            body_type: kind,
+            content,
+        }))
+    });
+
+    let description = (!entry.started_date_time.is_empty()).then(|| {
+        format!(
+            "Captured {} (response {}).",
+            entry.started_date_time, entry.response.status
+        )
+    });
+
+    return ImportedRequest {
+        name: format!("{method} {}", url.path()),
+        method,
+        url: base.to_string(),
+        query,
+        headers,
+        body,
+        description,
+    }
+    .into_request_file();
+}
@@ -0,0 +1,70 @@
+//! `.http` / `.rest` file (VS Code REST Client, JetBrains HTTP Client) → a directory of
+//! nativedoctor request files.
+//!
+//! Parsing is [`nd_core::model::http_file`], the same code `nativedoctor run api.http` uses, so the
+//! converted files send exactly what the `.http` file did: file variables are inlined, other
+//! `{{name}}` references become `${name}`, and `< ./file` bodies are read in. One file is written
+//! per request, named after the request.
+
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use nd_core::model::http_file::load_http_file;
+use regex::Regex;
+
+use crate::convert::{ImportReport, Plan};
+use crate::error::{Error, Result};
+use crate::output::OutputFormat;
+
+/// Options for [`import_http`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HttpImportOptions {
+    /// YAML or JSON output for each request file.
+    pub format: OutputFormat,
+}
+
+fn env_placeholder_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    return RE.get_or_init(|| {
+        Regex::new(r"\$\{(?:json:)?([A-Za-z_][A-Za-z0-9_]*)\}").expect("valid regex")
+    });
+}
+
+/// Reads a `.http` / `.rest` file and writes one request file per request into `out_dir`.
+/// Refuses to overwrite existing files; nothing is written in that case.
+pub fn import_http(
+    input: &Path,
+    out_dir: &Path,
+    options: HttpImportOptions,
+) -> Result<ImportReport> {
+    let files = load_http_file(input).map_err(|e| Error::HttpFile(e.to_string()))?;
+    if files.is_empty() {
+        return Err(Error::HttpFile(format!(
+            "{} has no requests",
+            input.display()
+        )));
+    }
+
+    let mut plan = Plan::new(out_dir, options.format);
+    let mut variables = BTreeSet::new();
+    for mut file in files {
+        file._path = None;
+        let text = serde_json::to_string(&file.request).expect("request serializes to JSON");
+        for caps in env_placeholder_re().captures_iter(&text) {
+            variables.insert(caps[1].to_string());
+        }
+        let name = file.name.clone().unwrap_or_default();
+        let stem = plan.stem(out_dir, &name);
+        plan.request(out_dir, &stem, file, &[], ".http", Vec::new());
+    }
+
+    if !variables.is_empty() {
+        let list: Vec<String> = variables.iter().map(|v| format!("${{{v}}}")).collect();
+        plan.note(format!(
+            "requests use {}; set them with --env or in the environment",
+            list.join(", ")
+        ));
+    }
+    return plan.write();
+}
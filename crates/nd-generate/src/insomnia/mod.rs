@@ -0,0 +1,395 @@
+//! Insomnia v4 JSON export → a directory of nativedoctor request files.
+//!
+//! Folders (`request_group`) become directories and requests become request files named after
+//! them; an export with several workspaces gets one subdirectory per workspace. `{{ _.var }}`
+//! placeholders become `${var}` (nested environment keys join with `_`: `{{ _.api.host }}` →
+//! `${api_host}`), and `{% uuid %}` / `{% faker %}` tags map to `${!name}` where possible (see
+//! [`crate::convert_template`]). Query parameters, headers, raw / url-encoded / multipart /
+//! GraphQL bodies, and bearer, basic, and API key auth (inherited from folders when a request
+//! has none) are carried over.
+//!
+//! The base environment (plus folder-level variables) is written to `.env`; each
+//! sub-environment goes to its own `NAME.env`, to combine with `--env .env --env NAME.env`.
+//! Pre-request and after-response scripts become stub `.rhai` files, as in [`crate::postman`].
+
+mod model;
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use nd_core::model::pairs::NameValueList;
+use serde_json::Value;
+
+use crate::convert::{
+    apply_auth, convert_body, convert_pairs, convert_template, has_header, note, read_json,
+    split_url, AuthSpec, BodySpec, ImportReport, ImportedRequest, Plan, ScriptSource, Variables,
+};
+use crate::error::{Error, Result};
+use crate::output::{sanitize_stem, unique_stem, OutputFormat};
+use model::{Export, Pair, Resource};
+
+/// Options for [`import_insomnia`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct InsomniaImportOptions {
+    /// YAML or JSON output for each request file.
+    pub format: OutputFormat,
+}
+
+/// Reads an Insomnia v4 export and writes the converted tree into `out_dir`. Refuses to
+/// overwrite existing files; nothing is written in that case.
+pub fn import_insomnia(
+    export: &Path,
+    out_dir: &Path,
+    options: InsomniaImportOptions,
+) -> Result<ImportReport> {
+    let export: Export = read_json(export, Error::Insomnia)?;
+    match export.export_format {
+        Some(4) => {}
+        Some(other) => {
+            return Err(Error::Insomnia(format!(
+                "unsupported export format {other} (export as Insomnia v4 JSON)"
+            )));
+        }
+        None => {
+            return Err(Error::Insomnia(
+                "missing __export_format (is this an Insomnia export?)".into(),
+            ));
+        }
+    }
+
+    let mut children: HashMap<&str, Vec<&Resource>> = HashMap::new();
+    for r in &export.resources {
+        children
+            .entry(r.parent_id.as_deref().unwrap_or(""))
+            .or_default()
+            .push(r);
+    }
+    for list in children.values_mut() {
+        list.sort_by(|a, b| {
+            let (a, b) = (a.meta_sort_key.unwrap_or(0.0), b.meta_sort_key.unwrap_or(0.0));
+            a.partial_cmp(&b).unwrap_or(std::cmp::Ordering::Equal)
+        });
+    }
+
+    let workspaces: Vec<&Resource> = export
+        .resources
+        .iter()
+        .filter(|r| r.kind == "workspace")
+        .collect();
+    let mut walk = Walk {
+        plan: Plan::new(out_dir, options.format),
+        children,
+    };
+    match workspaces.as_slice() {
+        [] => {
+            // Older exports point top-level items at a placeholder workspace id.
+            let ids: HashSet<&str> = export.resources.iter().map(|r| r.id.as_str()).collect();
+            let mut roots: Vec<&str> = Vec::new();
+            for r in &export.resources {
+                let parent = r.parent_id.as_deref().unwrap_or("");
+                if !ids.contains(parent) && !roots.contains(&parent) {
+                    roots.push(parent);
+                }
+            }
+            for root in roots {
+                walk.workspace(root, out_dir);
+            }
+        }
+        [only] => walk.workspace(&only.id, out_dir),
+        many => {
+            for ws in many {
+                let stem = walk.plan.stem(out_dir, &ws.name);
+                walk.workspace(&ws.id, &out_dir.join(stem));
+            }
+        }
+    }
+
+    let skipped: HashSet<&str> = export
+        .resources
+        .iter()
+        .map(|r| r.kind.as_str())
+        .filter(|k| !matches!(*k, "workspace" | "request_group" | "request" | "environment"))
+        .collect();
+    let mut skipped: Vec<&str> = skipped.into_iter().collect();
+    skipped.sort_unstable();
+    for kind in skipped {
+        walk.plan.note(format!("skipped {kind} resources"));
+    }
+    return walk.plan.write();
+}
+
+struct Walk<'a> {
+    plan: Plan,
+    children: HashMap<&'a str, Vec<&'a Resource>>,
+}
+
+impl<'a> Walk<'a> {
+    fn workspace(&mut self, id: &'a str, dir: &Path) {
+        let mut base = Variables::default();
+        let mut notes = Vec::new();
+        let mut subs: Vec<(&'a Resource, Variables)> = Vec::new();
+        for env in self.children_of(id) {
+            if env.kind != "environment" {
+                continue;
+            }
+            flatten_into(&mut base, "", env.data.as_ref(), &mut notes);
+            for sub in self.children_of(&env.id) {
+                if sub.kind == "environment" {
+                    let mut vars = Variables::default();
+                    flatten_into(&mut vars, "", sub.data.as_ref(), &mut notes);
+                    subs.push((sub, vars));
+                }
+            }
+        }
+
+        let mut folder_vars = Variables::default();
+        self.items(id, dir, None, &mut Vec::new(), &mut folder_vars, &mut notes);
+        for (k, v) in folder_vars.iter() {
+            base.set_default(k, v.clone());
+        }
+
+        if !base.is_empty() {
+            self.plan.env(
+                dir.join(".env"),
+                base.to_dotenv("Insomnia base environment and folder variables"),
+            );
+        }
+        let mut used = HashSet::new();
+        for (sub, vars) in subs {
+            let stem = unique_stem(&sanitize_stem(&sub.name), &mut used);
+            let comment = format!("Insomnia environment {:?}", sub.name);
+            self.plan
+                .env(dir.join(format!("{stem}.env")), vars.to_dotenv(&comment));
+        }
+        for n in notes {
+            self.plan.note(n);
+        }
+    }
+
+    fn children_of(&self, id: &str) -> Vec<&'a Resource> {
+        return self.children.get(id).cloned().unwrap_or_default();
+    }
+
+    fn items(
+        &mut self,
+        parent: &str,
+        dir: &Path,
+        auth: Option<&AuthSpec>,
+        scripts: &mut Vec<ScriptSource>,
+        folder_vars: &mut Variables,
+        notes: &mut Vec<String>,
+    ) {
+        for r in self.children_of(parent) {
+            match r.kind.as_str() {
+                "request_group" => {
+                    let stem = self.plan.stem(dir, &r.name);
+                    flatten_into(folder_vars, "", r.environment.as_ref(), notes);
+                    let own = auth_spec(r.authentication.as_ref());
+                    let folder_auth = match &own {
+                        Some(own) => own.as_ref(),
+                        None => auth,
+                    };
+                    let depth = scripts.len();
+                    scripts.extend(script_sources(r, "folder"));
+                    self.items(&r.id, &dir.join(stem), folder_auth, scripts, folder_vars, notes);
+                    scripts.truncate(depth);
+                }
+                "request" => {
+                    let stem = self.plan.stem(dir, &r.name);
+                    let own = auth_spec(r.authentication.as_ref());
+                    let request_auth = match &own {
+                        Some(own) => own.as_ref(),
+                        None => auth,
+                    };
+                    let mut request_notes = Vec::new();
+                    let file = request_file(r, request_auth, &mut request_notes);
+                    let mut sources = scripts.clone();
+                    sources.extend(script_sources(r, "request"));
+                    self.plan
+                        .request(dir, &stem, file, &sources, "Insomnia", request_notes);
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Pre-request and after-response scripts of a folder or request.
+fn script_sources(r: &Resource, what: &str) -> Vec<ScriptSource> {
+    let mut out = Vec::new();
+    if let Some(code) = &r.pre_request_script {
+        out.push(ScriptSource {
+            heading: format!("pre-request script from {what} {:?}", r.name),
+            before: true,
+            code: code.clone(),
+        });
+    }
+    if let Some(code) = &r.after_response_script {
+        out.push(ScriptSource {
+            heading: format!("after-response script from {what} {:?}", r.name),
+            before: false,
+            code: code.clone(),
+        });
+    }
+    return out;
+}
+
+/// Flattens nested environment data into `vars` (`{"api": {"host": …}}` → `api_host`).
+/// Values that reference other variables are converted to `${…}` but not resolved.
+fn flatten_into(vars: &mut Variables, prefix: &str, data: Option<&Value>, notes: &mut Vec<String>) {
+    let Some(Value::Object(map)) = data else {
+        return;
+    };
+    for (k, v) in map {
+        let key = if prefix.is_empty() {
+            k.clone()
+        } else {
+            format!("{prefix}.{k}")
+        };
+        let text = match v {
+            Value::Object(_) => {
+                flatten_into(vars, &key, Some(v), notes);
+                continue;
+            }
+            Value::String(s) => s.clone(),
+            Value::Null => String::new(),
+            other => other.to_string(),
+        };
+        let converted = convert_template(&text, notes);
+        if converted != text {
+            note(
+                notes,
+                "environment values that reference other variables are written as ${VAR} and \
+                 are not expanded"
+                    .into(),
+            );
+        }
+        vars.set(&key, converted);
+    }
+}
+
+/// `None`: inherit from the parent folder. `Some(None)`: explicitly no auth.
+fn auth_spec(auth: Option<&Value>) -> Option<Option<AuthSpec>> {
+    let auth = auth?;
+    let field = |name: &str| {
+        auth.get(name)
+            .and_then(|v| v.as_str())
+            .unwrap_or("")
+            .to_string()
+    };
+    if auth.get("disabled").and_then(|d| d.as_bool()) == Some(true) {
+        return Some(None);
+    }
+    return match auth.get("type").and_then(|t| t.as_str()) {
+        None | Some("inherit") => None,
+        Some("none") | Some("") => Some(None),
+        Some("bearer") => Some(Some(AuthSpec::Bearer {
+            token: field("token"),
+            prefix: Some(field("prefix")),
+        })),
+        Some("basic") => Some(Some(AuthSpec::Basic {
+            username: field("username"),
+            password: field("password"),
+        })),
+        Some("apikey") => Some(Some(AuthSpec::ApiKey {
+            key: field("key"),
+            value: field("value"),
+            in_query: field("addTo") == "queryParams",
+        })),
+        Some(other) => Some(Some(AuthSpec::Unsupported(other.to_string()))),
+    };
+}
+
+fn enabled(pairs: &[Pair]) -> Vec<(String, String)> {
+    return pairs
+        .iter()
+        .filter(|p| !p.disabled && !p.name.is_empty())
+        .map(|p| (p.name.clone(), p.value_text()))
+        .collect();
+}
+
+fn request_file(
+    r: &Resource,
+    auth: Option<&AuthSpec>,
+    notes: &mut Vec<String>,
+) -> nd_core::model::request::RequestFile {
+    let (url, mut query) = split_url(r.url.as_deref().unwrap_or(""), notes);
+    let params = enabled(&r.parameters);
+    for (k, v) in convert_pairs(params.iter().map(|(k, v)| (k.as_str(), v.as_str())), notes) {
+        query.append(k, v);
+    }
+    let headers = enabled(&r.headers);
+    let mut headers: NameValueList =
+        convert_pairs(headers.iter().map(|(k, v)| (k.as_str(), v.as_str())), notes);
+
+    if let Some(auth) = auth {
+        apply_auth(auth, &mut headers, &mut query, notes);
+    }
+    let body = r
+        .body
+        .as_ref()
+        .and_then(|b| body_spec(b, &mut headers, notes))
+        .and_then(|b| convert_body(&b, &mut headers, notes));
+
+    return ImportedRequest {
+        name: r.name.clone(),
+        method: r.method.clone().unwrap_or_else(|| "GET".into()),
+        url,
+        query,
+        headers,
+        body,
+        description: r.description.clone(),
+    }
+    .into_request_file();
+}
+
+fn body_spec(
+    body: &model::Body,
+    headers: &mut NameValueList,
+    notes: &mut Vec<String>,
+) -> Option<BodySpec> {
+    let mime = body.mime_type.as_deref().unwrap_or("");
+    match mime {
+        "application/x-www-form-urlencoded" => return Some(BodySpec::Form(enabled(&body.params))),
+        "multipart/form-data" => {
+            let mut fields = Vec::new();
+            for p in body.params.iter().filter(|p| !p.disabled) {
+                if p.kind.as_deref() == Some("file") {
+                    note(notes, format!("multipart file field {} was dropped", p.name));
+                    continue;
+                }
+                fields.push((p.name.clone(), p.value_text()));
+            }
+            return Some(BodySpec::Multipart(fields));
+        }
+        "application/graphql" => {
+            let text = body.text.as_deref().unwrap_or("");
+            let parsed: Value = serde_json::from_str(text).unwrap_or(Value::Null);
+            return Some(BodySpec::Graphql {
+                query: parsed
+                    .get("query")
+                    .and_then(|q| q.as_str())
+                    .unwrap_or(text)
+                    .to_string(),
+                variables: parsed
+                    .get("variables")
+                    .filter(|v| !v.is_null())
+                    .map(|v| v.to_string()),
+            });
+        }
+        _ if body.file_name.is_some() => {
+            note(notes, "file bodies are not supported; body dropped".into());
+            return None;
+        }
+        _ => {
+            let text = body.text.clone().filter(|t| !t.is_empty())?;
+            if !mime.is_empty() && !has_header(headers, "content-type") {
+                headers.append("Content-Type", mime);
+            }
+            return Some(BodySpec::Raw {
+                text,
+                hint: mime.to_string(),
+            });
+        }
+    }
+}
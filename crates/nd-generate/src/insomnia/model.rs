@@ -0,0 +1,91 @@
+//! Serde shapes for Insomnia v4 exports (`"__export_format": 4`). Every item is a flat
+//! `resources` entry; `parentId` links requests to folders and folders to the workspace.
+
+use serde::Deserialize;
+use serde_json::Value;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Export {
+    #[serde(rename = "__export_format", default)]
+    pub export_format: Option<u32>,
+    #[serde(default)]
+    pub resources: Vec<Resource>,
+}
+
+/// A `workspace`, `request_group` (folder), `request`, or `environment`; other types are skipped.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Resource {
+    #[serde(rename = "_id")]
+    pub id: String,
+    #[serde(rename = "_type")]
+    pub kind: String,
+    #[serde(default)]
+    pub parent_id: Option<String>,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub meta_sort_key: Option<f64>,
+    #[serde(default)]
+    pub method: Option<String>,
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub body: Option<Body>,
+    #[serde(default)]
+    pub parameters: Vec<Pair>,
+    #[serde(default)]
+    pub headers: Vec<Pair>,
+    /// `{"type": "bearer", "token": …}`; `{}` means inherit from the folder.
+    #[serde(default)]
+    pub authentication: Option<Value>,
+    #[serde(default)]
+    pub pre_request_script: Option<String>,
+    #[serde(default)]
+    pub after_response_script: Option<String>,
+    /// Environment variables (`environment` resources).
+    #[serde(default)]
+    pub data: Option<Value>,
+    /// Folder-level variables (`request_group` resources).
+    #[serde(default)]
+    pub environment: Option<Value>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Body {
+    #[serde(default)]
+    pub mime_type: Option<String>,
+    #[serde(default)]
+    pub text: Option<String>,
+    #[serde(default)]
+    pub params: Vec<Pair>,
+    #[serde(default)]
+    pub file_name: Option<String>,
+}
+
+/// Header, query parameter, or form field.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Pair {
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub value: Option<Value>,
+    #[serde(default)]
+    pub disabled: bool,
+    /// Form fields only: `file` for uploads.
+    #[serde(default, rename = "type")]
+    pub kind: Option<String>,
+}
+
+impl Pair {
+    pub fn value_text(&self) -> String {
+        return match &self.value {
+            None | Some(Value::Null) => String::new(),
+            Some(Value::String(s)) => s.clone(),
+            Some(other) => other.to_string(),
+        };
+    }
+}
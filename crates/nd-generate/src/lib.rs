@@ -1,18 +1,32 @@
 //! Generate nativedoctor request files from external API descriptions.
 //!
-//! Today the supported source is **OpenAPI 3.0.x** via [`openapi3`]. Additional sources can add
-//! their own top-level modules alongside it.
-//!
-//! OpenAPI **3.1** is rejected with a clear error until a dedicated code path exists.
+//! Supported sources: **OpenAPI 3.0.x** (plus 3.1 and Swagger 2.0, converted on load) via [`openapi3`], **curl** command lines via [`curl`],
+//! **Postman** collections (v2.0 / v2.1) via [`postman`], **Insomnia** v4 exports via
+//! [`insomnia`], **Bruno** collections via [`bruno`], **HAR** captures via [`har`], and `.http` /
+//! `.rest` files via [`http_file`]. The
+//! collection importers share placeholder conversion ([`convert_template`]) and report what they
+//! wrote in an [`ImportReport`]. Additional sources can add their own top-level modules alongside
+//! them, naming files with [`sanitize_stem`] / [`unique_stem`], writing
+//! them with [`write_request_file`], and optionally lifting credentials with [`secrets`].
 //!
 //! YAML output quotes `request.url` when it contains `${…}` so YAML 1.1 does not treat `$` as an
 //! alias. JSON output is unaffected.
 
+mod convert;
+pub mod bruno;
+pub mod curl;
 mod error;
+pub mod har;
+pub mod http_file;
+pub mod insomnia;
 pub mod openapi3;
+mod output;
+pub mod postman;
+pub mod secrets;
 
+pub use convert::{convert_template, var_ident, ImportReport};
 pub use error::{Error, Result};
-pub use openapi3::OutputFormat;
+pub use output::{sanitize_stem, unique_stem, write_request_file, OutputFormat};
 
 use std::path::Path;
 
@@ -21,18 +35,59 @@ use std::path::Path;
 pub struct GenerateOptions {
     /// YAML or JSON output for each generated request file.
     pub format: OutputFormat,
+    /// One subdirectory per tag (an operation's first tag) instead of a flat directory.
+    pub by_tag: bool,
+    /// Update the request files already in `out_dir` instead of overwriting them: files are
+    /// matched to operations by their `openapi.operation_id`, spec-derived fields are refreshed,
+    /// user edits to headers, bodies and names are kept. See [`SyncReport`].
+    pub sync: bool,
+    /// With `sync`, set `deprecated: true` on files whose operation left the spec instead of
+    /// deleting them.
+    pub deprecate_removed: bool,
 }
 
 /// Summary of a successful generation run.
 #[derive(Debug, Clone)]
 pub struct GenerateReport {
-    /// Absolute or relative paths of files created under `out_dir`.
+    /// Absolute or relative paths of files created under `out_dir` (with
+    /// [`GenerateOptions::sync`], only those added or rewritten).
     pub files_written: Vec<std::path::PathBuf>,
+    /// `index.rhai` per directory, importing each request file there as a module (and, at the
+    /// top, each tag directory's index).
+    pub index_scripts: Vec<std::path::PathBuf>,
+    /// `out_dir/.env.example`, listing every `${VAR}` the generated requests use (server
+    /// variables with their defaults, credentials for the spec's security schemes, parameters).
+    /// `None` when they use none.
+    pub env_example: Option<std::path::PathBuf>,
+    /// What [`GenerateOptions::sync`] did; `None` for a plain generate.
+    pub sync: Option<SyncReport>,
+}
+
+/// Request files a sync run touched, grouped by outcome.
+#[derive(Debug, Clone, Default)]
+pub struct SyncReport {
+    /// New operations, written like a fresh generate.
+    pub added: Vec<std::path::PathBuf>,
+    /// Existing files rewritten because their operation changed in the spec.
+    pub changed: Vec<std::path::PathBuf>,
+    /// Existing files that were already up to date.
+    pub unchanged: Vec<std::path::PathBuf>,
+    /// Files deleted because their operation is no longer in the spec.
+    pub removed: Vec<std::path::PathBuf>,
+    /// Files marked `deprecated` because their operation is no longer in the spec
+    /// ([`GenerateOptions::deprecate_removed`]).
+    pub deprecated: Vec<std::path::PathBuf>,
 }
 
-/// Read OpenAPI 3.0.x from `input`, then write one nativedoctor request file per HTTP operation into `out_dir`.
+/// Read OpenAPI 3.0.x / 3.1 or Swagger 2.0 from `input`, then write one nativedoctor request file per HTTP operation into `out_dir`.
+///
+/// File names derive from `operationId` or method + path; with [`GenerateOptions::by_tag`] each lands in a
+/// subdirectory named after its first tag. Every directory gets an `index.rhai` importing its requests as modules.
+/// Security requirements become auth headers or query parameters, and server variables become `${VAR}`s; every
+/// variable is listed in `out_dir/.env.example`. Each file records its `operationId`, which
+/// [`GenerateOptions::sync`] uses to update an existing directory in place.
 ///
-/// File names derive from `operationId` or method + path. OpenAPI 3.1+ and unsupported `$ref` forms return [`Error`].
+/// Other versions, constructs that cannot be converted to 3.0, and unresolvable `$ref`s return [`Error`].
 ///
 /// For lower-level access (load only, custom naming, etc.), see [`openapi3`].
 pub fn generate_from_openapi_path(
@@ -40,7 +95,7 @@ pub fn generate_from_openapi_path(
     out_dir: impl AsRef<Path>,
     options: GenerateOptions,
 ) -> Result<GenerateReport> {
-    openapi3::generate_from_path(input.as_ref(), out_dir.as_ref(), options.format)
+    openapi3::generate_from_path(input.as_ref(), out_dir.as_ref(), options)
 }
 
 /// Convert `{param}` path segments to nativedoctor `${param}` template syntax (OpenAPI-style paths).
@@ -1,15 +1,17 @@
 //! Map OpenAPI paths and operations into [`nd_core::RequestFile`] values.
 
-use std::collections::{HashMap, HashSet};
-
+use nd_core::model::pairs::NameValueList;
 use nd_core::model::request::{
-    HttpRequestSpec, RequestBody, RequestBodyKind, RequestBodyStructured, RequestFile,
-};
-use openapiv3::{
-    OpenAPI, Operation, Parameter, PathItem, ReferenceOr, RequestBody as OasRequestBody,
+    HttpRequestSpec, OpenApiLink, RequestBody, RequestBodyKind, RequestBodyStructured, RequestFile,
 };
+use openapiv3::{OpenAPI, Operation, Parameter, PathItem, RequestBody as OasRequestBody};
 
-use crate::error::{Error, Result};
+use super::example::media_type_example;
+use super::resolve::Refs;
+use super::security::{apply_security, credentials};
+use crate::convert::var_ident;
+use crate::error::Result;
+use crate::output::sanitize_stem;
 
 /// Convert `{param}` path segments to nativedoctor `${param}` template syntax.
 pub fn path_to_url_template(path: &str) -> String {
@@ -21,34 +23,45 @@ pub fn path_to_url_template(path: &str) -> String {
     .to_string()
 }
 
+/// First server URL, with `{variable}`s as `${variable}` templates (defaults go to
+/// `.env.example`).
 fn base_url(api: &OpenAPI) -> String {
     api.servers
         .first()
-        .map(|s| s.url.trim_end_matches('/').to_string())
+        .map(|s| server_url_template(&s.url).trim_end_matches('/').to_string())
         .unwrap_or_else(|| nd_constants::OPENAPI_GENERATE_BASE_URL_PLACEHOLDER.to_string())
 }
 
-fn merge_parameters(path_item: &PathItem, operation: &Operation) -> Result<Vec<Parameter>> {
-    let mut out = Vec::new();
-    for p in &path_item.parameters {
-        match p {
-            ReferenceOr::Item(param) => out.push(param.clone()),
-            ReferenceOr::Reference { .. } => return Err(Error::ParameterRef),
-        }
-    }
-    for p in &operation.parameters {
-        match p {
-            ReferenceOr::Item(param) => out.push(param.clone()),
-            ReferenceOr::Reference { .. } => return Err(Error::ParameterRef),
-        }
+fn server_url_template(url: &str) -> String {
+    let re = regex::Regex::new(r"\{([^}]+)\}").expect("valid regex");
+    re.replace_all(url, |caps: &regex::Captures| format!("${{{}}}", var_ident(&caps[1])))
+        .to_string()
+}
+
+/// Path-level parameters, overridden by operation-level ones with the same name and location.
+fn merge_parameters(
+    refs: Refs,
+    path_item: &PathItem,
+    operation: &Operation,
+) -> Result<Vec<Parameter>> {
+    let mut out: Vec<Parameter> = Vec::new();
+    for p in path_item.parameters.iter().chain(&operation.parameters) {
+        let param = refs.parameter(p)?;
+        let data = param.parameter_data_ref();
+        out.retain(|existing| {
+            let known = existing.parameter_data_ref();
+            known.name != data.name
+                || std::mem::discriminant(existing) != std::mem::discriminant(param)
+        });
+        out.push(param.clone());
     }
     Ok(out)
 }
 
 fn apply_parameters(
     params: &[Parameter],
-    query: &mut HashMap<String, String>,
-    headers: &mut HashMap<String, String>,
+    query: &mut NameValueList,
+    headers: &mut NameValueList,
 ) {
     for p in params {
         match p {
@@ -79,15 +92,14 @@ fn apply_parameters(
     }
 }
 
-fn json_request_body(op: &Operation) -> Result<Option<RequestBody>> {
+/// JSON body for the operation, synthesised from its `application/json` request body (see
+/// [`super::example`]).
+fn json_request_body(api: &OpenAPI, op: &Operation) -> Result<Option<RequestBody>> {
     let Some(rb_ref) = &op.request_body else {
         return Ok(None);
     };
-    let body: &OasRequestBody = match rb_ref {
-        ReferenceOr::Item(b) => b,
-        ReferenceOr::Reference { .. } => return Err(Error::RequestBodyRef),
-    };
-    let Some((_, _mt)) = body.content.iter().find(|(k, _)| {
+    let body: &OasRequestBody = Refs::new(api).request_body(rb_ref)?;
+    let Some((_, media)) = body.content.iter().find(|(k, _)| {
         let k = k.as_str();
         k == "application/json" || k.starts_with("application/json;")
     }) else {
@@ -95,7 +107,7 @@ fn json_request_body(op: &Operation) -> Result<Option<RequestBody>> {
     };
     Ok(Some(RequestBody::Structured(RequestBodyStructured {
         body_type: RequestBodyKind::Json,
-        content: serde_json::json!({}),
+        content: media_type_example(api, media),
     })))
 }
 
@@ -115,23 +127,25 @@ pub fn operation_to_request_file(
         format!("{base}/{path_part}")
     };
 
-    let mut query = HashMap::new();
-    let mut headers = HashMap::new();
-    let params = merge_parameters(path_item, operation)?;
+    let mut query = NameValueList::new();
+    let mut headers = NameValueList::new();
+    let params = merge_parameters(Refs::new(api), path_item, operation)?;
     apply_parameters(&params, &mut query, &mut headers);
+    let credentials = credentials(api)?;
+    apply_security(&credentials, api, operation, &mut headers, &mut query);
 
-    let body = json_request_body(operation)?;
+    let body = json_request_body(api, operation)?;
 
-    let name = operation
+    let operation_id = operation
         .operation_id
         .clone()
-        .or_else(|| Some(fallback_operation_name(method, path_template)));
+        .unwrap_or_else(|| fallback_operation_name(method, path_template));
 
     let deprecated = operation.deprecated;
 
     Ok(RequestFile {
         version: nd_constants::REQUEST_FILE_DEFAULT_VERSION.to_string(),
-        name,
+        name: Some(operation_id.clone()),
         request: HttpRequestSpec {
             method: method.to_ascii_uppercase(),
             url,
@@ -146,6 +160,10 @@ pub fn operation_to_request_file(
             follow_redirects: true,
             verify_tls: true,
         },
+        openapi: Some(OpenApiLink {
+            spec: None,
+            operation_id,
+        }),
         ..Default::default()
     })
 }
@@ -179,28 +197,6 @@ pub fn file_stem(operation: &Operation, method: &str, path: &str) -> String {
     sanitize_stem(&raw)
 }
 
-fn sanitize_stem(s: &str) -> String {
-    let mut out = String::with_capacity(s.len());
-    for c in s.chars() {
-        if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
-            out.push(c.to_ascii_lowercase());
-        } else {
-            out.push('-');
-        }
-    }
-    let out = out
-        .split('-')
-        .filter(|p| !p.is_empty())
-        .collect::<Vec<_>>()
-        .join("-");
-    if out.is_empty() {
-        "request".into()
-    } else {
-        out
-    }
-}
-
-/// Allocate unique `stem` by appending `_2`, `_3`, … on collision.
 #[cfg(test)]
 mod tests {
     use super::path_to_url_template;
@@ -213,20 +209,3 @@ mod tests {
         );
     }
 }
-
-/// Returns `stem` if unused, else `stem_2`, `stem_3`, … until unique (mutates `used`).
-pub fn unique_stem(stem: &str, used: &mut HashSet<String>) -> String {
-    if !used.contains(stem) {
-        used.insert(stem.to_string());
-        return stem.to_string();
-    }
-    let mut n = 2;
-    loop {
-        let candidate = format!("{stem}_{n}");
-        if !used.contains(&candidate) {
-            used.insert(candidate.clone());
-            return candidate;
-        }
-        n += 1;
-    }
-}
@@ -0,0 +1,128 @@
+//! `.env.example` for a generated collection.
+//!
+//! Lists every `${VAR}` (and `${json:VAR}`) the generated request files use: server variables
+//! first, with their `default` and the description and allowed values as a comment; then
+//! credentials, with a comment saying what they hold; then everything else (path, query and header
+//! parameters, body placeholders) empty, in order of first use.
+
+use std::fmt::Write as _;
+use std::sync::OnceLock;
+
+use nd_core::model::request::{RequestBody, RequestFile};
+use openapiv3::OpenAPI;
+use regex::Regex;
+use serde_json::Value;
+
+use super::security::credentials;
+use crate::convert::var_ident;
+use crate::error::Result;
+use crate::secrets::dotenv_quote;
+
+fn variable_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    return RE.get_or_init(|| {
+        Regex::new(r"\$\{(?:json:)?([A-Za-z_][A-Za-z0-9_]*)\}").expect("valid regex")
+    });
+}
+
+/// Dotenv text for the variables `files` use, or `None` when they use none.
+pub(crate) fn env_example<'a>(
+    api: &OpenAPI,
+    files: impl IntoIterator<Item = &'a RequestFile>,
+) -> Result<Option<String>> {
+    let mut used: Vec<String> = Vec::new();
+    for file in files {
+        let request = &file.request;
+        collect_str(&request.url, &mut used);
+        for (_, v) in request.query.iter().chain(request.headers.iter()) {
+            collect_str(v, &mut used);
+        }
+        match &request.body {
+            Some(RequestBody::Structured(s)) => collect(&s.content, &mut used),
+            Some(RequestBody::Json(v)) => collect(v, &mut used),
+            Some(RequestBody::Text(text)) => collect_str(text, &mut used),
+            None => {}
+        }
+    }
+    if used.is_empty() {
+        return Ok(None);
+    }
+
+    let server_variables = api.servers.first().and_then(|s| s.variables.as_ref());
+    let credentials = credentials(api)?;
+    let mut names: Vec<&String> = Vec::new();
+    let server_names = server_variables
+        .into_iter()
+        .flatten()
+        .map(|(k, _)| var_ident(k));
+    let credential_names = credentials.iter().map(|c| c.var.clone());
+    for name in server_names.chain(credential_names) {
+        if let Some(n) = used.iter().find(|n| **n == name) {
+            names.push(n);
+        }
+    }
+    for n in &used {
+        if !names.contains(&n) {
+            names.push(n);
+        }
+    }
+    let mut out = format!(
+        "# Variables used by the requests generated from {}.\n# Copy to .env (or pass with --env) and fill in the blanks.\n",
+        if api.info.title.is_empty() {
+            "the OpenAPI spec"
+        } else {
+            api.info.title.as_str()
+        }
+    );
+    for name in names {
+        let server = server_variables
+            .into_iter()
+            .flatten()
+            .find(|(k, _)| var_ident(k) == *name);
+        let mut value = String::new();
+        if let Some((_, variable)) = server {
+            let mut comment = variable.description.clone().unwrap_or_default();
+            if !variable.enumeration.is_empty() {
+                if !comment.is_empty() {
+                    comment.push(' ');
+                }
+                let _ = write!(comment, "(one of: {})", variable.enumeration.join(", "));
+            }
+            if !comment.is_empty() {
+                let _ = writeln!(out, "# {comment}");
+            }
+            value = variable.default.clone();
+        } else if let Some(credential) = credentials.iter().find(|c| c.var == *name) {
+            let _ = writeln!(out, "# {}: {}", credential.scheme, credential.hint);
+        } else if format!("${{{name}}}") == nd_constants::OPENAPI_GENERATE_BASE_URL_PLACEHOLDER {
+            let _ = writeln!(out, "# The spec has no `servers`; set the API's base URL.");
+        }
+        let value = if value.is_empty() {
+            value
+        } else {
+            dotenv_quote(&value)
+        };
+        let _ = writeln!(out, "{name}={value}");
+    }
+    return Ok(Some(out));
+}
+
+/// Variable names in `text`, skipping `${!dynamic}` templates.
+fn collect_str(text: &str, names: &mut Vec<String>) {
+    for caps in variable_re().captures_iter(text) {
+        let name = &caps[1];
+        if !names.iter().any(|n| n == name) {
+            names.push(name.to_string());
+        }
+    }
+}
+
+/// Variable names in every string of a JSON body.
+fn collect(value: &Value, names: &mut Vec<String>) {
+    match value {
+        Value::String(s) => collect_str(s, names),
+        Value::Array(items) => items.iter().for_each(|v| collect(v, names)),
+        Value::Object(fields) => fields.values().for_each(|v| collect(v, names)),
+        _ => {}
+    }
+}
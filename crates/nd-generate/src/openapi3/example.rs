@@ -0,0 +1,349 @@
+//! Example JSON request bodies synthesised from OpenAPI schemas.
+//!
+//! A media type's own `example` / `examples` win. Otherwise each schema yields its `example`, else
+//! its `default`, else a value built from its type: the first `enum` entry, `${!name}` dynamic
+//! templates for `uuid` / `email` / `date-time` strings, `${name}` placeholders for required
+//! ID-like properties (`id`, `user_id`, `petId`) and passwords, `minimum` for numbers, one item for
+//! arrays, and every property that is not `readOnly` for objects. `allOf` members are merged;
+//! `oneOf` / `anyOf` use the first alternative.
+//!
+//! Schema references are followed (see [`super::resolve`]); a property or array item that refers
+//! back to a schema being built is left out, so recursive models terminate.
+
+use openapiv3::{
+    AdditionalProperties, AnySchema, ArrayType, IntegerType, MediaType, NumberType, ObjectType,
+    OpenAPI, ReferenceOr, Schema, SchemaKind, StringFormat, StringType, Type,
+    VariantOrUnknownOrEmpty,
+};
+use serde_json::{json, Map, Value};
+
+use super::resolve::Refs;
+use crate::convert::var_ident;
+
+/// Nesting limit, in case a spec nests inline schemas absurdly deep.
+const MAX_DEPTH: usize = 16;
+
+/// Example body for a request (or response) media type.
+pub(crate) fn media_type_example(api: &OpenAPI, media: &MediaType) -> Value {
+    if let Some(example) = &media.example {
+        return example.clone();
+    }
+    let named = media.examples.values().find_map(|e| match e {
+        ReferenceOr::Item(example) => example.value.clone(),
+        ReferenceOr::Reference { .. } => None,
+    });
+    if let Some(value) = named {
+        return value;
+    }
+    let Some(schema) = &media.schema else {
+        return json!({});
+    };
+    return Examples::new(api)
+        .reference_or(schema, None, 0)
+        .unwrap_or_else(|| json!({}));
+}
+
+/// The property an example is built for, when it is one.
+#[derive(Clone, Copy)]
+struct Property<'a> {
+    name: &'a str,
+    required: bool,
+}
+
+impl Property<'_> {
+    /// `${name}` for required IDs, so callers fill in a real one.
+    fn id_placeholder(self) -> Option<String> {
+        let name = self.name;
+        let id_like = name.eq_ignore_ascii_case("id")
+            || name.ends_with("_id")
+            || (name.len() > 2 && (name.ends_with("Id") || name.ends_with("ID")));
+        return (self.required && id_like).then(|| var_ident(name));
+    }
+}
+
+struct Examples<'a> {
+    refs: Refs<'a>,
+    /// Component schemas being built, innermost last.
+    stack: Vec<String>,
+}
+
+impl<'a> Examples<'a> {
+    fn new(api: &'a OpenAPI) -> Self {
+        return Self {
+            refs: Refs::new(api),
+            stack: Vec::new(),
+        };
+    }
+
+    /// `None` when `reference` is part of a cycle (or cannot be resolved to a schema).
+    fn reference(
+        &mut self,
+        reference: &'a str,
+        property: Option<Property>,
+        depth: usize,
+    ) -> Option<Value> {
+        let (name, schema) = self.refs.schema_target(reference)?;
+        if self.stack.contains(&name) {
+            return None;
+        }
+        self.stack.push(name);
+        let value = self.reference_or(schema, property, depth + 1);
+        self.stack.pop();
+        return value;
+    }
+
+    fn reference_or(
+        &mut self,
+        schema: &'a ReferenceOr<Schema>,
+        property: Option<Property>,
+        depth: usize,
+    ) -> Option<Value> {
+        return match schema {
+            ReferenceOr::Item(schema) => Some(self.schema(schema, property, depth)),
+            ReferenceOr::Reference { reference } => self.reference(reference, property, depth),
+        };
+    }
+
+    fn boxed(
+        &mut self,
+        schema: &'a ReferenceOr<Box<Schema>>,
+        property: Option<Property>,
+        depth: usize,
+    ) -> Option<Value> {
+        return match schema {
+            ReferenceOr::Item(schema) => Some(self.schema(schema, property, depth)),
+            ReferenceOr::Reference { reference } => self.reference(reference, property, depth),
+        };
+    }
+
+    /// Whether `schema` (following references) is marked `readOnly`.
+    fn read_only(&self, schema: &ReferenceOr<Box<Schema>>) -> bool {
+        return match schema {
+            ReferenceOr::Item(schema) => schema.schema_data.read_only,
+            ReferenceOr::Reference { reference } => self
+                .refs
+                .schema_target(reference)
+                .and_then(|(_, schema)| self.refs.schema(schema).ok())
+                .is_some_and(|s| s.schema_data.read_only),
+        };
+    }
+
+    fn schema(&mut self, schema: &'a Schema, property: Option<Property>, depth: usize) -> Value {
+        if depth > MAX_DEPTH {
+            return Value::Null;
+        }
+        let data = &schema.schema_data;
+        if let Some(example) = &data.example {
+            return example.clone();
+        }
+        if let Some(default) = &data.default {
+            return default.clone();
+        }
+        return match &schema.schema_kind {
+            SchemaKind::Type(Type::String(s)) => string(s, property),
+            SchemaKind::Type(Type::Integer(i)) => integer(i, property),
+            SchemaKind::Type(Type::Number(n)) => number(n),
+            SchemaKind::Type(Type::Boolean(b)) => {
+                json!(b.enumeration.iter().flatten().next().copied().unwrap_or(true))
+            }
+            SchemaKind::Type(Type::Object(o)) => self.object(o, depth),
+            SchemaKind::Type(Type::Array(a)) => self.array(a, depth),
+            SchemaKind::OneOf { one_of: choices } | SchemaKind::AnyOf { any_of: choices } => {
+                self.first_of(choices, property, depth)
+            }
+            SchemaKind::AllOf { all_of } => self.all_of(all_of, property, depth),
+            SchemaKind::Not { .. } => Value::Null,
+            SchemaKind::Any(any) => self.any(any, property, depth),
+        };
+    }
+
+    fn object(&mut self, object: &'a ObjectType, depth: usize) -> Value {
+        return self.properties(
+            &object.properties,
+            &object.required,
+            object.additional_properties.as_ref(),
+            depth,
+        );
+    }
+
+    fn properties(
+        &mut self,
+        properties: impl IntoIterator<Item = (&'a String, &'a ReferenceOr<Box<Schema>>)>,
+        required: &[String],
+        additional: Option<&'a AdditionalProperties>,
+        depth: usize,
+    ) -> Value {
+        let mut out = Map::new();
+        for (name, schema) in properties {
+            if self.read_only(schema) {
+                continue;
+            }
+            let property = Property {
+                name,
+                required: required.iter().any(|r| r == name),
+            };
+            if let Some(value) = self.boxed(schema, Some(property), depth + 1) {
+                out.insert(name.clone(), value);
+            }
+        }
+        if out.is_empty() {
+            if let Some(AdditionalProperties::Schema(schema)) = additional {
+                if let Some(value) = self.reference_or(schema, None, depth + 1) {
+                    out.insert("key".into(), value);
+                }
+            }
+        }
+        return Value::Object(out);
+    }
+
+    fn array(&mut self, array: &'a ArrayType, depth: usize) -> Value {
+        let item = array
+            .items
+            .as_ref()
+            .and_then(|items| self.boxed(items, None, depth + 1));
+        return Value::Array(item.into_iter().collect());
+    }
+
+    fn first_of(
+        &mut self,
+        choices: &'a [ReferenceOr<Schema>],
+        property: Option<Property>,
+        depth: usize,
+    ) -> Value {
+        return choices
+            .iter()
+            .find_map(|c| self.reference_or(c, property, depth + 1))
+            .unwrap_or(Value::Null);
+    }
+
+    /// Objects are merged key by key; otherwise the last member with a value wins.
+    fn all_of(
+        &mut self,
+        members: &'a [ReferenceOr<Schema>],
+        property: Option<Property>,
+        depth: usize,
+    ) -> Value {
+        let mut merged = Map::new();
+        let mut other = None;
+        for member in members {
+            match self.reference_or(member, property, depth + 1) {
+                Some(Value::Object(fields)) => merged.extend(fields),
+                Some(Value::Null) | None => {}
+                Some(value) => other = Some(value),
+            }
+        }
+        if merged.is_empty() {
+            if let Some(value) = other {
+                return value;
+            }
+        }
+        return Value::Object(merged);
+    }
+
+    /// Schemas `openapiv3` could not classify (mixed keywords, or no `type`).
+    fn any(&mut self, any: &'a AnySchema, property: Option<Property>, depth: usize) -> Value {
+        if let Some(first) = any.enumeration.first() {
+            return first.clone();
+        }
+        if !any.all_of.is_empty() {
+            let mut value = self.all_of(&any.all_of, property, depth);
+            if let (Value::Object(fields), Value::Object(own)) = (
+                &mut value,
+                self.properties(
+                    &any.properties,
+                    &any.required,
+                    any.additional_properties.as_ref(),
+                    depth,
+                ),
+            ) {
+                fields.extend(own);
+            }
+            return value;
+        }
+        if let Some(choices) = [&any.one_of, &any.any_of].into_iter().find(|c| !c.is_empty()) {
+            return self.first_of(choices, property, depth);
+        }
+        if !any.properties.is_empty() || any.typ.as_deref() == Some("object") {
+            return self.properties(
+                &any.properties,
+                &any.required,
+                any.additional_properties.as_ref(),
+                depth,
+            );
+        }
+        if let Some(items) = &any.items {
+            let item = self.boxed(items, None, depth + 1);
+            return Value::Array(item.into_iter().collect());
+        }
+        return match any.typ.as_deref() {
+            Some("string") => string_for_format(any.format.as_deref().unwrap_or(""), property),
+            Some("integer") => property
+                .and_then(Property::id_placeholder)
+                .map(|v| json!(format!("${{json:{v}}}")))
+                .unwrap_or_else(|| json!(any.minimum.map_or(0, |m| m.ceil() as i64))),
+            Some("number") => json!(any.minimum.unwrap_or(0.0)),
+            Some("boolean") => json!(true),
+            Some("array") => json!([]),
+            _ => Value::Null,
+        };
+    }
+}
+
+fn string(s: &StringType, property: Option<Property>) -> Value {
+    if let Some(first) = s.enumeration.iter().flatten().next() {
+        return json!(first);
+    }
+    let format = match &s.format {
+        VariantOrUnknownOrEmpty::Item(StringFormat::Date) => "date",
+        VariantOrUnknownOrEmpty::Item(StringFormat::DateTime) => "date-time",
+        VariantOrUnknownOrEmpty::Item(StringFormat::Password) => "password",
+        VariantOrUnknownOrEmpty::Item(StringFormat::Byte) => "byte",
+        VariantOrUnknownOrEmpty::Item(StringFormat::Binary) => "binary",
+        VariantOrUnknownOrEmpty::Unknown(other) => other.as_str(),
+        VariantOrUnknownOrEmpty::Empty => "",
+    };
+    return string_for_format(format, property);
+}
+
+fn string_for_format(format: &str, property: Option<Property>) -> Value {
+    if let Some(var) = property.and_then(Property::id_placeholder) {
+        return json!(format!("${{{var}}}"));
+    }
+    let value = match format.to_ascii_lowercase().as_str() {
+        "uuid" => "${!uuidv4}".to_string(),
+        "email" | "idn-email" => "${!random_email}".to_string(),
+        "date-time" => "${!iso_timestamp}".to_string(),
+        "date" => "2024-01-01".to_string(),
+        "time" => "12:00:00Z".to_string(),
+        "uri" | "url" | "iri" => "https://example.com".to_string(),
+        "hostname" | "idn-hostname" => "example.com".to_string(),
+        "ipv4" => "192.0.2.1".to_string(),
+        "ipv6" => "2001:db8::1".to_string(),
+        // base64 of "example"
+        "byte" => "ZXhhbXBsZQ==".to_string(),
+        "binary" => String::new(),
+        "password" => {
+            let var = property.map_or_else(|| "password".to_string(), |p| var_ident(p.name));
+            format!("${{{var}}}")
+        }
+        _ => "string".to_string(),
+    };
+    return json!(value);
+}
+
+fn integer(i: &IntegerType, property: Option<Property>) -> Value {
+    if let Some(var) = property.and_then(Property::id_placeholder) {
+        return json!(format!("${{json:{var}}}"));
+    }
+    if let Some(first) = i.enumeration.iter().flatten().next() {
+        return json!(first);
+    }
+    return json!(i.minimum.unwrap_or(0));
+}
+
+fn number(n: &NumberType) -> Value {
+    if let Some(first) = n.enumeration.iter().flatten().next() {
+        return json!(first);
+    }
+    return json!(n.minimum.unwrap_or(0.0));
+}
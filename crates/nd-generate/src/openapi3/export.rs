@@ -0,0 +1,971 @@
+//! nativedoctor request files → an OpenAPI **3.0** document (`nativedoctor export openapi`).
+//!
+//! Requests are grouped into operations by method and templated path: the URL's query (which
+//! joins the request's `query` parameters) and fragment are dropped, and its server part (`https://host`, or a leading `${BASE_URL}`) becomes a
+//! server entry, with `${VAR}`s as server variables. Path segments that are `${VAR}` templates
+//! become path parameters; literal numbers and UUIDs do too (named after the segment before them,
+//! `/users/42` → `/users/{user_id}`), so requests recorded against concrete ids still group.
+//!
+//! Each operation takes its `operationId` from the request's `openapi` link or name, its summary,
+//! description, tags and `deprecated` from the request specs, and the union of their query and
+//! header parameters (required when every request sends one). `Authorization` and API-key headers
+//! become security schemes instead of parameters. Request bodies and, with recorded responses
+//! (HAR captures, matched to operations by method and path), response bodies get schemas inferred
+//! from every sample (see [`super::infer`]).
+
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use nd_core::har::Har;
+use nd_core::model::request::{RequestBody, RequestBodyKind, RequestFile};
+use nd_core::utils::template::placeholder_span_re;
+use openapiv3::{
+    APIKeyLocation, Components, Info, MediaType, OpenAPI, Operation, Parameter, ParameterData,
+    ParameterSchemaOrContent, PathItem, ReferenceOr, RequestBody as BodyObject, Response,
+    Responses, SecurityRequirement, SecurityScheme, Server, ServerVariable, StatusCode,
+};
+use serde_json::Value;
+
+use super::infer::{string_format, Shape};
+use crate::convert::{read_json, var_ident};
+use crate::error::{Error, Result};
+
+/// Title used when [`ExportOptions::title`] is not set.
+const DEFAULT_TITLE: &str = "Exported requests";
+/// `info.version` used when [`ExportOptions::version`] is not set.
+const DEFAULT_VERSION: &str = "1.0.0";
+
+/// Request file extensions picked up when walking a directory.
+const REQUEST_EXTENSIONS: [&str; 5] = ["json", "yaml", "yml", "http", "rest"];
+
+/// Headers that describe the transport rather than the API; never exported as parameters.
+const SKIPPED_HEADERS: [&str; 7] = [
+    "accept",
+    "accept-encoding",
+    "connection",
+    "content-length",
+    "content-type",
+    "cookie",
+    "user-agent",
+];
+
+/// Header names exported as an `apiKey` security scheme instead of a parameter.
+const API_KEY_HEADERS: [&str; 5] = [
+    "x-api-key",
+    "api-key",
+    "apikey",
+    "x-auth-token",
+    "x-access-token",
+];
+
+/// Options for [`export_openapi`].
+#[derive(Debug, Clone, Default)]
+pub struct ExportOptions {
+    /// `info.title` (default `Exported requests`).
+    pub title: Option<String>,
+    /// `info.version` (default `1.0.0`).
+    pub version: Option<String>,
+    /// HAR captures whose entries document responses; each entry is matched to an operation by
+    /// method and path.
+    pub har: Vec<PathBuf>,
+}
+
+/// The exported document and what went into it.
+#[derive(Debug, Clone)]
+pub struct ExportReport {
+    pub api: OpenAPI,
+    /// Request files (or `.http` requests) that became part of an operation.
+    pub requests: usize,
+    pub operations: usize,
+    /// HAR entries matched to an operation.
+    pub recorded: usize,
+    /// Files that were not exported, with the reason (not a request file, unsupported method, …).
+    pub skipped: Vec<(PathBuf, String)>,
+}
+
+/// Build an OpenAPI 3.0 document from the request files in `inputs` (files, or directories walked
+/// recursively). Files that do not load as request files are skipped and listed in the report.
+pub fn export_openapi(inputs: &[PathBuf], options: &ExportOptions) -> Result<ExportReport> {
+    let mut paths = Vec::new();
+    for input in inputs {
+        collect_files(input, &mut paths)?;
+    }
+
+    let mut skipped = Vec::new();
+    let mut servers: Vec<Server> = Vec::new();
+    let mut groups: Vec<Group> = Vec::new();
+    let mut requests = 0;
+    for path in paths {
+        let files = match RequestFile::from_file_all(&path) {
+            Ok(files) => files,
+            Err(err) => {
+                skipped.push((path, err.to_string()));
+                continue;
+            }
+        };
+        for file in files {
+            let method = file.request.method.to_ascii_uppercase();
+            if !is_openapi_method(&method) {
+                skipped.push((
+                    path.clone(),
+                    format!("method {method} has no OpenAPI equivalent"),
+                ));
+                continue;
+            }
+            let url = split_url(&file.request.url);
+            if let Some(server) = url.server {
+                if !servers.iter().any(|s| s.url == server.url) {
+                    servers.push(server);
+                }
+            }
+            let key = (method.clone(), url.key);
+            match groups
+                .iter_mut()
+                .find(|g| (&g.method, &g.key) == (&key.0, &key.1))
+            {
+                Some(group) => group.add(&url.segments, file),
+                None => {
+                    let mut group = Group {
+                        method,
+                        key: key.1,
+                        segments: url.segments.clone(),
+                        files: Vec::new(),
+                        path_shapes: Vec::new(),
+                        responses: BTreeMap::new(),
+                    };
+                    group.add(&url.segments, file);
+                    groups.push(group);
+                }
+            }
+            requests += 1;
+        }
+    }
+
+    let mut recorded = 0;
+    for har in &options.har {
+        let har: Har = read_json(har, Error::Har)?;
+        for entry in &har.log.entries {
+            if record_response(&mut groups, entry) {
+                recorded += 1;
+            }
+        }
+    }
+
+    let mut components = Components::default();
+    let mut operation_ids = HashSet::new();
+    let mut path_items: BTreeMap<String, PathItem> = BTreeMap::new();
+    for group in &groups {
+        let template = group.path_template();
+        let operation = group.operation(&mut operation_ids, &mut components);
+        let item = path_items.entry(template).or_default();
+        let slot = match group.method.as_str() {
+            "GET" => &mut item.get,
+            "PUT" => &mut item.put,
+            "POST" => &mut item.post,
+            "DELETE" => &mut item.delete,
+            "OPTIONS" => &mut item.options,
+            "HEAD" => &mut item.head,
+            "PATCH" => &mut item.patch,
+            _ => &mut item.trace,
+        };
+        *slot = Some(operation);
+    }
+
+    let mut api = OpenAPI {
+        openapi: "3.0.3".to_string(),
+        info: Info {
+            title: options
+                .title
+                .clone()
+                .unwrap_or_else(|| DEFAULT_TITLE.to_string()),
+            version: options
+                .version
+                .clone()
+                .unwrap_or_else(|| DEFAULT_VERSION.to_string()),
+            ..Default::default()
+        },
+        servers,
+        ..Default::default()
+    };
+    for (template, item) in path_items {
+        api.paths.paths.insert(template, ReferenceOr::Item(item));
+    }
+    if !components.security_schemes.is_empty() {
+        api.components = Some(components);
+    }
+
+    return Ok(ExportReport {
+        api,
+        requests,
+        operations: groups.len(),
+        recorded,
+        skipped,
+    });
+}
+
+/// Write `api` to `path`: JSON for a `.json` extension, YAML otherwise. Overwrites `path`.
+pub fn write_openapi(path: &Path, api: &OpenAPI) -> Result<()> {
+    let is_json = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("json"));
+    let text = if is_json {
+        serde_json::to_string_pretty(api)
+            .map(|t| t + "\n")
+            .map_err(|e| e.to_string())
+    } else {
+        serde_yaml::to_string(api).map_err(|e| e.to_string())
+    };
+    let text =
+        text.map_err(|e| Error::Export(format!("cannot serialize OpenAPI document: {e}")))?;
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        super::fs::create_dir(parent)?;
+    }
+    return std::fs::write(path, text).map_err(|source| Error::Io {
+        path: path.to_path_buf(),
+        source,
+    });
+}
+
+/// Request files under `input`, sorted per directory.
+fn collect_files(input: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    if !input.is_dir() {
+        if !input.exists() {
+            return Err(Error::Export(format!("{} does not exist", input.display())));
+        }
+        files.push(input.to_path_buf());
+        return Ok(());
+    }
+    let io_err = |source| Error::Io {
+        path: input.to_path_buf(),
+        source,
+    };
+    let mut entries = std::fs::read_dir(input)
+        .map_err(io_err)?
+        .map(|e| e.map(|e| e.path()))
+        .collect::<std::io::Result<Vec<_>>>()
+        .map_err(io_err)?;
+    entries.sort();
+    for path in entries {
+        if path.is_dir() {
+            collect_files(&path, files)?;
+            continue;
+        }
+        let ext = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or("")
+            .to_ascii_lowercase();
+        if REQUEST_EXTENSIONS.contains(&ext.as_str()) {
+            files.push(path);
+        }
+    }
+    return Ok(());
+}
+
+fn is_openapi_method(method: &str) -> bool {
+    return matches!(
+        method,
+        "GET" | "PUT" | "POST" | "DELETE" | "OPTIONS" | "HEAD" | "PATCH" | "TRACE"
+    );
+}
+
+/// One path segment of a request URL.
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Literal(String),
+    /// A whole-segment parameter: its name and the shape of the value seen.
+    Param(String, Shape),
+    /// A literal with `${VAR}` templates inside (`v${version}`), kept as `v{version}`.
+    Mixed(String, Vec<String>),
+}
+
+/// A request URL taken apart.
+struct SplitUrl {
+    server: Option<Server>,
+    segments: Vec<Segment>,
+    /// Grouping key: the path with every parameter as `{}`.
+    key: String,
+}
+
+fn split_url(raw: &str) -> SplitUrl {
+    let end = raw.find(['?', '#']).unwrap_or(raw.len());
+    let url = &raw[..end];
+
+    let path_start = if let Some(scheme_end) = url.find("://") {
+        let authority = scheme_end + 3;
+        url[authority..]
+            .find('/')
+            .map_or(url.len(), |i| authority + i)
+    } else if url.starts_with('/') {
+        0
+    } else {
+        url.find('/').unwrap_or(url.len())
+    };
+    let (server, path) = url.split_at(path_start);
+
+    let segments: Vec<Segment> = path
+        .split('/')
+        .filter(|s| !s.is_empty())
+        .scan(None::<String>, |previous, segment| {
+            let parsed = segment_of(segment, previous.as_deref());
+            *previous = Some(segment.to_string());
+            Some(parsed)
+        })
+        .collect();
+    let key = segments
+        .iter()
+        .map(|s| match s {
+            Segment::Literal(text) => text.clone(),
+            Segment::Param(..) => "{}".to_string(),
+            Segment::Mixed(text, _) => placeholder_span_re().replace_all(text, "{}").into_owned(),
+        })
+        .collect::<Vec<_>>()
+        .join("/");
+
+    return SplitUrl {
+        server: server_of(server.trim_end_matches('/')),
+        segments,
+        key: format!("/{key}"),
+    };
+}
+
+/// Query pairs written into the URL itself (`/users?page=2`).
+fn url_query(raw: &str) -> Vec<(String, String)> {
+    let Some((_, query)) = raw.split_once('?') else {
+        return Vec::new();
+    };
+    let query = query.split('#').next().unwrap_or_default();
+    return query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (name.to_string(), value.to_string())
+        })
+        .collect();
+}
+
+/// `${x}` / `${json:x}` → `x`; `None` for dynamic templates (`${!uuidv4}`).
+fn template_var(inner: &str) -> Option<String> {
+    if inner.starts_with('!') {
+        return None;
+    }
+    let name = inner.strip_prefix("json:").unwrap_or(inner);
+    return Some(var_ident(name));
+}
+
+fn segment_of(segment: &str, previous: Option<&str>) -> Segment {
+    let id_name = || match previous {
+        Some(prev) if !prev.contains("${") => format!("{}_id", singular(&var_ident(prev))),
+        _ => "id".to_string(),
+    };
+    if let Some(caps) = placeholder_span_re().captures(segment) {
+        let whole = caps.get(0).is_some_and(|m| m.as_str() == segment);
+        if whole {
+            return match template_var(&caps[1]) {
+                Some(name) => Segment::Param(name, Shape::Unknown),
+                None => Segment::Param(id_name(), Shape::String(string_format(segment))),
+            };
+        }
+        let mut names = Vec::new();
+        let text = placeholder_span_re().replace_all(segment, |caps: &regex::Captures| {
+            let name = template_var(&caps[1]).unwrap_or_else(id_name);
+            names.push(name.clone());
+            format!("${{{name}}}")
+        });
+        return Segment::Mixed(text.into_owned(), names);
+    }
+    if segment.parse::<u64>().is_ok() {
+        return Segment::Param(id_name(), Shape::Integer);
+    }
+    if string_format(segment) == Some("uuid") {
+        return Segment::Param(id_name(), Shape::String(Some("uuid")));
+    }
+    return Segment::Literal(segment.to_string());
+}
+
+/// `users` → `user`, `categories` → `category` (good enough for parameter names).
+fn singular(word: &str) -> String {
+    if let Some(stem) = word.strip_suffix("ies") {
+        return format!("{stem}y");
+    }
+    if word.ends_with("ss") {
+        return word.to_string();
+    }
+    return word.strip_suffix('s').unwrap_or(word).to_string();
+}
+
+/// `https://${HOST}/v1` → server `https://{HOST}/v1` with a `HOST` variable.
+fn server_of(server: &str) -> Option<Server> {
+    if server.is_empty() {
+        return None;
+    }
+    let mut variables: Vec<String> = Vec::new();
+    let url = placeholder_span_re().replace_all(server, |caps: &regex::Captures| {
+        let name = var_ident(caps[1].trim_start_matches('!'));
+        if !variables.contains(&name) {
+            variables.push(name.clone());
+        }
+        format!("{{{name}}}")
+    });
+    let mut server = Server {
+        url: url.into_owned(),
+        description: None,
+        variables: None,
+        extensions: Default::default(),
+    };
+    for name in variables {
+        let description = format!("`${{{name}}}` in the exported request files");
+        server
+            .variables
+            .get_or_insert_with(Default::default)
+            .insert(
+                name,
+                ServerVariable {
+                    enumeration: Vec::new(),
+                    default: String::new(),
+                    description: Some(description),
+                    extensions: Default::default(),
+                },
+            );
+    }
+    return Some(server);
+}
+
+/// What the HAR entries recorded for one status code.
+#[derive(Default)]
+struct Recorded {
+    description: String,
+    /// Media types (essence) with the shape of their JSON bodies.
+    content: Vec<(String, Shape)>,
+}
+
+/// Requests that share a method and templated path.
+struct Group {
+    method: String,
+    key: String,
+    /// Segments of the first request (parameter names come from it).
+    segments: Vec<Segment>,
+    files: Vec<RequestFile>,
+    /// Per segment, the merged shape of every request's value there.
+    path_shapes: Vec<Shape>,
+    responses: BTreeMap<u16, Recorded>,
+}
+
+impl Group {
+    fn add(&mut self, segments: &[Segment], file: RequestFile) {
+        if self.path_shapes.is_empty() {
+            self.path_shapes = vec![Shape::Unknown; segments.len()];
+        }
+        for (merged, segment) in self.path_shapes.iter_mut().zip(segments) {
+            if let Segment::Param(_, shape) = segment {
+                *merged = std::mem::replace(merged, Shape::Unknown).merge(shape.clone());
+            }
+        }
+        self.files.push(file);
+    }
+
+    /// Parameter names for each segment, made unique within the path.
+    fn param_names(&self) -> Vec<Vec<String>> {
+        let mut seen: HashSet<String> = HashSet::new();
+        let mut unique = |name: &String| {
+            let mut candidate = name.clone();
+            let mut n = 2;
+            while !seen.insert(candidate.clone()) {
+                candidate = format!("{name}_{n}");
+                n += 1;
+            }
+            candidate
+        };
+        return self
+            .segments
+            .iter()
+            .map(|s| match s {
+                Segment::Literal(_) => Vec::new(),
+                Segment::Param(name, _) => vec![unique(name)],
+                Segment::Mixed(_, names) => names.iter().map(&mut unique).collect(),
+            })
+            .collect();
+    }
+
+    fn path_template(&self) -> String {
+        let names = self.param_names();
+        let segments: Vec<String> = self
+            .segments
+            .iter()
+            .zip(&names)
+            .map(|(segment, names)| match segment {
+                Segment::Literal(text) => text.clone(),
+                Segment::Param(..) => format!("{{{}}}", names[0]),
+                Segment::Mixed(text, _) => {
+                    let mut names = names.iter();
+                    placeholder_span_re()
+                        .replace_all(text, |_: &regex::Captures| {
+                            format!("{{{}}}", names.next().map_or("", String::as_str))
+                        })
+                        .into_owned()
+                }
+            })
+            .collect();
+        return format!("/{}", segments.join("/"));
+    }
+
+    fn operation(
+        &self,
+        operation_ids: &mut HashSet<String>,
+        components: &mut Components,
+    ) -> Operation {
+        let first = &self.files[0];
+        let mut operation = Operation {
+            operation_id: Some(self.operation_id(operation_ids)),
+            summary: self
+                .files
+                .iter()
+                .find_map(|f| f.request.summary.clone())
+                .or_else(|| first.name.clone()),
+            description: self
+                .files
+                .iter()
+                .find_map(|f| f.request.description.clone()),
+            deprecated: self.files.iter().all(|f| f.request.deprecated),
+            ..Default::default()
+        };
+        for file in &self.files {
+            for tag in &file.request.tags {
+                if !operation.tags.contains(tag) {
+                    operation.tags.push(tag.clone());
+                }
+            }
+        }
+
+        // Path parameters.
+        for ((segment, names), shape) in self
+            .segments
+            .iter()
+            .zip(self.param_names())
+            .zip(&self.path_shapes)
+        {
+            let shape = match segment {
+                Segment::Param(..) => shape.clone(),
+                _ => Shape::Unknown,
+            };
+            for name in names {
+                operation
+                    .parameters
+                    .push(ReferenceOr::Item(Parameter::Path {
+                        parameter_data: parameter_data(name, true, &shape),
+                        style: Default::default(),
+                    }));
+            }
+        }
+
+        // Query parameters: union, required when every request sends them.
+        for (name, shape, required) in self.collect_params(|f| {
+            let mut pairs = url_query(&f.request.url);
+            pairs.extend(f.request.query.iter().map(|(k, v)| (k.clone(), v.clone())));
+            pairs
+        }) {
+            operation
+                .parameters
+                .push(ReferenceOr::Item(Parameter::Query {
+                    parameter_data: parameter_data(name, required, &shape),
+                    allow_reserved: false,
+                    style: Default::default(),
+                    allow_empty_value: None,
+                }));
+        }
+
+        // Headers: credentials become security schemes, the rest parameters.
+        let mut security: Vec<SecurityRequirement> = Vec::new();
+        for file in &self.files {
+            for (name, value) in file.request.headers.iter() {
+                let Some((scheme_name, scheme)) = security_scheme(name, value) else {
+                    continue;
+                };
+                components
+                    .security_schemes
+                    .entry(scheme_name.clone())
+                    .or_insert(ReferenceOr::Item(scheme));
+                if !security.iter().any(|r| r.contains_key(&scheme_name)) {
+                    let mut requirement = SecurityRequirement::default();
+                    requirement.insert(scheme_name, Vec::new());
+                    security.push(requirement);
+                }
+            }
+        }
+        if !security.is_empty() {
+            operation.security = Some(security);
+        }
+        for (name, shape, required) in self.collect_params(|f| {
+            f.request
+                .headers
+                .iter()
+                .filter(|(k, v)| {
+                    let lower = k.to_ascii_lowercase();
+                    !SKIPPED_HEADERS.contains(&lower.as_str()) && security_scheme(k, v).is_none()
+                })
+                .map(|(k, v)| (k.clone(), v.clone()))
+                .collect()
+        }) {
+            operation
+                .parameters
+                .push(ReferenceOr::Item(Parameter::Header {
+                    parameter_data: parameter_data(name, required, &shape),
+                    style: Default::default(),
+                }));
+        }
+
+        operation.request_body = self.request_body().map(ReferenceOr::Item);
+        operation.responses = self.responses();
+        return operation;
+    }
+
+    /// `openapi.operation_id` of a request, else its name in camelCase, else `method-path`;
+    /// unique across the document.
+    fn operation_id(&self, used: &mut HashSet<String>) -> String {
+        let linked = self
+            .files
+            .iter()
+            .find_map(|f| f.openapi.as_ref().map(|l| l.operation_id.clone()));
+        let named = || {
+            self.files
+                .iter()
+                .find_map(|f| f.name.as_deref().map(camel_case))
+                .filter(|n| !n.is_empty())
+        };
+        let base = linked.or_else(named).unwrap_or_else(|| {
+            let slug = self
+                .path_template()
+                .split(|c: char| !c.is_ascii_alphanumeric())
+                .filter(|s| !s.is_empty())
+                .collect::<Vec<_>>()
+                .join("-")
+                .to_ascii_lowercase();
+            format!("{}-{slug}", self.method.to_ascii_lowercase())
+        });
+        let mut candidate = base.clone();
+        let mut n = 2;
+        while !used.insert(candidate.clone()) {
+            candidate = format!("{base}{n}");
+            n += 1;
+        }
+        return candidate;
+    }
+
+    /// Names across all requests in first-seen order, with the merged shape of their values and
+    /// whether every request has them. Names compare case-insensitively (headers).
+    fn collect_params(
+        &self,
+        pairs: impl Fn(&RequestFile) -> Vec<(String, String)>,
+    ) -> Vec<(String, Shape, bool)> {
+        let mut params: Vec<(String, Shape, usize)> = Vec::new();
+        for file in &self.files {
+            let mut seen_here: HashSet<String> = HashSet::new();
+            for (name, value) in pairs(file) {
+                let lower = name.to_ascii_lowercase();
+                let shape = Shape::of_text(&value);
+                match params
+                    .iter_mut()
+                    .find(|(n, _, _)| n.eq_ignore_ascii_case(&name))
+                {
+                    Some((_, merged, count)) => {
+                        *merged = std::mem::replace(merged, Shape::Unknown).merge(shape);
+                        if seen_here.insert(lower) {
+                            *count += 1;
+                        }
+                    }
+                    None => {
+                        seen_here.insert(lower);
+                        params.push((name, shape, 1));
+                    }
+                }
+            }
+        }
+        return params
+            .into_iter()
+            .map(|(name, shape, count)| (name, shape, count == self.files.len()))
+            .collect();
+    }
+
+    fn request_body(&self) -> Option<BodyObject> {
+        let mut content: Vec<(String, Shape, Option<Value>)> = Vec::new();
+        let mut with_body = 0;
+        for file in &self.files {
+            let Some(body) = &file.request.body else {
+                continue;
+            };
+            let content_type = file
+                .request
+                .headers
+                .iter()
+                .find(|(k, _)| k.eq_ignore_ascii_case("content-type"))
+                .map(|(_, v)| essence(v))
+                .or_else(|| file.request.get_contenttype_hint().map(essence));
+            let Some(content_type) = content_type else {
+                continue;
+            };
+            with_body += 1;
+            let (shape, sample) = body_shape(body, &content_type);
+            let example = sample.filter(|v| !v.to_string().contains("${"));
+            match content.iter_mut().find(|(t, _, _)| *t == content_type) {
+                Some((_, merged, first_example)) => {
+                    *merged = std::mem::replace(merged, Shape::Unknown).merge(shape);
+                    if first_example.is_none() {
+                        *first_example = example;
+                    }
+                }
+                None => content.push((content_type, shape, example)),
+            }
+        }
+        if content.is_empty() {
+            return None;
+        }
+        let mut body = BodyObject {
+            required: with_body == self.files.len(),
+            ..Default::default()
+        };
+        for (content_type, shape, example) in content {
+            body.content.insert(
+                content_type,
+                MediaType {
+                    schema: Some(ReferenceOr::Item(shape.to_schema())),
+                    example,
+                    ..Default::default()
+                },
+            );
+        }
+        return Some(body);
+    }
+
+    fn responses(&self) -> Responses {
+        let mut responses = Responses::default();
+        if self.responses.is_empty() {
+            responses.default = Some(ReferenceOr::Item(Response {
+                description: "Response not recorded".to_string(),
+                ..Default::default()
+            }));
+            return responses;
+        }
+        for (status, recorded) in &self.responses {
+            let mut response = Response {
+                description: recorded.description.clone(),
+                ..Default::default()
+            };
+            for (media, shape) in &recorded.content {
+                let schema =
+                    (*shape != Shape::Unknown).then(|| ReferenceOr::Item(shape.to_schema()));
+                response.content.insert(
+                    media.clone(),
+                    MediaType {
+                        schema,
+                        ..Default::default()
+                    },
+                );
+            }
+            responses
+                .responses
+                .insert(StatusCode::Code(*status), ReferenceOr::Item(response));
+        }
+        return responses;
+    }
+}
+
+/// Shape of a request body sent as `content_type`, and the JSON value it came from.
+fn body_shape(body: &RequestBody, content_type: &str) -> (Shape, Option<Value>) {
+    let json = |value: &Value| (Shape::of(value), Some(value.clone()));
+    return match body {
+        RequestBody::Json(value) => json(value),
+        RequestBody::Structured(s) => match s.body_type {
+            RequestBodyKind::Json | RequestBodyKind::Graphql => json(&s.content),
+            RequestBodyKind::XWwwFormUrlencoded => {
+                let text = s.content.as_str().unwrap_or_default();
+                (form_shape(text), None)
+            }
+            RequestBodyKind::Binary => (Shape::String(Some("binary")), None),
+            _ => (Shape::String(None), None),
+        },
+        RequestBody::Text(text) if is_json(content_type) => match serde_json::from_str(text) {
+            Ok(value) => json(&value),
+            Err(_) => (Shape::Unknown, None),
+        },
+        RequestBody::Text(text) if content_type == "application/x-www-form-urlencoded" => {
+            (form_shape(text), None)
+        }
+        RequestBody::Text(_) => (Shape::String(None), None),
+    };
+}
+
+/// `a=1&b=x` → an object with one property per field.
+fn form_shape(text: &str) -> Shape {
+    let properties = text
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (name.to_string(), Shape::of_text(value), true)
+        })
+        .collect();
+    return Shape::Object(properties);
+}
+
+/// Lowercased media type without parameters.
+fn essence(content_type: &str) -> String {
+    let essence = content_type.split(';').next().unwrap_or_default();
+    return essence.trim().to_ascii_lowercase();
+}
+
+fn is_json(content_type: &str) -> bool {
+    return content_type == "application/json" || content_type.ends_with("+json");
+}
+
+/// Security scheme a credential header stands for: `Authorization: Bearer …` / `Basic …`, or an
+/// API-key header (`X-Api-Key`).
+fn security_scheme(name: &str, value: &str) -> Option<(String, SecurityScheme)> {
+    let lower = name.to_ascii_lowercase();
+    if lower == "authorization" {
+        let scheme = value.split_whitespace().next().unwrap_or_default();
+        let (key, scheme) = match scheme.to_ascii_lowercase().as_str() {
+            "bearer" => ("bearerAuth", "bearer"),
+            "basic" => ("basicAuth", "basic"),
+            _ => {
+                return Some((
+                    "authorization".to_string(),
+                    api_key_scheme(name.to_string()),
+                ))
+            }
+        };
+        return Some((
+            key.to_string(),
+            SecurityScheme::HTTP {
+                scheme: scheme.to_string(),
+                bearer_format: None,
+                description: None,
+                extensions: Default::default(),
+            },
+        ));
+    }
+    if API_KEY_HEADERS.contains(&lower.as_str()) {
+        return Some((var_ident(name), api_key_scheme(name.to_string())));
+    }
+    return None;
+}
+
+fn api_key_scheme(header: String) -> SecurityScheme {
+    return SecurityScheme::APIKey {
+        location: APIKeyLocation::Header,
+        name: header,
+        description: None,
+        extensions: Default::default(),
+    };
+}
+
+fn parameter_data(name: String, required: bool, shape: &Shape) -> ParameterData {
+    // Values that were all templates still travel as text.
+    let shape = match shape {
+        Shape::Unknown => Shape::String(None),
+        other => other.clone(),
+    };
+    return ParameterData {
+        name,
+        description: None,
+        required,
+        deprecated: None,
+        format: ParameterSchemaOrContent::Schema(ReferenceOr::Item(shape.to_schema())),
+        example: None,
+        examples: Default::default(),
+        explode: None,
+        extensions: Default::default(),
+    };
+}
+
+/// `Get user by id` / `get-user_by id` → `getUserById`.
+fn camel_case(name: &str) -> String {
+    let mut out = String::new();
+    for word in name
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|w| !w.is_empty())
+    {
+        let mut chars = word.chars();
+        let Some(first) = chars.next() else {
+            continue;
+        };
+        if out.is_empty() {
+            out.push(first.to_ascii_lowercase());
+        } else {
+            out.push(first.to_ascii_uppercase());
+        }
+        out.push_str(chars.as_str());
+    }
+    return out;
+}
+
+/// Match a HAR entry to the operation with the same method whose path is a suffix of the entry's
+/// (parameters match any segment; the most literal matches win) and record its response.
+fn record_response(groups: &mut [Group], entry: &nd_core::har::Entry) -> bool {
+    let Ok(url) = url::Url::parse(&entry.request.url) else {
+        return false;
+    };
+    let method = entry.request.method.to_ascii_uppercase();
+    let segments: Vec<String> = url
+        .path_segments()
+        .map(|s| s.filter(|s| !s.is_empty()).map(str::to_string).collect())
+        .unwrap_or_default();
+
+    let mut best: Option<(usize, usize, usize)> = None;
+    for (i, group) in groups.iter().enumerate() {
+        if group.method != method || group.segments.len() > segments.len() {
+            continue;
+        }
+        let tail = &segments[segments.len() - group.segments.len()..];
+        let mut literal = 0;
+        let matches = group
+            .segments
+            .iter()
+            .zip(tail)
+            .all(|(want, got)| match want {
+                Segment::Literal(text) => {
+                    literal += 1;
+                    text == got
+                }
+                Segment::Param(..) | Segment::Mixed(..) => true,
+            });
+        if !matches {
+            continue;
+        }
+        let score = (literal, group.segments.len(), i);
+        let better = best.is_none_or(|(l, n, _)| (literal, group.segments.len()) > (l, n));
+        if better {
+            best = Some(score);
+        }
+    }
+    let Some((_, _, index)) = best else {
+        return false;
+    };
+
+    let response = &entry.response;
+    let recorded = groups[index].responses.entry(response.status).or_default();
+    if recorded.description.is_empty() {
+        recorded.description = match response.status_text.trim() {
+            "" => format!("Status {}", response.status),
+            text => text.to_string(),
+        };
+    }
+    let media = essence(&response.content.mime_type);
+    if media.is_empty() {
+        return true;
+    }
+    let shape = match &response.content.text {
+        Some(text) if is_json(&media) && response.content.encoding.is_none() => {
+            serde_json::from_str::<Value>(text)
+                .map(|v| Shape::of(&v))
+                .unwrap_or(Shape::Unknown)
+        }
+        _ => Shape::Unknown,
+    };
+    match recorded.content.iter_mut().find(|(m, _)| *m == media) {
+        Some((_, merged)) => *merged = std::mem::replace(merged, Shape::Unknown).merge(shape),
+        None => recorded.content.push((media, shape)),
+    }
+    return true;
+}
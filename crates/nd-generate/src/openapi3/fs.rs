@@ -1,128 +1,127 @@
-//! Emit [`nd_core::RequestFile`] values to disk, with YAML quoting for `$` in URLs.
+//! Emit one request file per OpenAPI operation, plus a `.env.example` for the variables they use.
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
-use nd_constants::urls::{PUBLIC_REQUEST_JSON_SCHEMA_URL, PUBLIC_REQUEST_YAML_SCHEMA_URL};
 use nd_core::model::request::RequestFile;
-use nd_core::model::with_root_schema_url;
-use openapiv3::{OpenAPI, ReferenceOr};
+use openapiv3::OpenAPI;
 
-use super::build::{file_stem, operation_to_request_file, unique_stem};
+use super::build::{file_stem, operation_to_request_file};
+use super::env::env_example;
+use super::resolve::Refs;
 use crate::error::{Error, Result};
+use crate::output::{sanitize_stem, unique_stem, write_request_file, OutputFormat};
 
-/// Serialization format for generated request definitions.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
-pub enum OutputFormat {
-    #[default]
-    Yaml,
-    Json,
-}
-
-impl std::str::FromStr for OutputFormat {
-    type Err = String;
+/// Variables file written next to the generated requests.
+const ENV_EXAMPLE_FILE: &str = ".env.example";
 
-    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-        return match s.to_ascii_lowercase().as_str() {
-            "yaml" | "yml" => Ok(Self::Yaml),
-            "json" => Ok(Self::Json),
-            other => Err(format!("expected yaml or json, got {other}")),
-        };
-    }
+/// Generate one file per operation under `out_dir`.
+pub fn write_all_operations(
+    api: &OpenAPI,
+    out_dir: &Path,
+    format: OutputFormat,
+) -> Result<Vec<PathBuf>> {
+    let written = write_operations(api, out_dir, format, false, None)?;
+    return Ok(written.into_iter().map(|(path, _)| path).collect());
 }
 
-impl OutputFormat {
-    fn extension(self) -> &'static str {
-        return match self {
-            Self::Yaml => "yaml",
-            Self::Json => "json",
-        };
-    }
+/// Write `out_dir/.env.example` listing the variables `files` use; `None` when there are none.
+pub fn write_env_example<'a>(
+    api: &OpenAPI,
+    files: impl IntoIterator<Item = &'a RequestFile>,
+    out_dir: &Path,
+) -> Result<Option<PathBuf>> {
+    let Some(text) = env_example(api, files)? else {
+        return Ok(None);
+    };
+    let path = out_dir.join(ENV_EXAMPLE_FILE);
+    std::fs::write(&path, text).map_err(|source| Error::Io {
+        path: path.clone(),
+        source,
+    })?;
+    return Ok(Some(path));
 }
 
-/// YAML 1.1 treats `$` in plain scalars as alias syntax; quote `url:` values that contain `${...}`.
-fn quote_yaml_urls_containing_dollar(text: &str) -> String {
-    let re = regex::Regex::new(r"^(?P<prefix>\s+url:\s+)(?P<val>.+)$").expect("valid regex");
-    let mut out = String::with_capacity(text.len() + 16);
-    for line in text.lines() {
-        if let Some(cap) = re.captures(line) {
-            let val = cap.name("val").expect("val").as_str();
-            if val.contains("${") && !(val.starts_with('"') || val.starts_with('\'')) {
-                let prefix = cap.name("prefix").expect("prefix").as_str();
-                let escaped = val.replace('\\', "\\\\").replace('"', "\\\"");
-                out.push_str(prefix);
-                out.push('"');
-                out.push_str(&escaped);
-                out.push('"');
-                out.push('\n');
-                continue;
-            }
-        }
-        out.push_str(line);
-        out.push('\n');
-    }
-    return out;
+/// Where an operation's request file goes, before a unique file name is picked.
+pub(crate) struct PlannedOperation {
+    /// `out_dir`, or the tag subdirectory with `by_tag`.
+    pub dir: PathBuf,
+    /// File stem from [`file_stem`], not yet made unique.
+    pub stem: String,
+    pub file: RequestFile,
 }
 
-/// Serializes `file` and writes it to `path`, creating parent directories when needed.
-pub fn write_request_file(path: &Path, file: &RequestFile, format: OutputFormat) -> Result<()> {
-    let schema_url = match format {
-        OutputFormat::Yaml => PUBLIC_REQUEST_YAML_SCHEMA_URL,
-        OutputFormat::Json => PUBLIC_REQUEST_JSON_SCHEMA_URL,
-    };
-    let value = serde_json::to_value(file).map_err(Error::Json)?;
-    let value = with_root_schema_url(value, schema_url);
-    let data = match format {
-        OutputFormat::Yaml => {
-            let raw = serde_yaml::to_string(&value).map_err(Error::Yaml)?;
-            quote_yaml_urls_containing_dollar(&raw)
+/// Request file for every operation, in document order. With `by_tag`, each operation goes into a
+/// subdirectory named after its first tag (untagged ones stay in `out_dir`).
+pub(crate) fn plan_operations(
+    api: &OpenAPI,
+    out_dir: &Path,
+    by_tag: bool,
+) -> Result<Vec<PlannedOperation>> {
+    let refs = Refs::new(api);
+    let mut planned = Vec::new();
+    for (path_str, path_ref) in api.paths.iter() {
+        let path_item = refs.path_item(path_ref)?;
+
+        for (method, operation) in path_item.iter() {
+            let file = operation_to_request_file(api, path_str, method, operation, path_item)?;
+            let dir = match operation.tags.first() {
+                Some(tag) if by_tag => out_dir.join(sanitize_stem(tag)),
+                _ => out_dir.to_path_buf(),
+            };
+            planned.push(PlannedOperation {
+                dir,
+                stem: file_stem(operation, method, path_str),
+                file,
+            });
         }
-        OutputFormat::Json => serde_json::to_string_pretty(&value).map_err(Error::Json)?,
-    };
-    if let Some(parent) = path.parent() {
-        std::fs::create_dir_all(parent).map_err(|source| Error::Io {
-            path: parent.to_path_buf(),
-            source,
-        })?;
     }
-    std::fs::write(path, data).map_err(|source| Error::Io {
-        path: path.to_path_buf(),
-        source,
-    })?;
-    return Ok(());
+    return Ok(planned);
 }
 
-/// Generate one file per operation under `out_dir`.
-pub fn write_all_operations(
+/// Written files with the request each holds (see [`plan_operations`] for the layout). With
+/// `spec`, each file links to it (see [`link_spec`]).
+pub(crate) fn write_operations(
     api: &OpenAPI,
     out_dir: &Path,
     format: OutputFormat,
-) -> Result<Vec<PathBuf>> {
-    std::fs::create_dir_all(out_dir).map_err(|source| Error::Io {
-        path: out_dir.to_path_buf(),
-        source,
-    })?;
+    by_tag: bool,
+    spec: Option<&Path>,
+) -> Result<Vec<(PathBuf, RequestFile)>> {
+    create_dir(out_dir)?;
 
-    let mut used_stems = HashSet::new();
+    let mut used_stems: HashMap<PathBuf, HashSet<String>> = HashMap::new();
     let mut written = Vec::new();
-
-    for (path_str, path_ref) in api.paths.iter() {
-        let path_item = match path_ref {
-            ReferenceOr::Item(item) => item,
-            ReferenceOr::Reference { reference } => {
-                return Err(Error::PathItemRef(reference.clone()));
-            }
-        };
-
-        for (method, operation) in path_item.iter() {
-            let req = operation_to_request_file(api, path_str, method, operation, path_item)?;
-            let stem = file_stem(operation, method, path_str);
-            let unique = unique_stem(&stem, &mut used_stems);
-            let out_path = out_dir.join(format!("{unique}.{}", format.extension()));
-            write_request_file(&out_path, &req, format)?;
-            written.push(out_path);
-        }
+    for mut op in plan_operations(api, out_dir, by_tag)? {
+        let unique = unique_stem(&op.stem, used_stems.entry(op.dir.clone()).or_default());
+        create_dir(&op.dir)?;
+        let out_path = op.dir.join(format!("{unique}.{}", format.extension()));
+        link_spec(&mut op.file, &out_path, spec);
+        write_request_file(&out_path, &op.file, format)?;
+        written.push((out_path, op.file));
     }
 
     return Ok(written);
 }
+
+/// Point `file`'s `openapi.spec` at `spec`, relative to `path`'s directory so the link survives
+/// moving the spec and the requests together. The directory must exist.
+pub(crate) fn link_spec(file: &mut RequestFile, path: &Path, spec: Option<&Path>) {
+    let (Some(link), Some(spec)) = (file.openapi.as_mut(), spec) else {
+        return;
+    };
+    let dir = path.parent().unwrap_or(Path::new("."));
+    let dir = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+    let relative = pathdiff::diff_paths(spec, &dir).unwrap_or_else(|| spec.to_path_buf());
+    link.spec = Some(relative.to_string_lossy().replace('\\', "/"));
+}
+
+pub(crate) fn create_dir(dir: &Path) -> Result<()> {
+    if dir.is_dir() {
+        return Ok(());
+    }
+    return std::fs::create_dir_all(dir).map_err(|source| Error::Io {
+        path: dir.to_path_buf(),
+        source,
+    });
+}
@@ -0,0 +1,194 @@
+//! `index.rhai` scripts for generated request directories.
+//!
+//! Each directory gets an index that imports every request file in it as a module, and the top
+//! index also imports each tag directory's index, so one `import "index.rhai" as api;` exposes
+//! `api::users::get_user::invoke(...)` (or `api::get_user::invoke(...)` for a flat layout).
+
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use crate::error::{Error, Result};
+
+/// File name of the generated index scripts.
+pub(crate) const INDEX_FILE: &str = "index.rhai";
+
+/// Words Rhai reserves, which cannot be used as an import alias.
+const RHAI_RESERVED: &[&str] = &[
+    "true",
+    "false",
+    "let",
+    "const",
+    "if",
+    "else",
+    "switch",
+    "do",
+    "while",
+    "until",
+    "loop",
+    "for",
+    "in",
+    "continue",
+    "break",
+    "return",
+    "throw",
+    "try",
+    "catch",
+    "import",
+    "export",
+    "as",
+    "global",
+    "private",
+    "fn",
+    "call",
+    "curry",
+    "this",
+    "type_of",
+    "print",
+    "debug",
+    "eval",
+    "var",
+    "static",
+    "shared",
+    "goto",
+    "exit",
+    "match",
+    "case",
+    "public",
+    "protected",
+    "new",
+    "use",
+    "with",
+    "module",
+    "package",
+    "super",
+    "spawn",
+    "thread",
+    "go",
+    "sync",
+    "async",
+    "await",
+    "yield",
+    "default",
+    "void",
+    "null",
+    "nil",
+    "is",
+    "Fn",
+];
+
+/// Write `index.rhai` into `out_dir` and every directory below it that received a request file.
+/// `files` are the generated request files, in generation order.
+pub(crate) fn write_index_scripts(
+    title: &str,
+    out_dir: &Path,
+    files: &[PathBuf],
+) -> Result<Vec<PathBuf>> {
+    let mut dirs: Vec<(PathBuf, Vec<String>)> = vec![(out_dir.to_path_buf(), Vec::new())];
+    for file in files {
+        let dir = file.parent().unwrap_or(out_dir).to_path_buf();
+        let Some(name) = file.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        match dirs.iter_mut().find(|(d, _)| *d == dir) {
+            Some((_, names)) => names.push(name.to_string()),
+            None => dirs.push((dir, vec![name.to_string()])),
+        }
+    }
+
+    // Request imports per directory, then the top index also imports each subdirectory's.
+    let mut indexes: Vec<(PathBuf, Vec<(String, String)>)> = dirs
+        .into_iter()
+        .map(|(dir, names)| {
+            let mut used = HashSet::new();
+            let imports = names
+                .into_iter()
+                .map(|name| {
+                    let stem = Path::new(&name).file_stem().and_then(|s| s.to_str());
+                    let alias = alias(stem.unwrap_or(&name), &mut used);
+                    (name, alias)
+                })
+                .collect();
+            (dir, imports)
+        })
+        .collect();
+    let mut used: HashSet<String> = indexes[0].1.iter().map(|(_, a)| a.clone()).collect();
+    let mut subdirs = Vec::new();
+    for (dir, imports) in indexes.iter().skip(1) {
+        let rel = dir.strip_prefix(out_dir).unwrap_or(dir);
+        let rel = rel.to_string_lossy().replace('\\', "/");
+        let alias = alias(&rel, &mut used);
+        let example = imports
+            .first()
+            .map(|(_, first)| format!("{alias}::{first}"));
+        subdirs.push((format!("{rel}/{INDEX_FILE}"), alias, example));
+    }
+    let root_example = subdirs
+        .first()
+        .and_then(|(_, _, example)| example.clone())
+        .or_else(|| indexes[0].1.first().map(|(_, a)| a.clone()));
+    let root_imports = &mut indexes[0].1;
+    for (i, (path, alias, _)) in subdirs.into_iter().enumerate() {
+        root_imports.insert(i, (path, alias));
+    }
+
+    let mut written = Vec::new();
+    for (i, (dir, imports)) in indexes.iter().enumerate() {
+        if imports.is_empty() {
+            continue;
+        }
+        let (module, import_path, example) = if i == 0 {
+            (
+                "api".to_string(),
+                INDEX_FILE.to_string(),
+                root_example.clone(),
+            )
+        } else {
+            let rel = dir.strip_prefix(out_dir).unwrap_or(dir);
+            let rel = rel.to_string_lossy().replace('\\', "/");
+            let module = alias(&rel, &mut HashSet::new());
+            let example = imports.first().map(|(_, a)| a.clone());
+            (module, format!("{rel}/{INDEX_FILE}"), example)
+        };
+
+        let mut text = format!(
+            "// Request modules generated from {title} by `nativedoctor generate`.\n// import \"{import_path}\" as {module};"
+        );
+        if let Some(example) = example {
+            let _ = write!(text, " then {module}::{example}::invoke(#{{ ... }})");
+        }
+        text.push_str("\n\n");
+        for (path, alias) in imports {
+            let _ = writeln!(text, "import \"{path}\" as {alias};");
+        }
+
+        let path = dir.join(INDEX_FILE);
+        std::fs::write(&path, text).map_err(|source| Error::Io {
+            path: path.clone(),
+            source,
+        })?;
+        written.push(path);
+    }
+    return Ok(written);
+}
+
+/// Rhai identifier for a file stem or directory (`get-user` → `get_user`), unique within `used`.
+fn alias(stem: &str, used: &mut HashSet<String>) -> String {
+    let mut name: String = stem
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+        name.insert(0, '_');
+    }
+    if RHAI_RESERVED.contains(&name.as_str()) {
+        name.push('_');
+    }
+    let mut candidate = name.clone();
+    let mut n = 2;
+    while !used.insert(candidate.clone()) {
+        candidate = format!("{name}_{n}");
+        n += 1;
+    }
+    return candidate;
+}
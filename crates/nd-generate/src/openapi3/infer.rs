@@ -0,0 +1,218 @@
+//! Schemas inferred from sample values, for [`super::export`].
+//!
+//! Samples fold into a [`Shape`]: objects collect every property seen (required when every sample
+//! has it), array items merge, integers widen to numbers, and `null` makes a shape nullable.
+//! Strings get a `format` when every sample is a UUID, date-time, date or email, or the dynamic
+//! template that produces one (`${!uuidv4}`, `${!iso_timestamp}`, `${!random_email}`). Other
+//! `${VAR}` strings stay plain strings, while a whole-value `"${json:VAR}"` says nothing about the
+//! type. Samples that disagree give an untyped schema.
+
+use std::sync::OnceLock;
+
+use openapiv3::{
+    AnySchema, ArrayType, BooleanType, IntegerType, NumberType, ObjectType, ReferenceOr, Schema,
+    SchemaData, SchemaKind, StringFormat, StringType, Type, VariantOrUnknownOrEmpty,
+};
+use regex::Regex;
+use serde_json::Value;
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Shape {
+    /// Nothing constrains it yet (no samples, empty arrays, `${json:VAR}`).
+    Unknown,
+    /// Samples disagree on the type.
+    Mixed,
+    Null,
+    Boolean,
+    Integer,
+    Number,
+    /// With a `format` every sample had.
+    String(Option<&'static str>),
+    Array(Box<Shape>),
+    /// Properties in first-seen order, each with whether every sample had it.
+    Object(Vec<(String, Shape, bool)>),
+    Nullable(Box<Shape>),
+}
+
+fn json_placeholder_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    return RE.get_or_init(|| Regex::new(r"^\$\{json:[A-Za-z_][A-Za-z0-9_]*\}$").expect("regex"));
+}
+
+fn date_time_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    return RE.get_or_init(|| {
+        Regex::new(r"^\d{4}-\d{2}-\d{2}[Tt ]\d{2}:\d{2}:\d{2}(\.\d+)?([Zz]|[+-]\d{2}:?\d{2})?$")
+            .expect("regex")
+    });
+}
+
+fn date_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    return RE.get_or_init(|| Regex::new(r"^\d{4}-\d{2}-\d{2}$").expect("regex"));
+}
+
+fn uuid_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    return RE.get_or_init(|| {
+        Regex::new(r"^[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}$")
+            .expect("regex")
+    });
+}
+
+fn email_re() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    return RE.get_or_init(|| Regex::new(r"^[^@\s]+@[^@\s]+\.[^@\s]+$").expect("regex"));
+}
+
+/// `format` a string sample implies, if any.
+pub(crate) fn string_format(s: &str) -> Option<&'static str> {
+    return match s {
+        "${!uuidv4}" => Some("uuid"),
+        "${!iso_timestamp}" | "${!yesterday}" | "${!tomorrow}" => Some("date-time"),
+        "${!random_email}" => Some("email"),
+        _ if s.contains("${") => None,
+        _ if uuid_re().is_match(s) => Some("uuid"),
+        _ if date_time_re().is_match(s) => Some("date-time"),
+        _ if date_re().is_match(s) => Some("date"),
+        _ if email_re().is_match(s) => Some("email"),
+        _ => None,
+    };
+}
+
+impl Shape {
+    /// Shape of one JSON sample.
+    pub(crate) fn of(value: &Value) -> Shape {
+        return match value {
+            Value::Null => Shape::Null,
+            Value::Bool(_) => Shape::Boolean,
+            Value::Number(n) if n.is_f64() => Shape::Number,
+            Value::Number(_) => Shape::Integer,
+            Value::String(s) if json_placeholder_re().is_match(s) => Shape::Unknown,
+            Value::String(s) => Shape::String(string_format(s)),
+            Value::Array(items) => {
+                let items = items
+                    .iter()
+                    .map(Shape::of)
+                    .fold(Shape::Unknown, Shape::merge);
+                Shape::Array(Box::new(items))
+            }
+            Value::Object(fields) => Shape::Object(
+                fields
+                    .iter()
+                    .map(|(k, v)| (k.clone(), Shape::of(v), true))
+                    .collect(),
+            ),
+        };
+    }
+
+    /// Shape of a parameter value written as text (`42`, `true`, `${page}`).
+    pub(crate) fn of_text(text: &str) -> Shape {
+        if text.contains("${") && string_format(text).is_none() {
+            return Shape::Unknown;
+        }
+        if text.parse::<i64>().is_ok() {
+            return Shape::Integer;
+        }
+        if text.parse::<f64>().is_ok() {
+            return Shape::Number;
+        }
+        if text == "true" || text == "false" {
+            return Shape::Boolean;
+        }
+        return Shape::String(string_format(text));
+    }
+
+    /// The shape that fits samples of both `self` and `other`.
+    pub(crate) fn merge(self, other: Shape) -> Shape {
+        return match (self, other) {
+            (Shape::Unknown, s) | (s, Shape::Unknown) => s,
+            (Shape::Mixed, _) | (_, Shape::Mixed) => Shape::Mixed,
+            (Shape::Null, Shape::Null) => Shape::Null,
+            (Shape::Null, Shape::Nullable(s)) | (Shape::Nullable(s), Shape::Null) => {
+                Shape::Nullable(s)
+            }
+            (Shape::Null, s) | (s, Shape::Null) => Shape::Nullable(Box::new(s)),
+            (Shape::Nullable(a), Shape::Nullable(b)) => Shape::Nullable(Box::new(a.merge(*b))),
+            (Shape::Nullable(a), b) | (b, Shape::Nullable(a)) => {
+                Shape::Nullable(Box::new(a.merge(b)))
+            }
+            (Shape::Integer, Shape::Number) | (Shape::Number, Shape::Integer) => Shape::Number,
+            (Shape::String(a), Shape::String(b)) => Shape::String(if a == b { a } else { None }),
+            (Shape::Array(a), Shape::Array(b)) => Shape::Array(Box::new(a.merge(*b))),
+            (Shape::Object(a), Shape::Object(mut b)) => {
+                let mut merged = Vec::with_capacity(a.len());
+                for (name, shape, required) in a {
+                    match b.iter().position(|(n, _, _)| *n == name) {
+                        Some(i) => {
+                            let (_, other, other_required) = b.remove(i);
+                            merged.push((name, shape.merge(other), required && other_required));
+                        }
+                        None => merged.push((name, shape, false)),
+                    }
+                }
+                merged.extend(b.into_iter().map(|(name, shape, _)| (name, shape, false)));
+                Shape::Object(merged)
+            }
+            (a, b) if a == b => a,
+            _ => Shape::Mixed,
+        };
+    }
+
+    pub(crate) fn to_schema(&self) -> Schema {
+        let kind = match self {
+            Shape::Unknown | Shape::Mixed => SchemaKind::Any(AnySchema::default()),
+            Shape::Null => {
+                return Schema {
+                    schema_data: SchemaData {
+                        nullable: true,
+                        ..Default::default()
+                    },
+                    schema_kind: SchemaKind::Any(AnySchema::default()),
+                }
+            }
+            Shape::Nullable(inner) => {
+                let mut schema = inner.to_schema();
+                schema.schema_data.nullable = true;
+                return schema;
+            }
+            Shape::Boolean => SchemaKind::Type(Type::Boolean(BooleanType::default())),
+            Shape::Integer => SchemaKind::Type(Type::Integer(IntegerType::default())),
+            Shape::Number => SchemaKind::Type(Type::Number(NumberType::default())),
+            Shape::String(format) => {
+                let format = match format {
+                    Some("date-time") => VariantOrUnknownOrEmpty::Item(StringFormat::DateTime),
+                    Some("date") => VariantOrUnknownOrEmpty::Item(StringFormat::Date),
+                    Some(other) => VariantOrUnknownOrEmpty::Unknown(other.to_string()),
+                    None => VariantOrUnknownOrEmpty::Empty,
+                };
+                SchemaKind::Type(Type::String(StringType {
+                    format,
+                    ..Default::default()
+                }))
+            }
+            Shape::Array(items) => SchemaKind::Type(Type::Array(ArrayType {
+                items: Some(ReferenceOr::Item(Box::new(items.to_schema()))),
+                min_items: None,
+                max_items: None,
+                unique_items: false,
+            })),
+            Shape::Object(properties) => {
+                let mut object = ObjectType::default();
+                for (name, shape, required) in properties {
+                    object
+                        .properties
+                        .insert(name.clone(), ReferenceOr::Item(Box::new(shape.to_schema())));
+                    if *required {
+                        object.required.push(name.clone());
+                    }
+                }
+                SchemaKind::Type(Type::Object(object))
+            }
+        };
+        return Schema {
+            schema_data: SchemaData::default(),
+            schema_kind: kind,
+        };
+    }
+}
@@ -1,12 +1,27 @@
-//! Read OpenAPI 3.0.x specs from `.json`, `.yaml`, or `.yml`.
+//! Read OpenAPI specs from `.json`, `.yaml`, or `.yml`.
+//!
+//! The version field decides the path: **3.0.x** is deserialized directly, while **Swagger 2.0**
+//! ([`super::swagger2`]) and **OpenAPI 3.1** ([`super::v31`]) documents are first converted to
+//! 3.0 on the raw JSON value, so every version reaches the same [`OpenAPI`] model and generator.
 
 use std::path::Path;
 
 use openapiv3::OpenAPI;
+use serde_json::Value;
 
+use super::{swagger2, v31};
 use crate::error::{Error, Result};
 
-/// Read and deserialize OpenAPI 3.0.x from `.json`, `.yaml`, or `.yml` (extension-based).
+/// Spec versions [`load_openapi`] accepts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SpecVersion {
+    Swagger2,
+    OpenApi30,
+    OpenApi31,
+}
+
+/// Read and deserialize OpenAPI 3.0.x / 3.1.x or Swagger 2.0 from `.json`, `.yaml`, or `.yml`
+/// (extension-based). 2.0 and 3.1 documents are converted to 3.0.
 pub fn load_openapi(path: &Path) -> Result<OpenAPI> {
     let text = std::fs::read_to_string(path).map_err(|source| Error::Io {
         path: path.to_path_buf(),
@@ -17,20 +32,57 @@ pub fn load_openapi(path: &Path) -> Result<OpenAPI> {
         .and_then(|e| e.to_str())
         .unwrap_or("")
         .to_ascii_lowercase();
+    let yaml = matches!(ext.as_str(), "yaml" | "yml");
 
-    let api: OpenAPI = match ext.as_str() {
-        "yaml" | "yml" => serde_yaml::from_str(&text)?,
-        _ => serde_json::from_str(&text)?,
+    let doc: Value = if yaml {
+        serde_yaml::from_str(&text)?
+    } else {
+        serde_json::from_str(&text)?
     };
 
-    check_openapi_version(&api)?;
-    Ok(api)
+    let converted = match detect_version(&doc)? {
+        // Parse the text itself so YAML errors keep their line numbers.
+        SpecVersion::OpenApi30 if yaml => return Ok(serde_yaml::from_str(&text)?),
+        SpecVersion::OpenApi30 => return Ok(serde_json::from_str(&text)?),
+        SpecVersion::Swagger2 => ("Swagger 2.0", swagger2::upgrade(&doc)?),
+        SpecVersion::OpenApi31 => ("OpenAPI 3.1", v31::downgrade(&doc)?),
+    };
+    let (version, doc) = converted;
+    serde_json::from_value(doc).map_err(|source| Error::ConvertedSpec { version, source })
 }
 
-fn check_openapi_version(api: &OpenAPI) -> Result<()> {
-    let v = api.openapi.trim();
+fn detect_version(doc: &Value) -> Result<SpecVersion> {
+    if let Some(v) = doc.get("swagger") {
+        let v = version_string(v);
+        if v == "2.0" {
+            return Ok(SpecVersion::Swagger2);
+        }
+        return Err(Error::UnsupportedOpenApiVersion(format!("swagger {v}")));
+    }
+    let Some(v) = doc.get("openapi") else {
+        return Err(Error::MissingOpenApiVersion);
+    };
+    let v = version_string(v);
     if v == "3.0" || v.starts_with("3.0.") {
-        return Ok(());
+        return Ok(SpecVersion::OpenApi30);
+    }
+    if v == "3.1" || v.starts_with("3.1.") {
+        return Ok(SpecVersion::OpenApi31);
     }
-    Err(Error::UnsupportedOpenApiVersion(v.to_string()))
+    Err(Error::UnsupportedOpenApiVersion(v))
+}
+
+/// Version field as written; unquoted YAML (`swagger: 2.0`) arrives as a number.
+fn version_string(v: &Value) -> String {
+    return match v {
+        Value::String(s) => s.trim().to_string(),
+        Value::Number(n) if n.is_f64() => format!("{:?}", n.as_f64().unwrap_or_default()),
+        other => other.to_string(),
+    };
+}
+
+/// `parent` extended by one JSON-pointer token (`~` and `/` escaped), for locating constructs in
+/// conversion errors.
+pub(super) fn pointer(parent: &str, token: &str) -> String {
+    return format!("{parent}/{}", token.replace('~', "~0").replace('/', "~1"));
 }
@@ -1,28 +1,89 @@
-//! OpenAPI **3.0.x** → nativedoctor request files (`openapiv3`).
+//! OpenAPI **3.0.x** → nativedoctor request files (`openapiv3`), and back again with
+//! [`export_openapi`].
+//!
+//! **Swagger 2.0** and **OpenAPI 3.1** documents are converted to 3.0 when loaded (see
+//! [`load_openapi`]), so the mapping and file emission below only deal with one model.
 //!
 //! Other document sources (e.g. other API description formats) can live as sibling modules under
 //! `src/` later; this module keeps all OpenAPI-specific parsing, mapping, and file emission.
 
 mod build;
+mod env;
+mod example;
+mod export;
 mod fs;
+mod index;
+mod infer;
 mod load;
+mod resolve;
+mod security;
+mod sync;
+mod swagger2;
+mod v31;
 
-pub use build::{file_stem, operation_to_request_file, path_to_url_template, unique_stem};
-pub use fs::{write_all_operations, write_request_file, OutputFormat};
+pub use export::{export_openapi, write_openapi, ExportOptions, ExportReport};
+pub use build::{file_stem, operation_to_request_file, path_to_url_template};
+pub use crate::output::{unique_stem, write_request_file, OutputFormat};
+pub use fs::{write_all_operations, write_env_example};
 pub use load::load_openapi;
 
 use std::path::Path;
 
 use crate::error::Result;
-use crate::GenerateReport;
+use crate::{GenerateOptions, GenerateReport};
 
-/// Read OpenAPI from `input` and write one request file per operation into `out_dir`.
+/// Read OpenAPI from `input` and write one request file per operation, an `index.rhai` per
+/// directory, and `.env.example` into `out_dir`. With [`GenerateOptions::sync`], update the
+/// request files already there instead (see [`sync`]); the indexes and `.env.example` are
+/// rewritten from the result.
 pub(crate) fn generate_from_path(
     input: &Path,
     out_dir: &Path,
-    format: OutputFormat,
+    options: GenerateOptions,
 ) -> Result<GenerateReport> {
     let api = load::load_openapi(input)?;
-    let files_written = fs::write_all_operations(&api, out_dir, format)?;
-    Ok(GenerateReport { files_written })
+    let spec = input.canonicalize().ok();
+    let (written, sync) = if options.sync {
+        let (current, report) = sync::sync_operations(
+            &api,
+            out_dir,
+            options.format,
+            options.by_tag,
+            options.deprecate_removed,
+            spec.as_deref(),
+        )?;
+        (current, Some(report))
+    } else {
+        let written = fs::write_operations(
+            &api,
+            out_dir,
+            options.format,
+            options.by_tag,
+            spec.as_deref(),
+        )?;
+        (written, None)
+    };
+    let env_example = fs::write_env_example(&api, written.iter().map(|(_, file)| file), out_dir)?;
+    let files: Vec<_> = written.into_iter().map(|(path, _)| path).collect();
+    let files_written = match &sync {
+        Some(report) => report
+            .added
+            .iter()
+            .chain(&report.changed)
+            .chain(&report.deprecated)
+            .cloned()
+            .collect(),
+        None => files.clone(),
+    };
+    let title = match api.info.title.trim() {
+        "" => "the OpenAPI spec",
+        title => title,
+    };
+    let index_scripts = index::write_index_scripts(title, out_dir, &files)?;
+    Ok(GenerateReport {
+        files_written,
+        index_scripts,
+        env_example,
+        sync,
+    })
 }
@@ -0,0 +1,127 @@
+//! Local `$ref` resolution: `#/components/{schemas,parameters,requestBodies,securitySchemes}/…` and
+//! `#/paths/…` (path items).
+//!
+//! A reference may point at another reference; chains are followed until an item is reached, and
+//! a chain that comes back to a reference it already passed is reported as [`Error::RefCycle`].
+//! References into other files or to anything else are [`Error::UnresolvedRef`].
+
+use openapiv3::{OpenAPI, Parameter, PathItem, ReferenceOr, RequestBody, Schema, SecurityScheme};
+
+use crate::error::{Error, Result};
+
+const SCHEMAS: &str = "#/components/schemas/";
+const PARAMETERS: &str = "#/components/parameters/";
+const REQUEST_BODIES: &str = "#/components/requestBodies/";
+const SECURITY_SCHEMES: &str = "#/components/securitySchemes/";
+const PATHS: &str = "#/paths/";
+
+/// Resolves references against one document.
+#[derive(Clone, Copy)]
+pub(crate) struct Refs<'a> {
+    api: &'a OpenAPI,
+}
+
+impl<'a> Refs<'a> {
+    pub fn new(api: &'a OpenAPI) -> Self {
+        return Self { api };
+    }
+
+    pub fn schema(&self, item: &'a ReferenceOr<Schema>) -> Result<&'a Schema> {
+        let api = self.api;
+        return follow(item, move |reference| {
+            let name = component_name(reference, SCHEMAS)?;
+            return api.components.as_ref()?.schemas.get(&name);
+        });
+    }
+
+    pub fn parameter(&self, item: &'a ReferenceOr<Parameter>) -> Result<&'a Parameter> {
+        let api = self.api;
+        return follow(item, move |reference| {
+            let name = component_name(reference, PARAMETERS)?;
+            return api.components.as_ref()?.parameters.get(&name);
+        });
+    }
+
+    pub fn request_body(&self, item: &'a ReferenceOr<RequestBody>) -> Result<&'a RequestBody> {
+        let api = self.api;
+        return follow(item, move |reference| {
+            let name = component_name(reference, REQUEST_BODIES)?;
+            return api.components.as_ref()?.request_bodies.get(&name);
+        });
+    }
+
+    pub fn security_scheme(
+        &self,
+        item: &'a ReferenceOr<SecurityScheme>,
+    ) -> Result<&'a SecurityScheme> {
+        let api = self.api;
+        return follow(item, move |reference| {
+            let name = component_name(reference, SECURITY_SCHEMES)?;
+            return api.components.as_ref()?.security_schemes.get(&name);
+        });
+    }
+
+    pub fn path_item(&self, item: &'a ReferenceOr<PathItem>) -> Result<&'a PathItem> {
+        let api = self.api;
+        return follow(item, move |reference| {
+            let path = component_name(reference, PATHS)?;
+            return api.paths.paths.get(&path);
+        });
+    }
+
+    /// Component schema named by a `#/components/schemas/…` reference, one step (no chain).
+    pub fn schema_target(&self, reference: &str) -> Option<(String, &'a ReferenceOr<Schema>)> {
+        let name = component_name(reference, SCHEMAS)?;
+        let schema = self.api.components.as_ref()?.schemas.get(&name)?;
+        return Some((name, schema));
+    }
+}
+
+/// Follows `item` through references until an item is reached.
+fn follow<'a, T>(
+    mut item: &'a ReferenceOr<T>,
+    lookup: impl Fn(&str) -> Option<&'a ReferenceOr<T>>,
+) -> Result<&'a T> {
+    let mut seen: Vec<&'a str> = Vec::new();
+    loop {
+        match item {
+            ReferenceOr::Item(value) => return Ok(value),
+            ReferenceOr::Reference { reference } => {
+                if seen.contains(&reference.as_str()) {
+                    seen.push(reference);
+                    return Err(Error::RefCycle(seen.join(" → ")));
+                }
+                seen.push(reference);
+                item = lookup(reference).ok_or_else(|| Error::UnresolvedRef(reference.clone()))?;
+            }
+        }
+    }
+}
+
+/// Decoded last JSON-pointer token of `reference` under `prefix` (`#/paths/~1users~1%7Bid%7D` →
+/// `/users/{id}`).
+fn component_name(reference: &str, prefix: &str) -> Option<String> {
+    let token = reference.strip_prefix(prefix)?;
+    if token.is_empty() || token.contains('/') {
+        return None;
+    }
+    let decoded = percent_decode(token)?;
+    return Some(decoded.replace("~1", "/").replace("~0", "~"));
+}
+
+fn percent_decode(s: &str) -> Option<String> {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = s.get(i + 1..i + 3)?;
+            out.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            out.push(bytes[i]);
+            i += 1;
+        }
+    }
+    return String::from_utf8(out).ok();
+}
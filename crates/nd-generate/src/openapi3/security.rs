@@ -0,0 +1,159 @@
+//! Security schemes → credentials sent with generated requests.
+//!
+//! Each entry under `components.securitySchemes` gets one `${VAR}`, named the way
+//! [`crate::secrets`] names lifted credentials: `AUTH_TOKEN` for bearer, OAuth2 and OpenID
+//! Connect schemes, `BASIC_AUTH` (base64 of `username:password`) for basic auth, and the key name
+//! for API keys (`X-API-Key` → `X_API_KEY`). Later schemes wanting a taken name get `_2`, `_3`.
+//!
+//! An operation uses its own `security`, else the document's. Alternatives are tried in order and
+//! the first one that names at least one scheme is applied, so optional auth (`{}`) still gets
+//! credentials when the spec describes them.
+
+use nd_core::model::pairs::NameValueList;
+use openapiv3::{APIKeyLocation, OpenAPI, Operation, SecurityScheme};
+
+use super::resolve::Refs;
+use crate::error::Result;
+use crate::secrets::var_name;
+
+/// Where a credential goes on the request.
+#[derive(Debug, Clone)]
+enum Placement {
+    /// `name: {prefix} ${VAR}` (no prefix for API keys).
+    Header {
+        name: String,
+        prefix: Option<String>,
+    },
+    Query(String),
+    Cookie(String),
+}
+
+/// One security scheme and the variable that holds its secret.
+#[derive(Debug, Clone)]
+pub(crate) struct Credential {
+    /// Key under `components.securitySchemes`.
+    pub scheme: String,
+    pub var: String,
+    /// What to put in the variable, for `.env.example`.
+    pub hint: String,
+    placement: Placement,
+}
+
+/// Credentials for every security scheme the document defines, in definition order.
+pub(crate) fn credentials(api: &OpenAPI) -> Result<Vec<Credential>> {
+    let refs = Refs::new(api);
+    let mut out: Vec<Credential> = Vec::new();
+    let Some(components) = &api.components else {
+        return Ok(out);
+    };
+    for (name, scheme) in &components.security_schemes {
+        let (var, hint, placement) = match refs.security_scheme(scheme)? {
+            SecurityScheme::APIKey {
+                location,
+                name: key,
+                ..
+            } => {
+                let placement = match location {
+                    APIKeyLocation::Header => Placement::Header {
+                        name: key.clone(),
+                        prefix: None,
+                    },
+                    APIKeyLocation::Query => Placement::Query(key.clone()),
+                    APIKeyLocation::Cookie => Placement::Cookie(key.clone()),
+                };
+                (var_name(key), format!("API key ({key})"), placement)
+            }
+            SecurityScheme::HTTP {
+                scheme: http_scheme,
+                bearer_format,
+                ..
+            } => {
+                let (var, hint, prefix) = match http_scheme.to_ascii_lowercase().as_str() {
+                    "basic" => (
+                        "BASIC_AUTH",
+                        "base64 of username:password".to_string(),
+                        "Basic".to_string(),
+                    ),
+                    "bearer" => (
+                        "AUTH_TOKEN",
+                        match bearer_format {
+                            Some(format) => format!("bearer token ({format})"),
+                            None => "bearer token".to_string(),
+                        },
+                        "Bearer".to_string(),
+                    ),
+                    _ => (
+                        "AUTHORIZATION",
+                        format!("{http_scheme} credentials"),
+                        http_scheme.clone(),
+                    ),
+                };
+                let placement = Placement::Header {
+                    name: "Authorization".into(),
+                    prefix: Some(prefix),
+                };
+                (var.to_string(), hint, placement)
+            }
+            SecurityScheme::OAuth2 { .. } => access_token("OAuth2 access token"),
+            SecurityScheme::OpenIDConnect { .. } => access_token("OpenID Connect access token"),
+        };
+        let mut unique = var.clone();
+        let mut n = 2;
+        while out.iter().any(|c| c.var == unique) {
+            unique = format!("{var}_{n}");
+            n += 1;
+        }
+        out.push(Credential {
+            scheme: name.clone(),
+            var: unique,
+            hint,
+            placement,
+        });
+    }
+    return Ok(out);
+}
+
+fn access_token(hint: &str) -> (String, String, Placement) {
+    let placement = Placement::Header {
+        name: "Authorization".into(),
+        prefix: Some("Bearer".into()),
+    };
+    return ("AUTH_TOKEN".into(), hint.into(), placement);
+}
+
+/// Adds the headers / query parameters for the security requirement `operation` uses.
+pub(crate) fn apply_security(
+    credentials: &[Credential],
+    api: &OpenAPI,
+    operation: &Operation,
+    headers: &mut NameValueList,
+    query: &mut NameValueList,
+) {
+    let requirements = operation.security.as_ref().or(api.security.as_ref());
+    let Some(requirement) = requirements.into_iter().flatten().find(|r| !r.is_empty()) else {
+        return;
+    };
+    for scheme in requirement.keys() {
+        let Some(credential) = credentials.iter().find(|c| c.scheme == *scheme) else {
+            continue;
+        };
+        let value = format!("${{{}}}", credential.var);
+        match &credential.placement {
+            Placement::Header { name, prefix } => {
+                let value = match prefix {
+                    Some(prefix) => format!("{prefix} {value}"),
+                    None => value,
+                };
+                headers.insert(name.clone(), value);
+            }
+            Placement::Query(name) => query.insert(name.clone(), value),
+            Placement::Cookie(name) => {
+                let cookie = match headers.get("Cookie") {
+                    Some(existing) => format!("{existing}; {name}={value}"),
+                    None => format!("{name}={value}"),
+                };
+                headers.insert("Cookie", cookie);
+            }
+        }
+    }
+}
@@ -0,0 +1,636 @@
+//! Swagger 2.0 → OpenAPI 3.0, converted on the raw document.
+//!
+//! `host` / `basePath` / `schemes` become `servers`; `definitions`, `parameters`, `responses` and
+//! `securityDefinitions` move under `components`. `body` parameters become request bodies for each
+//! `consumes` type, `formData` parameters become a form object schema, and response `schema`s
+//! become content for each `produces` type. Parameter `type` / `format` / `items` move into
+//! `schema`, `type: file` becomes a binary string, and references are rewritten to their new
+//! locations.
+//!
+//! Anything without a 3.0 equivalent (`collectionFormat: tsv`, unknown parameter locations or
+//! security types) is collected and reported together as [`Error::UnsupportedConstructs`].
+
+use std::collections::HashSet;
+
+use serde_json::{json, Map, Value};
+
+use super::load::pointer;
+use crate::error::{Error, Result};
+
+const DEFAULT_MEDIA_TYPE: &str = "application/json";
+const FORM_URLENCODED: &str = "application/x-www-form-urlencoded";
+const MULTIPART: &str = "multipart/form-data";
+
+const METHODS: [&str; 7] = ["get", "put", "post", "delete", "options", "head", "patch"];
+
+/// Parameter fields that describe the value and move into the 3.0 `schema`.
+const SCHEMA_FIELDS: [&str; 16] = [
+    "type",
+    "format",
+    "items",
+    "enum",
+    "default",
+    "maximum",
+    "exclusiveMaximum",
+    "minimum",
+    "exclusiveMinimum",
+    "maxLength",
+    "minLength",
+    "pattern",
+    "maxItems",
+    "minItems",
+    "uniqueItems",
+    "multipleOf",
+];
+
+/// Convert a Swagger 2.0 document to an OpenAPI 3.0 one.
+pub(crate) fn upgrade(doc: &Value) -> Result<Value> {
+    let empty = Map::new();
+    let globals = doc
+        .get("parameters")
+        .and_then(Value::as_object)
+        .unwrap_or(&empty);
+    let mut upgrade = Upgrade {
+        globals,
+        consumes: media_types(doc.get("consumes")),
+        produces: media_types(doc.get("produces")),
+        unsupported: Vec::new(),
+    };
+    let mut out = upgrade.document(doc);
+    let body_params: HashSet<&str> = globals
+        .iter()
+        .filter(|(_, p)| location(p) == Some("body"))
+        .map(|(name, _)| name.as_str())
+        .collect();
+    rewrite_refs(&mut out, &body_params);
+
+    if !upgrade.unsupported.is_empty() {
+        return Err(Error::UnsupportedConstructs {
+            version: "Swagger 2.0",
+            constructs: upgrade.unsupported,
+        });
+    }
+    return Ok(out);
+}
+
+struct Upgrade<'a> {
+    /// Top-level `parameters`, so references to `body` / `formData` ones can be placed.
+    globals: &'a Map<String, Value>,
+    consumes: Vec<String>,
+    produces: Vec<String>,
+    unsupported: Vec<String>,
+}
+
+impl<'a> Upgrade<'a> {
+    fn document(&mut self, doc: &'a Value) -> Value {
+        let mut out = Map::new();
+        out.insert("openapi".into(), json!("3.0.3"));
+        out.insert(
+            "info".into(),
+            doc.get("info")
+                .cloned()
+                .unwrap_or_else(|| json!({"title": "", "version": ""})),
+        );
+        let servers = servers(doc);
+        if !servers.is_empty() {
+            out.insert("servers".into(), Value::Array(servers));
+        }
+        copy(doc, &mut out, &["tags", "externalDocs", "security"]);
+        copy_extensions(doc, &mut out);
+
+        let mut paths = Map::new();
+        for (path, item) in entries(doc.get("paths")) {
+            let at = pointer("#/paths", path);
+            paths.insert(path.clone(), self.path_item(item, &at));
+        }
+        out.insert("paths".into(), Value::Object(paths));
+
+        let mut components = Map::new();
+        let mut schemas = Map::new();
+        for (name, schema) in entries(doc.get("definitions")) {
+            schemas.insert(name.clone(), schema_upgrade(schema));
+        }
+        let mut parameters = Map::new();
+        let mut request_bodies = Map::new();
+        for (name, param) in self.globals {
+            let at = pointer("#/parameters", name);
+            match location(param) {
+                Some("body") => {
+                    let body = self.body(param, &self.consumes);
+                    request_bodies.insert(name.clone(), body);
+                }
+                // Inlined into each operation's form schema where referenced.
+                Some("formData") => {}
+                _ => {
+                    if let Some(p) = self.parameter(param, &at) {
+                        parameters.insert(name.clone(), p);
+                    }
+                }
+            }
+        }
+        let mut responses = Map::new();
+        for (name, response) in entries(doc.get("responses")) {
+            responses.insert(name.clone(), self.response(response, &self.produces));
+        }
+        let mut security = Map::new();
+        for (name, scheme) in entries(doc.get("securityDefinitions")) {
+            let at = pointer("#/securityDefinitions", name);
+            if let Some(s) = self.security_scheme(scheme, &at) {
+                security.insert(name.clone(), s);
+            }
+        }
+        for (key, map) in [
+            ("schemas", schemas),
+            ("parameters", parameters),
+            ("requestBodies", request_bodies),
+            ("responses", responses),
+            ("securitySchemes", security),
+        ] {
+            if !map.is_empty() {
+                components.insert(key.into(), Value::Object(map));
+            }
+        }
+        if !components.is_empty() {
+            out.insert("components".into(), Value::Object(components));
+        }
+        return Value::Object(out);
+    }
+
+    fn path_item(&mut self, item: &'a Value, at: &str) -> Value {
+        let mut out = Map::new();
+        copy(item, &mut out, &["$ref"]);
+        copy_extensions(item, &mut out);
+
+        // Path-level body / form parameters have no 3.0 home; they join each operation instead.
+        let shared: Vec<&Value> = item
+            .get("parameters")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .collect();
+        let mut inherited = Vec::new();
+        let mut params = Vec::new();
+        for (i, p) in shared.into_iter().enumerate() {
+            if matches!(self.location_of(p), Some("body" | "formData")) {
+                inherited.push(p);
+            } else if let Some(p) =
+                self.parameter(p, &pointer(&pointer(at, "parameters"), &i.to_string()))
+            {
+                params.push(p);
+            }
+        }
+        if !params.is_empty() {
+            out.insert("parameters".into(), Value::Array(params));
+        }
+
+        for method in METHODS {
+            if let Some(op) = item.get(method) {
+                let op = self.operation(op, &inherited, &pointer(at, method));
+                out.insert(method.into(), op);
+            }
+        }
+        return Value::Object(out);
+    }
+
+    fn operation(&mut self, op: &'a Value, inherited: &[&'a Value], at: &str) -> Value {
+        let mut out = Map::new();
+        copy(
+            op,
+            &mut out,
+            &[
+                "tags",
+                "summary",
+                "description",
+                "externalDocs",
+                "operationId",
+                "deprecated",
+                "security",
+            ],
+        );
+        copy_extensions(op, &mut out);
+        let consumes = op
+            .get("consumes")
+            .map(|c| media_types(Some(c)))
+            .unwrap_or_else(|| self.consumes.clone());
+        let produces = op
+            .get("produces")
+            .map(|p| media_types(Some(p)))
+            .unwrap_or_else(|| self.produces.clone());
+
+        let own: Vec<&Value> = op
+            .get("parameters")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .collect();
+        let mut params = Vec::new();
+        let mut body = None;
+        let mut form = Vec::new();
+        let overridden = |p: &&Value| {
+            !own.iter().any(|o| {
+                self.name_of(o) == self.name_of(p) && self.location_of(o) == self.location_of(p)
+            })
+        };
+        let all: Vec<&Value> = inherited
+            .iter()
+            .copied()
+            .filter(overridden)
+            .chain(own.iter().copied())
+            .collect();
+        for (i, p) in all.into_iter().enumerate() {
+            let here = pointer(&pointer(at, "parameters"), &i.to_string());
+            match self.location_of(p) {
+                Some("body") => body = Some(self.body_or_ref(p, &consumes)),
+                Some("formData") => form.push(self.resolve(p)),
+                _ => {
+                    if let Some(p) = self.parameter(p, &here) {
+                        params.push(p);
+                    }
+                }
+            }
+        }
+        if !params.is_empty() {
+            out.insert("parameters".into(), Value::Array(params));
+        }
+        if body.is_none() && !form.is_empty() {
+            body = Some(self.form_body(&form, &consumes));
+        }
+        if let Some(body) = body {
+            out.insert("requestBody".into(), body);
+        }
+
+        let mut responses = Map::new();
+        for (status, response) in entries(op.get("responses")) {
+            responses.insert(status.clone(), self.response(response, &produces));
+        }
+        out.insert("responses".into(), Value::Object(responses));
+        return Value::Object(out);
+    }
+
+    /// The parameter itself, or the top-level one it references.
+    fn resolve(&self, param: &'a Value) -> &'a Value {
+        let target = param
+            .get("$ref")
+            .and_then(Value::as_str)
+            .and_then(|r| r.strip_prefix("#/parameters/"))
+            .and_then(|name| self.globals.get(name));
+        return target.unwrap_or(param);
+    }
+
+    fn location_of(&self, param: &'a Value) -> Option<&'a str> {
+        return location(self.resolve(param));
+    }
+
+    fn name_of(&self, param: &'a Value) -> Option<&'a str> {
+        return self.resolve(param).get("name").and_then(Value::as_str);
+    }
+
+    /// Non-body parameter; references are kept (and rewritten later).
+    fn parameter(&mut self, param: &Value, at: &str) -> Option<Value> {
+        if param.get("$ref").is_some() {
+            return Some(param.clone());
+        }
+        let location = location(param).unwrap_or_default();
+        if !matches!(location, "query" | "header" | "path") {
+            self.unsupported
+                .push(format!("parameter location `{location}` at {at}"));
+            return None;
+        }
+        let mut out = Map::new();
+        copy(
+            param,
+            &mut out,
+            &["name", "in", "description", "required", "allowEmptyValue"],
+        );
+        copy_extensions(param, &mut out);
+        if location == "path" {
+            out.insert("required".into(), json!(true));
+        }
+        out.insert("schema".into(), value_schema(param));
+        match param.get("collectionFormat").and_then(Value::as_str) {
+            None | Some("csv") => {
+                if location == "query" && param.get("type").and_then(Value::as_str) == Some("array")
+                {
+                    out.insert("explode".into(), json!(false));
+                }
+            }
+            Some("multi") => {
+                out.insert("explode".into(), json!(true));
+            }
+            Some("ssv") => {
+                out.insert("style".into(), json!("spaceDelimited"));
+            }
+            Some("pipes") => {
+                out.insert("style".into(), json!("pipeDelimited"));
+            }
+            Some(other) => self
+                .unsupported
+                .push(format!("collectionFormat `{other}` at {at}")),
+        }
+        return Some(Value::Object(out));
+    }
+
+    fn body_or_ref(&self, param: &Value, consumes: &[String]) -> Value {
+        if param.get("$ref").is_some() {
+            return param.clone();
+        }
+        return self.body(param, consumes);
+    }
+
+    fn body(&self, param: &Value, consumes: &[String]) -> Value {
+        let schema = param
+            .get("schema")
+            .map(schema_upgrade)
+            .unwrap_or_else(|| json!({}));
+        let mut content = Map::new();
+        for media in consumes {
+            content.insert(media.clone(), json!({ "schema": schema }));
+        }
+        let mut out = Map::new();
+        copy(param, &mut out, &["description", "required"]);
+        copy_extensions(param, &mut out);
+        out.insert("content".into(), Value::Object(content));
+        return Value::Object(out);
+    }
+
+    /// `formData` parameters as one object schema, multipart when a file is among them.
+    fn form_body(&self, fields: &[&Value], consumes: &[String]) -> Value {
+        let mut properties = Map::new();
+        let mut required = Vec::new();
+        for field in fields {
+            let Some(name) = field.get("name").and_then(Value::as_str) else {
+                continue;
+            };
+            let mut schema = value_schema(field);
+            if let (Some(description), Value::Object(s)) = (field.get("description"), &mut schema) {
+                s.insert("description".into(), description.clone());
+            }
+            properties.insert(name.to_string(), schema);
+            if field.get("required").and_then(Value::as_bool) == Some(true) {
+                required.push(json!(name));
+            }
+        }
+        let has_file = fields
+            .iter()
+            .any(|f| f.get("type").and_then(Value::as_str) == Some("file"));
+        let mut media: Vec<&str> = consumes
+            .iter()
+            .map(String::as_str)
+            .filter(|m| *m == FORM_URLENCODED || *m == MULTIPART)
+            .filter(|m| !has_file || *m == MULTIPART)
+            .collect();
+        if media.is_empty() {
+            media.push(if has_file { MULTIPART } else { FORM_URLENCODED });
+        }
+        let mut schema = json!({ "type": "object", "properties": properties });
+        if !required.is_empty() {
+            schema["required"] = Value::Array(required);
+        }
+        let mut content = Map::new();
+        for m in media {
+            content.insert(m.to_string(), json!({ "schema": schema }));
+        }
+        return json!({ "content": content });
+    }
+
+    fn response(&self, response: &Value, produces: &[String]) -> Value {
+        if response.get("$ref").is_some() {
+            return response.clone();
+        }
+        let mut out = Map::new();
+        out.insert(
+            "description".into(),
+            response
+                .get("description")
+                .cloned()
+                .unwrap_or_else(|| json!("")),
+        );
+        copy_extensions(response, &mut out);
+        let mut headers = Map::new();
+        for (name, header) in entries(response.get("headers")) {
+            let mut h = Map::new();
+            copy(header, &mut h, &["description"]);
+            h.insert("schema".into(), value_schema(header));
+            headers.insert(name.clone(), Value::Object(h));
+        }
+        if !headers.is_empty() {
+            out.insert("headers".into(), Value::Object(headers));
+        }
+        if let Some(schema) = response.get("schema") {
+            let schema = schema_upgrade(schema);
+            let examples = response.get("examples");
+            let mut content = Map::new();
+            for media in produces {
+                let mut m = json!({ "schema": schema });
+                if let Some(example) = examples.and_then(|e| e.get(media)) {
+                    m["example"] = example.clone();
+                }
+                content.insert(media.clone(), m);
+            }
+            out.insert("content".into(), Value::Object(content));
+        }
+        return Value::Object(out);
+    }
+
+    fn security_scheme(&mut self, scheme: &Value, at: &str) -> Option<Value> {
+        let kind = scheme
+            .get("type")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        let mut out = Map::new();
+        copy(scheme, &mut out, &["description"]);
+        copy_extensions(scheme, &mut out);
+        match kind {
+            "basic" => {
+                out.insert("type".into(), json!("http"));
+                out.insert("scheme".into(), json!("basic"));
+            }
+            "apiKey" => copy(scheme, &mut out, &["type", "name", "in"]),
+            "oauth2" => {
+                let flow = scheme
+                    .get("flow")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default();
+                let (name, urls): (&str, &[&str]) = match flow {
+                    "implicit" => ("implicit", &["authorizationUrl"]),
+                    "password" => ("password", &["tokenUrl"]),
+                    "application" => ("clientCredentials", &["tokenUrl"]),
+                    "accessCode" => ("authorizationCode", &["authorizationUrl", "tokenUrl"]),
+                    other => {
+                        self.unsupported
+                            .push(format!("OAuth2 flow `{other}` at {at}"));
+                        return None;
+                    }
+                };
+                let mut f = Map::new();
+                copy(scheme, &mut f, urls);
+                f.insert(
+                    "scopes".into(),
+                    scheme.get("scopes").cloned().unwrap_or_else(|| json!({})),
+                );
+                out.insert("type".into(), json!("oauth2"));
+                out.insert("flows".into(), json!({ name: f }));
+            }
+            other => {
+                self.unsupported
+                    .push(format!("security scheme type `{other}` at {at}"));
+                return None;
+            }
+        }
+        return Some(Value::Object(out));
+    }
+}
+
+/// `servers` from `schemes`, `host` and `basePath`; just the base path when there is no host.
+fn servers(doc: &Value) -> Vec<Value> {
+    let base = doc.get("basePath").and_then(Value::as_str).unwrap_or("");
+    let base = base.trim_end_matches('/');
+    let Some(host) = doc.get("host").and_then(Value::as_str) else {
+        if base.is_empty() {
+            return Vec::new();
+        }
+        return vec![json!({ "url": base })];
+    };
+    let schemes: Vec<&str> = doc
+        .get("schemes")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .collect();
+    let schemes = if schemes.is_empty() {
+        vec!["https"]
+    } else {
+        schemes
+    };
+    return schemes
+        .into_iter()
+        .map(|scheme| json!({ "url": format!("{scheme}://{host}{base}") }))
+        .collect();
+}
+
+/// Schema for a non-body parameter, header or form field, built from its value fields.
+fn value_schema(param: &Value) -> Value {
+    let mut schema = Map::new();
+    for field in SCHEMA_FIELDS {
+        if let Some(v) = param.get(field) {
+            schema.insert(field.into(), v.clone());
+        }
+    }
+    return schema_upgrade(&Value::Object(schema));
+}
+
+/// Schema differences: `type: file`, `x-nullable` and string `discriminator`s; applied through
+/// nested schemas.
+fn schema_upgrade(schema: &Value) -> Value {
+    let Value::Object(fields) = schema else {
+        return schema.clone();
+    };
+    let mut out = Map::new();
+    for (key, value) in fields {
+        let value = match key.as_str() {
+            "properties" => Value::Object(
+                entries(Some(value))
+                    .map(|(name, s)| (name.clone(), schema_upgrade(s)))
+                    .collect(),
+            ),
+            "items" | "additionalProperties" | "not" => schema_upgrade(value),
+            "allOf" | "anyOf" | "oneOf" => Value::Array(
+                value
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .map(schema_upgrade)
+                    .collect(),
+            ),
+            "type" if value == "file" => {
+                out.insert("format".into(), json!("binary"));
+                json!("string")
+            }
+            "x-nullable" => {
+                out.insert("nullable".into(), value.clone());
+                continue;
+            }
+            "discriminator" if value.is_string() => json!({ "propertyName": value }),
+            _ => value.clone(),
+        };
+        out.insert(key.clone(), value);
+    }
+    return Value::Object(out);
+}
+
+/// Point references at their `components` locations.
+fn rewrite_refs(value: &mut Value, body_params: &HashSet<&str>) {
+    match value {
+        Value::Object(fields) => {
+            if let Some(Value::String(reference)) = fields.get_mut("$ref") {
+                if let Some(rewritten) = rewrite_ref(reference, body_params) {
+                    *reference = rewritten;
+                }
+            }
+            for v in fields.values_mut() {
+                rewrite_refs(v, body_params);
+            }
+        }
+        Value::Array(items) => {
+            for v in items {
+                rewrite_refs(v, body_params);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn rewrite_ref(reference: &str, body_params: &HashSet<&str>) -> Option<String> {
+    if let Some(name) = reference.strip_prefix("#/definitions/") {
+        return Some(format!("#/components/schemas/{name}"));
+    }
+    if let Some(name) = reference.strip_prefix("#/parameters/") {
+        if body_params.contains(name) {
+            return Some(format!("#/components/requestBodies/{name}"));
+        }
+        return Some(format!("#/components/parameters/{name}"));
+    }
+    if let Some(name) = reference.strip_prefix("#/responses/") {
+        return Some(format!("#/components/responses/{name}"));
+    }
+    return None;
+}
+
+fn location(param: &Value) -> Option<&str> {
+    return param.get("in").and_then(Value::as_str);
+}
+
+fn media_types(value: Option<&Value>) -> Vec<String> {
+    let types: Vec<String> = value
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .map(str::to_string)
+        .collect();
+    if types.is_empty() {
+        return vec![DEFAULT_MEDIA_TYPE.to_string()];
+    }
+    return types;
+}
+
+fn entries(value: Option<&Value>) -> impl Iterator<Item = (&String, &Value)> {
+    return value.and_then(Value::as_object).into_iter().flatten();
+}
+
+fn copy(from: &Value, to: &mut Map<String, Value>, keys: &[&str]) {
+    for key in keys {
+        if let Some(v) = from.get(*key) {
+            to.insert((*key).to_string(), v.clone());
+        }
+    }
+}
+
+fn copy_extensions(from: &Value, to: &mut Map<String, Value>) {
+    for (key, v) in entries(Some(from)) {
+        if key.starts_with("x-") {
+            to.insert(key.clone(), v.clone());
+        }
+    }
+}
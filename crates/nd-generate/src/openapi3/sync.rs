@@ -0,0 +1,174 @@
+//! `generate --sync`: bring a previously generated directory up to date with a changed spec.
+//!
+//! Existing request files are matched to operations by their `openapi.operation_id` link. For a
+//! matched file, the spec decides the method, URL, summary, description, tags, `deprecated` and
+//! which query parameters exist; values the user filled in for parameters that still exist are
+//! kept, as are headers (missing spec headers are added), the body, the name and every other
+//! field. Operations without a file are written like a fresh `generate`; files whose operation
+//! is gone are deleted, or marked `deprecated` when asked.
+//!
+//! Files that are not request files, fail to load, or have no `openapi` link are left alone.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use nd_core::model::pairs::NameValueList;
+use nd_core::model::request::RequestFile;
+use openapiv3::OpenAPI;
+
+use super::fs::{create_dir, link_spec, plan_operations};
+use crate::convert::has_header;
+use crate::error::{Error, Result};
+use crate::output::{unique_stem, write_request_file, OutputFormat};
+use crate::SyncReport;
+
+/// Every request file now in `out_dir` (kept, updated or added, in document order, then
+/// deprecated ones) and what the sync did.
+pub(crate) fn sync_operations(
+    api: &OpenAPI,
+    out_dir: &Path,
+    format: OutputFormat,
+    by_tag: bool,
+    deprecate_removed: bool,
+    spec: Option<&Path>,
+) -> Result<(Vec<(PathBuf, RequestFile)>, SyncReport)> {
+    create_dir(out_dir)?;
+    let mut existing = Vec::new();
+    let mut used_stems: HashMap<PathBuf, HashSet<String>> = HashMap::new();
+    scan(out_dir, &mut existing, &mut used_stems)?;
+
+    let mut report = SyncReport::default();
+    let mut current = Vec::new();
+    for mut op in plan_operations(api, out_dir, by_tag)? {
+        let operation_id = op.file.openapi.as_ref().map(|l| &l.operation_id);
+        let matched = existing
+            .iter()
+            .position(|(_, file): &(PathBuf, RequestFile)| {
+                file.openapi.as_ref().map(|l| &l.operation_id) == operation_id
+            });
+        let Some(i) = matched else {
+            let unique = unique_stem(&op.stem, used_stems.entry(op.dir.clone()).or_default());
+            create_dir(&op.dir)?;
+            let path = op.dir.join(format!("{unique}.{}", format.extension()));
+            link_spec(&mut op.file, &path, spec);
+            write_request_file(&path, &op.file, format)?;
+            report.added.push(path.clone());
+            current.push((path, op.file));
+            continue;
+        };
+
+        let (path, old) = existing.remove(i);
+        let mut merged = merge(&old, op.file);
+        link_spec(&mut merged, &path, spec);
+        if merged == old {
+            report.unchanged.push(path.clone());
+        } else {
+            let file_format = OutputFormat::from_path(&path).unwrap_or(format);
+            write_request_file(&path, &merged, file_format)?;
+            report.changed.push(path.clone());
+        }
+        current.push((path, merged));
+    }
+
+    for (path, mut file) in existing {
+        if !deprecate_removed {
+            std::fs::remove_file(&path).map_err(|source| Error::Io {
+                path: path.clone(),
+                source,
+            })?;
+            report.removed.push(path);
+            continue;
+        }
+        if file.request.deprecated {
+            report.unchanged.push(path.clone());
+        } else {
+            file.request.deprecated = true;
+            let file_format = OutputFormat::from_path(&path).unwrap_or(format);
+            write_request_file(&path, &file, file_format)?;
+            report.deprecated.push(path.clone());
+        }
+        current.push((path, file));
+    }
+
+    return Ok((current, report));
+}
+
+/// `old` updated with the spec-derived fields of `generated`.
+fn merge(old: &RequestFile, generated: RequestFile) -> RequestFile {
+    let mut merged = old.clone();
+    let spec = generated.request;
+    let request = &mut merged.request;
+    request.method = spec.method;
+    request.url = spec.url;
+    request.summary = spec.summary;
+    request.description = spec.description;
+    request.tags = spec.tags;
+    request.deprecated = spec.deprecated;
+
+    // Kept parameters stay in the file's order; new ones follow in the spec's.
+    let mut query = NameValueList::new();
+    for (name, value) in request.query.iter() {
+        if spec.query.get(name).is_some() {
+            query.append(name.clone(), value.clone());
+        }
+    }
+    for (name, value) in spec.query.iter() {
+        if request.query.get(name).is_none() {
+            query.append(name.clone(), value.clone());
+        }
+    }
+    request.query = query;
+
+    for (name, value) in spec.headers.iter() {
+        if !has_header(&request.headers, name) {
+            request.headers.append(name.clone(), value.clone());
+        }
+    }
+    if request.body.is_none() {
+        request.body = spec.body;
+    }
+    merged.openapi = generated.openapi;
+    return merged;
+}
+
+/// Request files under `dir` that link to an OpenAPI operation, and the file stems already taken
+/// in each directory.
+fn scan(
+    dir: &Path,
+    files: &mut Vec<(PathBuf, RequestFile)>,
+    used_stems: &mut HashMap<PathBuf, HashSet<String>>,
+) -> Result<()> {
+    let io_err = |source| Error::Io {
+        path: dir.to_path_buf(),
+        source,
+    };
+    let mut entries = std::fs::read_dir(dir)
+        .map_err(io_err)?
+        .map(|e| e.map(|e| e.path()))
+        .collect::<std::io::Result<Vec<_>>>()
+        .map_err(io_err)?;
+    entries.sort();
+
+    for path in entries {
+        if path.is_dir() {
+            scan(&path, files, used_stems)?;
+            continue;
+        }
+        if OutputFormat::from_path(&path).is_none() {
+            continue;
+        }
+        if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
+            used_stems
+                .entry(dir.to_path_buf())
+                .or_default()
+                .insert(stem.to_string());
+        }
+        let Ok(file) = RequestFile::from_file(&path) else {
+            continue;
+        };
+        if file.openapi.is_some() {
+            files.push((path, file));
+        }
+    }
+    return Ok(());
+}
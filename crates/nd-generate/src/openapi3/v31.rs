@@ -0,0 +1,281 @@
+//! OpenAPI 3.1 → 3.0, converted on the raw document.
+//!
+//! 3.1 schemas are full JSON Schema; the common differences are rewritten in place: `type` lists
+//! with `"null"` (and `{type: "null"}` alternatives) become `nullable`, `const` becomes a one-entry
+//! `enum`, a schema `examples` list becomes `example`, numeric `exclusiveMinimum` /
+//! `exclusiveMaximum` become the 3.0 boolean form, `contentEncoding: base64` / `contentMediaType`
+//! become `byte` / `binary` formats, and `true` / `false` schemas become `{}` / `{not: {}}`.
+//! Path items referenced from `components/pathItems` are inlined, and `webhooks` (requests the API
+//! sends rather than receives) are dropped.
+//!
+//! Keywords that change a schema's shape in ways 3.0 cannot express (`prefixItems`,
+//! `if` / `then` / `else`, `dependentSchemas`, `$dynamicRef`, `$recursiveRef`) are collected and
+//! reported together as [`Error::UnsupportedConstructs`].
+
+use serde_json::{json, Value};
+
+use super::load::pointer;
+use crate::error::{Error, Result};
+
+const METHODS: [&str; 8] = [
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+const UNSUPPORTED_KEYWORDS: [&str; 7] = [
+    "prefixItems",
+    "if",
+    "then",
+    "else",
+    "dependentSchemas",
+    "$dynamicRef",
+    "$recursiveRef",
+];
+
+const PATH_ITEM_REF: &str = "#/components/pathItems/";
+
+/// Convert an OpenAPI 3.1 document to an OpenAPI 3.0 one.
+pub(crate) fn downgrade(doc: &Value) -> Result<Value> {
+    let mut out = doc.clone();
+    let mut unsupported = Vec::new();
+    let Value::Object(root) = &mut out else {
+        return Err(Error::UnsupportedConstructs {
+            version: "OpenAPI 3.1",
+            constructs: vec!["document root is not an object".into()],
+        });
+    };
+    root.insert("openapi".into(), json!("3.0.3"));
+    root.remove("webhooks");
+    root.remove("jsonSchemaDialect");
+    let path_items = root
+        .get_mut("components")
+        .and_then(Value::as_object_mut)
+        .and_then(|c| c.remove("pathItems"));
+
+    let paths = root.entry("paths").or_insert_with(|| json!({}));
+    if let Value::Object(paths) = paths {
+        for (path, item) in paths.iter_mut() {
+            let at = pointer("#/paths", path);
+            let target = item
+                .get("$ref")
+                .and_then(Value::as_str)
+                .and_then(|r| r.strip_prefix(PATH_ITEM_REF))
+                .and_then(|name| path_items.as_ref()?.get(name));
+            if let Some(target) = target {
+                *item = target.clone();
+            }
+            path_item(item, &at, &mut unsupported);
+        }
+    }
+
+    if let Some(Value::Object(components)) = root.get_mut("components") {
+        for (name, schema) in entries_mut(components.get_mut("schemas")) {
+            downgrade_schema(
+                schema,
+                &pointer("#/components/schemas", name),
+                &mut unsupported,
+            );
+        }
+        for (name, param) in entries_mut(components.get_mut("parameters")) {
+            parameter(
+                param,
+                &pointer("#/components/parameters", name),
+                &mut unsupported,
+            );
+        }
+        for (name, header) in entries_mut(components.get_mut("headers")) {
+            parameter(
+                header,
+                &pointer("#/components/headers", name),
+                &mut unsupported,
+            );
+        }
+        for (name, body) in entries_mut(components.get_mut("requestBodies")) {
+            let at = pointer("#/components/requestBodies", name);
+            content(body, &at, &mut unsupported);
+        }
+        for (name, response_item) in entries_mut(components.get_mut("responses")) {
+            let at = pointer("#/components/responses", name);
+            response(response_item, &at, &mut unsupported);
+        }
+    }
+
+    if !unsupported.is_empty() {
+        return Err(Error::UnsupportedConstructs {
+            version: "OpenAPI 3.1",
+            constructs: unsupported,
+        });
+    }
+    return Ok(out);
+}
+
+fn path_item(item: &mut Value, at: &str, unsupported: &mut Vec<String>) {
+    parameters(item, at, unsupported);
+    for method in METHODS {
+        let Some(op) = item.get_mut(method) else {
+            continue;
+        };
+        let at = pointer(at, method);
+        parameters(op, &at, unsupported);
+        if let Some(body) = op.get_mut("requestBody") {
+            content(body, &pointer(&at, "requestBody"), unsupported);
+        }
+        let responses_at = pointer(&at, "responses");
+        for (status, r) in entries_mut(op.get_mut("responses")) {
+            response(r, &pointer(&responses_at, status), unsupported);
+        }
+    }
+}
+
+fn parameters(owner: &mut Value, at: &str, unsupported: &mut Vec<String>) {
+    let Some(Value::Array(params)) = owner.get_mut("parameters") else {
+        return;
+    };
+    let at = pointer(at, "parameters");
+    for (i, param) in params.iter_mut().enumerate() {
+        parameter(param, &pointer(&at, &i.to_string()), unsupported);
+    }
+}
+
+/// Parameter or header: a `schema`, or `content` like a body.
+fn parameter(param: &mut Value, at: &str, unsupported: &mut Vec<String>) {
+    if let Some(schema) = param.get_mut("schema") {
+        downgrade_schema(schema, &pointer(at, "schema"), unsupported);
+    }
+    content(param, at, unsupported);
+}
+
+fn response(response: &mut Value, at: &str, unsupported: &mut Vec<String>) {
+    content(response, at, unsupported);
+    let headers_at = pointer(at, "headers");
+    for (name, header) in entries_mut(response.get_mut("headers")) {
+        parameter(header, &pointer(&headers_at, name), unsupported);
+    }
+}
+
+/// Schemas of every media type under `owner.content`.
+fn content(owner: &mut Value, at: &str, unsupported: &mut Vec<String>) {
+    let content_at = pointer(at, "content");
+    for (media, m) in entries_mut(owner.get_mut("content")) {
+        if let Some(schema) = m.get_mut("schema") {
+            let at = pointer(&pointer(&content_at, media), "schema");
+            downgrade_schema(schema, &at, unsupported);
+        }
+    }
+}
+
+fn downgrade_schema(schema: &mut Value, at: &str, unsupported: &mut Vec<String>) {
+    let fields = match schema {
+        Value::Bool(true) => {
+            *schema = json!({});
+            return;
+        }
+        Value::Bool(false) => {
+            *schema = json!({ "not": {} });
+            return;
+        }
+        Value::Object(fields) => fields,
+        _ => return,
+    };
+
+    for keyword in UNSUPPORTED_KEYWORDS {
+        if fields.contains_key(keyword) {
+            unsupported.push(format!("`{keyword}` at {at}"));
+        }
+    }
+
+    if let Some(Value::Array(types)) = fields.get("type") {
+        let mut types: Vec<Value> = types.clone();
+        let before = types.len();
+        types.retain(|t| t != "null");
+        if types.len() < before {
+            fields.insert("nullable".into(), json!(true));
+        }
+        match types.len() {
+            0 => {
+                fields.remove("type");
+            }
+            1 => {
+                fields.insert("type".into(), types.remove(0));
+            }
+            _ if fields.contains_key("anyOf") => {
+                unsupported.push(format!("`type` list alongside `anyOf` at {at}"));
+            }
+            _ => {
+                fields.remove("type");
+                let any_of = types.into_iter().map(|t| json!({ "type": t })).collect();
+                fields.insert("anyOf".into(), Value::Array(any_of));
+            }
+        }
+    } else if fields.get("type").is_some_and(|t| t == "null") {
+        fields.remove("type");
+        fields.insert("nullable".into(), json!(true));
+    }
+
+    if let Some(value) = fields.remove("const") {
+        fields.insert("enum".into(), json!([value]));
+    }
+    if let Some(Value::Array(mut examples)) = fields.remove("examples") {
+        if !fields.contains_key("example") && !examples.is_empty() {
+            fields.insert("example".into(), examples.remove(0));
+        }
+    }
+    for (exclusive, bound) in [
+        ("exclusiveMinimum", "minimum"),
+        ("exclusiveMaximum", "maximum"),
+    ] {
+        if let Some(limit) = fields.get(exclusive).filter(|v| v.is_number()).cloned() {
+            fields.insert(bound.into(), limit);
+            fields.insert(exclusive.into(), json!(true));
+        }
+    }
+    if !fields.contains_key("format") {
+        let encoding = fields.get("contentEncoding").and_then(Value::as_str);
+        let format = if encoding == Some("base64") {
+            Some("byte")
+        } else if fields.contains_key("contentMediaType") {
+            Some("binary")
+        } else {
+            None
+        };
+        if let Some(format) = format {
+            fields.insert("format".into(), json!(format));
+        }
+    }
+
+    for key in ["anyOf", "oneOf"] {
+        let Some(Value::Array(choices)) = fields.get_mut(key) else {
+            continue;
+        };
+        let before = choices.len();
+        choices.retain(|c| *c != json!({ "type": "null" }));
+        if choices.len() < before {
+            fields.insert("nullable".into(), json!(true));
+        }
+    }
+
+    for (key, value) in fields.iter_mut() {
+        let at = pointer(at, key);
+        match key.as_str() {
+            "properties" | "patternProperties" | "$defs" => {
+                for (name, s) in entries_mut(Some(value)) {
+                    downgrade_schema(s, &pointer(&at, name), unsupported);
+                }
+            }
+            // `additionalProperties: false` is already valid 3.0.
+            "additionalProperties" if value.is_boolean() => {}
+            "items" | "additionalProperties" | "not" | "contains" | "propertyNames" => {
+                downgrade_schema(value, &at, unsupported);
+            }
+            "allOf" | "anyOf" | "oneOf" => {
+                for (i, s) in value.as_array_mut().into_iter().flatten().enumerate() {
+                    downgrade_schema(s, &pointer(&at, &i.to_string()), unsupported);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn entries_mut(value: Option<&mut Value>) -> impl Iterator<Item = (&String, &mut Value)> {
+    return value.and_then(Value::as_object_mut).into_iter().flatten();
+}
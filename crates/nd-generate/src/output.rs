@@ -0,0 +1,139 @@
+//! Write [`nd_core::RequestFile`] values to disk as YAML or JSON, shared by every importer, plus
+//! the file naming helpers they use.
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use nd_constants::urls::{PUBLIC_REQUEST_JSON_SCHEMA_URL, PUBLIC_REQUEST_YAML_SCHEMA_URL};
+use nd_core::model::request::RequestFile;
+use nd_core::model::with_root_schema_url;
+
+use crate::error::{Error, Result};
+
+/// Serialization format for generated request definitions.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    #[default]
+    Yaml,
+    Json,
+}
+
+impl std::str::FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        return match s.to_ascii_lowercase().as_str() {
+            "yaml" | "yml" => Ok(Self::Yaml),
+            "json" => Ok(Self::Json),
+            other => Err(format!("expected yaml or json, got {other}")),
+        };
+    }
+}
+
+impl OutputFormat {
+    /// File extension (without the dot) for this format.
+    pub fn extension(self) -> &'static str {
+        return match self {
+            Self::Yaml => "yaml",
+            Self::Json => "json",
+        };
+    }
+
+    /// Format implied by a `.json` / `.yaml` / `.yml` extension, if any.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?;
+        return ext.parse().ok();
+    }
+}
+
+/// Lowercase filename stem from free text: runs of anything but `[a-z0-9_-]` become one `-`
+/// (`"Get User (v2)"` → `get-user-v2`). Empty input yields `request`.
+pub fn sanitize_stem(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+            out.push(c.to_ascii_lowercase());
+        } else {
+            out.push('-');
+        }
+    }
+    let out = out
+        .split('-')
+        .filter(|p| !p.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    if out.is_empty() {
+        "request".into()
+    } else {
+        out
+    }
+}
+
+/// Returns `stem` if unused, else `stem_2`, `stem_3`, … until unique (mutates `used`).
+pub fn unique_stem(stem: &str, used: &mut HashSet<String>) -> String {
+    if !used.contains(stem) {
+        used.insert(stem.to_string());
+        return stem.to_string();
+    }
+    let mut n = 2;
+    loop {
+        let candidate = format!("{stem}_{n}");
+        if !used.contains(&candidate) {
+            used.insert(candidate.clone());
+            return candidate;
+        }
+        n += 1;
+    }
+}
+
+/// YAML 1.1 treats `$` in plain scalars as alias syntax; quote `url:` values that contain `${...}`.
+fn quote_yaml_urls_containing_dollar(text: &str) -> String {
+    let re = regex::Regex::new(r"^(?P<prefix>\s+url:\s+)(?P<val>.+)$").expect("valid regex");
+    let mut out = String::with_capacity(text.len() + 16);
+    for line in text.lines() {
+        if let Some(cap) = re.captures(line) {
+            let val = cap.name("val").expect("val").as_str();
+            if val.contains("${") && !(val.starts_with('"') || val.starts_with('\'')) {
+                let prefix = cap.name("prefix").expect("prefix").as_str();
+                let escaped = val.replace('\\', "\\\\").replace('"', "\\\"");
+                out.push_str(prefix);
+                out.push('"');
+                out.push_str(&escaped);
+                out.push('"');
+                out.push('\n');
+                continue;
+            }
+        }
+        out.push_str(line);
+        out.push('\n');
+    }
+    return out;
+}
+
+/// Serializes `file` and writes it to `path`, creating parent directories when needed.
+pub fn write_request_file(path: &Path, file: &RequestFile, format: OutputFormat) -> Result<()> {
+    let schema_url = match format {
+        OutputFormat::Yaml => PUBLIC_REQUEST_YAML_SCHEMA_URL,
+        OutputFormat::Json => PUBLIC_REQUEST_JSON_SCHEMA_URL,
+    };
+    let value = serde_json::to_value(file).map_err(Error::Json)?;
+    let value = with_root_schema_url(value, schema_url);
+    let data = match format {
+        OutputFormat::Yaml => {
+            let raw = serde_yaml::to_string(&value).map_err(Error::Yaml)?;
+            quote_yaml_urls_containing_dollar(&raw)
+        }
+        OutputFormat::Json => serde_json::to_string_pretty(&value).map_err(Error::Json)?,
+    };
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|source| Error::Io {
+            path: parent.to_path_buf(),
+            source,
+        })?;
+    }
+    std::fs::write(path, data).map_err(|source| Error::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    return Ok(());
+}
@@ -0,0 +1,260 @@
+//! Map one Postman request onto a [`RequestFile`]: method, URL, headers, body, and auth.
+
+use nd_core::model::pairs::NameValueList;
+use nd_core::model::request::RequestFile;
+
+use super::model::{value_text, Auth, Body, Headers, KeyValue, Request, Segment, Segments, Url};
+use crate::convert::{
+    apply_auth, convert_body, convert_pairs, note, split_url, AuthSpec, BodySpec, ImportedRequest,
+    Variables,
+};
+
+/// Builds the request file for item `name`. `auth` is the effective auth (the request's own, or
+/// the nearest folder's / the collection's). URL path variable defaults go to `path_variables`.
+pub fn request_to_request_file(
+    name: &str,
+    request: &Request,
+    auth: Option<&Auth>,
+    notes: &mut Vec<String>,
+    path_variables: &mut Variables,
+) -> RequestFile {
+    let def = match request {
+        Request::Url(url) => {
+            let (url, query) = split_url(url, notes);
+            return ImportedRequest {
+                name: name.to_string(),
+                method: "GET".into(),
+                url,
+                query,
+                headers: NameValueList::new(),
+                body: None,
+                description: None,
+            }
+            .into_request_file();
+        }
+        Request::Full(def) => def,
+    };
+
+    let (url, mut query) = match &def.url {
+        Some(Url::Raw(raw)) => split_url(raw, notes),
+        Some(Url::Detailed(u)) => {
+            for v in &u.variable {
+                let Some(key) = v.key.as_deref().or(v.id.as_deref()) else {
+                    continue;
+                };
+                let value = value_text(v.value.as_ref());
+                if !value.is_empty() {
+                    path_variables.set_default(key, value);
+                }
+            }
+            let raw = match &u.raw {
+                Some(raw) => raw.clone(),
+                None => url_from_parts(
+                    u.protocol.as_deref(),
+                    u.host.as_ref(),
+                    u.port.as_deref(),
+                    u.path.as_ref(),
+                ),
+            };
+            let (url, raw_query) = split_url(&raw, notes);
+            match &u.query {
+                Some(list) => (url, enabled_pairs(list, notes)),
+                None => (url, raw_query),
+            }
+        }
+        None => (String::new(), NameValueList::new()),
+    };
+
+    let mut headers = match &def.header {
+        Some(Headers::List(list)) => enabled_pairs(list, notes),
+        Some(Headers::Raw(block)) => convert_pairs(
+            block
+                .lines()
+                .filter_map(|line| line.split_once(':'))
+                .map(|(k, v)| (k.trim(), v.trim())),
+            notes,
+        ),
+        None => NameValueList::new(),
+    };
+
+    if let Some(auth) = auth.and_then(auth_spec) {
+        apply_auth(&auth, &mut headers, &mut query, notes);
+    }
+    let body = def
+        .body
+        .as_ref()
+        .filter(|b| !b.disabled)
+        .and_then(|b| body_spec(b, &headers, notes))
+        .and_then(|b| convert_body(&b, &mut headers, notes));
+
+    return ImportedRequest {
+        name: name.to_string(),
+        method: def.method.clone().unwrap_or_else(|| "GET".into()),
+        url,
+        query,
+        headers,
+        body,
+        description: def
+            .description
+            .as_ref()
+            .and_then(|d| d.text())
+            .map(str::to_string),
+    }
+    .into_request_file();
+}
+
+fn url_from_parts(
+    protocol: Option<&str>,
+    host: Option<&Segments>,
+    port: Option<&str>,
+    path: Option<&Segments>,
+) -> String {
+    let mut out = String::new();
+    if let Some(p) = protocol {
+        out.push_str(p);
+        out.push_str("://");
+    }
+    out.push_str(&join_segments(host, "."));
+    if let Some(port) = port {
+        out.push(':');
+        out.push_str(port);
+    }
+    let path = join_segments(path, "/");
+    if !path.is_empty() {
+        if !path.starts_with('/') {
+            out.push('/');
+        }
+        out.push_str(&path);
+    }
+    return out;
+}
+
+fn join_segments(segments: Option<&Segments>, sep: &str) -> String {
+    return match segments {
+        None => String::new(),
+        Some(Segments::One(s)) => s.clone(),
+        Some(Segments::Many(parts)) => parts
+            .iter()
+            .map(|p| match p {
+                Segment::Text(s) => s.as_str(),
+                Segment::Object { value } => value.as_deref().unwrap_or(""),
+            })
+            .collect::<Vec<_>>()
+            .join(sep),
+    };
+}
+
+/// Enabled `key` / `value` pairs in order, unconverted.
+fn enabled_raw_pairs(list: &[KeyValue]) -> Vec<(String, String)> {
+    return list
+        .iter()
+        .filter(|kv| !kv.disabled)
+        .filter_map(|kv| Some((kv.key.clone()?, value_text(kv.value.as_ref()))))
+        .collect();
+}
+
+/// Enabled `key` / `value` pairs in order, with templates converted.
+fn enabled_pairs(list: &[KeyValue], notes: &mut Vec<String>) -> NameValueList {
+    let pairs = enabled_raw_pairs(list);
+    return convert_pairs(pairs.iter().map(|(k, v)| (k.as_str(), v.as_str())), notes);
+}
+
+/// Looks up `name` in the auth parameter block (`[{key, value}]` in v2.1, `{name: value}` in v2.0).
+fn auth_param(auth: &Auth, name: &str) -> Option<String> {
+    let value = match auth.params.get(&auth.kind)? {
+        serde_json::Value::Array(items) => items
+            .iter()
+            .find(|item| item.get("key").and_then(|k| k.as_str()) == Some(name))?
+            .get("value"),
+        serde_json::Value::Object(map) => map.get(name),
+        _ => None,
+    };
+    return Some(value_text(value)).filter(|v| !v.is_empty());
+}
+
+fn auth_spec(auth: &Auth) -> Option<AuthSpec> {
+    let param = |name: &str| auth_param(auth, name).unwrap_or_default();
+    return match auth.kind.as_str() {
+        "noauth" | "inherit" => None,
+        "bearer" => Some(AuthSpec::Bearer {
+            token: param("token"),
+            prefix: None,
+        }),
+        "basic" => Some(AuthSpec::Basic {
+            username: param("username"),
+            password: param("password"),
+        }),
+        "apikey" => Some(AuthSpec::ApiKey {
+            key: auth_param(auth, "key").unwrap_or_else(|| "X-API-Key".into()),
+            value: param("value"),
+            in_query: param("in") == "query",
+        }),
+        "oauth2" => match auth_param(auth, "accessToken") {
+            Some(token) if param("addTokenTo") == "queryParams" => Some(AuthSpec::ApiKey {
+                key: "access_token".into(),
+                value: token,
+                in_query: true,
+            }),
+            Some(token) => Some(AuthSpec::Bearer {
+                token,
+                prefix: None,
+            }),
+            None => Some(AuthSpec::Unsupported(
+                "oauth2 without a stored access token".into(),
+            )),
+        },
+        other => Some(AuthSpec::Unsupported(other.to_string())),
+    };
+}
+
+fn body_spec(body: &Body, headers: &NameValueList, notes: &mut Vec<String>) -> Option<BodySpec> {
+    match body.mode.as_deref() {
+        Some("raw") => {
+            let language = body
+                .options
+                .as_ref()
+                .and_then(|o| o.pointer("/raw/language"))
+                .and_then(|l| l.as_str())
+                .filter(|l| !l.is_empty());
+            let content_type = headers
+                .iter()
+                .find(|(k, _)| k.eq_ignore_ascii_case("content-type"))
+                .map(|(_, v)| v.as_str());
+            return Some(BodySpec::Raw {
+                text: body.raw.clone().unwrap_or_default(),
+                hint: language.or(content_type).unwrap_or("").to_string(),
+            });
+        }
+        Some("urlencoded") => return Some(BodySpec::Form(enabled_raw_pairs(&body.urlencoded))),
+        Some("formdata") => {
+            let mut fields = Vec::new();
+            for field in body.formdata.iter().filter(|f| !f.disabled) {
+                let Some(key) = field.key.clone() else {
+                    continue;
+                };
+                if field.kind.as_deref() == Some("file") {
+                    note(notes, format!("form-data file field {key} was dropped"));
+                    continue;
+                }
+                fields.push((key, value_text(field.value.as_ref())));
+            }
+            return Some(BodySpec::Multipart(fields));
+        }
+        Some("graphql") => {
+            let graphql = body.graphql.as_ref()?;
+            return Some(BodySpec::Graphql {
+                query: graphql.query.clone().unwrap_or_default(),
+                variables: match &graphql.variables {
+                    Some(serde_json::Value::String(text)) => Some(text.clone()),
+                    Some(serde_json::Value::Null) | None => None,
+                    Some(other) => Some(other.to_string()),
+                },
+            });
+        }
+        Some(other) => {
+            note(notes, format!("{other} bodies are not supported; body dropped"));
+            return None;
+        }
+        None => return None,
+    }
+}
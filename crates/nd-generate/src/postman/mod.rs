@@ -0,0 +1,172 @@
+//! Postman collection (v2.0 / v2.1) → a directory of nativedoctor request files.
+//!
+//! Folders become directories and requests become request files named after the item.
+//! `{{var}}` placeholders become `${var}` and dynamic variables such as `{{$guid}}` map to
+//! `${!uuidv4}` where an equivalent exists (see [`crate::convert_template`]).
+//! Headers, query parameters, raw / url-encoded / form-data / GraphQL bodies, and bearer, basic,
+//! API key, and stored OAuth 2.0 token auth are carried over; auth is inherited from folders and
+//! the collection like Postman does.
+//!
+//! Collection variables, environment values (which win), and URL path variable defaults are
+//! written to one dotenv file. Pre-request and test scripts cannot run as Rhai, so each request
+//! that has any (including inherited folder and collection scripts) gets a stub `.rhai` next to
+//! it that imports the request, with the original JavaScript kept as comments.
+
+mod build;
+mod model;
+
+use std::path::{Path, PathBuf};
+
+use crate::convert::{read_json, ImportReport, Plan, ScriptSource, Variables};
+use crate::error::{Error, Result};
+use crate::output::OutputFormat;
+use model::{value_text, Auth, Collection, Environment, Event, Item, Request};
+
+/// Options for [`import_postman`].
+#[derive(Debug, Clone, Default)]
+pub struct PostmanImportOptions {
+    /// YAML or JSON output for each request file.
+    pub format: OutputFormat,
+    /// Dotenv file for variables; defaults to `.env` inside the output directory.
+    pub env_out: Option<PathBuf>,
+}
+
+/// Reads the collection (and optional environment export) and writes the converted tree into
+/// `out_dir`. Refuses to overwrite existing files; nothing is written in that case.
+pub fn import_postman(
+    collection: &Path,
+    environment: Option<&Path>,
+    out_dir: &Path,
+    options: PostmanImportOptions,
+) -> Result<ImportReport> {
+    let collection: Collection = read_json(collection, Error::Postman)?;
+    if let Some(schema) = &collection.info.schema {
+        if !schema.contains("/v2.") {
+            return Err(Error::Postman(format!(
+                "unsupported schema {schema} (export the collection as v2.1)"
+            )));
+        }
+    }
+    let environment: Option<Environment> = environment
+        .map(|p| read_json(p, Error::Postman))
+        .transpose()?;
+
+    let mut walk = Walk {
+        plan: Plan::new(out_dir, options.format),
+        path_variables: Variables::default(),
+    };
+    let mut scripts = vec![(
+        format!("collection {:?}", collection.info.name),
+        collection.event.as_slice(),
+    )];
+    walk.items(
+        &collection.item,
+        out_dir,
+        effective_auth(collection.auth.as_ref(), None),
+        &mut scripts,
+    );
+
+    let mut variables = Variables::default();
+    for v in collection.variable.iter().filter(|v| !v.disabled) {
+        if let Some(key) = v.key.as_deref().or(v.id.as_deref()) {
+            variables.set(key, value_text(v.value.as_ref()));
+        }
+    }
+    if let Some(env) = &environment {
+        for v in env.values.iter().filter(|v| v.enabled) {
+            variables.set(&v.key, value_text(v.value.as_ref()));
+        }
+    }
+    for (k, v) in walk.path_variables.iter() {
+        variables.set_default(k, v.clone());
+    }
+
+    let mut plan = walk.plan;
+    if !variables.is_empty() {
+        let path = options.env_out.unwrap_or_else(|| out_dir.join(".env"));
+        let comment = format!(
+            "Variables imported from Postman collection {:?}",
+            collection.info.name
+        );
+        plan.env(path, variables.to_dotenv(&comment));
+    }
+    return plan.write();
+}
+
+/// `own` unless it is missing or `inherit`, else the inherited auth.
+fn effective_auth<'a>(own: Option<&'a Auth>, inherited: Option<&'a Auth>) -> Option<&'a Auth> {
+    return own.filter(|a| a.kind != "inherit").or(inherited);
+}
+
+struct Walk {
+    plan: Plan,
+    /// URL path variable defaults, written after collection and environment values.
+    path_variables: Variables,
+}
+
+impl Walk {
+    fn items<'a>(
+        &mut self,
+        items: &'a [Item],
+        dir: &Path,
+        auth: Option<&'a Auth>,
+        scripts: &mut Vec<(String, &'a [Event])>,
+    ) {
+        for item in items {
+            let stem = self.plan.stem(dir, &item.name);
+            if let Some(children) = &item.item {
+                scripts.push((format!("folder {:?}", item.name), item.event.as_slice()));
+                let folder_auth = effective_auth(item.auth.as_ref(), auth);
+                self.items(children, &dir.join(&stem), folder_auth, scripts);
+                scripts.pop();
+                continue;
+            }
+            let Some(request) = &item.request else {
+                self.plan
+                    .note(format!("item {:?} has no request; skipped", item.name));
+                continue;
+            };
+
+            let own_auth = match request {
+                Request::Full(def) => def.auth.as_ref().or(item.auth.as_ref()),
+                Request::Url(_) => item.auth.as_ref(),
+            };
+            let mut notes = Vec::new();
+            let file = build::request_to_request_file(
+                &item.name,
+                request,
+                effective_auth(own_auth, auth),
+                &mut notes,
+                &mut self.path_variables,
+            );
+
+            scripts.push((format!("request {:?}", item.name), item.event.as_slice()));
+            let sources = script_sources(scripts);
+            scripts.pop();
+            self.plan.request(dir, &stem, file, &sources, "Postman", notes);
+        }
+    }
+}
+
+/// Pre-request scripts (collection → folders → request) run before the request; test scripts after.
+fn script_sources(scripts: &[(String, &[Event])]) -> Vec<ScriptSource> {
+    let mut out = Vec::new();
+    for (origin, events) in scripts {
+        for event in events.iter().filter(|e| !e.disabled) {
+            let (before, label) = match event.listen.as_str() {
+                "prerequest" => (true, "pre-request"),
+                "test" => (false, "test"),
+                _ => continue,
+            };
+            let Some(exec) = event.script.as_ref().and_then(|s| s.exec.as_ref()) else {
+                continue;
+            };
+            out.push(ScriptSource {
+                heading: format!("{label} script from {origin}"),
+                before,
+                code: exec.lines().join("\n"),
+            });
+        }
+    }
+    return out;
+}
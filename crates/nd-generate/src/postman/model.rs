@@ -0,0 +1,262 @@
+//! Serde shapes for the parts of Postman collection (v2.0 / v2.1) and environment exports that
+//! the importer reads. Unknown fields are ignored; most fields are optional because Postman omits
+//! empty ones.
+
+use serde::Deserialize;
+use serde_json::Value;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Collection {
+    pub info: Info,
+    #[serde(default)]
+    pub item: Vec<Item>,
+    #[serde(default)]
+    pub auth: Option<Auth>,
+    #[serde(default)]
+    pub event: Vec<Event>,
+    #[serde(default)]
+    pub variable: Vec<Variable>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Info {
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub schema: Option<String>,
+}
+
+/// A folder (has `item`) or a request (has `request`).
+#[derive(Debug, Clone, Deserialize)]
+pub struct Item {
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub item: Option<Vec<Item>>,
+    #[serde(default)]
+    pub request: Option<Request>,
+    #[serde(default)]
+    pub auth: Option<Auth>,
+    #[serde(default)]
+    pub event: Vec<Event>,
+}
+
+/// `request` is either a bare URL string (GET) or a full definition.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum Request {
+    Url(String),
+    Full(Box<RequestDef>),
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct RequestDef {
+    #[serde(default)]
+    pub method: Option<String>,
+    #[serde(default)]
+    pub url: Option<Url>,
+    #[serde(default)]
+    pub header: Option<Headers>,
+    #[serde(default)]
+    pub body: Option<Body>,
+    #[serde(default)]
+    pub auth: Option<Auth>,
+    #[serde(default)]
+    pub description: Option<Description>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum Url {
+    Raw(String),
+    Detailed(UrlDef),
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct UrlDef {
+    #[serde(default)]
+    pub raw: Option<String>,
+    #[serde(default)]
+    pub protocol: Option<String>,
+    #[serde(default)]
+    pub host: Option<Segments>,
+    #[serde(default)]
+    pub port: Option<String>,
+    #[serde(default)]
+    pub path: Option<Segments>,
+    #[serde(default)]
+    pub query: Option<Vec<KeyValue>>,
+    #[serde(default)]
+    pub variable: Vec<Variable>,
+}
+
+/// `host` / `path`: one dotted or slashed string, or a list of segments.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum Segments {
+    One(String),
+    Many(Vec<Segment>),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum Segment {
+    Text(String),
+    Object {
+        #[serde(default)]
+        value: Option<String>,
+    },
+}
+
+/// Headers are usually a list, but may be exported as one raw `Name: value` block.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum Headers {
+    List(Vec<KeyValue>),
+    Raw(String),
+}
+
+/// Header, query parameter, url-encoded field, or form-data field.
+#[derive(Debug, Clone, Deserialize)]
+pub struct KeyValue {
+    #[serde(default)]
+    pub key: Option<String>,
+    #[serde(default)]
+    pub value: Option<Value>,
+    #[serde(default)]
+    pub disabled: bool,
+    /// Form-data only: `text` or `file`.
+    #[serde(default, rename = "type")]
+    pub kind: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Body {
+    #[serde(default)]
+    pub mode: Option<String>,
+    #[serde(default)]
+    pub raw: Option<String>,
+    #[serde(default)]
+    pub urlencoded: Vec<KeyValue>,
+    #[serde(default)]
+    pub formdata: Vec<KeyValue>,
+    #[serde(default)]
+    pub graphql: Option<GraphqlBody>,
+    /// `options.raw.language`: `json`, `xml`, `text`, `html`, `javascript`.
+    #[serde(default)]
+    pub options: Option<Value>,
+    #[serde(default)]
+    pub disabled: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct GraphqlBody {
+    #[serde(default)]
+    pub query: Option<String>,
+    /// JSON text (Postman stores variables as a string).
+    #[serde(default)]
+    pub variables: Option<Value>,
+}
+
+/// `type` plus one parameter block named after it: a list of `{key, value}` (v2.1) or a map (v2.0).
+#[derive(Debug, Clone, Deserialize)]
+pub struct Auth {
+    #[serde(rename = "type")]
+    pub kind: String,
+    #[serde(flatten)]
+    pub params: serde_json::Map<String, Value>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Event {
+    #[serde(default)]
+    pub listen: String,
+    #[serde(default)]
+    pub script: Option<Script>,
+    #[serde(default)]
+    pub disabled: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Script {
+    #[serde(default)]
+    pub exec: Option<Exec>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum Exec {
+    Lines(Vec<String>),
+    Text(String),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum Description {
+    Text(String),
+    Object {
+        #[serde(default)]
+        content: Option<String>,
+    },
+}
+
+/// Collection variable or URL path variable.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Variable {
+    #[serde(default)]
+    pub key: Option<String>,
+    #[serde(default)]
+    pub id: Option<String>,
+    #[serde(default)]
+    pub value: Option<Value>,
+    #[serde(default)]
+    pub disabled: bool,
+}
+
+/// Postman environment export.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Environment {
+    #[serde(default)]
+    pub values: Vec<EnvironmentValue>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct EnvironmentValue {
+    pub key: String,
+    #[serde(default)]
+    pub value: Option<Value>,
+    #[serde(default = "enabled_default")]
+    pub enabled: bool,
+}
+
+fn enabled_default() -> bool {
+    return true;
+}
+
+impl Exec {
+    pub fn lines(&self) -> Vec<&str> {
+        return match self {
+            Self::Lines(lines) => lines.iter().flat_map(|l| l.split('\n')).collect(),
+            Self::Text(text) => text.lines().collect(),
+        };
+    }
+}
+
+impl Description {
+    pub fn text(&self) -> Option<&str> {
+        return match self {
+            Self::Text(s) => Some(s.as_str()),
+            Self::Object { content } => content.as_deref(),
+        }
+        .filter(|s| !s.trim().is_empty());
+    }
+}
+
+/// Postman values are usually strings but may be numbers or booleans; `null` becomes empty.
+pub fn value_text(value: Option<&Value>) -> String {
+    return match value {
+        None | Some(Value::Null) => String::new(),
+        Some(Value::String(s)) => s.clone(),
+        Some(other) => other.to_string(),
+    };
+}
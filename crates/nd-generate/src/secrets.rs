@@ -0,0 +1,203 @@
+//! Replace recognisable credentials in an imported [`RequestFile`] with `${VAR}` placeholders.
+//!
+//! Covers `Authorization`, `Cookie`, secret-looking header and query names (`x-api-key`,
+//! `access_token`, …), and matching keys in JSON and url-encoded bodies. Values that already
+//! contain `${` are left alone.
+
+use nd_core::model::request::{RequestBody, RequestBodyKind, RequestFile};
+
+/// One value moved out of the request file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LiftedSecret {
+    /// Variable name used in the `${VAR}` placeholder.
+    pub var: String,
+    /// Original value (write it to a dotenv file, never back into the request).
+    pub value: String,
+    /// Where it was found, e.g. `header authorization` or `query api_key`.
+    pub location: String,
+}
+
+/// Rewrites `file` in place and returns what was lifted, in the order found.
+pub fn lift_secrets(file: &mut RequestFile) -> Vec<LiftedSecret> {
+    let mut lifted = Vec::new();
+    let spec = &mut file.request;
+
+    for (name, value) in spec.headers.iter_mut() {
+        let lower = name.to_ascii_lowercase();
+        let location = format!("header {lower}");
+        if lower == "authorization" {
+            *value = lift_authorization(value, &location, &mut lifted);
+        } else if lower == "cookie" || is_secret_name(name) {
+            let var = if lower == "cookie" {
+                "COOKIE".to_string()
+            } else {
+                var_name(name)
+            };
+            *value = lift(value, &var, &location, &mut lifted);
+        }
+    }
+
+    for (name, value) in spec.query.iter_mut() {
+        if is_secret_name(name) {
+            *value = lift(value, &var_name(name), &format!("query {name}"), &mut lifted);
+        }
+    }
+
+    match &mut spec.body {
+        Some(RequestBody::Json(v)) => lift_json(v, &mut lifted),
+        Some(RequestBody::Structured(s)) => match s.body_type {
+            RequestBodyKind::Json | RequestBodyKind::Graphql => {
+                lift_json(&mut s.content, &mut lifted);
+            }
+            RequestBodyKind::XWwwFormUrlencoded => {
+                if let serde_json::Value::String(text) = &s.content {
+                    let rewritten = lift_form(text, &mut lifted);
+                    s.content = serde_json::Value::String(rewritten);
+                }
+            }
+            _ => {}
+        },
+        _ => {}
+    }
+
+    return lifted;
+}
+
+/// Formats lifted secrets as dotenv lines (`VAR=value`), one per variable.
+pub fn to_dotenv(lifted: &[LiftedSecret]) -> String {
+    let mut out = String::new();
+    for s in lifted {
+        out.push_str(&format!("{}={}\n", s.var, dotenv_quote(&s.value)));
+    }
+    return out;
+}
+
+/// Leaves simple values bare; otherwise single-quotes (literal) or double-quotes with escapes.
+pub(crate) fn dotenv_quote(value: &str) -> String {
+    let bare = value
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || "-_.:/+=@".contains(c));
+    if bare && !value.is_empty() {
+        return value.to_string();
+    }
+    if !value.contains('\'') && !value.contains('\n') {
+        return format!("'{value}'");
+    }
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('$', "\\$")
+        .replace('\n', "\\n");
+    return format!("\"{escaped}\"");
+}
+
+fn lift_authorization(value: &str, location: &str, lifted: &mut Vec<LiftedSecret>) -> String {
+    let Some((scheme, credentials)) = value.split_once(' ') else {
+        return lift(value, "AUTHORIZATION", location, lifted);
+    };
+    let var = match scheme.to_ascii_lowercase().as_str() {
+        "bearer" => "AUTH_TOKEN",
+        "basic" => "BASIC_AUTH",
+        _ => return lift(value, "AUTHORIZATION", location, lifted),
+    };
+    let placeholder = lift(credentials.trim(), var, location, lifted);
+    return format!("{scheme} {placeholder}");
+}
+
+fn lift_json(value: &mut serde_json::Value, lifted: &mut Vec<LiftedSecret>) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (k, v) in map.iter_mut() {
+                match v {
+                    serde_json::Value::String(s) if is_secret_name(k) => {
+                        *s = lift(s, &var_name(k), &format!("body {k}"), lifted);
+                    }
+                    _ => lift_json(v, lifted),
+                }
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for v in items {
+                lift_json(v, lifted);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn lift_form(text: &str, lifted: &mut Vec<LiftedSecret>) -> String {
+    return text
+        .split('&')
+        .map(|pair| match pair.split_once('=') {
+            Some((k, v)) if is_secret_name(k) => {
+                format!("{k}={}", lift(v, &var_name(k), &format!("body {k}"), lifted))
+            }
+            _ => pair.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("&");
+}
+
+/// Returns the `${VAR}` placeholder for `value`, reusing a variable when the same value was
+/// already lifted and suffixing `_2`, `_3`, … when the name is taken by a different value.
+fn lift(value: &str, var: &str, location: &str, lifted: &mut Vec<LiftedSecret>) -> String {
+    if value.is_empty() || value.contains("${") {
+        return value.to_string();
+    }
+    if let Some(existing) = lifted.iter().find(|s| s.value == value) {
+        return format!("${{{}}}", existing.var);
+    }
+    let mut candidate = var.to_string();
+    let mut n = 2;
+    while lifted.iter().any(|s| s.var == candidate) {
+        candidate = format!("{var}_{n}");
+        n += 1;
+    }
+    lifted.push(LiftedSecret {
+        var: candidate.clone(),
+        value: value.to_string(),
+        location: location.to_string(),
+    });
+    return format!("${{{candidate}}}");
+}
+
+/// Header, query, and body field names treated as credentials.
+fn is_secret_name(name: &str) -> bool {
+    let n: String = name
+        .to_ascii_lowercase()
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect();
+    if matches!(n.as_str(), "key" | "auth" | "sig" | "signature" | "authorization") {
+        return true;
+    }
+    return ["token", "secret", "password", "passwd", "apikey", "credential", "session"]
+        .iter()
+        .any(|w| n.contains(w));
+}
+
+/// `x-api-key` → `X_API_KEY`.
+pub(crate) fn var_name(name: &str) -> String {
+    let upper: String = name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let joined = upper
+        .split('_')
+        .filter(|p| !p.is_empty())
+        .collect::<Vec<_>>()
+        .join("_");
+    if joined.is_empty() {
+        return "SECRET".into();
+    }
+    if joined.starts_with(|c: char| c.is_ascii_digit()) {
+        return format!("_{joined}");
+    }
+    return joined;
+}
@@ -0,0 +1,138 @@
+use std::path::Path;
+
+use nd_core::model::request::{RequestBody, RequestBodyKind, RequestFile};
+use nd_generate::bruno::{import_bruno, BrunoImportOptions};
+
+fn write(dir: &Path, rel: &str, text: &str) {
+    let path = dir.join(rel);
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(path, text).unwrap();
+}
+
+fn collection(root: &Path) {
+    write(root, "bruno.json", r#"{"version": "1", "name": "Shop", "type": "collection"}"#);
+    write(
+        root,
+        "collection.bru",
+        "headers {\n  X-Client: nativedoctor\n}\n\nauth {\n  mode: bearer\n}\n\nauth:bearer {\n  token: {{token}}\n}\n\nscript:pre-request {\n  req.setHeader(\"X-Start\", Date.now());\n}\n",
+    );
+    write(
+        root,
+        "environments/Local.bru",
+        "vars {\n  baseUrl: http://localhost:8080\n  ~debug: true\n}\nvars:secret [\n  token\n]\n",
+    );
+    write(
+        root,
+        "users/folder.bru",
+        "meta {\n  name: User Admin\n  seq: 1\n}\n\nauth {\n  mode: inherit\n}\n",
+    );
+    write(
+        root,
+        "users/Get user.bru",
+        "meta {\n  name: Get user\n  type: http\n  seq: 2\n}\n\nget {\n  url: {{baseUrl}}/users/:id?expand=orders\n  body: none\n  auth: inherit\n}\n\nparams:query {\n  expand: orders\n  ~debug: 1\n}\n\nparams:path {\n  id: 42\n}\n\nheaders {\n  x-client: override\n}\n\ntests {\n  test(\"ok\", function() {\n    expect(res.status).to.equal(200);\n  });\n}\n",
+    );
+    write(
+        root,
+        "users/Create user.bru",
+        "meta {\n  name: Create user\n  type: http\n  seq: 1\n}\n\npost {\n  url: {{baseUrl}}/users\n  body: json\n  auth: none\n}\n\nbody:json {\n  {\n    \"name\": \"{{name}}\",\n    \"age\": {{age}}\n  }\n}\n\ndocs {\n  Creates a user.\n}\n",
+    );
+    write(
+        root,
+        "Login.bru",
+        "meta {\n  name: Login\n  type: http\n  seq: 1\n}\n\npost {\n  url: {{baseUrl}}/login\n  body: formUrlEncoded\n  auth: basic\n}\n\nauth:basic {\n  username: bob\n  password: pw\n}\n\nbody:form-urlencoded {\n  remember: yes please\n  otp: {{otp}}\n}\n\nvars:pre-request {\n  otp: 000000\n}\n",
+    );
+}
+
+#[test]
+fn import_bruno_writes_folders_requests_scripts_and_environments() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path().join("shop");
+    collection(&root);
+    let out = dir.path().join("out");
+
+    let report = import_bruno(&root, &out, BrunoImportOptions::default()).unwrap();
+    let names: Vec<_> = report
+        .files_written
+        .iter()
+        .map(|p| p.strip_prefix(&out).unwrap().to_string_lossy().into_owned())
+        .collect();
+    assert_eq!(
+        names,
+        vec![
+            "user-admin/create-user.yaml",
+            "user-admin/get-user.yaml",
+            "login.yaml"
+        ]
+    );
+
+    let get = RequestFile::from_file(&out.join("user-admin/get-user.yaml")).unwrap();
+    assert_eq!(get.request.method, "GET");
+    assert_eq!(get.request.url, "${baseUrl}/users/${id}");
+    assert_eq!(get.request.query.len(), 1);
+    assert_eq!(get.request.query.get("expand"), Some("orders"));
+    assert_eq!(get.request.headers.len(), 2);
+    assert_eq!(get.request.headers.get("x-client"), Some("override"));
+    assert_eq!(get.request.headers.get("Authorization"), Some("Bearer ${token}"));
+
+    let create = RequestFile::from_file(&out.join("user-admin/create-user.yaml")).unwrap();
+    assert_eq!(create.request.method, "POST");
+    assert!(create.request.headers.get("Authorization").is_none());
+    assert_eq!(create.request.description.as_deref(), Some("Creates a user."));
+    match create.request.body {
+        Some(RequestBody::Structured(s)) => {
+            assert_eq!(s.body_type, RequestBodyKind::Json);
+            assert_eq!(
+                s.content,
+                serde_json::json!({"name": "${name}", "age": "${json:age}"})
+            );
+        }
+        other => panic!("expected JSON body, got {other:?}"),
+    }
+
+    let login = RequestFile::from_file(&out.join("login.yaml")).unwrap();
+    assert_eq!(login.request.headers.get("Authorization"), Some("Basic Ym9iOnB3"));
+    match login.request.body {
+        Some(RequestBody::Structured(s)) => {
+            assert_eq!(s.body_type, RequestBodyKind::XWwwFormUrlencoded);
+            assert_eq!(s.content, serde_json::json!("remember=yes+please&otp=${otp}"));
+        }
+        other => panic!("expected form body, got {other:?}"),
+    }
+
+    let stub = std::fs::read_to_string(out.join("user-admin/get-user.rhai")).unwrap();
+    assert!(stub.contains("import \"get-user.yaml\" as request;"));
+    let pre = stub.find("req.setHeader").unwrap();
+    let invoke = stub.find("request::invoke()").unwrap();
+    let test = stub.find("expect(res.status)").unwrap();
+    assert!(pre < invoke && invoke < test);
+    assert_eq!(report.scripts_written.len(), 3);
+
+    assert_eq!(report.env_files, vec![out.join("local.env"), out.join(".env")]);
+    let local = std::fs::read_to_string(out.join("local.env")).unwrap();
+    assert!(local.contains("baseUrl=http://localhost:8080\n"));
+    assert!(local.contains("token=''\n"));
+    assert!(!local.contains("debug"));
+    let dotenv = std::fs::read_to_string(out.join(".env")).unwrap();
+    assert!(dotenv.contains("id=42\n"));
+    assert!(dotenv.contains("otp=000000\n"));
+    assert!(report.notes.iter().any(|n| n.contains("secret values (token)")));
+}
+
+#[test]
+fn import_bruno_accepts_a_single_file_and_rejects_plain_directories() {
+    let dir = tempfile::tempdir().unwrap();
+    let root = dir.path().join("shop");
+    collection(&root);
+    let out = dir.path().join("out");
+
+    let report = import_bruno(&root.join("Login.bru"), &out, BrunoImportOptions::default()).unwrap();
+    assert_eq!(report.files_written, vec![out.join("login.yaml")]);
+
+    let err = import_bruno(&root.join("users"), &out, BrunoImportOptions::default()).unwrap_err();
+    assert!(err.to_string().contains("no bruno.json found"));
+
+    write(&root, "Broken.bru", "meta {\n  name: Broken\n");
+    let err = import_bruno(&root.join("Broken.bru"), &out, BrunoImportOptions::default())
+        .unwrap_err();
+    assert!(err.to_string().contains("block meta is not closed"));
+}
@@ -0,0 +1,178 @@
+use nd_core::model::request::{RequestBody, RequestBodyKind};
+use nd_generate::curl::{import_curl_command, split_command_line, CurlImportOptions};
+use nd_generate::secrets::to_dotenv;
+
+fn pairs(list: &nd_core::model::pairs::NameValueList) -> Vec<(&str, &str)> {
+    list.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect()
+}
+
+#[test]
+fn split_command_line_handles_quotes_and_continuations() {
+    let words = split_command_line(
+        "curl 'https://x.test/a b' \\\n  -H \"X-Q: \\\"q\\\"\" --data-raw $'{\"a\":\"it\\'s\\n\"}'",
+    )
+    .unwrap();
+    assert_eq!(
+        words,
+        vec![
+            "curl",
+            "https://x.test/a b",
+            "-H",
+            "X-Q: \"q\"",
+            "--data-raw",
+            "{\"a\":\"it's\n\"}",
+        ]
+    );
+    assert!(split_command_line("curl 'unterminated").is_err());
+}
+
+#[test]
+fn import_devtools_command_maps_method_headers_query_and_json_body() {
+    let imported = import_curl_command(
+        r#"curl 'https://api.example.test/v1/items?page=2&tag=a&tag=b' \
+          -XPUT -sSL \
+          -H 'Content-Type: application/json' \
+          -H 'accept-encoding: gzip, deflate, br' \
+          -H 'X-Trace: 1' \
+          --data-raw '{"name":"x","n":1}' \
+          --compressed -k -m 2.5"#,
+        CurlImportOptions::default(),
+    )
+    .unwrap();
+    let file = imported.file;
+
+    assert_eq!(file.name.as_deref(), Some("PUT /v1/items"));
+    assert_eq!(file.request.method, "PUT");
+    assert_eq!(file.request.url, "https://api.example.test/v1/items");
+    assert_eq!(
+        pairs(&file.request.query),
+        vec![("page", "2"), ("tag", "a"), ("tag", "b")]
+    );
+    assert_eq!(
+        pairs(&file.request.headers),
+        vec![("Content-Type", "application/json"), ("X-Trace", "1")]
+    );
+    match file.request.body {
+        Some(RequestBody::Structured(s)) => {
+            assert_eq!(s.body_type, RequestBodyKind::Json);
+            assert_eq!(s.content, serde_json::json!({"name": "x", "n": 1}));
+        }
+        other => panic!("expected JSON body, got {other:?}"),
+    }
+    assert!(file.request.follow_redirects);
+    assert!(!file.request.verify_tls);
+    assert_eq!(file.request.timeout_secs, Some(3));
+    assert!(imported.secrets.is_empty());
+    assert!(imported
+        .notes
+        .iter()
+        .any(|n| n == "dropped header accept-encoding"));
+    assert!(imported.notes.iter().any(|n| n == "ignored -s"));
+}
+
+#[test]
+fn import_data_defaults_to_post_form_body_and_get_moves_it_to_query() {
+    let post = import_curl_command(
+        "curl https://x.test/login -d user=bob --data-urlencode 'note=a b&c'",
+        CurlImportOptions::default(),
+    )
+    .unwrap()
+    .file;
+    assert_eq!(post.request.method, "POST");
+    assert!(!post.request.follow_redirects);
+    match post.request.body {
+        Some(RequestBody::Structured(s)) => {
+            assert_eq!(s.body_type, RequestBodyKind::XWwwFormUrlencoded);
+            assert_eq!(s.content, serde_json::json!("user=bob&note=a+b%26c"));
+        }
+        other => panic!("expected form body, got {other:?}"),
+    }
+
+    let get = import_curl_command(
+        "curl -G x.test/search -d q=rust --data-urlencode 'lang=en us'",
+        CurlImportOptions::default(),
+    )
+    .unwrap()
+    .file;
+    assert_eq!(get.request.method, "GET");
+    assert_eq!(get.request.url, "http://x.test/search");
+    assert_eq!(
+        pairs(&get.request.query),
+        vec![("q", "rust"), ("lang", "en us")]
+    );
+    assert!(get.request.body.is_none());
+}
+
+#[test]
+fn import_user_and_form_fields_build_basic_auth_and_multipart_body() {
+    let file = import_curl_command(
+        "curl -u bob:pw -F a=1 -F 'b=two words' https://x.test/upload",
+        CurlImportOptions::default(),
+    )
+    .unwrap()
+    .file;
+
+    assert_eq!(file.request.method, "POST");
+    assert_eq!(file.request.headers.get("Authorization"), Some("Basic Ym9iOnB3"));
+    let content_type = file.request.headers.get("Content-Type").unwrap();
+    assert!(content_type.starts_with("multipart/form-data; boundary="));
+    match file.request.body {
+        Some(RequestBody::Structured(s)) => {
+            assert_eq!(s.body_type, RequestBodyKind::FormData);
+            let text = s.content.as_str().unwrap();
+            assert!(text.contains("name=\"b\"\r\n\r\ntwo words\r\n"));
+        }
+        other => panic!("expected multipart body, got {other:?}"),
+    }
+}
+
+#[test]
+fn import_lift_secrets_replaces_credentials_with_placeholders() {
+    let imported = import_curl_command(
+        r#"curl 'https://x.test/v1?api_key=k1&page=1' -H 'Authorization: Bearer t0k' -H 'X-Api-Key: k1' -b 'sid=abc; theme=dark' --json '{"user":"bob","password":"p w","nested":{"client_secret":"s3"}}'"#,
+        CurlImportOptions { lift_secrets: true },
+    )
+    .unwrap();
+    let request = &imported.file.request;
+
+    assert_eq!(request.headers.get("Authorization"), Some("Bearer ${AUTH_TOKEN}"));
+    assert_eq!(request.headers.get("X-Api-Key"), Some("${X_API_KEY}"));
+    assert_eq!(request.headers.get("Cookie"), Some("${COOKIE}"));
+    // Same value as the header, so the variable is reused.
+    assert_eq!(request.query.get("api_key"), Some("${X_API_KEY}"));
+    assert_eq!(request.query.get("page"), Some("1"));
+    match &request.body {
+        Some(RequestBody::Structured(s)) => assert_eq!(
+            s.content,
+            serde_json::json!({
+                "user": "bob",
+                "password": "${PASSWORD}",
+                "nested": {"client_secret": "${CLIENT_SECRET}"}
+            })
+        ),
+        other => panic!("expected JSON body, got {other:?}"),
+    }
+
+    let vars: Vec<&str> = imported.secrets.iter().map(|s| s.var.as_str()).collect();
+    assert_eq!(
+        vars,
+        vec!["AUTH_TOKEN", "X_API_KEY", "COOKIE", "CLIENT_SECRET", "PASSWORD"]
+    );
+    let dotenv = to_dotenv(&imported.secrets);
+    assert!(dotenv.contains("AUTH_TOKEN=t0k\n"));
+    assert!(dotenv.contains("COOKIE='sid=abc; theme=dark'\n"));
+    assert!(dotenv.contains("PASSWORD='p w'\n"));
+}
+
+#[test]
+fn import_rejects_file_references_and_missing_url() {
+    let err = import_curl_command(
+        "curl https://x.test -d @body.json",
+        CurlImportOptions::default(),
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("reading the body from a file"));
+
+    let err = import_curl_command("curl -H 'A: b'", CurlImportOptions::default()).unwrap_err();
+    assert!(err.to_string().contains("no URL found"));
+}
@@ -0,0 +1,156 @@
+use std::path::Path;
+
+use nd_core::model::request::{RequestBody, RequestBodyKind, RequestFile};
+use nd_generate::har::{import_har, HarImportOptions};
+
+fn entry(method: &str, url: &str, headers: &[(&str, &str)], post: Option<(&str, &str)>) -> serde_json::Value {
+    let headers: Vec<_> = headers
+        .iter()
+        .map(|(n, v)| serde_json::json!({"name": n, "value": v}))
+        .collect();
+    let mut request = serde_json::json!({
+        "method": method,
+        "url": url,
+        "httpVersion": "HTTP/2.0",
+        "headers": headers,
+        "queryString": [],
+        "cookies": [],
+        "headersSize": -1,
+        "bodySize": 0
+    });
+    if let Some((mime, text)) = post {
+        request["postData"] = serde_json::json!({"mimeType": mime, "text": text});
+    }
+    serde_json::json!({
+        "startedDateTime": "2026-01-02T03:04:05.000Z",
+        "time": 12.5,
+        "request": request,
+        "response": {"status": 200, "statusText": "OK", "headers": [], "content": {"size": 0, "mimeType": ""}},
+        "timings": {"send": 1, "wait": 10, "receive": 1.5}
+    })
+}
+
+fn write_har(dir: &Path, entries: Vec<serde_json::Value>) -> std::path::PathBuf {
+    let path = dir.join("capture.har");
+    let har = serde_json::json!({"log": {"version": "1.2", "creator": {"name": "test", "version": "1"}, "entries": entries}});
+    std::fs::write(&path, serde_json::to_string(&har).unwrap()).unwrap();
+    path
+}
+
+fn capture() -> Vec<serde_json::Value> {
+    vec![
+        entry(
+            "GET",
+            "https://api.example.test/users?page=1&tag=a%20b",
+            &[
+                (":authority", "api.example.test"),
+                ("accept", "application/json"),
+                ("sec-fetch-mode", "cors"),
+                ("authorization", "Bearer tok-1"),
+            ],
+            None,
+        ),
+        entry("GET", "https://api.example.test/users?page=2", &[], None),
+        entry(
+            "POST",
+            "https://api.example.test/users",
+            &[("Content-Type", "application/json"), ("Content-Length", "15")],
+            Some(("application/json", r#"{"name":"Ada"}"#)),
+        ),
+        entry("GET", "https://cdn.example.test/app.js", &[], None),
+        entry("GET", "data:image/png;base64,AAAA", &[], None),
+    ]
+}
+
+#[test]
+fn import_har_filters_dedupes_and_maps_requests() {
+    let dir = tempfile::tempdir().unwrap();
+    let input = write_har(dir.path(), capture());
+    let out = dir.path().join("out");
+
+    let report = import_har(
+        &input,
+        &out,
+        HarImportOptions {
+            hosts: vec!["api.example.test".into()],
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    let names: Vec<_> = report
+        .files_written
+        .iter()
+        .map(|p| p.strip_prefix(&out).unwrap().to_string_lossy().into_owned())
+        .collect();
+    assert_eq!(names, vec!["get-users.yaml", "post-users.yaml"]);
+    assert!(report.notes.iter().any(|n| n.starts_with("skipped 1 repeated entries")));
+    assert!(report.notes.iter().any(|n| n.starts_with("skipped 1 entries filtered out")));
+    assert!(report.notes.iter().any(|n| n.starts_with("skipped 1 entries that are not http(s)")));
+
+    let get = RequestFile::from_file(&out.join("get-users.yaml")).unwrap();
+    assert_eq!(get.name.as_deref(), Some("GET /users"));
+    assert_eq!(get.request.url, "https://api.example.test/users");
+    assert_eq!(get.request.query.get("page"), Some("1"));
+    assert_eq!(get.request.query.get("tag"), Some("a b"));
+    assert_eq!(get.request.headers.len(), 2);
+    assert_eq!(get.request.headers.get("authorization"), Some("Bearer tok-1"));
+    assert!(get.request.headers.get("sec-fetch-mode").is_none());
+
+    let post = RequestFile::from_file(&out.join("post-users.yaml")).unwrap();
+    assert_eq!(post.request.method, "POST");
+    assert!(post.request.headers.get("Content-Length").is_none());
+    match post.request.body {
+        Some(RequestBody::Structured(s)) => {
+            assert_eq!(s.body_type, RequestBodyKind::Json);
+            assert_eq!(s.content, serde_json::json!({"name": "Ada"}));
+        }
+        other => panic!("expected JSON body, got {other:?}"),
+    }
+}
+
+#[test]
+fn import_har_groups_hosts_filters_methods_and_lifts_secrets() {
+    let dir = tempfile::tempdir().unwrap();
+    let input = write_har(dir.path(), capture());
+    let out = dir.path().join("out");
+
+    let report = import_har(
+        &input,
+        &out,
+        HarImportOptions {
+            methods: vec!["get".into()],
+            keep_duplicates: true,
+            lift_secrets: true,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    let names: Vec<_> = report
+        .files_written
+        .iter()
+        .map(|p| p.strip_prefix(&out).unwrap().to_string_lossy().into_owned())
+        .collect();
+    assert_eq!(
+        names,
+        vec![
+            "api-example-test/get-users.yaml",
+            "api-example-test/get-users_2.yaml",
+            "cdn-example-test/get-app-js.yaml"
+        ]
+    );
+
+    let get = RequestFile::from_file(&out.join("api-example-test/get-users.yaml")).unwrap();
+    let auth = get.request.headers.get("authorization").unwrap();
+    assert!(auth.starts_with("Bearer ${"), "{auth}");
+    assert_eq!(report.env_files, vec![out.join(".env")]);
+    let dotenv = std::fs::read_to_string(out.join(".env")).unwrap();
+    assert!(dotenv.contains("=tok-1\n"));
+}
+
+#[test]
+fn import_har_rejects_empty_captures() {
+    let dir = tempfile::tempdir().unwrap();
+    let input = write_har(dir.path(), vec![]);
+    let err = import_har(&input, &dir.path().join("out"), HarImportOptions::default()).unwrap_err();
+    assert!(err.to_string().contains("no entries"));
+}
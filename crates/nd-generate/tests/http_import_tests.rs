@@ -0,0 +1,57 @@
+use nd_core::model::request::{RequestBody, RequestBodyKind, RequestFile};
+use nd_generate::http_file::{import_http, HttpImportOptions};
+
+const API_HTTP: &str = r#"@host = https://shop.example.test
+
+### List users
+GET {{host}}/users?limit=10
+Authorization: Bearer {{token}}
+
+###
+# @name Create user
+POST {{host}}/users
+Content-Type: application/json
+
+{"name": "Ada", "id": "{{$guid}}"}
+"#;
+
+#[test]
+fn writes_one_request_file_per_request() {
+    let tmp = tempfile::tempdir().unwrap();
+    let input = tmp.path().join("shop.http");
+    std::fs::write(&input, API_HTTP).unwrap();
+    let out = tmp.path().join("out");
+
+    let report = import_http(&input, &out, HttpImportOptions::default()).unwrap();
+
+    assert_eq!(report.files_written.len(), 2);
+    assert!(
+        report.notes.iter().any(|n| n.contains("${token}")),
+        "{:?}",
+        report.notes
+    );
+
+    let list = RequestFile::from_file(&out.join("list-users.yaml")).unwrap();
+    assert_eq!(list.name.as_deref(), Some("List users"));
+    assert_eq!(list.request.url, "https://shop.example.test/users?limit=10");
+    assert_eq!(
+        list.request.headers.get("Authorization"),
+        Some("Bearer ${token}")
+    );
+
+    let create = RequestFile::from_file(&out.join("create-user.yaml")).unwrap();
+    assert_eq!(create.request.method, "POST");
+    match create.request.body {
+        Some(RequestBody::Structured(s)) => {
+            assert_eq!(s.body_type, RequestBodyKind::Json);
+            assert_eq!(
+                s.content,
+                serde_json::json!({"name": "Ada", "id": "${!uuidv4}"})
+            );
+        }
+        other => panic!("unexpected body: {other:?}"),
+    }
+
+    let again = import_http(&input, &out, HttpImportOptions::default());
+    assert!(again.is_err(), "existing files must not be overwritten");
+}
@@ -0,0 +1,190 @@
+use std::path::Path;
+
+use nd_core::model::request::{RequestBody, RequestBodyKind, RequestFile};
+use nd_generate::insomnia::{import_insomnia, InsomniaImportOptions};
+
+fn write_json(dir: &Path, name: &str, value: serde_json::Value) -> std::path::PathBuf {
+    let path = dir.join(name);
+    std::fs::write(&path, serde_json::to_string_pretty(&value).unwrap()).unwrap();
+    path
+}
+
+fn export() -> serde_json::Value {
+    serde_json::json!({
+        "_type": "export",
+        "__export_format": 4,
+        "resources": [
+            {"_id": "wrk_1", "_type": "workspace", "parentId": null, "name": "Shop"},
+            {
+                "_id": "env_base", "_type": "environment", "parentId": "wrk_1", "name": "Base Environment",
+                "data": {"base_url": "https://shop.example.test", "api": {"key": "k-1"}}
+            },
+            {
+                "_id": "env_dev", "_type": "environment", "parentId": "env_base", "name": "Dev",
+                "data": {"base_url": "http://localhost:8080"}
+            },
+            {
+                "_id": "fld_users", "_type": "request_group", "parentId": "wrk_1", "name": "Users",
+                "metaSortKey": -2,
+                "authentication": {"type": "bearer", "token": "{{ _.token }}"},
+                "environment": {"token": "secret"}
+            },
+            {
+                "_id": "req_list", "_type": "request", "parentId": "fld_users", "name": "List users",
+                "metaSortKey": 2,
+                "method": "GET",
+                "url": "{{ _.base_url }}/users?page=1",
+                "parameters": [
+                    {"name": "limit", "value": "10"},
+                    {"name": "off", "value": "1", "disabled": true}
+                ],
+                "headers": [{"name": "X-Request-Id", "value": "{% uuid 'v4' %}"}],
+                "authentication": {},
+                "afterResponseScript": "insomnia.test('ok', () => {});"
+            },
+            {
+                "_id": "req_create", "_type": "request", "parentId": "fld_users", "name": "Create user",
+                "metaSortKey": 1,
+                "method": "POST",
+                "url": "{{ _.base_url }}/users",
+                "body": {"mimeType": "application/json", "text": "{\"name\": \"{{ _.name }}\"}"},
+                "authentication": {"type": "none"}
+            },
+            {
+                "_id": "req_key", "_type": "request", "parentId": "wrk_1", "name": "With key",
+                "method": "GET",
+                "url": "{{ _.base_url }}/keyed",
+                "authentication": {"type": "apikey", "key": "X-Api-Key", "value": "{{ _.api.key }}"}
+            },
+            {"_id": "spc_1", "_type": "api_spec", "parentId": "wrk_1", "fileName": "Shop"}
+        ]
+    })
+}
+
+#[test]
+fn import_insomnia_writes_folders_requests_and_environments() {
+    let dir = tempfile::tempdir().unwrap();
+    let input = write_json(dir.path(), "Insomnia_export.json", export());
+    let out = dir.path().join("out");
+
+    let report = import_insomnia(&input, &out, InsomniaImportOptions::default()).unwrap();
+    let names: Vec<_> = report
+        .files_written
+        .iter()
+        .map(|p| p.strip_prefix(&out).unwrap().to_string_lossy().into_owned())
+        .collect();
+    assert_eq!(
+        names,
+        vec!["users/create-user.yaml", "users/list-users.yaml", "with-key.yaml"]
+    );
+
+    let list = RequestFile::from_file(&out.join("users/list-users.yaml")).unwrap();
+    assert_eq!(list.request.url, "${base_url}/users");
+    assert_eq!(list.request.query.get("page"), Some("1"));
+    assert_eq!(list.request.query.get("limit"), Some("10"));
+    assert!(list.request.query.get("off").is_none());
+    assert_eq!(list.request.headers.get("X-Request-Id"), Some("${!uuidv4}"));
+    assert_eq!(
+        list.request.headers.get("Authorization"),
+        Some("Bearer ${token}")
+    );
+
+    let create = RequestFile::from_file(&out.join("users/create-user.yaml")).unwrap();
+    assert!(create.request.headers.get("Authorization").is_none());
+    assert_eq!(
+        create.request.headers.get("Content-Type"),
+        Some("application/json")
+    );
+    match create.request.body {
+        Some(RequestBody::Structured(s)) => {
+            assert_eq!(s.body_type, RequestBodyKind::Json);
+            assert_eq!(s.content, serde_json::json!({"name": "${name}"}));
+        }
+        other => panic!("expected JSON body, got {other:?}"),
+    }
+
+    let keyed = RequestFile::from_file(&out.join("with-key.yaml")).unwrap();
+    assert_eq!(keyed.request.headers.get("X-Api-Key"), Some("${api_key}"));
+
+    let stub = std::fs::read_to_string(out.join("users/list-users.rhai")).unwrap();
+    assert!(stub.contains("import \"list-users.yaml\" as request;"));
+    assert!(stub.contains("// insomnia.test('ok', () => {});"));
+    assert_eq!(report.scripts_written.len(), 1);
+
+    assert_eq!(report.env_files, vec![out.join(".env"), out.join("dev.env")]);
+    let base = std::fs::read_to_string(out.join(".env")).unwrap();
+    assert!(base.contains("base_url=https://shop.example.test\n"));
+    assert!(base.contains("api_key=k-1\n"));
+    assert!(base.contains("token=secret\n"));
+    let dev = std::fs::read_to_string(out.join("dev.env")).unwrap();
+    assert!(dev.contains("base_url=http://localhost:8080\n"));
+    assert!(report.notes.iter().any(|n| n == "skipped api_spec resources"));
+}
+
+#[test]
+fn import_insomnia_multipart_replaces_the_content_type_header() {
+    let dir = tempfile::tempdir().unwrap();
+    let input = write_json(
+        dir.path(),
+        "export.json",
+        serde_json::json!({
+            "_type": "export",
+            "__export_format": 4,
+            "resources": [
+                {"_id": "wrk_1", "_type": "workspace", "parentId": null, "name": "Shop"},
+                {
+                    "_id": "req_upload", "_type": "request", "parentId": "wrk_1", "name": "Upload",
+                    "method": "POST",
+                    "url": "http://localhost/upload",
+                    "headers": [{"name": "Content-Type", "value": "multipart/form-data"}],
+                    "body": {
+                        "mimeType": "multipart/form-data",
+                        "params": [{"name": "title", "value": "{{ _.title }}"}]
+                    }
+                }
+            ]
+        }),
+    );
+    let out = dir.path().join("out");
+    import_insomnia(&input, &out, InsomniaImportOptions::default()).unwrap();
+
+    let upload = RequestFile::from_file(&out.join("upload.yaml")).unwrap();
+    let content_types: Vec<_> = upload
+        .request
+        .headers
+        .iter()
+        .filter(|(k, _)| k.eq_ignore_ascii_case("content-type"))
+        .map(|(_, v)| v.as_str())
+        .collect();
+    assert_eq!(
+        content_types,
+        vec!["multipart/form-data; boundary=nativedoctor-form-boundary"]
+    );
+    match upload.request.body {
+        Some(RequestBody::Structured(s)) => {
+            assert_eq!(s.body_type, RequestBodyKind::FormData);
+            let text = s.content.as_str().unwrap();
+            assert!(text.starts_with("--nativedoctor-form-boundary\r\n"), "{text}");
+            assert!(text.contains("name=\"title\"\r\n\r\n${title}\r\n"), "{text}");
+        }
+        other => panic!("expected multipart body, got {other:?}"),
+    }
+}
+
+#[test]
+fn import_insomnia_rejects_other_export_formats() {
+    let dir = tempfile::tempdir().unwrap();
+    let input = write_json(
+        dir.path(),
+        "old.json",
+        serde_json::json!({"__export_format": 3, "resources": []}),
+    );
+    let err = import_insomnia(&input, &dir.path().join("out"), InsomniaImportOptions::default())
+        .unwrap_err();
+    assert!(err.to_string().contains("unsupported export format 3"));
+
+    let input = write_json(dir.path(), "other.json", serde_json::json!({"item": []}));
+    let err = import_insomnia(&input, &dir.path().join("out"), InsomniaImportOptions::default())
+        .unwrap_err();
+    assert!(err.to_string().contains("missing __export_format"));
+}
@@ -0,0 +1,250 @@
+use std::path::{Path, PathBuf};
+
+use nd_generate::openapi3::{export_openapi, load_openapi, write_openapi, ExportOptions};
+use nd_generate::{generate_from_openapi_path, GenerateOptions};
+use serde_json::{json, Value};
+
+fn write(dir: &Path, name: &str, text: &str) -> PathBuf {
+    let path = dir.join(name);
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(&path, text).unwrap();
+    path
+}
+
+/// Hand-written requests for a small legacy API.
+fn collection(dir: &Path) {
+    write(
+        dir,
+        "users/get_user.yaml",
+        r#"name: Get user
+request:
+  method: GET
+  url: "${BASE_URL}/v1/users/${user_id}"
+  summary: Fetch one user
+  tags: [users]
+  query:
+    expand: profile
+  headers:
+    Authorization: "Bearer ${TOKEN}"
+    Accept: application/json
+"#,
+    );
+    write(
+        dir,
+        "users/get_user_42.yaml",
+        r#"name: Get user 42
+request:
+  method: GET
+  url: "${BASE_URL}/v1/users/42?verbose=true"
+  tags: [users, legacy]
+  headers:
+    X-Request-Id: "${!uuidv4}"
+"#,
+    );
+    write(
+        dir,
+        "users/create_user.json",
+        r#"{
+  "name": "Create user",
+  "request": {
+    "method": "POST",
+    "url": "${BASE_URL}/v1/users",
+    "description": "Registers a user.",
+    "deprecated": true,
+    "headers": {"X-Api-Key": "${API_KEY}"},
+    "body": {"type": "json", "content": {"email": "a@example.test", "age": 31, "nick": null}}
+  }
+}"#,
+    );
+    write(
+        dir,
+        "users/create_user_min.yaml",
+        r#"request:
+  method: POST
+  url: "${BASE_URL}/v1/users"
+  body:
+    type: json
+    content: {email: "${!random_email}", age: 2.5}
+"#,
+    );
+    write(dir, "notes.txt", "not a request");
+    write(dir, "broken.yaml", "request: [not, a, request]");
+}
+
+fn export(dir: &Path, har: Vec<PathBuf>) -> (Value, nd_generate::openapi3::ExportReport) {
+    let options = ExportOptions {
+        title: Some("Legacy".to_string()),
+        version: None,
+        har,
+    };
+    let report = export_openapi(&[dir.to_path_buf()], &options).unwrap();
+    (serde_json::to_value(&report.api).unwrap(), report)
+}
+
+fn param<'a>(operation: &'a Value, name: &str) -> &'a Value {
+    operation["parameters"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|p| p["name"] == name)
+        .unwrap_or_else(|| panic!("no parameter {name} in {operation}"))
+}
+
+#[test]
+fn export_groups_requests_into_operations() {
+    let tmp = tempfile::tempdir().unwrap();
+    collection(tmp.path());
+    let (api, report) = export(tmp.path(), Vec::new());
+
+    assert_eq!(report.requests, 4);
+    assert_eq!(report.operations, 2);
+    assert_eq!(report.skipped.len(), 1, "{:?}", report.skipped);
+    assert!(report.skipped[0].0.ends_with("broken.yaml"));
+
+    assert_eq!(api["openapi"], "3.0.3");
+    assert_eq!(api["info"], json!({"title": "Legacy", "version": "1.0.0"}));
+    assert_eq!(api["servers"][0]["url"], "{BASE_URL}");
+    assert!(api["servers"][0]["variables"]["BASE_URL"].is_object());
+
+    let paths = api["paths"].as_object().unwrap();
+    let keys: Vec<&str> = paths.keys().map(String::as_str).collect();
+    assert_eq!(keys, vec!["/v1/users", "/v1/users/{user_id}"]);
+
+    let get = &api["paths"]["/v1/users/{user_id}"]["get"];
+    assert_eq!(get["operationId"], "getUser");
+    assert_eq!(get["summary"], "Fetch one user");
+    assert_eq!(get["tags"], json!(["users", "legacy"]));
+    assert!(get.get("deprecated").is_none());
+    // `${user_id}` in one request and `42` in the other.
+    let user_id = param(get, "user_id");
+    assert_eq!(user_id["in"], "path");
+    assert_eq!(user_id["required"], true);
+    assert_eq!(user_id["schema"], json!({"type": "integer"}));
+    // Each query parameter appears in only one of the two requests.
+    let expand = param(get, "expand");
+    assert_eq!(expand["in"], "query");
+    assert!(expand.get("required").is_none(), "{expand}");
+    assert_eq!(param(get, "verbose")["schema"], json!({"type": "boolean"}));
+    assert_eq!(param(get, "X-Request-Id")["in"], "header");
+    assert!(get["parameters"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .all(|p| p["name"] != "Authorization" && p["name"] != "Accept"));
+    assert_eq!(get["security"], json!([{"bearerAuth": []}]));
+    assert_eq!(
+        get["responses"],
+        json!({"default": {"description": "Response not recorded"}})
+    );
+
+    let post = &api["paths"]["/v1/users"]["post"];
+    assert_eq!(post["operationId"], "createUser");
+    assert_eq!(post["description"], "Registers a user.");
+    // Only one of the two requests is deprecated.
+    assert!(post.get("deprecated").is_none());
+    assert_eq!(post["security"], json!([{"X_Api_Key": []}]));
+    assert_eq!(
+        api["components"]["securitySchemes"],
+        json!({
+            "bearerAuth": {"type": "http", "scheme": "bearer"},
+            "X_Api_Key": {"type": "apiKey", "in": "header", "name": "X-Api-Key"},
+        })
+    );
+}
+
+#[test]
+fn export_infers_request_body_schemas() {
+    let tmp = tempfile::tempdir().unwrap();
+    collection(tmp.path());
+    let (api, _) = export(tmp.path(), Vec::new());
+
+    let body = &api["paths"]["/v1/users"]["post"]["requestBody"];
+    assert_eq!(body["required"], true);
+    let media = &body["content"]["application/json"];
+    assert_eq!(
+        media["schema"],
+        json!({
+            "type": "object",
+            "properties": {
+                "email": {"type": "string", "format": "email"},
+                "age": {"type": "number"},
+                "nick": {"nullable": true},
+            },
+            "required": ["age", "email"],
+        })
+    );
+    // The example is the first body without placeholders.
+    assert_eq!(
+        media["example"],
+        json!({"email": "a@example.test", "age": 31, "nick": null})
+    );
+}
+
+#[test]
+fn export_documents_recorded_responses() {
+    let tmp = tempfile::tempdir().unwrap();
+    collection(tmp.path());
+    let entry = |method: &str, url: &str, status: u16, text: &str, mime: &str| {
+        json!({
+            "request": {"method": method, "url": url},
+            "response": {
+                "status": status,
+                "statusText": if status == 200 { "OK" } else { "" },
+                "content": {"mimeType": mime, "text": text},
+            },
+        })
+    };
+    let har = json!({"log": {"version": "1.2", "entries": [
+        entry("GET", "https://api.example.test/v1/users/7", 200,
+              r#"{"id": 7, "name": "Ada", "created": "2024-01-02T03:04:05Z"}"#, "application/json; charset=utf-8"),
+        entry("GET", "https://api.example.test/v1/users/8", 200,
+              r#"{"id": 8, "name": null}"#, "application/json"),
+        entry("GET", "https://api.example.test/v1/users/9", 404, "missing", "text/plain"),
+        entry("DELETE", "https://api.example.test/v1/users/9", 204, "", ""),
+    ]}});
+    let har_path = write(tmp.path(), "capture.har", &har.to_string());
+    let (api, report) = export(tmp.path(), vec![har_path]);
+
+    assert_eq!(report.recorded, 3);
+    let responses = &api["paths"]["/v1/users/{user_id}"]["get"]["responses"];
+    assert!(responses.get("default").is_none());
+    assert_eq!(responses["200"]["description"], "OK");
+    assert_eq!(
+        responses["200"]["content"]["application/json"]["schema"],
+        json!({
+            "type": "object",
+            "properties": {
+                "id": {"type": "integer"},
+                "name": {"type": "string", "nullable": true},
+                "created": {"type": "string", "format": "date-time"},
+            },
+            "required": ["id", "name"],
+        })
+    );
+    assert_eq!(responses["404"]["description"], "Status 404");
+    assert_eq!(responses["404"]["content"], json!({"text/plain": {}}));
+}
+
+#[test]
+fn exported_spec_generates_equivalent_requests() {
+    let tmp = tempfile::tempdir().unwrap();
+    collection(tmp.path());
+    let (_, report) = export(tmp.path(), Vec::new());
+    let spec = tmp.path().join("spec/openapi.yaml");
+    write_openapi(&spec, &report.api).unwrap();
+
+    let text = std::fs::read_to_string(&spec).unwrap();
+    assert!(text.starts_with("openapi: 3.0.3\n"), "{text}");
+    let reloaded = load_openapi(&spec).unwrap();
+    assert_eq!(reloaded, report.api);
+
+    let out = tmp.path().join("generated");
+    generate_from_openapi_path(&spec, &out, GenerateOptions::default()).unwrap();
+    let get = nd_core::model::request::RequestFile::from_file(&out.join("getuser.yaml")).unwrap();
+    assert_eq!(get.request.method, "GET");
+    assert_eq!(get.request.url, "${BASE_URL}/v1/users/${user_id}");
+    assert_eq!(
+        get.openapi.map(|l| l.operation_id).as_deref(),
+        Some("getUser")
+    );
+}
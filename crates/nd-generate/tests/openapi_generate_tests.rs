@@ -0,0 +1,611 @@
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use nd_core::env::RuntimeEnv;
+use nd_core::model::request::{RequestBody, RequestFile};
+use nd_core::rhai::{resolver::RhaiScriptRunOptions, run::run_rhai_script};
+use nd_core::stream::Session;
+use nd_generate::{generate_from_openapi_path, GenerateOptions};
+use serde_json::json;
+
+fn generate(dir: &Path, spec: &str) -> std::path::PathBuf {
+    let input = dir.join("openapi.yaml");
+    std::fs::write(&input, spec).unwrap();
+    let out = dir.join("out");
+    generate_from_openapi_path(&input, &out, GenerateOptions::default()).unwrap();
+    out
+}
+
+fn json_body(file: &RequestFile) -> serde_json::Value {
+    match &file.request.body {
+        Some(RequestBody::Structured(s)) => s.content.clone(),
+        other => panic!("expected a structured JSON body, got {other:?}"),
+    }
+}
+
+const SPEC: &str = r##"
+openapi: 3.0.3
+info: {title: Shop, version: "1"}
+servers: [{url: "https://shop.example.test"}]
+paths:
+  /orders:
+    post:
+      operationId: createOrder
+      requestBody:
+        content:
+          application/json:
+            schema: {$ref: "#/components/schemas/NewOrder"}
+      responses: {"201": {description: created}}
+  /notes:
+    post:
+      operationId: createNote
+      requestBody:
+        content:
+          application/json:
+            schema: {type: object, properties: {text: {type: string}}}
+            example: {text: "from the spec"}
+      responses: {"201": {description: created}}
+components:
+  schemas:
+    Base:
+      type: object
+      required: [customer_id]
+      properties:
+        id: {type: string, format: uuid, readOnly: true}
+        customer_id: {type: integer}
+    NewOrder:
+      allOf:
+        - $ref: "#/components/schemas/Base"
+        - type: object
+          required: [items]
+          properties:
+            status: {type: string, enum: [pending, paid]}
+            email: {type: string, format: email}
+            placed_at: {type: string, format: date-time}
+            quantity: {type: integer, minimum: 1}
+            gift: {type: boolean, default: false}
+            note: {type: string, example: "leave at the door"}
+            items:
+              type: array
+              items: {$ref: "#/components/schemas/Item"}
+            payment:
+              oneOf:
+                - {type: object, properties: {card: {type: string}}}
+                - {type: object, properties: {iban: {type: string}}}
+    Item:
+      type: object
+      required: [sku]
+      properties:
+        sku: {type: string}
+        ref: {type: string, format: uuid}
+        parent: {$ref: "#/components/schemas/Item"}
+"##;
+
+#[test]
+fn request_bodies_are_synthesised_from_schemas() {
+    let tmp = tempfile::tempdir().unwrap();
+    let out = generate(tmp.path(), SPEC);
+
+    let order = RequestFile::from_file(&out.join("createorder.yaml")).unwrap();
+    assert_eq!(
+        json_body(&order),
+        json!({
+            "customer_id": "${json:customer_id}",
+            "status": "pending",
+            "email": "${!random_email}",
+            "placed_at": "${!iso_timestamp}",
+            "quantity": 1,
+            "gift": false,
+            "note": "leave at the door",
+            "items": [{"sku": "string", "ref": "${!uuidv4}"}],
+            "payment": {"card": "string"}
+        })
+    );
+}
+
+#[test]
+fn media_type_example_wins_over_the_schema() {
+    let tmp = tempfile::tempdir().unwrap();
+    let out = generate(tmp.path(), SPEC);
+
+    let note = RequestFile::from_file(&out.join("createnote.yaml")).unwrap();
+    assert_eq!(json_body(&note), json!({"text": "from the spec"}));
+}
+
+const REFS_SPEC: &str = r##"
+openapi: 3.0.3
+info: {title: Refs, version: "1"}
+servers: [{url: "https://api.example.test"}]
+paths:
+  /users/{user_id}:
+    parameters:
+      - $ref: "#/components/parameters/UserId"
+      - {name: limit, in: query, schema: {type: integer}}
+    put:
+      operationId: updateUser
+      parameters:
+        - $ref: "#/components/parameters/Limit"
+        - $ref: "#/components/parameters/Trace"
+      requestBody: {$ref: "#/components/requestBodies/UserBody"}
+      responses: {"200": {description: ok}}
+  /people/{user_id}:
+    $ref: "#/paths/~1users~1%7Buser_id%7D"
+components:
+  parameters:
+    UserId: {name: user_id, in: path, required: true, schema: {type: string}}
+    Limit: {$ref: "#/components/parameters/PageSize"}
+    PageSize: {name: limit, in: query, schema: {type: integer, default: 20}}
+    Trace: {name: X-Trace, in: header, schema: {type: string}}
+  requestBodies:
+    UserBody:
+      content:
+        application/json:
+          schema: {$ref: "#/components/schemas/User"}
+  schemas:
+    User:
+      type: object
+      properties:
+        name: {type: string}
+"##;
+
+#[test]
+fn local_refs_are_resolved_for_parameters_bodies_and_path_items() {
+    let tmp = tempfile::tempdir().unwrap();
+    let out = generate(tmp.path(), REFS_SPEC);
+
+    let update = RequestFile::from_file(&out.join("updateuser.yaml")).unwrap();
+    assert_eq!(update.request.method, "PUT");
+    assert_eq!(
+        update.request.url,
+        "https://api.example.test/users/${user_id}"
+    );
+    assert_eq!(update.request.query.len(), 1, "{:?}", update.request.query);
+    assert!(update.request.headers.get("X-Trace").is_some());
+    assert_eq!(json_body(&update), json!({"name": "string"}));
+
+    let written = std::fs::read_dir(&out)
+        .unwrap()
+        .filter(|e| {
+            e.as_ref()
+                .unwrap()
+                .path()
+                .extension()
+                .is_some_and(|x| x == "yaml")
+        })
+        .count();
+    assert_eq!(written, 2, "the /people path item reuses /users");
+}
+
+#[test]
+fn ref_cycles_are_reported() {
+    let spec = REFS_SPEC.replace(
+        "PageSize: {name: limit, in: query, schema: {type: integer, default: 20}}",
+        "PageSize: {$ref: \"#/components/parameters/Limit\"}",
+    );
+    let tmp = tempfile::tempdir().unwrap();
+    let input = tmp.path().join("openapi.yaml");
+    std::fs::write(&input, spec).unwrap();
+
+    let err =
+        generate_from_openapi_path(&input, tmp.path().join("out"), GenerateOptions::default())
+            .unwrap_err();
+    assert!(err.to_string().contains("cycle"), "{err}");
+}
+
+const SWAGGER_SPEC: &str = r##"
+swagger: "2.0"
+info: {title: Pets, version: "1"}
+host: pets.example.test
+basePath: /v2
+schemes: [https]
+consumes: [application/json]
+parameters:
+  PetBody:
+    name: body
+    in: body
+    required: true
+    schema: {$ref: "#/definitions/Pet"}
+paths:
+  /pets/{petId}:
+    parameters:
+      - {name: petId, in: path, type: string}
+    put:
+      operationId: updatePet
+      parameters:
+        - $ref: "#/parameters/PetBody"
+        - {name: X-Request-Id, in: header, type: string}
+      responses:
+        200:
+          description: ok
+          schema: {$ref: "#/definitions/Pet"}
+  /pets/{petId}/photo:
+    post:
+      operationId: uploadPhoto
+      consumes: [multipart/form-data]
+      parameters:
+        - {name: petId, in: path, required: true, type: string}
+        - {name: caption, in: formData, type: string}
+        - {name: file, in: formData, type: file, required: true}
+      responses:
+        201: {description: created}
+definitions:
+  Pet:
+    type: object
+    required: [name]
+    properties:
+      name: {type: string, example: Rex}
+      tag: {type: string, x-nullable: true}
+"##;
+
+#[test]
+fn swagger_2_documents_are_converted() {
+    let tmp = tempfile::tempdir().unwrap();
+    let out = generate(tmp.path(), SWAGGER_SPEC);
+
+    let update = RequestFile::from_file(&out.join("updatepet.yaml")).unwrap();
+    assert_eq!(update.request.method, "PUT");
+    assert_eq!(
+        update.request.url,
+        "https://pets.example.test/v2/pets/${petId}"
+    );
+    assert!(update.request.headers.get("X-Request-Id").is_some());
+    assert_eq!(json_body(&update), json!({"name": "Rex", "tag": "string"}));
+
+    let api = nd_generate::openapi3::load_openapi(&tmp.path().join("openapi.yaml")).unwrap();
+    let upload = api.paths.paths["/pets/{petId}/photo"]
+        .as_item()
+        .and_then(|item| item.post.as_ref())
+        .unwrap();
+    let body = upload.request_body.as_ref().unwrap().as_item().unwrap();
+    assert!(body.content.contains_key("multipart/form-data"));
+}
+
+const OPENAPI_31_SPEC: &str = r##"{
+  "openapi": "3.1.0",
+  "info": {"title": "Notes", "version": "1"},
+  "servers": [{"url": "https://notes.example.test"}],
+  "paths": {
+    "/notes": {"$ref": "#/components/pathItems/Notes"}
+  },
+  "webhooks": {
+    "noteCreated": {"post": {"responses": {"200": {"description": "ok"}}}}
+  },
+  "components": {
+    "pathItems": {
+      "Notes": {
+        "post": {
+          "operationId": "createNote",
+          "requestBody": {
+            "content": {
+              "application/json": {"schema": {"$ref": "#/components/schemas/Note"}}
+            }
+          },
+          "responses": {"201": {"description": "created"}}
+        }
+      }
+    },
+    "schemas": {
+      "Note": {
+        "type": "object",
+        "properties": {
+          "title": {"type": ["string", "null"], "examples": ["Groceries"]},
+          "kind": {"const": "note"},
+          "priority": {"type": "integer", "exclusiveMinimum": 0},
+          "extra": true
+        }
+      }
+    }
+  }
+}"##;
+
+#[test]
+fn openapi_3_1_documents_are_converted() {
+    let tmp = tempfile::tempdir().unwrap();
+    let input = tmp.path().join("openapi.json");
+    std::fs::write(&input, OPENAPI_31_SPEC).unwrap();
+    let out = tmp.path().join("out");
+    let report = generate_from_openapi_path(&input, &out, GenerateOptions::default()).unwrap();
+    assert_eq!(report.files_written.len(), 1, "webhooks are not requests");
+
+    let note = RequestFile::from_file(&out.join("createnote.yaml")).unwrap();
+    assert_eq!(note.request.url, "https://notes.example.test/notes");
+    assert_eq!(
+        json_body(&note),
+        json!({"title": "Groceries", "kind": "note", "priority": 0, "extra": null})
+    );
+}
+
+#[test]
+fn unconvertible_constructs_are_listed() {
+    let spec = OPENAPI_31_SPEC
+        .replace(
+            r#""extra": true"#,
+            r#""point": {"type": "array", "prefixItems": [{"type": "number"}]}"#,
+        )
+        .replace(
+            r#""type": "object","#,
+            r#""type": "object", "if": {"required": ["kind"]}, "then": {"required": ["title"]},"#,
+        );
+    let tmp = tempfile::tempdir().unwrap();
+    let input = tmp.path().join("openapi.json");
+    std::fs::write(&input, spec).unwrap();
+
+    let err =
+        generate_from_openapi_path(&input, tmp.path().join("out"), GenerateOptions::default())
+            .unwrap_err();
+    let message = err.to_string();
+    assert!(message.contains("OpenAPI 3.1"), "{message}");
+    assert!(
+        message.contains("`if` at #/components/schemas/Note"),
+        "{message}"
+    );
+    assert!(
+        message.contains("`then` at #/components/schemas/Note"),
+        "{message}"
+    );
+    assert!(
+        message.contains("`prefixItems` at #/components/schemas/Note/properties/point"),
+        "{message}"
+    );
+}
+
+const SECURITY_SPEC: &str = r##"
+openapi: 3.0.3
+info: {title: Billing, version: "1"}
+servers:
+  - url: "https://{region}.billing.example.test/{version}"
+    variables:
+      region: {default: eu, enum: [eu, us], description: Data region}
+      version: {default: v1}
+security:
+  - bearerAuth: []
+paths:
+  /invoices/{invoice_id}:
+    get:
+      operationId: getInvoice
+      parameters:
+        - {name: invoice_id, in: path, required: true, schema: {type: string}}
+        - {name: Authorization, in: header, schema: {type: string}}
+      responses: {"200": {description: ok}}
+  /reports:
+    get:
+      operationId: listReports
+      security:
+        - {}
+        - apiKey: []
+          basic: []
+      responses: {"200": {description: ok}}
+  /health:
+    get:
+      operationId: health
+      security: []
+      responses: {"200": {description: ok}}
+components:
+  securitySchemes:
+    bearerAuth: {type: http, scheme: bearer, bearerFormat: JWT}
+    apiKey: {type: apiKey, in: query, name: api-key}
+    basic: {type: http, scheme: basic}
+"##;
+
+#[test]
+fn security_and_server_variables_become_placeholders() {
+    let tmp = tempfile::tempdir().unwrap();
+    let out = generate(tmp.path(), SECURITY_SPEC);
+
+    let invoice = RequestFile::from_file(&out.join("getinvoice.yaml")).unwrap();
+    assert_eq!(
+        invoice.request.url,
+        "https://${region}.billing.example.test/${version}/invoices/${invoice_id}"
+    );
+    assert_eq!(
+        invoice.request.headers.get("Authorization"),
+        Some("Bearer ${AUTH_TOKEN}")
+    );
+
+    let reports = RequestFile::from_file(&out.join("listreports.yaml")).unwrap();
+    assert_eq!(reports.request.query.get("api-key"), Some("${API_KEY}"));
+    assert_eq!(
+        reports.request.headers.get("Authorization"),
+        Some("Basic ${BASIC_AUTH}")
+    );
+
+    let health = RequestFile::from_file(&out.join("health.yaml")).unwrap();
+    assert!(health.request.headers.is_empty());
+
+    let env = std::fs::read_to_string(out.join(".env.example")).unwrap();
+    let lines: Vec<&str> = env.lines().filter(|l| !l.starts_with('#')).collect();
+    assert_eq!(
+        lines,
+        [
+            "region=eu",
+            "version=v1",
+            "AUTH_TOKEN=",
+            "API_KEY=",
+            "BASIC_AUTH=",
+            "invoice_id="
+        ]
+    );
+    assert!(env.contains("# Data region (one of: eu, us)"), "{env}");
+    assert!(env.contains("# bearerAuth: bearer token (JWT)"), "{env}");
+}
+
+const TAGGED_SPEC: &str = r##"
+openapi: 3.0.3
+info: {title: Shop, version: "1"}
+servers: [{url: "https://shop.example.test"}]
+paths:
+  /users/{id}:
+    get: {operationId: getUser, tags: [users], responses: {"200": {description: ok}}}
+    delete: {operationId: deleteUser, tags: [users, admin], responses: {"204": {description: gone}}}
+  /orders:
+    get: {operationId: listOrders, tags: [Order History], responses: {"200": {description: ok}}}
+  /health:
+    get: {operationId: health, responses: {"200": {description: ok}}}
+"##;
+
+#[test]
+fn by_tag_writes_subdirectories_and_index_scripts() {
+    let tmp = tempfile::tempdir().unwrap();
+    let input = tmp.path().join("openapi.yaml");
+    std::fs::write(&input, TAGGED_SPEC).unwrap();
+    let out = tmp.path().join("out");
+    let options = GenerateOptions {
+        by_tag: true,
+        ..Default::default()
+    };
+    let report = generate_from_openapi_path(&input, &out, options).unwrap();
+
+    assert!(out.join("users/getuser.yaml").is_file());
+    assert!(out.join("users/deleteuser.yaml").is_file());
+    assert!(out.join("order-history/listorders.yaml").is_file());
+    assert!(out.join("health.yaml").is_file());
+    assert_eq!(report.index_scripts.len(), 3);
+
+    let link = RequestFile::from_file(&out.join("users/getuser.yaml"))
+        .unwrap()
+        .openapi
+        .unwrap();
+    assert_eq!(link.operation_id, "getUser");
+    assert_eq!(link.spec.as_deref(), Some("../../openapi.yaml"));
+
+    let users = std::fs::read_to_string(out.join("users/index.rhai")).unwrap();
+    assert!(
+        users.contains("import \"getuser.yaml\" as getuser;"),
+        "{users}"
+    );
+    assert!(
+        users.contains("import \"deleteuser.yaml\" as deleteuser;"),
+        "{users}"
+    );
+    let root = std::fs::read_to_string(out.join("index.rhai")).unwrap();
+    assert!(
+        root.contains("import \"users/index.rhai\" as users;"),
+        "{root}"
+    );
+    assert!(
+        root.contains("import \"order-history/index.rhai\" as order_history;"),
+        "{root}"
+    );
+    assert!(root.contains("import \"health.yaml\" as health;"), "{root}");
+
+    // The index chain resolves as Rhai modules.
+    let main = tmp.path().join("main.rhai");
+    std::fs::write(&main, "import \"out/index.rhai\" as api;\n").unwrap();
+    let session = Session::new(|| Ok(RuntimeEnv::new()), None).unwrap();
+    run_rhai_script(
+        &main,
+        Arc::new(Mutex::new(session)),
+        RhaiScriptRunOptions::default(),
+    )
+    .unwrap();
+}
+
+const SYNC_SPEC_V1: &str = r##"
+openapi: 3.0.3
+info: {title: Users, version: "1"}
+servers: [{url: "https://users.example.test"}]
+paths:
+  /users/{id}:
+    parameters: [{name: id, in: path, required: true, schema: {type: string}}]
+    get:
+      operationId: getUser
+      parameters: [{name: fields, in: query, schema: {type: string}}]
+      responses: {"200": {description: ok}}
+    delete: {operationId: deleteUser, responses: {"204": {description: gone}}}
+  /users:
+    post:
+      operationId: createUser
+      requestBody:
+        content:
+          application/json:
+            schema: {type: object, properties: {name: {type: string}}}
+      responses: {"201": {description: created}}
+"##;
+
+const SYNC_SPEC_V2: &str = r##"
+openapi: 3.0.3
+info: {title: Users, version: "2"}
+servers: [{url: "https://users.example.test"}]
+paths:
+  /v2/users/{id}:
+    parameters: [{name: id, in: path, required: true, schema: {type: string}}]
+    get:
+      operationId: getUser
+      summary: Fetch one user
+      parameters:
+        - {name: fields, in: query, schema: {type: string}}
+        - {name: expand, in: query, schema: {type: string}}
+      responses: {"200": {description: ok}}
+  /users:
+    post:
+      operationId: createUser
+      requestBody:
+        content:
+          application/json:
+            schema: {type: object, properties: {name: {type: string}}}
+      responses: {"201": {description: created}}
+    get: {operationId: listUsers, responses: {"200": {description: ok}}}
+"##;
+
+#[test]
+fn sync_updates_spec_fields_and_keeps_user_edits() {
+    let tmp = tempfile::tempdir().unwrap();
+    let out = generate(tmp.path(), SYNC_SPEC_V1);
+
+    let get_path = out.join("getuser.yaml");
+    let mut get = RequestFile::from_file(&get_path).unwrap();
+    assert_eq!(get.openapi.as_ref().unwrap().operation_id, "getUser");
+    get.name = Some("Fetch Alice".into());
+    get.request.headers.insert("X-Trace", "on");
+    get.request.query.insert("fields", "name,email");
+    nd_generate::write_request_file(&get_path, &get, nd_generate::OutputFormat::Yaml).unwrap();
+    let create_path = out.join("createuser.yaml");
+    let create_before = std::fs::read_to_string(&create_path).unwrap();
+
+    let input = tmp.path().join("openapi.yaml");
+    std::fs::write(&input, SYNC_SPEC_V2).unwrap();
+    let options = GenerateOptions {
+        sync: true,
+        deprecate_removed: true,
+        ..Default::default()
+    };
+    let report = generate_from_openapi_path(&input, &out, options).unwrap();
+    let sync = report.sync.unwrap();
+    assert_eq!(sync.added, vec![out.join("listusers.yaml")]);
+    assert_eq!(sync.changed, vec![get_path.clone()]);
+    assert_eq!(sync.unchanged, vec![create_path.clone()]);
+    assert_eq!(sync.deprecated, vec![out.join("deleteuser.yaml")]);
+    assert!(sync.removed.is_empty());
+
+    let get = RequestFile::from_file(&get_path).unwrap();
+    assert_eq!(get.name.as_deref(), Some("Fetch Alice"));
+    assert!(
+        get.request.url.ends_with("/v2/users/${id}"),
+        "{}",
+        get.request.url
+    );
+    assert_eq!(get.request.summary.as_deref(), Some("Fetch one user"));
+    assert_eq!(get.request.headers.get("X-Trace"), Some("on"));
+    assert_eq!(get.request.query.get("fields"), Some("name,email"));
+    assert_eq!(get.request.query.get("expand"), Some(""));
+    assert_eq!(
+        std::fs::read_to_string(&create_path).unwrap(),
+        create_before
+    );
+    let deleted = RequestFile::from_file(&out.join("deleteuser.yaml")).unwrap();
+    assert!(deleted.request.deprecated);
+    let index = std::fs::read_to_string(out.join("index.rhai")).unwrap();
+    assert!(index.contains("as listusers;"), "{index}");
+    assert!(index.contains("as deleteuser;"), "{index}");
+
+    // Without --deprecate-removed the stale file goes away.
+    let options = GenerateOptions {
+        sync: true,
+        ..Default::default()
+    };
+    let report = generate_from_openapi_path(&input, &out, options).unwrap();
+    let sync = report.sync.unwrap();
+    assert_eq!(sync.removed, vec![out.join("deleteuser.yaml")]);
+    assert!(sync.added.is_empty() && sync.changed.is_empty(), "{sync:?}");
+    assert!(!out.join("deleteuser.yaml").exists());
+}
@@ -0,0 +1,258 @@
+use std::path::Path;
+
+use nd_core::model::request::{RequestBody, RequestBodyKind, RequestFile};
+use nd_generate::postman::{import_postman, PostmanImportOptions};
+use nd_generate::OutputFormat;
+
+fn pairs(list: &nd_core::model::pairs::NameValueList) -> Vec<(&str, &str)> {
+    list.iter().map(|(k, v)| (k.as_str(), v.as_str())).collect()
+}
+
+fn write_json(dir: &Path, name: &str, value: serde_json::Value) -> std::path::PathBuf {
+    let path = dir.join(name);
+    std::fs::write(&path, serde_json::to_string_pretty(&value).unwrap()).unwrap();
+    path
+}
+
+fn collection() -> serde_json::Value {
+    serde_json::json!({
+        "info": {
+            "name": "Shop API",
+            "schema": "https://schema.getpostman.com/json/collection/v2.1.0/collection.json"
+        },
+        "auth": {"type": "bearer", "bearer": [{"key": "token", "value": "{{access-token}}", "type": "string"}]},
+        "variable": [
+            {"key": "baseUrl", "value": "https://shop.example.test"},
+            {"key": "access-token", "value": "from-collection"}
+        ],
+        "event": [
+            {"listen": "prerequest", "script": {"type": "text/javascript", "exec": ["console.log('collection');"]}}
+        ],
+        "item": [
+            {
+                "name": "Users",
+                "item": [
+                    {
+                        "name": "Get user",
+                        "request": {
+                            "method": "GET",
+                            "header": [
+                                {"key": "X-Trace", "value": "{{$guid}}"},
+                                {"key": "X-Off", "value": "1", "disabled": true}
+                            ],
+                            "url": {
+                                "raw": "{{baseUrl}}/users/:id?expand=orders&expand=cart",
+                                "host": ["{{baseUrl}}"],
+                                "path": ["users", ":id"],
+                                "query": [
+                                    {"key": "expand", "value": "orders"},
+                                    {"key": "expand", "value": "cart"},
+                                    {"key": "debug", "value": "1", "disabled": true}
+                                ],
+                                "variable": [{"key": "id", "value": "42"}]
+                            }
+                        },
+                        "event": [
+                            {"listen": "test", "script": {"exec": ["pm.test(\"ok\", function () {", "  pm.response.to.have.status(200);", "});"]}}
+                        ]
+                    },
+                    {
+                        "name": "Create user",
+                        "request": {
+                            "method": "post",
+                            "auth": {"type": "noauth"},
+                            "header": [{"key": "Content-Type", "value": "application/json"}],
+                            "body": {
+                                "mode": "raw",
+                                "raw": "{\"name\": \"{{name}}\", \"age\": {{age}}}",
+                                "options": {"raw": {"language": "json"}}
+                            },
+                            "url": "{{baseUrl}}/users"
+                        }
+                    }
+                ]
+            },
+            {
+                "name": "Login",
+                "request": {
+                    "method": "POST",
+                    "auth": {"type": "basic", "basic": [
+                        {"key": "username", "value": "bob"},
+                        {"key": "password", "value": "pw"}
+                    ]},
+                    "body": {"mode": "urlencoded", "urlencoded": [
+                        {"key": "remember", "value": "yes please"},
+                        {"key": "otp", "value": "{{otp}}"}
+                    ]},
+                    "url": {"raw": "{{baseUrl}}/login"}
+                }
+            },
+            {
+                "name": "Upload",
+                "request": {
+                    "method": "POST",
+                    "auth": {"type": "apikey", "apikey": [
+                        {"key": "key", "value": "api_key"},
+                        {"key": "value", "value": "{{apiKey}}"},
+                        {"key": "in", "value": "query"}
+                    ]},
+                    "body": {"mode": "formdata", "formdata": [
+                        {"key": "title", "value": "cat", "type": "text"},
+                        {"key": "file", "type": "file", "src": "/tmp/cat.png"}
+                    ]},
+                    "url": "{{baseUrl}}/upload"
+                }
+            }
+        ]
+    })
+}
+
+#[test]
+fn import_postman_writes_folders_requests_scripts_and_variables() {
+    let dir = tempfile::tempdir().unwrap();
+    let input = write_json(dir.path(), "shop.postman_collection.json", collection());
+    let env = write_json(
+        dir.path(),
+        "dev.postman_environment.json",
+        serde_json::json!({
+            "name": "dev",
+            "values": [
+                {"key": "access-token", "value": "from-env", "enabled": true},
+                {"key": "unused", "value": "x", "enabled": false}
+            ]
+        }),
+    );
+    let out = dir.path().join("out");
+
+    let report =
+        import_postman(&input, Some(&env), &out, PostmanImportOptions::default()).unwrap();
+    let names: Vec<_> = report
+        .files_written
+        .iter()
+        .map(|p| p.strip_prefix(&out).unwrap().to_string_lossy().into_owned())
+        .collect();
+    assert_eq!(
+        names,
+        vec![
+            "users/get-user.yaml",
+            "users/create-user.yaml",
+            "login.yaml",
+            "upload.yaml"
+        ]
+    );
+
+    let get = RequestFile::from_file(&out.join("users/get-user.yaml")).unwrap();
+    assert_eq!(get.name.as_deref(), Some("Get user"));
+    assert_eq!(get.request.method, "GET");
+    assert_eq!(get.request.url, "${baseUrl}/users/${id}");
+    assert_eq!(
+        pairs(&get.request.query),
+        vec![("expand", "orders"), ("expand", "cart")]
+    );
+    assert_eq!(get.request.headers.len(), 2);
+    assert_eq!(get.request.headers.get("X-Trace"), Some("${!uuidv4}"));
+    assert_eq!(
+        get.request.headers.get("Authorization"),
+        Some("Bearer ${access_token}")
+    );
+
+    let create = RequestFile::from_file(&out.join("users/create-user.yaml")).unwrap();
+    assert_eq!(create.request.method, "POST");
+    assert!(create.request.headers.get("Authorization").is_none());
+    match create.request.body {
+        Some(RequestBody::Structured(s)) => {
+            assert_eq!(s.body_type, RequestBodyKind::Json);
+            assert_eq!(
+                s.content,
+                serde_json::json!({"name": "${name}", "age": "${json:age}"})
+            );
+        }
+        other => panic!("expected JSON body, got {other:?}"),
+    }
+
+    let stub = std::fs::read_to_string(out.join("users/get-user.rhai")).unwrap();
+    assert!(stub.contains("import \"get-user.yaml\" as request;"));
+    assert!(stub.contains("// Postman pre-request script from collection \"Shop API\":\n// console.log('collection');"));
+    assert!(stub.contains("// Postman test script from request \"Get user\":\n// pm.test("));
+    let pre = stub.find("console.log").unwrap();
+    let invoke = stub.find("request::invoke()").unwrap();
+    let test = stub.find("pm.test").unwrap();
+    assert!(pre < invoke && invoke < test);
+    assert_eq!(report.scripts_written.len(), 4);
+
+    let dotenv = std::fs::read_to_string(out.join(".env")).unwrap();
+    assert_eq!(report.env_files, vec![out.join(".env")]);
+    assert!(dotenv.contains("baseUrl=https://shop.example.test\n"));
+    assert!(dotenv.contains("access_token=from-env\n"));
+    assert!(dotenv.contains("id=42\n"));
+    assert!(!dotenv.contains("unused"));
+}
+
+#[test]
+fn import_postman_maps_form_bodies_and_auth_kinds() {
+    let dir = tempfile::tempdir().unwrap();
+    let input = write_json(dir.path(), "shop.json", collection());
+    let out = dir.path().join("out");
+    let report = import_postman(
+        &input,
+        None,
+        &out,
+        PostmanImportOptions {
+            format: OutputFormat::Json,
+            env_out: Some(dir.path().join("vars.env")),
+        },
+    )
+    .unwrap();
+    assert_eq!(report.env_files, vec![dir.path().join("vars.env")]);
+
+    let login = RequestFile::from_file(&out.join("login.json")).unwrap();
+    assert_eq!(login.request.headers.get("Authorization"), Some("Basic Ym9iOnB3"));
+    match login.request.body {
+        Some(RequestBody::Structured(s)) => {
+            assert_eq!(s.body_type, RequestBodyKind::XWwwFormUrlencoded);
+            assert_eq!(s.content, serde_json::json!("remember=yes+please&otp=${otp}"));
+        }
+        other => panic!("expected form body, got {other:?}"),
+    }
+
+    let upload = RequestFile::from_file(&out.join("upload.json")).unwrap();
+    assert_eq!(upload.request.query.get("api_key"), Some("${apiKey}"));
+    assert!(upload.request.headers.get("Authorization").is_none());
+    match upload.request.body {
+        Some(RequestBody::Structured(s)) => {
+            assert_eq!(s.body_type, RequestBodyKind::FormData);
+            let text = s.content.as_str().unwrap();
+            assert!(text.contains("name=\"title\"\r\n\r\ncat\r\n"));
+            assert!(!text.contains("cat.png"));
+        }
+        other => panic!("expected multipart body, got {other:?}"),
+    }
+    assert!(report
+        .notes
+        .iter()
+        .any(|n| n == "upload.json: form-data file field file was dropped"));
+}
+
+#[test]
+fn import_postman_refuses_to_overwrite_and_rejects_v1() {
+    let dir = tempfile::tempdir().unwrap();
+    let input = write_json(dir.path(), "shop.json", collection());
+    let out = dir.path().join("out");
+    std::fs::create_dir_all(&out).unwrap();
+    std::fs::write(out.join("login.yaml"), "keep").unwrap();
+
+    let err = import_postman(&input, None, &out, PostmanImportOptions::default()).unwrap_err();
+    assert!(err.to_string().contains("refusing to overwrite"));
+    assert!(!out.join("upload.yaml").exists());
+
+    let v1 = write_json(
+        dir.path(),
+        "old.json",
+        serde_json::json!({
+            "info": {"name": "old", "schema": "https://schema.getpostman.com/json/collection/v1.0.0/collection.json"},
+            "item": []
+        }),
+    );
+    let err = import_postman(&v1, None, &out, PostmanImportOptions::default()).unwrap_err();
+    assert!(err.to_string().contains("unsupported schema"));
+}
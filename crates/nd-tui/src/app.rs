@@ -3,6 +3,7 @@
 use std::time::Duration;
 
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use nd_core::rhai::checkpoint::Checkpoints;
 use nd_core::stream::events::Event;
 use serde_json::Value;
 
@@ -43,6 +44,10 @@ pub struct App {
     pub current_path: String,
     pub session_label: String,
     pub checkpoint_note: String,
+    /// The checkpoint the script is blocked on, resumed with `c`.
+    pub checkpoint_id: Option<String>,
+    /// The `step(...)` later timeline lines are indented under.
+    pub current_step: Option<String>,
     pub footer_status: String,
     pub runner_done: bool,
     pub runner_error: Option<String>,
@@ -50,6 +55,7 @@ pub struct App {
     pub focus: PaneFocus,
     pub right_tab: RightTab,
     body_raw: Vec<u8>,
+    checkpoints: Checkpoints,
 }
 
 impl App {
@@ -69,6 +75,8 @@ impl App {
             current_path: String::new(),
             session_label: String::new(),
             checkpoint_note: String::new(),
+            checkpoint_id: None,
+            current_step: None,
             footer_status: String::from("running…"),
             runner_done: false,
             runner_error: None,
@@ -76,9 +84,18 @@ impl App {
             focus: PaneFocus::Timeline,
             right_tab: RightTab::Preview,
             body_raw: Vec::new(),
+            checkpoints: Checkpoints::new(),
         }
     }
 
+    /// Like [`Self::new`], resuming script checkpoints through `checkpoints`.
+    pub fn with_checkpoints(checkpoints: Checkpoints) -> Self {
+        return Self {
+            checkpoints,
+            ..Self::new()
+        };
+    }
+
     pub fn should_fail_exit(&self) -> bool {
         self.runner_error.is_some()
     }
@@ -153,6 +170,8 @@ impl App {
     }
 
     fn apply_event(&mut self, ev: Event) {
+        // Step headers and ends stay at the left edge; everything in between is indented.
+        let mut grouped = true;
         let line = match ev {
             Event::SessionStarted { id, elapsed } => {
                 self.session_label = id.chars().take(8).collect();
@@ -257,6 +276,7 @@ impl App {
                 elapsed,
                 ..
             } => {
+                self.current_step = None;
                 let err = error
                     .map(|e| format!(" — {e}"))
                     .unwrap_or_default();
@@ -309,6 +329,7 @@ impl App {
                 format!("[log:{lvl}] {message}")
             }
             Event::CheckpointWaiting {
+                checkpoint_id,
                 message,
                 script,
                 observe,
@@ -316,28 +337,74 @@ impl App {
             } => {
                 let obs = serde_json::to_string(&observe).unwrap_or_else(|_| "{}".into());
                 self.checkpoint_note = format!("checkpoint ({script}): {message}\n{obs}");
+                self.checkpoint_id = Some(checkpoint_id);
                 format!("checkpoint waiting: {message}")
             }
             Event::CheckpointResumed { checkpoint_id, .. } => {
                 self.checkpoint_note.clear();
+                self.checkpoint_id = None;
                 format!("checkpoint resumed: {checkpoint_id}")
             }
             Event::Error { message, .. } => {
                 format!("ERROR: {message}")
             }
             Event::AssertCalled {
-                passed, message, ..
+                passed,
+                message,
+                pointer,
+                ..
             } => {
+                let at = match pointer.as_deref() {
+                    Some("") => " (at body)".to_string(),
+                    Some(pointer) => format!(" (at {pointer})"),
+                    None => String::new(),
+                };
                 format!(
-                    "assert {}: {message}",
+                    "assert {}: {message}{at}",
                     if passed { "ok" } else { "FAILED" }
                 )
             }
-            Event::NewStepEncountered { name, .. } => {
-                format!("step: {name}")
+            Event::NewStepEncountered { name, elapsed, .. } => {
+                grouped = false;
+                self.current_step = Some(name.clone());
+                format!("[{:>6.3}s] step: {name}", fmt_secs(elapsed))
+            }
+            Event::StepFinished {
+                name,
+                success,
+                error,
+                duration,
+                ..
+            } => {
+                grouped = false;
+                self.current_step = None;
+                let err = error.map(|e| format!(" — {e}")).unwrap_or_default();
+                format!(
+                    "step {}: {name} ({} ms){err}",
+                    if success { "ok" } else { "FAILED" },
+                    duration.as_millis()
+                )
             }
+            Event::PollAttempt {
+                label,
+                attempt,
+                done,
+                elapsed,
+                ..
+            } => format!(
+                "[{:>6.3}s] poll {label} #{attempt}: {}",
+                fmt_secs(elapsed),
+                if done { "done" } else { "not yet" }
+            ),
+            Event::PollTimedOut {
+                label, attempts, ..
+            } => format!("poll {label} TIMED OUT after {attempts} attempt(s)"),
         };
-        self.push_timeline(line);
+        if grouped && self.current_step.is_some() {
+            self.push_timeline(format!("  {line}"));
+        } else {
+            self.push_timeline(line);
+        }
     }
 
     pub fn handle_key(&mut self, key: KeyEvent) -> bool {
@@ -345,10 +412,20 @@ impl App {
             return false;
         }
         if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            self.checkpoints.close();
             return true;
         }
         match key.code {
-            KeyCode::Char('q') | KeyCode::Char('Q') => return true,
+            KeyCode::Char('q') | KeyCode::Char('Q') => {
+                // Let a script parked on a checkpoint finish instead of blocking the runner forever.
+                self.checkpoints.close();
+                return true;
+            }
+            KeyCode::Char('c') | KeyCode::Char('C') => {
+                if let Some(id) = self.checkpoint_id.as_deref() {
+                    self.checkpoints.resume(id);
+                }
+            }
             KeyCode::Tab => {
                 self.focus = match self.focus {
                     PaneFocus::Timeline => PaneFocus::Right,
@@ -431,4 +508,58 @@ mod tests {
         assert!(app.runner_done);
         assert!(app.runner_error.is_none());
     }
+
+    #[test]
+    fn checkpoint_events_track_the_waiting_id() {
+        let mut app = App::with_checkpoints(Checkpoints::new());
+        app.apply(TuiMsg::Event(Event::CheckpointWaiting {
+            session_id: "x".into(),
+            checkpoint_id: "cp1".into(),
+            script: "s.rhai".into(),
+            message: "look".into(),
+            observe: serde_json::json!({}),
+            elapsed: Duration::from_millis(1),
+        }));
+        assert_eq!(app.checkpoint_id.as_deref(), Some("cp1"));
+        assert!(app.checkpoint_note.contains("look"));
+
+        let c = KeyEvent::new(KeyCode::Char('c'), KeyModifiers::NONE);
+        assert!(!app.handle_key(c));
+
+        app.apply(TuiMsg::Event(Event::CheckpointResumed {
+            session_id: "x".into(),
+            elapsed: Duration::from_millis(2),
+            checkpoint_id: "cp1".into(),
+        }));
+        assert!(app.checkpoint_id.is_none());
+        assert!(app.checkpoint_note.is_empty());
+    }
+
+    #[test]
+    fn step_events_group_the_timeline() {
+        let mut app = App::new();
+        app.apply(TuiMsg::Event(Event::NewStepEncountered {
+            session_id: "x".into(),
+            name: "login".into(),
+            elapsed: Duration::from_millis(1),
+        }));
+        app.apply(TuiMsg::Event(Event::Error {
+            session_id: "x".into(),
+            elapsed: Duration::from_millis(2),
+            message: "boom".into(),
+        }));
+        app.apply(TuiMsg::Event(Event::StepFinished {
+            session_id: "x".into(),
+            name: "login".into(),
+            success: false,
+            error: Some("boom".into()),
+            duration: Duration::from_millis(5),
+            elapsed: Duration::from_millis(6),
+        }));
+
+        assert!(app.timeline[0].ends_with("step: login"));
+        assert_eq!(app.timeline[1], "  ERROR: boom");
+        assert_eq!(app.timeline[2], "step FAILED: login (5 ms) — boom");
+        assert!(app.current_step.is_none());
+    }
 }
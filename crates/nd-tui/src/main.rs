@@ -34,7 +34,7 @@ struct Args {
     #[arg(long = "no-stream", action = ArgAction::SetTrue)]
     no_stream: bool,
 
-    /// Request files (`*.json`, `*.yaml`, `*.yml`) or Rhai scripts (`*.rhai`).
+    /// Request files (`*.json`, `*.yaml`, `*.yml`, `*.http`, `*.rest`) or Rhai scripts (`*.rhai`).
     #[arg(value_name = "FILE", required = true)]
     paths: Vec<PathBuf>,
 }
@@ -6,6 +6,7 @@ use std::sync::{Arc, Mutex};
 use nd_core::env::RuntimeEnv;
 use nd_core::execute::format::format_prepared_request;
 use nd_core::model::request::RequestFile;
+use nd_core::rhai::checkpoint::Checkpoints;
 use nd_core::rhai::{resolver::RhaiScriptRunOptions, run::run_rhai_script};
 use nd_core::stream::events::Event;
 use nd_core::stream::{MutexSession, Session};
@@ -60,21 +61,26 @@ pub async fn run_tui(opts: TuiOptions) -> Result<(), String> {
         })),
     )?));
 
+    // Scripts block in `checkpoint()` until the UI resumes them (see `App::handle_key`).
+    let checkpoints = Checkpoints::new();
+
     let run_opts = opts.clone();
     let sess = session.clone();
     let tx_done = tx.clone();
+    let gate = checkpoints.clone();
     tokio::spawn(async move {
-        let result = run_paths_loop(&run_opts, sess, &tx_done).await;
+        let result = run_paths_loop(&run_opts, sess, &tx_done, &gate).await;
         let _ = tx_done.send(TuiMsg::RunnerFinished(result));
     });
 
-    ui::run_terminal(rx).await
+    ui::run_terminal(rx, checkpoints).await
 }
 
 async fn run_paths_loop(
     opts: &TuiOptions,
     session: Arc<Mutex<Session>>,
     tx: &UnboundedSender<TuiMsg>,
+    checkpoints: &Checkpoints,
 ) -> Result<(), String> {
     for path in opts.paths.iter() {
         let session = session.clone();
@@ -101,15 +107,15 @@ async fn run_paths_loop(
             .unwrap_or_default();
 
         match ext.as_str() {
-            "json" | "yaml" | "yml" => {
+            "json" | "yaml" | "yml" | "http" | "rest" => {
                 run_request_tui(path, opts, session.clone(), tx).await?;
             }
             "rhai" => {
-                run_script_tui(path, opts, session.clone()).await?;
+                run_script_tui(path, opts, session.clone(), checkpoints).await?;
             }
             _ => {
                 return Err(String::from(
-                    "Invalid file type. only json, yaml, yml, rhai files accepted",
+                    "Invalid file type. only json, yaml, yml, http, rest, rhai files accepted",
                 ));
             }
         }
@@ -128,7 +134,20 @@ async fn run_request_tui(
     session: Arc<Mutex<Session>>,
     tx: &UnboundedSender<TuiMsg>,
 ) -> Result<(), String> {
-    let document = RequestFile::from_file(path).map_err(|e| e.to_string())?;
+    // `.http` / `.rest` files can hold several requests; they run in order.
+    let documents = RequestFile::from_file_all(path).map_err(|e| e.to_string())?;
+    for document in &documents {
+        run_document_tui(document, opts, session.clone(), tx).await?;
+    }
+    Ok(())
+}
+
+async fn run_document_tui(
+    document: &RequestFile,
+    opts: &TuiOptions,
+    session: Arc<Mutex<Session>>,
+    tx: &UnboundedSender<TuiMsg>,
+) -> Result<(), String> {
 
     if opts.no_network_io || opts.verbose {
         let request = document
@@ -162,13 +181,18 @@ async fn run_script_tui(
     path: &Path,
     opts: &TuiOptions,
     session: Arc<Mutex<Session>>,
+    checkpoints: &Checkpoints,
 ) -> Result<(), String> {
-    run_rhai_script(
-        path,
-        session,
-        RhaiScriptRunOptions {
-            no_network_io: opts.no_network_io,
-        },
-    )
-    .map_err(|e| e.to_string())
+    // The script blocks its thread in `sleep()` and at checkpoints; off the runtime workers, the
+    // UI task keeps running and can resume it.
+    let path = path.to_path_buf();
+    let options = RhaiScriptRunOptions {
+        no_network_io: opts.no_network_io,
+        checkpoints: Some(checkpoints.clone()),
+        ..Default::default()
+    };
+    tokio::task::spawn_blocking(move || run_rhai_script(&path, session, options))
+        .await
+        .map_err(|e| format!("spawn_blocking: {e}"))?
+        .map_err(|e| e.to_string())
 }
@@ -8,6 +8,7 @@ use crossterm::{
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use futures_util::StreamExt;
+use nd_core::rhai::checkpoint::Checkpoints;
 use ratatui::prelude::*;
 use ratatui::widgets::{Block, Borders, Gauge, List, ListItem, Paragraph, Wrap};
 use tokio::sync::mpsc::UnboundedReceiver;
@@ -16,14 +17,17 @@ use tokio::time::{interval, MissedTickBehavior};
 use crate::app::{App, PaneFocus, RightTab};
 use crate::runner::TuiMsg;
 
-pub async fn run_terminal(mut rx: UnboundedReceiver<TuiMsg>) -> Result<(), String> {
+pub async fn run_terminal(
+    mut rx: UnboundedReceiver<TuiMsg>,
+    checkpoints: Checkpoints,
+) -> Result<(), String> {
     enable_raw_mode().map_err(|e| e.to_string())?;
     let mut stdout = stdout();
     execute!(stdout, EnterAlternateScreen).map_err(|e| e.to_string())?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend).map_err(|e| e.to_string())?;
 
-    let mut app = App::new();
+    let mut app = App::with_checkpoints(checkpoints);
     let mut reader = EventStream::new();
     let mut tick = interval(std::time::Duration::from_millis(120));
     tick.set_missed_tick_behavior(MissedTickBehavior::Skip);
@@ -41,10 +45,10 @@ pub async fn run_terminal(mut rx: UnboundedReceiver<TuiMsg>) -> Result<(), Strin
             }
             ev = reader.next() => {
                 match ev {
-                    Some(Ok(CrosstermEvent::Key(key))) => {
-                        if key.kind == KeyEventKind::Press && app.handle_key(key) {
-                            break;
-                        }
+                    Some(Ok(CrosstermEvent::Key(key)))
+                        if key.kind == KeyEventKind::Press && app.handle_key(key) =>
+                    {
+                        break;
                     }
                     Some(Ok(CrosstermEvent::Resize(_, _))) => {}
                     Some(Err(e)) => {
@@ -151,7 +155,7 @@ fn draw(f: &mut Frame<'_>, app: &App) {
         if app.checkpoint_note.is_empty() {
             ""
         } else {
-            "checkpoint active │ "
+            "checkpoint active — c to continue │ "
         }
     );
     let footer = Paragraph::new(help).block(Block::default().borders(Borders::TOP));
@@ -90,6 +90,34 @@ export async function sendHttp(
   return j;
 }
 
+export const SNIPPET_LANGUAGES = [
+  { id: "curl", label: "curl" },
+  { id: "httpie", label: "HTTPie" },
+  { id: "python", label: "Python (requests)" },
+  { id: "javascript", label: "JavaScript (fetch)" },
+  { id: "rust", label: "Rust (reqwest)" },
+] as const;
+
+export type SnippetLanguage = (typeof SNIPPET_LANGUAGES)[number]["id"];
+
+export interface SnippetResponse {
+  language: SnippetLanguage;
+  snippet: string;
+}
+
+/** Expands the request server-side (no network I/O) and renders it as code. */
+export async function fetchSnippet(
+  body: SendRequestPayload & { language: SnippetLanguage },
+): Promise<SnippetResponse> {
+  const r = await fetch(`${API}/requests/snippet`, {
+    method: "POST",
+    headers: { "Content-Type": "application/json" },
+    body: JSON.stringify(body),
+  });
+  if (!r.ok) throw new Error(await readApiErrorMessage(r));
+  return (await r.json()) as SnippetResponse;
+}
+
 export interface ScriptRunResponse {
   ok: boolean;
   error?: string;
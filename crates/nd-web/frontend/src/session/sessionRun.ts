@@ -15,6 +15,8 @@ export type SessionEventListener = (event: unknown) => void;
  */
 export interface SessionRun {
     subscribe(listener: SessionEventListener): () => void;
+    /** Continue a script blocked on the `CheckpointWaiting` event with this id. */
+    resume(checkpointId: string): void;
     readonly completed: Promise<RunCompleteMessage>;
 }
 
@@ -25,9 +27,9 @@ function wsUrl(): string {
 
 export function startSessionRun(cmd: Record<string, unknown>): SessionRun {
     const listeners = new Set<SessionEventListener>();
+    const ws = new WebSocket(wsUrl());
 
     const completed = new Promise<RunCompleteMessage>((resolve, reject) => {
-        const ws = new WebSocket(wsUrl());
         let settled = false;
 
         const finish = (fn: () => void) => {
@@ -95,6 +97,12 @@ export function startSessionRun(cmd: Record<string, unknown>): SessionRun {
                 listeners.delete(listener);
             };
         },
+        resume(checkpointId: string) {
+            if (ws.readyState !== WebSocket.OPEN) return;
+            ws.send(
+                JSON.stringify({ type: "resume", checkpoint_id: checkpointId }),
+            );
+        },
         completed,
     };
 }
@@ -2,7 +2,7 @@ import { defineStore } from "pinia";
 import { computed, ref, watch } from "vue";
 import YAML from "yaml";
 import { fetchFile, saveFile } from "@/api";
-import type { EditorTab, ReqSubTab } from "@/types/editor";
+import type { EditorTab, KeyValuePair, ReqSubTab } from "@/types/editor";
 
 export const HTTP_METHODS = [
     "GET",
@@ -202,65 +202,87 @@ export const useEditorStore = defineStore("editor", () => {
         }
     }
 
-    function recordFromQueryish(
-        src: Record<string, unknown> | undefined,
-    ): Record<string, string> {
-        const out: Record<string, string> = {};
-        if (!src || typeof src !== "object") return out;
-        for (const [k, v] of Object.entries(src)) {
-            if (v === null || v === undefined) {
-                out[k] = "";
-            } else if (
-                typeof v === "string" ||
-                typeof v === "number" ||
-                typeof v === "boolean"
-            ) {
-                out[k] = String(v);
-            } else {
-                out[k] = JSON.stringify(v);
-            }
+    function pairValue(v: unknown): string {
+        if (v === null || v === undefined) return "";
+        if (
+            typeof v === "string" ||
+            typeof v === "number" ||
+            typeof v === "boolean"
+        ) {
+            return String(v);
+        }
+        return JSON.stringify(v);
+    }
+
+    /** `query` / `headers` may be a map or a list of `{ name, value }`; both keep their order. */
+    function pairsFromQueryish(src: unknown): KeyValuePair[] {
+        if (Array.isArray(src)) {
+            return src
+                .filter((e) => e && typeof e === "object" && "name" in e)
+                .map((e): KeyValuePair => [String(e.name), pairValue(e.value)]);
         }
-        return out;
+        if (!src || typeof src !== "object") return [];
+        return Object.entries(src).map(
+            ([k, v]): KeyValuePair => [k, pairValue(v)],
+        );
     }
 
-    const queryRecord = computed({
+    /**
+     * Writes the pairs back in the shape the document already used; the list form whenever a name
+     * repeats, since a map would drop all but one value.
+     */
+    function queryishFromPairs(
+        previous: unknown,
+        pairs: KeyValuePair[],
+    ): unknown {
+        const names = new Set(pairs.map(([name]) => name));
+        if (Array.isArray(previous) || names.size < pairs.length) {
+            return pairs.map(([name, value]) => ({ name, value }));
+        }
+        return Object.fromEntries(pairs);
+    }
+
+    const queryPairs = computed({
         get() {
             const r = requestSpec.value;
-            const q = r?.query as Record<string, unknown> | undefined;
-            return recordFromQueryish(q);
+            return pairsFromQueryish(r?.query);
         },
-        set(rec: Record<string, string>) {
+        set(pairs: KeyValuePair[]) {
             const req = ensureRequestDoc();
             if (!req) return;
-            req.query = { ...rec };
+            req.query = queryishFromPairs(req.query, pairs);
             applyRequestField();
         },
     });
 
-    const headersRecord = computed({
+    const headersPairs = computed({
         get() {
             const r = requestSpec.value;
-            const h = r?.headers as Record<string, unknown> | undefined;
-            return recordFromQueryish(h);
+            return pairsFromQueryish(r?.headers);
         },
-        set(rec: Record<string, string>) {
+        set(pairs: KeyValuePair[]) {
             const req = ensureRequestDoc();
             if (!req) return;
-            req.headers = { ...rec };
+            req.headers = queryishFromPairs(req.headers, pairs);
             applyRequestField();
         },
     });
 
-    const overridesRecord = computed({
-        get() {
+    /** Overrides are a map: a repeated name keeps its last value. */
+    const overridesPairs = computed({
+        get(): KeyValuePair[] {
             const t = activeTab.value;
-            if (!t || t.kind !== "request") return {};
-            return parseOverridesToRecord(t.overridesJson);
+            if (!t || t.kind !== "request") return [];
+            return Object.entries(parseOverridesToRecord(t.overridesJson));
         },
-        set(rec: Record<string, string>) {
+        set(pairs: KeyValuePair[]) {
             const t = activeTab.value;
             if (!t || t.kind !== "request") return;
-            t.overridesJson = JSON.stringify(rec, null, 2);
+            t.overridesJson = JSON.stringify(
+                Object.fromEntries(pairs),
+                null,
+                2,
+            );
         },
     });
 
@@ -332,9 +354,9 @@ export const useEditorStore = defineStore("editor", () => {
         applyRequestField,
         syncDoc,
         parseOverridesForSend,
-        queryRecord,
-        headersRecord,
-        overridesRecord,
+        queryPairs,
+        headersPairs,
+        overridesPairs,
         overridesJsonError,
         scriptRaw,
         scriptDirty,
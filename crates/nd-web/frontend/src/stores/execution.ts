@@ -1,7 +1,12 @@
 import { defineStore } from "pinia";
 import { computed, reactive, ref } from "vue";
-import type { ExecutionResultDto, RuntimeEnvEntry } from "@/api";
-import { startSessionRun } from "@/session/sessionRun";
+import {
+    fetchSnippet,
+    type ExecutionResultDto,
+    type RuntimeEnvEntry,
+    type SnippetLanguage,
+} from "@/api";
+import { startSessionRun, type SessionRun } from "@/session/sessionRun";
 import {
     appendLogFromStreamEvent,
     patchRuntimeEnvFromEvent,
@@ -26,6 +31,12 @@ export const useExecutionStore = defineStore("execution", () => {
     const scriptTimelineByPath = reactive<Record<string, TimelineReducerState>>(
         {},
     );
+    /** Checkpoint a running script is blocked on, keyed by script path. */
+    const waitingCheckpointByPath = reactive<
+        Record<string, { id: string; message: string } | null>
+    >({});
+    /** Live script runs, so a waiting checkpoint can be resumed over the same socket. */
+    const scriptRuns = new Map<string, SessionRun>();
     /** Wall clock sync for extrapolating session time between WebSocket events. */
     const scriptTimelineWallSyncByPath = reactive<
         Record<string, { lastWallMs: number }>
@@ -60,6 +71,9 @@ export const useExecutionStore = defineStore("execution", () => {
         for (const k of Object.keys(scriptTimelineWallSyncByPath)) {
             delete scriptTimelineWallSyncByPath[k];
         }
+        for (const k of Object.keys(waitingCheckpointByPath)) {
+            delete waitingCheckpointByPath[k];
+        }
     }
 
     /** Full reset when opening a new editor tab from the workspace (matches prior global wipe). */
@@ -160,9 +174,25 @@ export const useExecutionStore = defineStore("execution", () => {
             type: "run_script",
             path,
         });
+        scriptRuns.set(path, run);
+        waitingCheckpointByPath[path] = null;
 
         const unsubs: (() => void)[] = [];
 
+        unsubs.push(
+            run.subscribe((ev) => {
+                const o = ev as Record<string, Record<string, unknown>>;
+                if (o.CheckpointWaiting) {
+                    waitingCheckpointByPath[path] = {
+                        id: String(o.CheckpointWaiting.checkpoint_id ?? ""),
+                        message: String(o.CheckpointWaiting.message ?? ""),
+                    };
+                } else if (o.CheckpointResumed) {
+                    waitingCheckpointByPath[path] = null;
+                }
+            }),
+        );
+
         unsubs.push(
             run.subscribe((ev) => {
                 const cur = runtimeEnvByPath[path] ?? [];
@@ -200,10 +230,50 @@ export const useExecutionStore = defineStore("execution", () => {
             const benignEnd =
                 completedOk && sendErrByPath[path] == null;
             flushOpenTimelineSpans(timeline, benignEnd);
+            scriptRuns.delete(path);
+            waitingCheckpointByPath[path] = null;
             sending.value = false;
         }
     }
 
+    /** Continues the script at `path` past the checkpoint it is waiting on. */
+    function resumeCheckpoint(path: string) {
+        const waiting = waitingCheckpointByPath[path];
+        if (!waiting) return;
+        scriptRuns.get(path)?.resume(waiting.id);
+    }
+
+    /** Renders the active request as `language` code and copies it to the clipboard. */
+    async function copyAs(language: SnippetLanguage) {
+        const { useEditorStore } = await import("./editor");
+        const editor = useEditorStore();
+        const t = editor.activeTab;
+        if (!t || t.kind !== "request") return;
+        const path = t.path;
+        editor.syncDoc(t);
+        if (!t.doc) {
+            sendErrByPath[path] = t.parseError ?? "Cannot parse document";
+            return;
+        }
+        const parsed = editor.parseOverridesForSend(t.overridesJson);
+        if (!parsed.ok) {
+            sendErrByPath[path] = parsed.message;
+            return;
+        }
+        try {
+            const { snippet } = await fetchSnippet({
+                source_path: path,
+                document: t.doc,
+                overrides: parsed.overrides,
+                language,
+            });
+            await navigator.clipboard.writeText(snippet);
+            sendErrByPath[path] = null;
+        } catch (e) {
+            sendErrByPath[path] = e instanceof Error ? e.message : String(e);
+        }
+    }
+
     return {
         response,
         sendErrByPath,
@@ -214,9 +284,12 @@ export const useExecutionStore = defineStore("execution", () => {
         runtimeEnvByPath,
         scriptTimelineByPath,
         scriptTimelineWallSyncByPath,
+        waitingCheckpointByPath,
         prettyResponse,
         resetAfterOpenFile,
         doSend,
         doRunScript,
+        resumeCheckpoint,
+        copyAs,
     };
 });
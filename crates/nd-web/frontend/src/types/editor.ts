@@ -21,3 +21,6 @@ export type ReqSubTab =
     | "body"
     | "input"
     | "auth";
+
+/** One `name`/`value` row of a key–value editor; names may repeat and order is kept. */
+export type KeyValuePair = [name: string, value: string];
@@ -10,6 +10,7 @@ export function parseLogFromTimelineInstant(
     return {
         level: String(rec.level ?? "info"),
         message: String(rec.message ?? ""),
+        pointer: typeof rec.pointer === "string" ? rec.pointer : null,
     };
 }
 
@@ -28,10 +29,10 @@ export function logLevelChipClass(level: string): string {
     return "bg-muted/80 text-foreground ring-border ring-1";
 }
 
-/** Parse `{ AssertCalled: { passed?, message? } }` from a timeline instant row's `raw`. */
+/** Parse `{ AssertCalled: { passed?, message?, pointer? } }` from a timeline instant row's `raw`. */
 export function parseAssertFromTimelineInstant(
     raw: unknown,
-): { passed: boolean; message: string } | null {
+): { passed: boolean; message: string; pointer: string | null } | null {
     if (!raw || typeof raw !== "object") return null;
     const inner = (raw as Record<string, unknown>).AssertCalled;
     if (!inner || typeof inner !== "object") return null;
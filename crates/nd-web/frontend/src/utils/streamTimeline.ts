@@ -12,6 +12,8 @@ export type TimelineRowInstant = {
     variant: string;
     label: string;
     raw: unknown;
+    /** Row id of the `Step` span this event happened in. */
+    stepId?: string;
 };
 
 export type TimelineRowSpan = {
@@ -24,6 +26,8 @@ export type TimelineRowSpan = {
     status: TimelineSpanStatus;
     rawStart: unknown;
     rawEnd?: unknown;
+    /** Row id of the `Step` span this event happened in. */
+    stepId?: string;
 };
 
 export type TimelineRow = TimelineRowInstant | TimelineRowSpan;
@@ -39,6 +43,8 @@ export type TimelineReducerState = {
     openSessionRowId: string | null;
     /** checkpoint_id -> span row id */
     openCheckpoints: Map<string, string>;
+    /** `step(...)` span later rows are grouped under (until the next step or its end). */
+    openStepRowId: string | null;
 };
 
 const SKIP_TIMELINE_KEYS = new Set([
@@ -71,6 +77,12 @@ function normRequestName(n: unknown): string {
     return String(n);
 }
 
+/** Pairs an HTTP start with its completion: `request_id`, or the request name from older servers. */
+function httpKey(inner: Record<string, unknown>): string {
+    if (typeof inner.request_id === "string") return inner.request_id;
+    return normRequestName(inner.request_name);
+}
+
 /** Externally tagged session event: single variant key on the object (e.g. `Log`, `HttpRequestStarted`). */
 export function getEventVariantKey(data: unknown): string | null {
     if (!data || typeof data !== "object") return null;
@@ -100,6 +112,7 @@ export function createEmptyTimelineState(): TimelineReducerState {
         openScriptRowId: null,
         openSessionRowId: null,
         openCheckpoints: new Map(),
+        openStepRowId: null,
     };
 }
 
@@ -188,6 +201,23 @@ function closeCheckpointSpan(
     row.status = "ok";
 }
 
+function closeStepSpan(
+    state: TimelineReducerState,
+    endMs: number,
+    rawEnd: unknown,
+    success: boolean,
+): void {
+    const id = state.openStepRowId;
+    state.openStepRowId = null;
+    if (!id) return;
+    bumpElapsed(state, endMs);
+    const row = state.rows.find((r) => r.id === id) as TimelineRowSpan | undefined;
+    if (!row || row.kind !== "span") return;
+    row.endMs = endMs;
+    row.rawEnd = rawEnd;
+    row.status = success ? "ok" : "error";
+}
+
 export function applyStreamEventToTimeline(
     state: TimelineReducerState,
     data: unknown,
@@ -198,6 +228,8 @@ export function applyStreamEventToTimeline(
 
     const inner = innerPayload(data, variant);
     const tMs = elapsedFromInner(inner);
+    const stepId = state.openStepRowId;
+    const firstNewRow = state.rows.length;
 
     switch (variant) {
         case "SessionStarted": {
@@ -227,7 +259,7 @@ export function applyStreamEventToTimeline(
             const shortUrl =
                 url.length > 48 ? `${url.slice(0, 45)}…` : url;
             const label = `${method} ${shortUrl}`;
-            const nameKey = normRequestName(inner.request_name);
+            const nameKey = httpKey(inner);
             const id = nextId(state);
             state.openHttpStack.push({ rowId: id, nameKey });
             state.rows.push({
@@ -248,7 +280,7 @@ export function applyStreamEventToTimeline(
                 status === 0 || (status >= 200 && status < 400);
             closeHttpSpan(
                 state,
-                normRequestName(inner.request_name),
+                httpKey(inner),
                 tMs,
                 data,
                 httpOk,
@@ -274,6 +306,7 @@ export function applyStreamEventToTimeline(
         }
         case "ScriptFinished": {
             const success = Boolean(inner.success);
+            closeStepSpan(state, tMs, data, success);
             closeScriptSpan(state, tMs, data, success);
             break;
         }
@@ -301,7 +334,10 @@ export function applyStreamEventToTimeline(
             break;
         }
         case "Log": {
-            const msg = String(inner.message ?? "");
+            const pointer = inner.pointer;
+            const at =
+                typeof pointer === "string" ? ` (at ${pointer || "body"})` : "";
+            const msg = `${String(inner.message ?? "")}${at}`;
             pushInstant(state, variant, msg, tMs, data);
             break;
         }
@@ -335,8 +371,50 @@ export function applyStreamEventToTimeline(
             break;
         }
         case "NewStepEncountered": {
+            // A plain `step(name)` runs until the next step starts.
+            closeStepSpan(state, tMs, data, true);
             const name = String(inner.name ?? "step");
-            pushInstant(state, variant, name, tMs, data);
+            const id = nextId(state);
+            state.openStepRowId = id;
+            state.rows.push({
+                kind: "span",
+                id,
+                variant: "Step",
+                label: name,
+                startMs: tMs,
+                endMs: null,
+                status: "running",
+                rawStart: data,
+            });
+            return;
+        }
+        case "StepFinished": {
+            closeStepSpan(state, tMs, data, Boolean(inner.success));
+            break;
+        }
+        case "PollAttempt": {
+            const label = String(inner.label ?? "poll");
+            const attempt = Number(inner.attempt ?? 0);
+            const status = inner.done ? "done" : "not yet";
+            pushInstant(
+                state,
+                variant,
+                `Poll ${label} #${attempt}: ${status}`,
+                tMs,
+                data,
+            );
+            break;
+        }
+        case "PollTimedOut": {
+            const label = String(inner.label ?? "poll");
+            const n = Number(inner.attempts ?? 0);
+            pushInstant(
+                state,
+                variant,
+                `Poll ${label} timed out (${n} attempt${n === 1 ? "" : "s"})`,
+                tMs,
+                data,
+            );
             break;
         }
         case "FileLoaded": {
@@ -360,6 +438,10 @@ export function applyStreamEventToTimeline(
             pushInstant(state, variant, variant, tMs, data);
         }
     }
+
+    if (stepId) {
+        for (const row of state.rows.slice(firstNewRow)) row.stepId = stepId;
+    }
 }
 
 /** Close any spans still open after the run ends (WebSocket finished). */
@@ -413,4 +495,15 @@ export function flushOpenTimelineSpans(
         }
     }
     state.openCheckpoints.clear();
+
+    if (state.openStepRowId) {
+        const row = state.rows.find((r) => r.id === state.openStepRowId) as
+            | TimelineRowSpan
+            | undefined;
+        if (row?.kind === "span" && row.endMs == null) {
+            row.endMs = endMs;
+            row.status = runOk ? "ok" : "error";
+        }
+        state.openStepRowId = null;
+    }
 }
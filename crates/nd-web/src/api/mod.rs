@@ -8,8 +8,10 @@
 //! | GET | `/file?path=` | Raw file text for the editor (sandboxed to roots) |
 //! | PUT | `/file` | JSON body `{ path, content }` writes UTF-8 text to an existing file under roots |
 //! | POST | `/requests/send` | Run a request (from disk or inline JSON document) |
+//! | POST | `/requests/snippet` | Expand a request and render it as curl / HTTPie / Python / JS / Rust code |
 //! | POST | `/scripts/run` | Run a Rhai script under `nd-core` semantics |
-//! | GET | `/ws` | WebSocket: send one run command, receive [`nd_core::stream::events::Event`] JSON then `run_complete` |
+//! | GET | `/sessions/{id}/har` | Download a run's HTTP exchanges as a HAR 1.2 file (runs started with `har: true`) |
+//! | GET | `/ws` | WebSocket: send one run command, receive [`nd_core::stream::events::Event`] JSON then `run_complete`; `resume` messages continue script checkpoints |
 //!
 //! Non-API paths are served from [`crate::embed`] (SPA fallback to `index.html`).
 
@@ -30,6 +32,8 @@ use crate::embed::embedded_static_response;
 pub mod file;
 pub mod script;
 pub mod send;
+pub mod session;
+pub mod snippet;
 pub mod workspace;
 pub mod ws;
 
@@ -62,7 +66,9 @@ pub fn api_router(state: AppState) -> Router {
         .route("/workspace", get(workspace::get_workspace))
         .route("/file", get(file::get_file).put(file::put_file))
         .route("/requests/send", post(send::post_send))
+        .route("/requests/snippet", post(snippet::post_snippet))
         .route("/scripts/run", post(script::post_script_run))
+        .route("/sessions/{id}/har", get(session::get_session_har))
         .route("/ws", get(ws::session_ws))
         .with_state(state)
 }
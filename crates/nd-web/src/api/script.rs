@@ -17,6 +17,9 @@ use crate::path_sandbox::resolve_allowed_file;
 #[derive(Deserialize)]
 pub struct RunScriptBody {
     pub path: String,
+    /// Keep the run's exchanges for `GET /api/sessions/{id}/har`.
+    #[serde(default)]
+    pub har: bool,
 }
 
 /// Result of running a Rhai script, including log lines from [`Event::Log`] on the run [`Session`].
@@ -68,11 +71,14 @@ pub async fn post_script_run(
     ));
 
     {
-        let sid = session
+        let mut guard = session
             .lock()
-            .map_err(|e| json_err(e.to_string(), StatusCode::INTERNAL_SERVER_ERROR))?
-            .session_id()
-            .to_string();
+            .map_err(|e| json_err(e.to_string(), StatusCode::INTERNAL_SERVER_ERROR))?;
+        if body.har {
+            guard.keep_results();
+        }
+        let sid = guard.session_id().to_string();
+        drop(guard);
 
         state
             .sessions
@@ -82,19 +88,26 @@ pub async fn post_script_run(
     }
 
     let session_for_thread = session.clone();
+    let roots = state.roots.to_vec();
     let res = tokio::task::spawn_blocking(move || {
         run_rhai_script(
             &allowed,
             session_for_thread,
-            RhaiScriptRunOptions { no_network_io },
+            // Nothing can resume a checkpoint over a plain request; they are recorded and passed.
+            RhaiScriptRunOptions {
+                no_network_io,
+                checkpoints: None,
+                fs_roots: roots,
+            },
         )
     })
     .await
     .map_err(|e| json_err(e.to_string(), StatusCode::INTERNAL_SERVER_ERROR))?;
 
-    let logs: Vec<ScriptLogLine> = session
+    let mut guard = session
         .lock()
-        .map_err(|e| json_err(e.to_string(), StatusCode::INTERNAL_SERVER_ERROR))?
+        .map_err(|e| json_err(e.to_string(), StatusCode::INTERNAL_SERVER_ERROR))?;
+    let logs: Vec<ScriptLogLine> = guard
         .events()
         .iter()
         .filter_map(|e| {
@@ -115,6 +128,13 @@ pub async fn post_script_run(
             }
         })
         .collect();
+    // Only a HAR download needs the timeline after the response.
+    if body.har {
+        guard.end();
+    } else {
+        let _ = guard.finish();
+    }
+    drop(guard);
 
     match res {
         Ok(()) => Ok(Json(ScriptRunResponse {
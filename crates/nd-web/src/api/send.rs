@@ -65,31 +65,8 @@ pub async fn post_send(
     State(state): State<AppState>,
     Json(body): Json<SendHttpBody>,
 ) -> Result<Json<HttpSendResponse>, Response> {
-    let source = PathBuf::from(&body.source_path);
-    let allowed = resolve_allowed_file(&source, state.roots.as_ref()).map_err(|e| {
-        let code = if e.contains("outside") {
-            StatusCode::FORBIDDEN
-        } else {
-            StatusCode::NOT_FOUND
-        };
-        json_err(e, code)
-    })?;
-
-    let mut doc = if let Some(v) = body.document {
-        let mut d: RequestFile = serde_json::from_value(v).map_err(|e| {
-            json_err(
-                format!("invalid request document: {e}"),
-                StatusCode::BAD_REQUEST,
-            )
-        })?;
-        d._path = Some(allowed.clone());
-        d
-    } else {
-        RequestFile::from_file(&allowed)
-            .map_err(|e| json_err(e.to_string(), StatusCode::BAD_REQUEST))?
-    };
-
-    doc._path = Some(allowed);
+    let doc = load_request_document(&state, &body.source_path, body.document)
+        .map_err(|(msg, code)| json_err(msg, code))?;
 
     let overrides = if body.overrides.is_empty() {
         None
@@ -147,6 +124,38 @@ pub async fn post_send(
     }))
 }
 
+/// Loads the request at `source_path` (sandboxed to roots), or parses `document` in its place.
+/// Errors carry the message and status for [`json_err`].
+pub(crate) fn load_request_document(
+    state: &AppState,
+    source_path: &str,
+    document: Option<serde_json::Value>,
+) -> Result<RequestFile, (String, StatusCode)> {
+    let source = PathBuf::from(source_path);
+    let allowed = resolve_allowed_file(&source, state.roots.as_ref()).map_err(|e| {
+        let code = if e.contains("outside") {
+            StatusCode::FORBIDDEN
+        } else {
+            StatusCode::NOT_FOUND
+        };
+        (e, code)
+    })?;
+
+    let mut doc = if let Some(v) = document {
+        serde_json::from_value::<RequestFile>(v).map_err(|e| {
+            (
+                format!("invalid request document: {e}"),
+                StatusCode::BAD_REQUEST,
+            )
+        })?
+    } else {
+        RequestFile::from_file(&allowed).map_err(|e| (e.to_string(), StatusCode::BAD_REQUEST))?
+    };
+
+    doc._path = Some(allowed);
+    return Ok(doc);
+}
+
 pub(crate) fn execution_to_dto(exec: &ExecutionResult) -> ExecutionResultDto {
     let body_utf8 = std::str::from_utf8(&exec.body).ok();
     let (body_text, body_base64) = if let Some(s) = body_utf8 {
@@ -0,0 +1,36 @@
+use axum::extract::{Path, State};
+use axum::http::{header, HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use nd_core::har;
+
+use super::{json_err, AppState};
+
+/// HAR 1.2 download of a session started over `/ws` or `/scripts/run` (404 for unknown ids).
+pub async fn get_session_har(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Response, Response> {
+    let session = state
+        .sessions
+        .lock()
+        .map_err(|e| json_err(e.to_string(), StatusCode::INTERNAL_SERVER_ERROR))?
+        .get(&id)
+        .cloned()
+        .ok_or_else(|| json_err(format!("unknown session: {id}"), StatusCode::NOT_FOUND))?;
+
+    let har = har::from_session(
+        &*session
+            .lock()
+            .map_err(|e| json_err(e.to_string(), StatusCode::INTERNAL_SERVER_ERROR))?,
+    );
+
+    let disposition = format!("attachment; filename=\"nativedoctor-{id}.har\"");
+    let mut response = Json(har).into_response();
+    if let Ok(value) = HeaderValue::from_str(&disposition) {
+        response
+            .headers_mut()
+            .insert(header::CONTENT_DISPOSITION, value);
+    }
+    return Ok(response);
+}
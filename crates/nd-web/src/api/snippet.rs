@@ -0,0 +1,55 @@
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::Response;
+use axum::Json;
+use nd_core::execute::snippet::{format_snippet, SnippetLanguage};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::send::{load_request_document, runtime_env_for_state};
+use super::{json_err, AppState};
+
+/// Body for [`post_snippet`]: same request selection as `/requests/send`, plus the target language.
+#[derive(Deserialize)]
+pub struct SnippetBody {
+    pub source_path: String,
+    pub document: Option<serde_json::Value>,
+    #[serde(default)]
+    pub overrides: HashMap<String, String>,
+    /// `curl`, `httpie`, `python`, `javascript` (or `js`), `rust`.
+    pub language: SnippetLanguage,
+}
+
+#[derive(Serialize)]
+pub struct SnippetResponse {
+    pub language: SnippetLanguage,
+    pub snippet: String,
+}
+
+/// Expands the request (no network I/O) and renders it for the editor's "copy as" menu.
+pub async fn post_snippet(
+    State(state): State<AppState>,
+    Json(body): Json<SnippetBody>,
+) -> Result<Json<SnippetResponse>, Response> {
+    let doc = load_request_document(&state, &body.source_path, body.document)
+        .map_err(|(msg, code)| json_err(msg, code))?;
+    let overrides = if body.overrides.is_empty() {
+        None
+    } else {
+        Some(&body.overrides)
+    };
+
+    let runtime =
+        runtime_env_for_state(&state).map_err(|e| json_err(e, StatusCode::BAD_REQUEST))?;
+    let prep = doc
+        .request
+        .expand_with_overrides(&runtime, overrides)
+        .map_err(|e| json_err(e.to_string(), StatusCode::BAD_REQUEST))?;
+    let snippet = format_snippet(&prep, body.language)
+        .map_err(|e| json_err(e.to_string(), StatusCode::BAD_REQUEST))?;
+
+    return Ok(Json(SnippetResponse {
+        language: body.language,
+        snippet,
+    }));
+}
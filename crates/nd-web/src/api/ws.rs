@@ -1,4 +1,7 @@
 //! WebSocket: one JSON command per connection, stream session events as JSON text frames, then `run_complete`.
+//!
+//! While a script runs, the client may send `{"type": "resume", "checkpoint_id": "..."}` to continue
+//! past the [`Event::CheckpointWaiting`] with that id. Closing the socket resumes every checkpoint.
 
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -7,6 +10,8 @@ use std::sync::{Arc, Mutex};
 use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
 use axum::extract::State;
 use axum::response::IntoResponse;
+use futures_util::{SinkExt, StreamExt};
+use nd_core::rhai::checkpoint::Checkpoints;
 use nd_core::model::request::RequestFile;
 use nd_core::rhai::{resolver::RhaiScriptRunOptions, run::run_rhai_script};
 use nd_core::stream::events::Event;
@@ -30,12 +35,46 @@ pub enum RunCommand {
         overrides: HashMap<String, String>,
         #[serde(default)]
         stream: bool,
+        /// Keep the run's exchanges for `GET /api/sessions/{id}/har`.
+        #[serde(default)]
+        har: bool,
     },
     RunScript {
         path: String,
+        /// Keep the run's exchanges for `GET /api/sessions/{id}/har`.
+        #[serde(default)]
+        har: bool,
     },
 }
 
+impl RunCommand {
+    fn har(&self) -> bool {
+        return match self {
+            Self::RunRequest { har, .. } | Self::RunScript { har, .. } => *har,
+        };
+    }
+}
+
+/// Ends the session. Without `har` its timeline is dropped here; with it, the timeline and
+/// results stay for the HAR endpoint.
+fn end_session(session: &Arc<Mutex<Session>>, har: bool) {
+    if let Ok(mut s) = session.lock() {
+        if har {
+            s.end();
+        } else {
+            let _ = s.finish();
+        }
+    }
+}
+
+/// Messages from the client after the [`RunCommand`].
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientMessage {
+    /// Continue a script blocked in `checkpoint()`.
+    Resume { checkpoint_id: String },
+}
+
 #[derive(Serialize)]
 struct RunComplete {
     kind: &'static str,
@@ -117,7 +156,12 @@ async fn handle_socket(mut socket: WebSocket, state: AppState) {
             }
         })),
     ) {
-        Ok(s) => s,
+        Ok(mut s) => {
+            if cmd.har() {
+                s.keep_results();
+            }
+            s
+        }
         Err(e) => {
             let msg = serde_json::to_string(&json!({
                 "kind": "error",
@@ -136,18 +180,69 @@ async fn handle_socket(mut socket: WebSocket, state: AppState) {
         g.insert(session_id, arc_session.clone());
     }
 
-    let run_handle = tokio::spawn(run_command(cmd, state, arc_session.clone(), tx_holder));
-
-    while let Some(ev) = rx.recv().await {
-        let payload = match serde_json::to_string(&ev) {
-            Ok(s) => s,
-            Err(_) => continue,
-        };
-        if socket.send(Message::Text(payload.into())).await.is_err() {
-            break;
+    let checkpoints = Checkpoints::new();
+    let run_handle = tokio::spawn(run_command(
+        cmd,
+        state,
+        arc_session.clone(),
+        tx_holder,
+        checkpoints.clone(),
+    ));
+
+    let (mut sender, mut receiver) = socket.split();
+    let mut client_open = true;
+
+    loop {
+        tokio::select! {
+            ev = rx.recv() => {
+                let Some(ev) = ev else {
+                    break;
+                };
+                let payload = match serde_json::to_string(&ev) {
+                    Ok(s) => s,
+                    Err(_) => continue,
+                };
+                if sender.send(Message::Text(payload.into())).await.is_err() {
+                    break;
+                }
+            }
+            msg = receiver.next(), if client_open => {
+                let text = match msg {
+                    Some(Ok(Message::Text(t))) => t.to_string(),
+                    Some(Ok(Message::Close(_))) | Some(Err(_)) | None => {
+                        // Nobody is left to resume the script.
+                        client_open = false;
+                        checkpoints.close();
+                        continue;
+                    }
+                    Some(Ok(_)) => continue,
+                };
+                let error = match serde_json::from_str::<ClientMessage>(&text) {
+                    Ok(ClientMessage::Resume { checkpoint_id }) => {
+                        if checkpoints.resume(&checkpoint_id) {
+                            None
+                        } else {
+                            Some(format!("no checkpoint `{checkpoint_id}` is waiting"))
+                        }
+                    }
+                    Err(e) => Some(format!("invalid message: {e}")),
+                };
+                if let Some(message) = error {
+                    let msg = serde_json::to_string(&json!({
+                        "kind": "error",
+                        "message": message,
+                    }))
+                    .unwrap_or_else(|_| "{}".into());
+                    if sender.send(Message::Text(msg.into())).await.is_err() {
+                        break;
+                    }
+                }
+            }
         }
     }
 
+    // The socket may have failed while the script waits on a checkpoint.
+    checkpoints.close();
     let run_result = run_handle.await;
 
     let (ok, error, result) = match run_result {
@@ -163,7 +258,7 @@ async fn handle_socket(mut socket: WebSocket, state: AppState) {
         result,
     })
     .unwrap_or_else(|_| r#"{"kind":"run_complete","ok":false}"#.into());
-    let _ = socket.send(Message::Text(complete.into())).await;
+    let _ = sender.send(Message::Text(complete.into())).await;
 }
 
 struct RunOutcome {
@@ -177,6 +272,7 @@ async fn run_command(
     state: AppState,
     arc_session: Arc<Mutex<Session>>,
     tx_holder: Arc<Mutex<Option<tokio::sync::mpsc::UnboundedSender<Event>>>>,
+    checkpoints: Checkpoints,
 ) -> Result<RunOutcome, String> {
     match cmd {
         RunCommand::RunRequest {
@@ -184,6 +280,7 @@ async fn run_command(
             document,
             overrides,
             stream,
+            har,
         } => {
             let source = PathBuf::from(&source_path);
             let allowed =
@@ -212,8 +309,10 @@ async fn run_command(
                     .expand_with_overrides(&runtime, overrides_ref)
                     .map_err(|e| e.to_string())?;
 
+                let request_id = nanoid::nanoid!();
                 arc_session.emit(|id, e| Event::HttpRequestStarted {
                     session_id: id,
+                    request_id: request_id.clone(),
                     request_name: doc.name.clone(),
                     method: prep.method.to_string(),
                     url: prep.url.clone(),
@@ -221,13 +320,14 @@ async fn run_command(
                 });
                 arc_session.emit(|id, e| Event::HttpResponseCompleted {
                     session_id: id,
+                    request_id,
                     request_name: doc.name.clone(),
                     status: 0,
                     final_url: prep.url.clone(),
                     elapsed: e,
                 });
 
-                let _ = arc_session.lock().map(|mut s| s.finish());
+                end_session(&arc_session, har);
                 if let Ok(mut g) = tx_holder.lock() {
                     g.take();
                 }
@@ -257,7 +357,7 @@ async fn run_command(
                 .execute_with_overrides(arc_session.clone(), overrides_ref, stream)
                 .await;
 
-            let _ = arc_session.lock().map(|mut session| session.finish());
+            end_session(&arc_session, har);
 
             if let Ok(mut g) = tx_holder.lock() {
                 g.take();
@@ -276,20 +376,26 @@ async fn run_command(
                 },
             })
         }
-        RunCommand::RunScript { path } => {
+        RunCommand::RunScript { path, har } => {
             let p = PathBuf::from(&path);
             let allowed =
                 resolve_allowed_file(&p, state.roots.as_ref()).map_err(|e| e.to_string())?;
 
             let session = arc_session.clone();
             let no_network_io = state.no_network_io;
+            let roots = state.roots.to_vec();
             let res = tokio::task::spawn_blocking(move || {
-                run_rhai_script(&allowed, session, RhaiScriptRunOptions { no_network_io })
+                let options = RhaiScriptRunOptions {
+                    no_network_io,
+                    checkpoints: Some(checkpoints),
+                    fs_roots: roots,
+                };
+                run_rhai_script(&allowed, session, options)
             })
             .await
             .map_err(|e| format!("spawn_blocking: {e}"))?;
 
-            let _ = arc_session.lock().map(|mut s| s.finish());
+            end_session(&arc_session, har);
             if let Ok(mut g) = tx_holder.lock() {
                 g.take();
             }
@@ -5,6 +5,8 @@
 
 use std::path::{Path, PathBuf};
 
+use nd_core::utils::path::is_under_roots;
+
 /// Canonicalize each configured root directory.
 ///
 /// Returns an error string if a path is missing, not a directory, or cannot be canonicalized.
@@ -37,8 +39,3 @@ pub fn resolve_allowed_file(candidate: &Path, roots: &[PathBuf]) -> Result<PathB
         Err("path outside workspace roots".into())
     }
 }
-
-/// `true` if `canon` is exactly a root or nested under one (component-wise prefix match).
-pub fn is_under_roots(canon: &Path, roots: &[PathBuf]) -> bool {
-    roots.iter().any(|root| canon.starts_with(root))
-}
@@ -1,8 +1,8 @@
 //! Workspace discovery: aggregate non-recursive listings from one or more directory roots.
 //!
 //! Each root is scanned independently; results are grouped per root so the UI can show labels when
-//! multiple roots are configured. Invalid request files are omitted from the tree but reported in
-//! `skipped_requests`.
+//! multiple roots are configured. Invalid request files (and `.http` / `.rest` files, which the editor
+//! cannot open) are omitted from the tree but reported in `skipped_requests`.
 
 use std::path::PathBuf;
 
@@ -10,6 +10,7 @@ use nd_core::discover::{
     list_request_paths, list_rhai_paths, partition_valid_request_paths, SkippedRequestFile,
 };
 use nd_core::error::{Error, Result as NdResult};
+use nd_core::model::http_file::is_http_file;
 use tracing::debug;
 
 use crate::path_sandbox::canonicalize_roots;
@@ -72,8 +73,16 @@ pub fn build_workspace(roots: &[PathBuf]) -> NdResult<WorkspaceSnapshot> {
     let mut script_groups: Vec<GroupedFiles> = Vec::new();
 
     for (i, root) in canon.iter().enumerate() {
-        let raw_requests = list_request_paths(root)?;
-        let (valid, skipped) = partition_valid_request_paths(&raw_requests);
+        // The editor works on JSON / YAML documents; `.http` files run from the CLI or Rhai.
+        let (http_files, raw_requests): (Vec<PathBuf>, Vec<PathBuf>) = list_request_paths(root)?
+            .into_iter()
+            .partition(|p| is_http_file(p));
+        let (valid, mut skipped) = partition_valid_request_paths(&raw_requests);
+        skipped.extend(http_files.into_iter().map(|path| SkippedRequestFile {
+            path,
+            message: ".http / .rest files are not editable here; run them with `nativedoctor run`"
+                .into(),
+        }));
         for s in &skipped {
             debug!(
                 path = %s.path.display(),
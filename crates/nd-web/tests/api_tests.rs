@@ -1,5 +1,7 @@
 use axum::body::{to_bytes, Body};
 use axum::http::{header::CONTENT_TYPE, Request, StatusCode};
+use nd_core::env::RuntimeEnv;
+use nd_core::stream::Session;
 use nd_web::{api_router, app_router, AppState};
 use std::collections::HashMap;
 use std::path::PathBuf;
@@ -86,6 +88,92 @@ async fn workspace_lists_valid_request_skips_bad_json() {
     assert_eq!(v["skipped_requests"].as_array().unwrap().len(), 1);
 }
 
+#[tokio::test]
+async fn snippet_renders_unsaved_document_as_curl() {
+    let dir = tempfile::tempdir().unwrap();
+    let f = dir.path().join("req.yaml");
+    std::fs::write(&f, "request:\n  method: GET\n  url: https://example.com\n").unwrap();
+    let roots = vec![dir.path().canonicalize().unwrap()];
+    let app = api_router(test_state(roots));
+    let body = serde_json::json!({
+        "source_path": f.to_string_lossy(),
+        "document": {
+            "request": {
+                "method": "POST",
+                "url": "https://example.com/items/${ID}",
+                "body": "hi"
+            }
+        },
+        "overrides": { "ID": "7" },
+        "language": "curl",
+    });
+    let res = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/requests/snippet")
+                .header(CONTENT_TYPE, "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+    let v: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(v["language"], "curl");
+    let snippet = v["snippet"].as_str().unwrap();
+    assert!(snippet.starts_with("curl -X POST 'https://example.com/items/7'"));
+    assert!(snippet.contains("--data-raw 'hi'"));
+}
+
+#[tokio::test]
+async fn session_har_downloads_known_sessions_only() {
+    let dir = tempfile::tempdir().unwrap();
+    let state = test_state(vec![dir.path().canonicalize().unwrap()]);
+    let session = Session::new(|| Ok(RuntimeEnv::new()), None).unwrap();
+    let id = session.session_id().to_string();
+    state
+        .sessions
+        .lock()
+        .unwrap()
+        .insert(id.clone(), Arc::new(Mutex::new(session)));
+    let app = api_router(state);
+
+    let res = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri(format!("/sessions/{id}/har"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let disposition = res
+        .headers()
+        .get(axum::http::header::CONTENT_DISPOSITION)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or("");
+    assert!(disposition.contains(&format!("nativedoctor-{id}.har")));
+    let body = to_bytes(res.into_body(), usize::MAX).await.unwrap();
+    let v: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(v["log"]["version"], "1.2");
+    assert_eq!(v["log"]["entries"].as_array().unwrap().len(), 0);
+
+    let res = app
+        .oneshot(
+            Request::builder()
+                .uri("/sessions/missing/har")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(res.status(), StatusCode::NOT_FOUND);
+}
+
 #[tokio::test]
 async fn app_router_root_serves_embedded_index() {
     let dir = tempfile::tempdir().unwrap();
@@ -51,7 +51,7 @@ async fn websocket_run_request_dry_run_streams_events_and_run_complete() {
         "overrides": {},
         "stream": false,
     });
-    ws.send(Message::Text(cmd.to_string().into()))
+    ws.send(Message::Text(cmd.to_string()))
         .await
         .expect("send command");
 
@@ -76,3 +76,76 @@ async fn websocket_run_request_dry_run_streams_events_and_run_complete() {
     assert!(saw_event, "expected at least one session event");
     assert!(complete_ok, "expected run_complete ok");
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn websocket_resume_continues_a_script_checkpoint() {
+    let dir = tempfile::tempdir().unwrap();
+    let script = dir.path().join("pause.rhai");
+    std::fs::write(
+        &script,
+        "checkpoint(true, \"look\", #{ n: 1 });\nset(\"after\", \"yes\");\n",
+    )
+    .unwrap();
+
+    let roots = vec![dir.path().canonicalize().unwrap()];
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+
+    let app = api_router(test_state(roots));
+    tokio::spawn(async move {
+        let _ = axum::serve(listener, app).await;
+    });
+
+    tokio::time::sleep(Duration::from_millis(80)).await;
+
+    let url = format!("ws://127.0.0.1:{}/ws", addr.port());
+    let (mut ws, _) = tokio_tungstenite::connect_async(url)
+        .await
+        .expect("websocket connect");
+
+    let cmd = json!({ "type": "run_script", "path": script.to_string_lossy() });
+    ws.send(Message::Text(cmd.to_string()))
+        .await
+        .expect("send command");
+
+    let mut resumed = false;
+    let mut pushed_after_resume = false;
+    let mut complete_ok = false;
+
+    while let Some(msg) = tokio::time::timeout(Duration::from_secs(10), ws.next())
+        .await
+        .expect("checkpoint was never resumed")
+    {
+        let Message::Text(t) = msg.expect("ws message") else {
+            continue;
+        };
+        let v: serde_json::Value = serde_json::from_str(t.as_str()).expect("json");
+        if let Some(waiting) = v.get("CheckpointWaiting") {
+            assert_eq!(waiting["message"], "look");
+            assert_eq!(waiting["observe"], json!({ "n": 1 }));
+
+            let unknown = json!({ "type": "resume", "checkpoint_id": "nope" });
+            ws.send(Message::Text(unknown.to_string())).await.unwrap();
+
+            let resume = json!({ "type": "resume", "checkpoint_id": waiting["checkpoint_id"] });
+            ws.send(Message::Text(resume.to_string())).await.unwrap();
+        }
+        if v.get("kind").and_then(|k| k.as_str()) == Some("error") {
+            assert!(v["message"].as_str().unwrap().contains("`nope`"), "{v}");
+        }
+        if v.get("CheckpointResumed").is_some() {
+            resumed = true;
+        }
+        if resumed && v["RuntimeVariablePushed"]["key"] == "after" {
+            pushed_after_resume = true;
+        }
+        if v.get("kind").and_then(|k| k.as_str()) == Some("run_complete") {
+            complete_ok = v["ok"].as_bool() == Some(true);
+            break;
+        }
+    }
+
+    assert!(resumed, "expected CheckpointResumed");
+    assert!(pushed_after_resume, "script should continue after resume");
+    assert!(complete_ok, "expected run_complete ok");
+}